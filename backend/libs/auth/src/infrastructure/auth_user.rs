@@ -44,12 +44,10 @@ where
         .claims;
 
         match claims.tenant == auth_state.use_cases.tenant {
-            true => {
-                return Ok(AuthUser {
-                    user_id: claims.sub,
-                    tenant: claims.tenant,
-                });
-            }
+            true => Ok(AuthUser {
+                user_id: claims.sub,
+                tenant: claims.tenant,
+            }),
             false => {
                 tracing::warn!(
                     "Tenant claim {} did not match for tenant {} for user_id {}",
@@ -57,7 +55,7 @@ where
                     auth_state.use_cases.tenant,
                     claims.sub
                 );
-                return Err(StatusCode::UNAUTHORIZED);
+                Err(StatusCode::UNAUTHORIZED)
             }
         }
     }
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_documents_blob_sha256;
+ALTER TABLE documents DROP COLUMN size_bytes;
+ALTER TABLE documents DROP COLUMN mime_type;
+ALTER TABLE documents DROP COLUMN file_name;
+ALTER TABLE documents DROP COLUMN blob_sha256;
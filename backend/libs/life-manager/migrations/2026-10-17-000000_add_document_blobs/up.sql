@@ -0,0 +1,6 @@
+ALTER TABLE documents ADD COLUMN blob_sha256 TEXT;
+ALTER TABLE documents ADD COLUMN file_name TEXT;
+ALTER TABLE documents ADD COLUMN mime_type TEXT;
+ALTER TABLE documents ADD COLUMN size_bytes BIGINT;
+
+CREATE INDEX idx_documents_blob_sha256 ON documents(blob_sha256);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_document_tags_tag_id;
+DROP TABLE document_tags;
+DROP TABLE tags;
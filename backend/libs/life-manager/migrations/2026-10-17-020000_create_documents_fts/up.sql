@@ -0,0 +1,26 @@
+-- Full-text index over document titles and content. The index keeps its own copy of the text
+-- keyed by document_id because documents has no stable integer rowid to reference.
+CREATE VIRTUAL TABLE documents_fts USING fts5(
+    document_id UNINDEXED,
+    title,
+    content,
+    tokenize = 'porter unicode61',
+    prefix = '2 3'
+);
+
+INSERT INTO documents_fts (document_id, title, content)
+SELECT id, title, content FROM documents;
+
+CREATE TRIGGER documents_fts_after_insert AFTER INSERT ON documents BEGIN
+    INSERT INTO documents_fts (document_id, title, content)
+    VALUES (new.id, new.title, new.content);
+END;
+
+CREATE TRIGGER documents_fts_after_update AFTER UPDATE OF title, content ON documents BEGIN
+    UPDATE documents_fts SET title = new.title, content = new.content
+    WHERE document_id = old.id;
+END;
+
+CREATE TRIGGER documents_fts_after_delete AFTER DELETE ON documents BEGIN
+    DELETE FROM documents_fts WHERE document_id = old.id;
+END;
@@ -0,0 +1,34 @@
+DROP TRIGGER documents_fts_after_delete;
+DROP TRIGGER documents_fts_after_update;
+DROP TRIGGER documents_fts_after_insert;
+DROP TABLE documents_fts;
+
+CREATE VIRTUAL TABLE documents_fts USING fts5(
+    document_id UNINDEXED,
+    title,
+    content,
+    tokenize = 'porter unicode61',
+    prefix = '2 3'
+);
+
+INSERT INTO documents_fts (document_id, title, content)
+SELECT id, title, content FROM documents;
+
+CREATE TRIGGER documents_fts_after_insert AFTER INSERT ON documents BEGIN
+    INSERT INTO documents_fts (document_id, title, content)
+    VALUES (new.id, new.title, new.content);
+END;
+
+CREATE TRIGGER documents_fts_after_update AFTER UPDATE OF title, content ON documents BEGIN
+    UPDATE documents_fts SET title = new.title, content = new.content
+    WHERE document_id = old.id;
+END;
+
+CREATE TRIGGER documents_fts_after_delete AFTER DELETE ON documents BEGIN
+    DELETE FROM documents_fts WHERE document_id = old.id;
+END;
+
+ALTER TABLE documents DROP COLUMN ocr_used;
+ALTER TABLE documents DROP COLUMN page_count;
+ALTER TABLE documents DROP COLUMN text_reader;
+ALTER TABLE documents DROP COLUMN extracted_text;
@@ -0,0 +1,38 @@
+-- Raw text read from the uploaded file, kept next to the summary stored in content.
+ALTER TABLE documents ADD COLUMN extracted_text TEXT;
+ALTER TABLE documents ADD COLUMN text_reader TEXT;
+ALTER TABLE documents ADD COLUMN page_count INTEGER;
+ALTER TABLE documents ADD COLUMN ocr_used BOOLEAN;
+
+-- Rebuild the full-text index so the extracted text is searchable as well.
+DROP TRIGGER documents_fts_after_delete;
+DROP TRIGGER documents_fts_after_update;
+DROP TRIGGER documents_fts_after_insert;
+DROP TABLE documents_fts;
+
+CREATE VIRTUAL TABLE documents_fts USING fts5(
+    document_id UNINDEXED,
+    title,
+    content,
+    extracted_text,
+    tokenize = 'porter unicode61',
+    prefix = '2 3'
+);
+
+INSERT INTO documents_fts (document_id, title, content, extracted_text)
+SELECT id, title, content, COALESCE(extracted_text, '') FROM documents;
+
+CREATE TRIGGER documents_fts_after_insert AFTER INSERT ON documents BEGIN
+    INSERT INTO documents_fts (document_id, title, content, extracted_text)
+    VALUES (new.id, new.title, new.content, COALESCE(new.extracted_text, ''));
+END;
+
+CREATE TRIGGER documents_fts_after_update AFTER UPDATE OF title, content, extracted_text ON documents BEGIN
+    UPDATE documents_fts
+    SET title = new.title, content = new.content, extracted_text = COALESCE(new.extracted_text, '')
+    WHERE document_id = old.id;
+END;
+
+CREATE TRIGGER documents_fts_after_delete AFTER DELETE ON documents BEGIN
+    DELETE FROM documents_fts WHERE document_id = old.id;
+END;
@@ -0,0 +1,2 @@
+DROP INDEX idx_documents_deleted_at;
+ALTER TABLE documents DROP COLUMN deleted_at;
@@ -0,0 +1,4 @@
+-- Documents in the trash have deleted_at set; they are purged once it is older than the
+-- retention period.
+ALTER TABLE documents ADD COLUMN deleted_at TIMESTAMP;
+CREATE INDEX idx_documents_deleted_at ON documents (deleted_at);
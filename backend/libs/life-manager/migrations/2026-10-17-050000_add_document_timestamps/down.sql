@@ -0,0 +1,5 @@
+DROP INDEX idx_documents_user_updated_at;
+DROP INDEX idx_documents_user_created_at;
+ALTER TABLE documents DROP COLUMN document_date;
+ALTER TABLE documents DROP COLUMN updated_at;
+ALTER TABLE documents DROP COLUMN created_at;
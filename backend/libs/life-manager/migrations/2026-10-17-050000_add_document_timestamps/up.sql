@@ -0,0 +1,11 @@
+-- SQLite cannot add a NOT NULL column with a non-constant default, so existing rows are backfilled
+-- with the migration time after the columns are added.
+ALTER TABLE documents ADD COLUMN created_at TIMESTAMP NOT NULL DEFAULT '1970-01-01 00:00:00';
+ALTER TABLE documents ADD COLUMN updated_at TIMESTAMP NOT NULL DEFAULT '1970-01-01 00:00:00';
+UPDATE documents SET created_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP;
+
+-- Date printed on the document itself, when known.
+ALTER TABLE documents ADD COLUMN document_date DATE;
+
+CREATE INDEX idx_documents_user_created_at ON documents (user_id, created_at);
+CREATE INDEX idx_documents_user_updated_at ON documents (user_id, updated_at);
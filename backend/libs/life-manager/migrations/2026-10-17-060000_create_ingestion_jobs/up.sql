@@ -0,0 +1,17 @@
+-- Uploaded files waiting to be read and summarized by the ingestion workers. The file itself is
+-- in the blob store.
+CREATE TABLE ingestion_jobs (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    blob_sha256 TEXT NOT NULL,
+    file_name TEXT NOT NULL,
+    mime_type TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    document_id TEXT,
+    error TEXT,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX idx_ingestion_jobs_status_created_at ON ingestion_jobs (status, created_at);
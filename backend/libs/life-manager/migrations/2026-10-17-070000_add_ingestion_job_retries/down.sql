@@ -0,0 +1,3 @@
+DROP INDEX idx_ingestion_jobs_status_next_attempt_at;
+ALTER TABLE ingestion_jobs DROP COLUMN next_attempt_at;
+ALTER TABLE ingestion_jobs DROP COLUMN attempts;
@@ -0,0 +1,8 @@
+-- Retryable failures put a job back in the queue with a delay, so workers only claim queued jobs
+-- whose next attempt is due.
+ALTER TABLE ingestion_jobs ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE ingestion_jobs ADD COLUMN next_attempt_at TIMESTAMP NOT NULL DEFAULT '1970-01-01 00:00:00';
+
+UPDATE ingestion_jobs SET next_attempt_at = created_at;
+
+CREATE INDEX idx_ingestion_jobs_status_next_attempt_at ON ingestion_jobs (status, next_attempt_at);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_ingestion_jobs_user_blob_sha256;
+DROP INDEX IF EXISTS idx_documents_user_content_sha256;
+ALTER TABLE documents DROP COLUMN content_sha256;
@@ -0,0 +1,19 @@
+-- SHA-256 of the uploaded file, unique per user so the same file is not ingested twice. Copies
+-- uploaded before this check existed keep NULL; only the oldest copy of a file gets the hash.
+ALTER TABLE documents ADD COLUMN content_sha256 TEXT;
+
+UPDATE documents
+SET content_sha256 = blob_sha256
+WHERE blob_sha256 IS NOT NULL
+  AND NOT EXISTS (
+      SELECT 1 FROM documents AS older
+      WHERE older.user_id = documents.user_id
+        AND older.blob_sha256 = documents.blob_sha256
+        AND (older.created_at < documents.created_at
+             OR (older.created_at = documents.created_at AND older.id < documents.id))
+  );
+
+CREATE UNIQUE INDEX idx_documents_user_content_sha256 ON documents (user_id, content_sha256);
+
+-- Uploads of a file that is still being ingested are duplicates too.
+CREATE INDEX idx_ingestion_jobs_user_blob_sha256 ON ingestion_jobs (user_id, blob_sha256);
@@ -0,0 +1,2 @@
+ALTER TABLE ingestion_jobs DROP COLUMN similar_document_ids;
+ALTER TABLE documents DROP COLUMN text_simhash;
@@ -0,0 +1,6 @@
+-- SimHash of the extracted text, stored as the signed 64 bit value of its bits. Documents read
+-- before this column existed have none.
+ALTER TABLE documents ADD COLUMN text_simhash BIGINT;
+
+-- Documents that look like the one a job created, as a JSON array of IDs.
+ALTER TABLE ingestion_jobs ADD COLUMN similar_document_ids TEXT NOT NULL DEFAULT '[]';
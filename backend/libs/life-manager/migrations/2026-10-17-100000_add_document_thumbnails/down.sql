@@ -0,0 +1 @@
+ALTER TABLE documents DROP COLUMN thumbnail_sha256;
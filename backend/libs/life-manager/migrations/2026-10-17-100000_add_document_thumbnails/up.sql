@@ -0,0 +1,3 @@
+-- Content address of the document's thumbnail in the blob store. Documents read before this
+-- column existed, and files that cannot be previewed, have none.
+ALTER TABLE documents ADD COLUMN thumbnail_sha256 TEXT;
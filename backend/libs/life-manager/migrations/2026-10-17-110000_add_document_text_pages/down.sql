@@ -0,0 +1 @@
+ALTER TABLE documents DROP COLUMN text_pages;
@@ -0,0 +1,2 @@
+-- Text of each page, as a JSON array of {page, text, error}, for documents read page by page.
+ALTER TABLE documents ADD COLUMN text_pages TEXT;
@@ -0,0 +1,3 @@
+-- Image preprocessing chosen for the upload, as comma separated step names. NULL runs the
+-- reader's default chain.
+ALTER TABLE ingestion_jobs ADD COLUMN preprocessing TEXT;
@@ -0,0 +1,3 @@
+DROP TABLE user_settings;
+ALTER TABLE documents DROP COLUMN text_languages;
+ALTER TABLE ingestion_jobs DROP COLUMN ocr_languages;
@@ -0,0 +1,10 @@
+-- Languages chosen for an upload, e.g. `eng+deu`. NULL lets the reader detect them.
+ALTER TABLE ingestion_jobs ADD COLUMN ocr_languages TEXT;
+-- Languages the text of a document was read in.
+ALTER TABLE documents ADD COLUMN text_languages TEXT;
+
+CREATE TABLE user_settings (
+    user_id TEXT PRIMARY KEY NOT NULL,
+    -- Languages the user's uploads are read in unless an upload chooses its own.
+    ocr_languages TEXT
+);
@@ -0,0 +1,2 @@
+DROP TABLE document_words;
+ALTER TABLE documents DROP COLUMN ocr_confidence;
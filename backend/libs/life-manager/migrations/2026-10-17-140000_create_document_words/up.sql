@@ -0,0 +1,17 @@
+-- Mean confidence, from 0 to 100, of the words OCR read in a document.
+ALTER TABLE documents ADD COLUMN ocr_confidence REAL;
+
+CREATE TABLE document_words (
+    document_id TEXT NOT NULL REFERENCES documents(id) ON DELETE CASCADE,
+    -- Reading order of the word in the document.
+    position INTEGER NOT NULL,
+    page INTEGER NOT NULL,
+    text TEXT NOT NULL,
+    -- Bounding box in pixels of the page image OCR read.
+    box_left INTEGER NOT NULL,
+    box_top INTEGER NOT NULL,
+    box_width INTEGER NOT NULL,
+    box_height INTEGER NOT NULL,
+    confidence REAL NOT NULL,
+    PRIMARY KEY (document_id, position)
+);
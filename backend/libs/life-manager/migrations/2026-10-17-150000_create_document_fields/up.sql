@@ -0,0 +1,15 @@
+-- Typed fields read from the text of a document, such as the amount and due date of a bill.
+CREATE TABLE document_fields (
+    document_id TEXT PRIMARY KEY NOT NULL REFERENCES documents(id) ON DELETE CASCADE,
+    -- `bill`, `receipt` or `other`, which decides the fields that are set.
+    document_type TEXT NOT NULL,
+    vendor TEXT,
+    total_amount REAL,
+    -- ISO 4217 code, e.g. `EUR`.
+    currency TEXT,
+    due_date DATE,
+    account_number TEXT,
+    invoice_number TEXT
+);
+
+CREATE INDEX idx_document_fields_due_date ON document_fields(due_date);
@@ -0,0 +1,2 @@
+DROP TABLE document_classifications;
+DROP TABLE categories;
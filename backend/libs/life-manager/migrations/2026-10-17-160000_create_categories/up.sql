@@ -0,0 +1,26 @@
+-- Categories each user files documents under, such as "utility bill" or "tax".
+CREATE TABLE categories (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    name TEXT NOT NULL COLLATE NOCASE,
+    description TEXT NOT NULL DEFAULT '',
+    -- JSON array of keywords and `/regex/` rules for classifying without the model.
+    rules TEXT NOT NULL DEFAULT '[]',
+    -- JSON array of tags suggested for documents of the category.
+    tags TEXT NOT NULL DEFAULT '[]',
+    UNIQUE (user_id, name)
+);
+
+-- The category of a document, suggested at ingestion or chosen by the user.
+CREATE TABLE document_classifications (
+    document_id TEXT PRIMARY KEY NOT NULL REFERENCES documents(id) ON DELETE CASCADE,
+    category_id TEXT NOT NULL REFERENCES categories(id) ON DELETE CASCADE,
+    -- JSON array of the tags that go with the category.
+    tags TEXT NOT NULL DEFAULT '[]',
+    -- From 0 to 1.
+    confidence REAL NOT NULL,
+    -- `suggested`, `accepted` or `overridden`.
+    status TEXT NOT NULL
+);
+
+CREATE INDEX idx_document_classifications_category_id ON document_classifications(category_id);
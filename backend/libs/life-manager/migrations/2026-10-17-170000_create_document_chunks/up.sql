@@ -0,0 +1,16 @@
+-- Passages of a document's text with their embeddings, for semantic search.
+CREATE TABLE document_chunks (
+    document_id TEXT NOT NULL REFERENCES documents(id) ON DELETE CASCADE,
+    -- Order of the passage in the document.
+    position INTEGER NOT NULL,
+    -- Page the passage was read from, if the text was read page by page.
+    page INTEGER,
+    text TEXT NOT NULL,
+    -- Embedding model; vectors of different models are not compared.
+    model TEXT NOT NULL,
+    -- Little-endian f32 values of the vector.
+    embedding BLOB NOT NULL,
+    PRIMARY KEY (document_id, position)
+);
+
+CREATE INDEX document_chunks_model ON document_chunks (model, document_id);
@@ -1,5 +1,15 @@
+pub mod category_repository;
 pub mod create_document_command;
+pub mod document_policy;
 pub mod document_repository;
 pub mod document_use_cases;
+pub mod embed_documents_command;
 pub mod get_document_query;
 pub mod get_documents_query;
+pub mod ingest_document_command;
+pub mod ingestion_event_bus;
+pub mod ingestion_job_repository;
+pub mod ingestion_queue;
+pub mod purge_trash_command;
+pub mod retry_policy;
+pub mod user_settings_repository;
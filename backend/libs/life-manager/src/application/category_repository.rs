@@ -0,0 +1,38 @@
+use std::{error::Error, fmt};
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::category::Category;
+
+/**
+ * Port for the categories each user files documents under.
+ */
+#[async_trait]
+pub trait CategoryRepository: Sync + Send {
+    /// The user's categories, ordered by name.
+    async fn get_categories(
+        &self,
+        user_id: &Uuid,
+    ) -> Result<Vec<Category>, Box<dyn Error + Send + Sync>>;
+    /// Adds the category, or replaces the one with its ID. Fails with [`CategoryNameTaken`] if
+    /// the user has another category of the same name, whatever its case.
+    async fn save_category(&self, category: &Category) -> Result<(), Box<dyn Error + Send + Sync>>;
+    /// Deletes the category. Documents filed under it lose their classification.
+    async fn delete_category(
+        &self,
+        category: &Category,
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/// The user already has a category of the name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryNameTaken(pub String);
+
+impl fmt::Display for CategoryNameTaken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "A category named '{}' already exists", self.0)
+    }
+}
+
+impl Error for CategoryNameTaken {}
@@ -0,0 +1,71 @@
+use uuid::Uuid;
+
+use crate::domain::document::Document;
+
+/**
+ * What a principal wants to do with a document.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocumentAction {
+    Read,
+    Write,
+    Delete,
+}
+
+/**
+ * Decides whether a user may act on a document. Every document use case checks it before
+ * touching a document; a denial must look the same as a missing document so that IDs of other
+ * users' documents are not revealed.
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DocumentPolicy;
+
+impl DocumentPolicy {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Documents are private: only their owner may read, change or delete them.
+    pub fn allows(&self, user_id: Uuid, action: DocumentAction, document: &Document) -> bool {
+        match action {
+            DocumentAction::Read | DocumentAction::Write | DocumentAction::Delete => {
+                document.user_id == user_id
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owner_is_allowed_every_action() {
+        let owner = Uuid::new_v4();
+        let document = Document::new("Bill", "Power bill", owner);
+        let policy = DocumentPolicy::new();
+
+        for action in [
+            DocumentAction::Read,
+            DocumentAction::Write,
+            DocumentAction::Delete,
+        ] {
+            assert!(policy.allows(owner, action, &document));
+        }
+    }
+
+    #[test]
+    fn test_other_user_is_denied_every_action() {
+        let document = Document::new("Bill", "Power bill", Uuid::new_v4());
+        let policy = DocumentPolicy::new();
+        let other_user = Uuid::new_v4();
+
+        for action in [
+            DocumentAction::Read,
+            DocumentAction::Write,
+            DocumentAction::Delete,
+        ] {
+            assert!(!policy.allows(other_user, action, &document));
+        }
+    }
+}
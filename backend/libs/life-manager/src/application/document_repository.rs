@@ -1,23 +1,121 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::domain::document::Document;
+use crate::domain::{
+    document::Document,
+    document_chunk::{DocumentChunk, SemanticHit},
+    document_classification::DocumentClassification,
+    document_listing::DocumentListing,
+    document_search::{SearchHit, SearchQuery},
+    document_similarity::SimilarDocument,
+    ocr_word::OcrWord,
+    tag::TagCount,
+};
 
 /**
- * Port for document repository operations.
+ * Port for document repository operations. Reads are scoped to the owner's documents. Listings,
+ * tag counts and search leave out documents in the trash; [`DocumentRepository::get_document`]
+ * returns them so they can be restored.
  */
 #[async_trait]
 pub trait DocumentRepository: Sync + Send {
-    async fn get_document(&self, id: Uuid) -> Option<Document>;
+    async fn get_document(&self, user_id: &Uuid, id: Uuid) -> Option<Document>;
+    /// The user's oldest document, in the trash or not, whose original file has the SHA-256.
+    async fn find_document_by_sha256(&self, user_id: &Uuid, sha256: &str) -> Option<Document>;
     async fn get_documents(&self, user_id: &Uuid, limit: &u32) -> Vec<Document>;
-    async fn get_documents_title_cursor(
-        &self,
-        user_id: &Uuid,
-        limit: &u32,
-        title: &str,
-    ) -> Vec<Document>;
+    /// Up to `listing.limit` of the user's documents in the listing's sort order, starting after
+    /// its cursor.
+    async fn list_documents(&self, user_id: &Uuid, listing: &DocumentListing) -> Vec<Document>;
     async fn save_document(
         &self,
         document: Document,
     ) -> Result<Document, Box<dyn std::error::Error>>;
+    /// Writes the document's title, content and tags, replacing its previous tags, and returns
+    /// the stored document.
+    async fn update_document(
+        &self,
+        document: Document,
+    ) -> Result<Document, Box<dyn std::error::Error>>;
+    /// Moves the document to the trash.
+    async fn trash_document(
+        &self,
+        document: &Document,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<Document, Box<dyn std::error::Error>>;
+    /// Takes the document out of the trash.
+    async fn restore_document(
+        &self,
+        document: &Document,
+    ) -> Result<Document, Box<dyn std::error::Error>>;
+    /// Lists the user's documents in the trash, most recently deleted first.
+    async fn get_trash(&self, user_id: &Uuid, limit: &u32) -> Vec<Document>;
+    /// Permanently deletes documents moved to the trash before the cutoff and returns them.
+    async fn purge_trash(
+        &self,
+        deleted_before: DateTime<Utc>,
+    ) -> Result<Vec<Document>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Whether any document, of any user and in the trash or not, has the blob as its file or
+    /// thumbnail.
+    async fn references_blob(
+        &self,
+        sha256: &str,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+    /// Adds tags to the document, creating them for its owner if needed, and returns the updated
+    /// document.
+    async fn add_tags(
+        &self,
+        document: &Document,
+        tags: &[String],
+    ) -> Result<Document, Box<dyn std::error::Error>>;
+    /// Removes a tag from the document and returns the updated document.
+    async fn remove_tag(
+        &self,
+        document: &Document,
+        tag: &str,
+    ) -> Result<Document, Box<dyn std::error::Error>>;
+    /// Files the document under the classification's category, replacing any earlier one. Unless
+    /// the classification is only suggested, its tags are added to the document.
+    async fn set_classification(
+        &self,
+        document: &Document,
+        classification: &DocumentClassification,
+    ) -> Result<Document, Box<dyn std::error::Error>>;
+    /// Lists the user's tags with the number of documents carrying each one, ordered by name.
+    async fn get_tags(&self, user_id: &Uuid) -> Vec<TagCount>;
+    /// The user's other documents, not in the trash, whose text fingerprint is at most
+    /// `max_distance` bits from the document's, closest first. Empty if the document has no
+    /// fingerprint.
+    async fn find_similar_documents(
+        &self,
+        document: &Document,
+        max_distance: u32,
+        limit: &u32,
+    ) -> Vec<SimilarDocument>;
+    /// Words OCR read in the document, in reading order. Empty if it was not read with OCR.
+    async fn get_words(&self, document: &Document) -> Vec<OcrWord>;
+    /// Full-text search over the user's document titles and content, best matches first.
+    async fn search(&self, user_id: &Uuid, query: &SearchQuery, limit: &u32) -> Vec<SearchHit>;
+    /// Replaces the chunks of the document with the embedded ones.
+    async fn save_chunks(
+        &self,
+        document: &Document,
+        chunks: &[DocumentChunk],
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    /// Up to `limit` documents of any user, not in the trash, that have no chunks embedded by the
+    /// model, oldest first.
+    async fn documents_to_embed(
+        &self,
+        model: &str,
+        limit: &u32,
+    ) -> Result<Vec<Document>, Box<dyn std::error::Error + Send + Sync>>;
+    /// The user's documents whose chunks embedded by the model are closest in meaning to the
+    /// query vector, best matches first.
+    async fn semantic_search(
+        &self,
+        user_id: &Uuid,
+        model: &str,
+        query: &[f32],
+        limit: &u32,
+    ) -> Vec<SemanticHit>;
 }
@@ -1,13 +1,387 @@
-use std::sync::Arc;
+use std::{error::Error, sync::Arc};
+
+use tokio_stream::StreamExt;
+use uuid::Uuid;
 
 use crate::{
-    application::document_repository::DocumentRepository,
-    domain::{document_summarizer::DocumentSummarizer, document_text_reader::DocumentTextReader},
+    application::{
+        category_repository::CategoryRepository,
+        document_policy::{DocumentAction, DocumentPolicy},
+        document_repository::DocumentRepository,
+        ingestion_queue::IngestionQueue,
+        user_settings_repository::UserSettingsRepository,
+    },
+    domain::{
+        answer_generator::AnswerGenerator,
+        assistant_answer::{AssistantAnswer, CONTEXT_PASSAGES, ContextPassage, NO_CONTEXT_ANSWER},
+        blob_store::{BlobStore, DocumentBlob},
+        category::Category,
+        document::Document,
+        document_chunk::{DocumentChunk, SemanticHit},
+        document_classification::{DocumentClassification, classify_by_rules},
+        document_classifier::DocumentClassifier,
+        document_field_extractor::DocumentFieldExtractor,
+        document_fields::DocumentFields,
+        document_summarizer::DocumentSummarizer,
+        document_text_reader::DocumentTextReader,
+        embedding_provider::EmbeddingProvider,
+        ingestion_job::IngestionJob,
+        ocr_language::{InvalidOcrLanguages, OcrLanguages},
+        pdf_page_renderer::PdfPageRenderer,
+        thumbnail::{THUMBNAIL_SIZE, is_thumbnailable_image, make_thumbnail},
+        uploaded_document_input::UploadedDocumentInput,
+    },
 };
 
+/**
+ * What became of an uploaded file. Files the user already has are not stored or read again.
+ */
+#[derive(Debug)]
+pub enum UploadOutcome {
+    Queued(IngestionJob),
+    /// The user already has a document, possibly in the trash, for the same bytes.
+    Duplicate(Box<Document>),
+    /// The same bytes are still waiting for or going through ingestion.
+    AlreadyQueued(IngestionJob),
+}
+
 #[derive(Clone)]
 pub struct DocumentUseCases {
     pub document_repository: Arc<dyn DocumentRepository>,
     pub reader: Arc<dyn DocumentTextReader>,
     pub summarizer: Arc<dyn DocumentSummarizer>,
+    pub blob_store: Arc<dyn BlobStore>,
+    pub policy: DocumentPolicy,
+    pub ingestion_queue: IngestionQueue,
+    pub pdf_renderer: Arc<dyn PdfPageRenderer>,
+    pub user_settings: Arc<dyn UserSettingsRepository>,
+    pub field_extractor: Arc<dyn DocumentFieldExtractor>,
+    pub categories: Arc<dyn CategoryRepository>,
+    pub classifier: Arc<dyn DocumentClassifier>,
+    pub embeddings: Arc<dyn EmbeddingProvider>,
+    pub answer_generator: Arc<dyn AnswerGenerator>,
+    /// Least similarity of a passage to a question for the assistant to answer from it. Depends
+    /// on the embedding model.
+    pub min_context_similarity: f32,
+    /// Languages OCR can read in, which uploads and user settings choose from.
+    pub ocr_languages: OcrLanguages,
+}
+
+impl DocumentUseCases {
+    /// The user's document if the policy allows the action on it. Documents in the trash, missing
+    /// documents and denied actions all give [`None`].
+    pub async fn find_document(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        action: DocumentAction,
+    ) -> Option<Document> {
+        self.authorized_document(user_id, id, action)
+            .await
+            .filter(|document| !document.is_trashed())
+    }
+
+    /// Like [`DocumentUseCases::find_document`], but only finds documents in the trash.
+    pub async fn find_trashed_document(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        action: DocumentAction,
+    ) -> Option<Document> {
+        self.authorized_document(user_id, id, action)
+            .await
+            .filter(|document| document.is_trashed())
+    }
+
+    /// The OCR languages chosen by their Tesseract codes, if OCR can read in each of them.
+    pub fn choose_ocr_languages(
+        &self,
+        codes: Vec<String>,
+    ) -> Result<OcrLanguages, InvalidOcrLanguages> {
+        OcrLanguages::new(codes)?.available_in(&self.ocr_languages)
+    }
+
+    /// Stores an uploaded file in the blob store and queues it for ingestion, unless the user
+    /// already has a document or a pending job for the same bytes. An upload without OCR
+    /// languages gets the user's default ones, if the user has set any.
+    pub async fn queue_upload(
+        &self,
+        upload: &UploadedDocumentInput,
+    ) -> Result<UploadOutcome, Box<dyn Error + Send + Sync>> {
+        let file = upload.file.clone();
+        let sha256 = tokio::task::spawn_blocking(move || file.content_address()).await??;
+        if let Some(document) = self
+            .document_repository
+            .find_document_by_sha256(&upload.user_id, &sha256)
+            .await
+        {
+            return Ok(UploadOutcome::Duplicate(Box::new(document)));
+        }
+        let jobs = &self.ingestion_queue.jobs;
+        if let Some(job) = jobs.find_pending_job(&upload.user_id, &sha256).await {
+            return Ok(UploadOutcome::AlreadyQueued(job));
+        }
+
+        let languages = match &upload.languages {
+            Some(languages) => Some(languages.clone()),
+            None => {
+                self.user_settings
+                    .get_settings(&upload.user_id)
+                    .await?
+                    .ocr_languages
+            }
+        };
+        let sha256 = self.blob_store.put_file(&upload.file).await?;
+        let blob = DocumentBlob {
+            sha256,
+            file_name: upload.file_name.clone(),
+            mime_type: upload.mime_type().to_string(),
+            size_bytes: upload.file.size_bytes(),
+        };
+        let job = self
+            .ingestion_queue
+            .enqueue(
+                IngestionJob::new(upload.user_id, blob)
+                    .with_preprocessing(upload.preprocessing.clone())
+                    .with_languages(languages),
+            )
+            .await?;
+        Ok(UploadOutcome::Queued(job))
+    }
+
+    /// Makes a thumbnail of an uploaded image or of the first page of an uploaded PDF and stores
+    /// it in the blob store. Returns its content address, or [`None`] for other files.
+    pub async fn store_thumbnail(
+        &self,
+        upload: &UploadedDocumentInput,
+    ) -> Result<Option<String>, Box<dyn Error + Send + Sync>> {
+        let image = if upload.is_pdf() {
+            self.pdf_renderer
+                .render_page(upload.file.path(), 1, THUMBNAIL_SIZE)
+                .await?
+        } else if is_thumbnailable_image(&upload.file.head()?) {
+            tokio::fs::read(upload.file.path()).await?
+        } else {
+            return Ok(None);
+        };
+        let thumbnail = tokio::task::spawn_blocking(move || make_thumbnail(&image)).await??;
+        Ok(Some(self.blob_store.put(&thumbnail).await?))
+    }
+
+    /// Reads the typed fields of a document from its extracted text, or [`None`] if it has no
+    /// text.
+    pub async fn extract_fields(
+        &self,
+        document: &Document,
+    ) -> Result<Option<DocumentFields>, Box<dyn Error>> {
+        let Some(text) = readable_text(document) else {
+            return Ok(None);
+        };
+        Ok(Some(self.field_extractor.extract_fields(text).await?))
+    }
+
+    /// Suggests which of its owner's categories a document belongs to, from its extracted text.
+    /// When the classifier fails, e.g. because Ollama is down, the categories' rules decide
+    /// instead. [`None`] if the document has no text, the owner no categories or none fits.
+    pub async fn classify_document(
+        &self,
+        document: &Document,
+    ) -> Result<Option<DocumentClassification>, Box<dyn Error + Send + Sync>> {
+        let Some(text) = readable_text(document) else {
+            return Ok(None);
+        };
+        let categories = self.categories.get_categories(&document.user_id).await?;
+        if categories.is_empty() {
+            return Ok(None);
+        }
+        match self.classifier.classify(text, &categories).await {
+            Ok(classification) => Ok(classification),
+            Err(e) => {
+                tracing::warn!(
+                    "Classifying document {} by rules, the classifier failed: {}",
+                    document.id,
+                    e
+                );
+                Ok(classify_by_rules(text, &categories))
+            }
+        }
+    }
+
+    /// Splits the title, summary and extracted text of a document into chunks and embeds them.
+    /// A document without any text gets [`DocumentChunk::nothing_to_embed`].
+    pub async fn embed_document(
+        &self,
+        document: &Document,
+    ) -> Result<Vec<DocumentChunk>, Box<dyn Error + Send + Sync>> {
+        let chunks = DocumentChunk::split(document);
+        if chunks.is_empty() {
+            return Ok(vec![DocumentChunk::nothing_to_embed(
+                self.embeddings.model(),
+            )]);
+        }
+        let texts: Vec<String> = chunks.iter().map(|chunk| chunk.text.clone()).collect();
+        let embeddings = self.embeddings.embed(&texts).await?;
+        if embeddings.len() != chunks.len() {
+            return Err(format!(
+                "Got {} embeddings for {} chunks of document {}",
+                embeddings.len(),
+                chunks.len(),
+                document.id
+            )
+            .into());
+        }
+        let model = self.embeddings.model();
+        Ok(chunks
+            .into_iter()
+            .zip(embeddings)
+            .map(|(chunk, embedding)| chunk.embedded(model, embedding))
+            .collect())
+    }
+
+    /// Embeds a document again after its title or content changed. When that fails its old
+    /// chunks are dropped, so that searches do not match the old text and the backfill embeds it
+    /// on the next start.
+    pub async fn reembed_document(
+        &self,
+        document: &Document,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let chunks = self.embed_document(document).await.unwrap_or_else(|e| {
+            tracing::warn!("No embeddings for document {}: {}", document.id, e);
+            vec![]
+        });
+        self.document_repository
+            .save_chunks(document, &chunks)
+            .await
+    }
+
+    /// The user's documents closest in meaning to the query, best matches first.
+    pub async fn semantic_search(
+        &self,
+        user_id: Uuid,
+        query: &str,
+        limit: u32,
+    ) -> Result<Vec<SemanticHit>, Box<dyn Error + Send + Sync>> {
+        let embedding = self
+            .embeddings
+            .embed(&[query.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or("No embedding for the query")?;
+        Ok(self
+            .document_repository
+            .semantic_search(&user_id, self.embeddings.model(), &embedding, &limit)
+            .await)
+    }
+
+    /// Passages of the user's documents closest to the question, to answer it from, if at least
+    /// [`DocumentUseCases::min_context_similarity`] similar to it. Only the user's own documents
+    /// outside the trash are searched.
+    pub async fn find_context(
+        &self,
+        user_id: Uuid,
+        question: &str,
+    ) -> Result<Vec<ContextPassage>, Box<dyn Error + Send + Sync>> {
+        let hits = self
+            .semantic_search(user_id, question, CONTEXT_PASSAGES)
+            .await?;
+        Ok(hits
+            .iter()
+            .filter(|hit| hit.score >= self.min_context_similarity)
+            .map(ContextPassage::from_hit)
+            .collect())
+    }
+
+    /// Answers a question from the user's documents, citing the passages the answer is based
+    /// on. The model is not asked when no document relates to the question.
+    pub async fn ask(
+        &self,
+        user_id: Uuid,
+        question: &str,
+    ) -> Result<AssistantAnswer, Box<dyn Error + Send + Sync>> {
+        let passages = self.find_context(user_id, question).await?;
+        if passages.is_empty() {
+            return Ok(AssistantAnswer::new(NO_CONTEXT_ANSWER, &passages));
+        }
+        let mut pieces = self.answer_generator.answer(question, &passages).await?;
+        let mut answer = String::new();
+        while let Some(piece) = pieces.next().await {
+            answer.push_str(&piece?);
+        }
+        Ok(AssistantAnswer::new(&answer, &passages))
+    }
+
+    /// One of the user's categories.
+    pub async fn find_category(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<Category>, Box<dyn Error + Send + Sync>> {
+        let categories = self.categories.get_categories(&user_id).await?;
+        Ok(categories.into_iter().find(|category| category.id == id))
+    }
+
+    async fn authorized_document(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        action: DocumentAction,
+    ) -> Option<Document> {
+        let document = self.document_repository.get_document(&user_id, id).await?;
+        if !self.policy.allows(user_id, action, &document) {
+            tracing::warn!("User {} denied {:?} on document {}", user_id, action, id);
+            return None;
+        }
+        Some(document)
+    }
+}
+
+/// The text read from the document, unless it is blank.
+fn readable_text(document: &Document) -> Option<&str> {
+    document
+        .extracted_text
+        .as_ref()
+        .map(|extracted| extracted.text.trim())
+        .filter(|text| !text.is_empty())
+}
+
+#[cfg(test)]
+impl DocumentUseCases {
+    /// Use cases over in-memory adapters that need no model or service. Tests swap in the ones
+    /// they care about with struct update syntax.
+    pub(crate) fn for_tests() -> Self {
+        use crate::infrastructure::{
+            category::category_collection::CategoryCollection,
+            document::document_collection::DocumentCollection,
+            extractive_answer_generator::ExtractiveAnswerGenerator,
+            hashing_embedding_provider::HashingEmbeddingProvider,
+            in_memory_blob_store::InMemoryBlobStore,
+            ingestion_job::ingestion_job_collection::IngestionJobCollection,
+            noop_document_text_reader::NoOpDocumentTextReader,
+            noop_pdf_page_renderer::NoOpPdfPageRenderer,
+            ollama_document_summarizer_adapter::OllamaDocumentSummarizerAdapter,
+            rule_document_classifier::RuleDocumentClassifier,
+            stub_document_field_extractor::StubDocumentFieldExtractor,
+            user_settings::user_settings_collection::UserSettingsCollection,
+        };
+
+        Self {
+            document_repository: Arc::new(DocumentCollection::new()),
+            reader: Arc::new(NoOpDocumentTextReader::new()),
+            summarizer: Arc::new(OllamaDocumentSummarizerAdapter::default()),
+            blob_store: Arc::new(InMemoryBlobStore::new()),
+            policy: DocumentPolicy::new(),
+            ingestion_queue: IngestionQueue::new(Arc::new(IngestionJobCollection::new())),
+            pdf_renderer: Arc::new(NoOpPdfPageRenderer::new()),
+            user_settings: Arc::new(UserSettingsCollection::new()),
+            field_extractor: Arc::new(StubDocumentFieldExtractor::new()),
+            categories: Arc::new(CategoryCollection::new()),
+            classifier: Arc::new(RuleDocumentClassifier::new()),
+            embeddings: Arc::new(HashingEmbeddingProvider::new()),
+            answer_generator: Arc::new(ExtractiveAnswerGenerator::new()),
+            // Related text scores about 0.3 with the hashing embeddings, unrelated text 0.
+            min_context_similarity: 0.1,
+            ocr_languages: OcrLanguages::default(),
+        }
+    }
 }
@@ -0,0 +1,110 @@
+use std::{error::Error, sync::Arc};
+
+use tokio::task::JoinHandle;
+
+use crate::application::document_use_cases::DocumentUseCases;
+
+/// Documents embedded per batch.
+const EMBED_BATCH_SIZE: u32 = 20;
+
+/**
+ * Embeds documents that have no chunks for the current embedding model yet: those stored before
+ * semantic search, those whose embedding failed at ingestion and all of them after the model
+ * changed. Their stored title, summary and extracted text are chunked as at ingestion.
+ */
+pub struct EmbedDocumentsCommand {
+    document_use_cases: Arc<DocumentUseCases>,
+}
+
+impl EmbedDocumentsCommand {
+    pub fn new(document_use_cases: Arc<DocumentUseCases>) -> Self {
+        EmbedDocumentsCommand { document_use_cases }
+    }
+
+    /// Embeds the next batch of documents and returns how many were embedded. Documents without
+    /// any text count too, as they are marked so that they are not picked again. Stops at the
+    /// first document that cannot be embedded, as the model is most likely unavailable.
+    pub async fn execute(&self) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let repository = &self.document_use_cases.document_repository;
+        let model = self.document_use_cases.embeddings.model();
+        let documents = repository
+            .documents_to_embed(model, &EMBED_BATCH_SIZE)
+            .await?;
+        let mut embedded = 0;
+        for document in documents {
+            let chunks = self.document_use_cases.embed_document(&document).await?;
+            repository.save_chunks(&document, &chunks).await?;
+            embedded += 1;
+        }
+        Ok(embedded)
+    }
+
+    /// Runs batches in the background until no document is left to embed or embedding fails.
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut total = 0;
+            loop {
+                match self.execute().await {
+                    Ok(0) => break,
+                    Ok(embedded) => total += embedded,
+                    Err(e) => {
+                        tracing::warn!("Stopped embedding stored documents: {}", e);
+                        break;
+                    }
+                }
+            }
+            if total > 0 {
+                tracing::info!("Embedded {} stored documents", total);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::application::document_repository::DocumentRepository;
+    use crate::domain::document::Document;
+    use crate::domain::document_chunk::DocumentChunk;
+    use crate::infrastructure::document::document_collection::DocumentCollection;
+
+    #[tokio::test]
+    async fn test_embeds_stored_documents_once() {
+        // Given
+        let repo = Arc::new(DocumentCollection::new());
+        let user_id = Uuid::new_v4();
+        for (title, content) in [
+            ("Auto policy", "Insurance for the family car"),
+            ("", " "),
+            ("Power bill", "Electricity for March"),
+        ] {
+            repo.save_document(Document::new(title, content, user_id))
+                .await
+                .unwrap();
+        }
+        let command = EmbedDocumentsCommand::new(Arc::new(DocumentUseCases {
+            document_repository: repo.clone(),
+            ..DocumentUseCases::for_tests()
+        }));
+
+        // When
+        let first = command.execute().await.unwrap();
+        let second = command.execute().await.unwrap();
+
+        // Then
+        assert_eq!(first, 3);
+        assert_eq!(second, 0);
+        let documents = repo.documents.lock().await;
+        let chunks = &documents[0].chunks;
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].text, "Auto policy Insurance for the family car");
+        assert_eq!(chunks[0].model, "hashing-256");
+        // The document without text is marked, not embedded
+        assert_eq!(
+            documents[1].chunks,
+            [DocumentChunk::nothing_to_embed("hashing-256")]
+        );
+    }
+}
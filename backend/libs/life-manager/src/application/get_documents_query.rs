@@ -2,52 +2,68 @@ use std::sync::Arc;
 
 use uuid::Uuid;
 
-use crate::{application::document_repository::DocumentRepository, domain::document::Document};
+use crate::{
+    application::document_repository::DocumentRepository,
+    domain::{
+        document_listing::{
+            DateRange, DocumentCursor, DocumentListing, DocumentPage, DocumentSort,
+        },
+        tag::TagFilter,
+    },
+};
 
-pub struct GetDocumentsQuery {
+/**
+ * Fetches one page of the user's documents. Asks the repository for one document more than the
+ * page size to find out whether another page follows.
+ */
+pub struct ListDocumentsQuery {
     doc_repo: Arc<dyn DocumentRepository>,
     user_id: Uuid,
     limit: u32,
+    sort: DocumentSort,
+    after: Option<DocumentCursor>,
+    tags: TagFilter,
+    dates: DateRange,
 }
 
-impl GetDocumentsQuery {
-    pub fn new(doc_repo: Arc<dyn DocumentRepository>, user_id: Uuid, limit: u32) -> Self {
-        GetDocumentsQuery {
-            doc_repo,
-            user_id,
-            limit,
-        }
-    }
-
-    pub async fn execute(&self) -> Vec<Document> {
-        self.doc_repo
-            .get_documents(&self.user_id, &self.limit)
-            .await
-    }
-}
-
-pub struct GetDocumentsTitleCursorQuery {
-    query: GetDocumentsQuery,
-    title: String,
-}
-
-impl GetDocumentsTitleCursorQuery {
+impl ListDocumentsQuery {
     pub fn new(
         doc_repo: Arc<dyn DocumentRepository>,
         user_id: Uuid,
-        title: String,
+        sort: DocumentSort,
+        after: Option<DocumentCursor>,
+        tags: TagFilter,
+        dates: DateRange,
         limit: u32,
     ) -> Self {
-        GetDocumentsTitleCursorQuery {
-            query: GetDocumentsQuery::new(doc_repo, user_id, limit),
-            title,
+        ListDocumentsQuery {
+            doc_repo,
+            user_id,
+            limit,
+            sort,
+            after,
+            tags,
+            dates,
         }
     }
 
-    pub async fn execute(&self) -> Vec<Document> {
-        self.query
-            .doc_repo
-            .get_documents_title_cursor(&self.query.user_id, &self.query.limit, &self.title)
-            .await
+    pub async fn execute(&self) -> DocumentPage {
+        let listing = DocumentListing {
+            sort: self.sort,
+            after: self.after.clone(),
+            tags: self.tags.clone(),
+            dates: self.dates,
+            limit: self.limit + 1,
+        };
+        let mut items = self.doc_repo.list_documents(&self.user_id, &listing).await;
+        let next_cursor = if items.len() > self.limit as usize {
+            items.truncate(self.limit as usize);
+            items
+                .last()
+                .map(|last| DocumentCursor::after(last, &self.sort).encode())
+        } else {
+            None
+        };
+        DocumentPage { items, next_cursor }
     }
 }
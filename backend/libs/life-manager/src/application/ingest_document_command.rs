@@ -0,0 +1,765 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::{TimeDelta, Utc};
+use tokio::task::JoinHandle;
+
+use crate::{
+    application::{document_use_cases::DocumentUseCases, retry_policy::RetryPolicy},
+    domain::{
+        document::Document,
+        document_similarity::DEFAULT_MAX_DISTANCE,
+        ingestion_event::{IngestionEvent, IngestionStage},
+        ingestion_failure::IngestionFailure,
+        ingestion_job::IngestionJob,
+        uploaded_document_input::UploadedDocumentInput,
+    },
+};
+
+/// How often idle workers look at the queue without being woken up.
+const JOB_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Likely duplicates recorded on a job that created a document.
+const SIMILAR_LIMIT: u32 = 10;
+
+/**
+ * Turns a queued upload into a document: reads the text of the stored file, summarizes it,
+ * makes a thumbnail, reads typed fields such as the amount of a bill, suggests one of the
+ * user's categories, embeds chunks of the text for semantic search and saves the document,
+ * then records the outcome on the job, with documents that read almost the same as the new
+ * one. Retryable failures are queued again according to the [`RetryPolicy`].
+ * Each stage is published on the queue's event bus.
+ * +--------+     +-----------+     +-----------+     +--------+     +--------+
+ * |        |     |           |     |           |     |        |     |        |
+ * | Worker |---->| BlobStore |---->| Tesseract |---->| Ollama |---->| SQLite |
+ * |        |     |           |     |           |     |        |     |        |
+ * +--------+     +-----------+     +-----------+     +--------+     +--------+
+ */
+pub struct IngestDocumentCommand {
+    document_use_cases: Arc<DocumentUseCases>,
+    retry_policy: RetryPolicy,
+}
+
+impl IngestDocumentCommand {
+    pub fn new(document_use_cases: Arc<DocumentUseCases>, retry_policy: RetryPolicy) -> Self {
+        IngestDocumentCommand {
+            document_use_cases,
+            retry_policy,
+        }
+    }
+
+    /// Runs a claimed job and stores it as succeeded, queued for another attempt or failed.
+    pub async fn execute(&self, mut job: IngestionJob) -> IngestionJob {
+        tracing::info!(
+            "Ingesting {} for job {} (attempt {})",
+            job.blob.file_name,
+            job.id,
+            job.attempts
+        );
+        let stage = match self.ingest(&job).await {
+            Ok(document) => {
+                tracing::info!("Job {} created document {}", job.id, document.id);
+                let similar = self
+                    .document_use_cases
+                    .document_repository
+                    .find_similar_documents(&document, DEFAULT_MAX_DISTANCE, &SIMILAR_LIMIT)
+                    .await;
+                job.succeed(
+                    document.id,
+                    similar.iter().map(|similar| similar.document.id).collect(),
+                );
+                IngestionStage::Saved
+            }
+            Err(failure) if self.retry_policy.should_retry(&failure, job.attempts) => {
+                let delay = self.retry_policy.delay_after(job.attempts);
+                tracing::warn!(
+                    "Attempt {} of job {} failed, retrying in {:?}: {}",
+                    job.attempts,
+                    job.id,
+                    delay,
+                    failure
+                );
+                let delay = TimeDelta::from_std(delay).unwrap_or(TimeDelta::MAX);
+                job.retry_at(failure.message, Utc::now() + delay);
+                IngestionStage::Failed
+            }
+            Err(failure) => {
+                tracing::error!("Job {} failed: {}", job.id, failure);
+                job.fail(failure.message);
+                IngestionStage::Failed
+            }
+        };
+        let queue = &self.document_use_cases.ingestion_queue;
+        if let Err(e) = queue.jobs.update_job(&job).await {
+            tracing::error!("Error recording outcome of job {}: {}", job.id, e);
+        }
+        queue.events.publish(IngestionEvent::new(&job, stage));
+        job
+    }
+
+    async fn ingest(&self, job: &IngestionJob) -> Result<Document, IngestionFailure> {
+        // Two uploads of the same file can both be queued before either is saved.
+        if let Some(document) = self
+            .document_use_cases
+            .document_repository
+            .find_document_by_sha256(&job.user_id, &job.blob.sha256)
+            .await
+        {
+            tracing::info!("Job {} is a duplicate of document {}", job.id, document.id);
+            return Ok(document);
+        }
+        let file = self
+            .document_use_cases
+            .blob_store
+            .get_file(&job.blob.sha256)
+            .await
+            .map_err(|e| IngestionFailure::from_error("Error reading uploaded file", &*e))?
+            .ok_or_else(|| {
+                IngestionFailure::permanent(format!("Uploaded file {} is missing", job.blob.sha256))
+            })?;
+        let uploaded_document_input =
+            UploadedDocumentInput::new(job.blob.file_name.clone(), file, job.user_id)
+                .map_err(|e| IngestionFailure::from_error("Error reading uploaded file", &e))?
+                .with_preprocessing(job.preprocessing.clone())
+                .with_languages(job.languages.clone());
+        let events = &self.document_use_cases.ingestion_queue.events;
+        let extracted = Document::read_text(
+            &uploaded_document_input,
+            self.document_use_cases.reader.clone(),
+        )
+        .await?;
+        events.publish(IngestionEvent::new(job, IngestionStage::TextExtracted));
+        events.publish(IngestionEvent::new(job, IngestionStage::Summarizing));
+        let mut document = Document::from_text(
+            extracted,
+            job.user_id,
+            self.document_use_cases.summarizer.clone(),
+        )
+        .await?;
+        document.blob = Some(job.blob.clone());
+        // A document without a preview is still worth saving.
+        document.thumbnail_sha256 = self
+            .document_use_cases
+            .store_thumbnail(&uploaded_document_input)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("No thumbnail for job {}: {}", job.id, e);
+                None
+            });
+        // So is one whose fields could not be read, unless asking again may read them.
+        document.fields = match self.document_use_cases.extract_fields(&document).await {
+            Ok(fields) => fields,
+            Err(e) => {
+                let failure = IngestionFailure::from_error("Error reading document fields", &*e);
+                let retryable = e
+                    .downcast_ref::<IngestionFailure>()
+                    .is_some_and(|failure| failure.retryable);
+                if retryable && self.retry_policy.should_retry(&failure, job.attempts) {
+                    return Err(failure);
+                }
+                tracing::warn!("No fields for job {}: {}", job.id, e);
+                None
+            }
+        };
+        document.classification = self
+            .document_use_cases
+            .classify_document(&document)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("No category for job {}: {}", job.id, e);
+                None
+            });
+        // Documents saved without chunks are embedded by the backfill on the next start.
+        document.chunks = self
+            .document_use_cases
+            .embed_document(&document)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("No embeddings for job {}: {}", job.id, e);
+                vec![]
+            });
+        document.print_details();
+        let saved = self
+            .document_use_cases
+            .document_repository
+            .save_document(document)
+            .await
+            .map_err(|e| IngestionFailure::from_error("Error saving document", &*e))?;
+        Ok(saved)
+    }
+
+    /// Starts `workers` background tasks that run jobs from the queue one at a time.
+    pub fn spawn(self, workers: usize) -> Vec<JoinHandle<()>> {
+        let command = Arc::new(self);
+        (0..workers)
+            .map(|_| {
+                let command = command.clone();
+                tokio::spawn(async move {
+                    let queue = command.document_use_cases.ingestion_queue.clone();
+                    loop {
+                        let job = queue.next_job(JOB_POLL_INTERVAL).await;
+                        command.execute(job).await;
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+    use std::io::Cursor;
+
+    use async_trait::async_trait;
+    use chrono::NaiveDate;
+    use image::{DynamicImage, ImageFormat, RgbImage};
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::domain::blob_store::DocumentBlob;
+    use crate::domain::category::Category;
+    use crate::domain::document_classification::{ClassificationStatus, DocumentClassification};
+    use crate::domain::document_classifier::DocumentClassifier;
+    use crate::domain::document_field_extractor::DocumentFieldExtractor;
+    use crate::domain::document_fields::{DocumentFields, DocumentType};
+    use crate::domain::document_similarity::simhash;
+    use crate::domain::document_summarizer::{DocumentSummarizer, DocumentSummaryResult};
+    use crate::domain::document_text_reader::{DocumentTextReader, ExtractedText};
+    use crate::domain::ingestion_job::IngestionJobStatus;
+
+    struct MockTextReader {
+        failure: Option<IngestionFailure>,
+    }
+
+    #[async_trait]
+    impl DocumentTextReader for MockTextReader {
+        async fn read_image(
+            &self,
+            uploaded_document_input: &UploadedDocumentInput,
+        ) -> Result<ExtractedText, Box<dyn Error>> {
+            if let Some(failure) = &self.failure {
+                return Err(Box::new(failure.clone()));
+            }
+            Ok(ExtractedText {
+                text: String::from_utf8_lossy(&uploaded_document_input.file.read()?).to_string(),
+                reader: "mock".to_string(),
+                page_count: Some(1),
+                ocr_used: false,
+                pages: vec![],
+                languages: vec![],
+                words: vec![],
+                confidence: None,
+            })
+        }
+    }
+
+    struct MockSummarizer;
+
+    #[async_trait]
+    impl DocumentSummarizer for MockSummarizer {
+        async fn summarize(&self, text: &str) -> Result<DocumentSummaryResult, Box<dyn Error>> {
+            Ok(DocumentSummaryResult {
+                summary: text.to_string(),
+                title: String::from("Receipt"),
+            })
+        }
+    }
+
+    struct GivenUpload {
+        document_use_cases: Arc<DocumentUseCases>,
+        job: IngestionJob,
+    }
+
+    fn given_retry_policy() -> RetryPolicy {
+        RetryPolicy::new(2, Duration::from_secs(60), Duration::from_secs(60))
+    }
+
+    /// A classifier whose model is not reachable.
+    struct UnreachableClassifier;
+
+    #[async_trait]
+    impl DocumentClassifier for UnreachableClassifier {
+        async fn classify(
+            &self,
+            _text: &str,
+            _categories: &[Category],
+        ) -> Result<Option<DocumentClassification>, Box<dyn std::error::Error>> {
+            Err("Connection refused".into())
+        }
+    }
+
+    /// A field extractor whose model answers with invalid JSON.
+    struct InvalidJsonFieldExtractor;
+
+    #[async_trait]
+    impl DocumentFieldExtractor for InvalidJsonFieldExtractor {
+        async fn extract_fields(&self, _text: &str) -> Result<DocumentFields, Box<dyn Error>> {
+            Err(Box::new(IngestionFailure::retryable(
+                "Ollama answered with invalid JSON",
+            )))
+        }
+    }
+
+    fn given_document_use_cases(reader_failure: Option<IngestionFailure>) -> Arc<DocumentUseCases> {
+        Arc::new(DocumentUseCases {
+            reader: Arc::new(MockTextReader {
+                failure: reader_failure,
+            }),
+            summarizer: Arc::new(MockSummarizer),
+            ..DocumentUseCases::for_tests()
+        })
+    }
+
+    async fn given_queued_file(
+        document_use_cases: &DocumentUseCases,
+        file_name: &str,
+        mime_type: &str,
+        data: &[u8],
+    ) -> IngestionJob {
+        let blob = DocumentBlob {
+            sha256: document_use_cases.blob_store.put(data).await.unwrap(),
+            file_name: file_name.to_string(),
+            mime_type: mime_type.to_string(),
+            size_bytes: data.len() as u64,
+        };
+        document_use_cases
+            .ingestion_queue
+            .enqueue(IngestionJob::new(Uuid::new_v4(), blob))
+            .await
+            .unwrap()
+    }
+
+    async fn given_queued_upload(reader_failure: Option<IngestionFailure>) -> GivenUpload {
+        let document_use_cases = given_document_use_cases(reader_failure);
+        let job = given_queued_file(
+            &document_use_cases,
+            "receipt.txt",
+            "text/plain",
+            b"Total: 12.50",
+        )
+        .await;
+        GivenUpload {
+            document_use_cases,
+            job,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_workers_turn_queued_uploads_into_documents() {
+        // Given
+        let GivenUpload {
+            document_use_cases,
+            job,
+        } = given_queued_upload(None).await;
+
+        // When
+        let workers =
+            IngestDocumentCommand::new(document_use_cases.clone(), given_retry_policy()).spawn(2);
+        let mut stored = job.clone();
+        for _ in 0..100 {
+            stored = document_use_cases
+                .ingestion_queue
+                .jobs
+                .get_job(&job.user_id, job.id)
+                .await
+                .unwrap();
+            if stored.is_finished() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        workers.iter().for_each(JoinHandle::abort);
+
+        // Then
+        assert_eq!(stored.status, IngestionJobStatus::Succeeded);
+        let document = document_use_cases
+            .document_repository
+            .get_document(&job.user_id, stored.document_id.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(document.title, "Receipt");
+        assert_eq!(document.content, "Total: 12.50");
+        assert_eq!(document.blob, Some(job.blob));
+    }
+
+    #[tokio::test]
+    async fn test_execute_publishes_each_stage() {
+        // Given
+        let GivenUpload {
+            document_use_cases, ..
+        } = given_queued_upload(None).await;
+        let queue = &document_use_cases.ingestion_queue;
+        let mut events = queue.events.subscribe();
+        let job = queue.next_job(Duration::from_millis(10)).await;
+
+        // When
+        let job = IngestDocumentCommand::new(document_use_cases.clone(), given_retry_policy())
+            .execute(job)
+            .await;
+
+        // Then
+        let mut stages = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            assert_eq!(event.job_id, job.id);
+            stages.push(event.stage);
+        }
+        assert_eq!(
+            stages,
+            [
+                IngestionStage::TextExtracted,
+                IngestionStage::Summarizing,
+                IngestionStage::Saved
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_permanent_failure_fails_the_job() {
+        // Given
+        let GivenUpload {
+            document_use_cases, ..
+        } = given_queued_upload(Some(IngestionFailure::permanent("Could not read PDF"))).await;
+        let job = document_use_cases
+            .ingestion_queue
+            .next_job(Duration::from_millis(10))
+            .await;
+
+        // When
+        let job = IngestDocumentCommand::new(document_use_cases.clone(), given_retry_policy())
+            .execute(job)
+            .await;
+
+        // Then
+        assert_eq!(job.status, IngestionJobStatus::Failed);
+        assert!(job.error.unwrap().contains("Could not read PDF"));
+        let stored = document_use_cases
+            .ingestion_queue
+            .jobs
+            .get_job(&job.user_id, job.id)
+            .await
+            .unwrap();
+        assert_eq!(stored.status, IngestionJobStatus::Failed);
+        assert!(
+            document_use_cases
+                .document_repository
+                .get_documents(&job.user_id, &10)
+                .await
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retryable_failure_is_queued_again_with_a_delay() {
+        // Given
+        let GivenUpload {
+            document_use_cases, ..
+        } = given_queued_upload(Some(IngestionFailure::retryable(
+            "Tesseract is unreachable",
+        )))
+        .await;
+        let queue = &document_use_cases.ingestion_queue;
+        let job = queue.next_job(Duration::from_millis(10)).await;
+
+        // When
+        let job = IngestDocumentCommand::new(document_use_cases.clone(), given_retry_policy())
+            .execute(job)
+            .await;
+
+        // Then
+        assert_eq!(job.status, IngestionJobStatus::Queued);
+        assert_eq!(job.attempts, 1);
+        assert!(job.error.unwrap().contains("Tesseract is unreachable"));
+        assert!(job.next_attempt_at >= Utc::now() + TimeDelta::seconds(29));
+        assert!(queue.jobs.claim_next_job().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retryable_failure_on_the_last_attempt_fails_the_job() {
+        // Given
+        let GivenUpload {
+            document_use_cases, ..
+        } = given_queued_upload(Some(IngestionFailure::retryable(
+            "Tesseract is unreachable",
+        )))
+        .await;
+        let mut job = document_use_cases
+            .ingestion_queue
+            .next_job(Duration::from_millis(10))
+            .await;
+        job.attempts = given_retry_policy().max_attempts;
+
+        // When
+        let job = IngestDocumentCommand::new(document_use_cases.clone(), given_retry_policy())
+            .execute(job)
+            .await;
+
+        // Then
+        assert_eq!(job.status, IngestionJobStatus::Failed);
+        assert!(job.error.unwrap().contains("Tesseract is unreachable"));
+    }
+
+    #[tokio::test]
+    async fn test_job_for_a_file_that_is_already_a_document_is_linked_without_reading_it() {
+        // Given
+        let GivenUpload {
+            document_use_cases, ..
+        } = given_queued_upload(Some(IngestionFailure::permanent("Reader must not run"))).await;
+        let job = document_use_cases
+            .ingestion_queue
+            .next_job(Duration::from_millis(10))
+            .await;
+        let mut existing = Document::new("Receipt", "Total: 12.50", job.user_id);
+        existing.blob = Some(job.blob.clone());
+        let existing = document_use_cases
+            .document_repository
+            .save_document(existing)
+            .await
+            .unwrap();
+
+        // When
+        let job = IngestDocumentCommand::new(document_use_cases.clone(), given_retry_policy())
+            .execute(job)
+            .await;
+
+        // Then
+        assert_eq!(job.status, IngestionJobStatus::Succeeded);
+        assert_eq!(job.document_id, Some(existing.id));
+    }
+
+    #[tokio::test]
+    async fn test_succeeded_job_lists_documents_with_similar_text() {
+        // Given
+        let GivenUpload {
+            document_use_cases, ..
+        } = given_queued_upload(None).await;
+        let job = document_use_cases
+            .ingestion_queue
+            .next_job(Duration::from_millis(10))
+            .await;
+        let mut earlier_scan = Document::new("Receipt", "", job.user_id);
+        earlier_scan.text_simhash = simhash("TOTAL 12.50");
+        let earlier_scan = document_use_cases
+            .document_repository
+            .save_document(earlier_scan)
+            .await
+            .unwrap();
+
+        // When
+        let job = IngestDocumentCommand::new(document_use_cases.clone(), given_retry_policy())
+            .execute(job)
+            .await;
+
+        // Then
+        assert_eq!(job.status, IngestionJobStatus::Succeeded);
+        assert_eq!(job.similar_document_ids, [earlier_scan.id]);
+    }
+
+    #[tokio::test]
+    async fn test_image_upload_gets_a_thumbnail() {
+        // Given
+        let document_use_cases = given_document_use_cases(None);
+        let mut png = Cursor::new(Vec::new());
+        DynamicImage::ImageRgb8(RgbImage::new(600, 800))
+            .write_to(&mut png, ImageFormat::Png)
+            .unwrap();
+        given_queued_file(&document_use_cases, "scan.png", "image/png", png.get_ref()).await;
+        let job = document_use_cases
+            .ingestion_queue
+            .next_job(Duration::from_millis(10))
+            .await;
+
+        // When
+        let job = IngestDocumentCommand::new(document_use_cases.clone(), given_retry_policy())
+            .execute(job)
+            .await;
+
+        // Then
+        assert_eq!(job.status, IngestionJobStatus::Succeeded);
+        let document = document_use_cases
+            .document_repository
+            .get_document(&job.user_id, job.document_id.unwrap())
+            .await
+            .unwrap();
+        let thumbnail = document_use_cases
+            .blob_store
+            .get(&document.thumbnail_sha256.unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        let thumbnail = image::load_from_memory_with_format(&thumbnail, ImageFormat::WebP).unwrap();
+        assert_eq!((thumbnail.width(), thumbnail.height()), (192, 256));
+    }
+
+    #[tokio::test]
+    async fn test_pdf_that_cannot_be_rendered_is_saved_without_a_thumbnail() {
+        // Given
+        let document_use_cases = given_document_use_cases(None);
+        given_queued_file(
+            &document_use_cases,
+            "bill.pdf",
+            "application/pdf",
+            b"%PDF-1.4",
+        )
+        .await;
+        let job = document_use_cases
+            .ingestion_queue
+            .next_job(Duration::from_millis(10))
+            .await;
+
+        // When
+        let job = IngestDocumentCommand::new(document_use_cases.clone(), given_retry_policy())
+            .execute(job)
+            .await;
+
+        // Then
+        assert_eq!(job.status, IngestionJobStatus::Succeeded);
+        let document = document_use_cases
+            .document_repository
+            .get_document(&job.user_id, job.document_id.unwrap())
+            .await
+            .unwrap();
+        assert!(document.thumbnail_sha256.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_bill_upload_gets_its_fields() {
+        // Given
+        let document_use_cases = given_document_use_cases(None);
+        given_queued_file(
+            &document_use_cases,
+            "bill.txt",
+            "text/plain",
+            b"City Power invoice\nAmount due: EUR 84.20\nDue date: 2026-11-01",
+        )
+        .await;
+        let job = document_use_cases
+            .ingestion_queue
+            .next_job(Duration::from_millis(10))
+            .await;
+
+        // When
+        let job = IngestDocumentCommand::new(document_use_cases.clone(), given_retry_policy())
+            .execute(job)
+            .await;
+
+        // Then
+        assert_eq!(job.status, IngestionJobStatus::Succeeded);
+        let document = document_use_cases
+            .document_repository
+            .get_document(&job.user_id, job.document_id.unwrap())
+            .await
+            .unwrap();
+        let fields = document.fields.unwrap();
+        assert_eq!(fields.document_type, DocumentType::Bill);
+        assert_eq!(fields.total_amount, Some(84.2));
+        assert_eq!(fields.currency.as_deref(), Some("EUR"));
+        assert_eq!(fields.due_date, NaiveDate::from_ymd_opt(2026, 11, 1));
+    }
+
+    #[tokio::test]
+    async fn test_fields_are_read_again_until_the_last_attempt() {
+        // Given
+        let document_use_cases = Arc::new(DocumentUseCases {
+            field_extractor: Arc::new(InvalidJsonFieldExtractor),
+            ..(*given_document_use_cases(None)).clone()
+        });
+        given_queued_file(
+            &document_use_cases,
+            "bill.txt",
+            "text/plain",
+            b"Amount due: EUR 84.20",
+        )
+        .await;
+        let queue = &document_use_cases.ingestion_queue;
+        let job = queue.next_job(Duration::from_millis(10)).await;
+        let command = IngestDocumentCommand::new(document_use_cases.clone(), given_retry_policy());
+
+        // When
+        let mut job = command.execute(job).await;
+        let retried = job.clone();
+        job.attempts = given_retry_policy().max_attempts;
+        let last = command.execute(job).await;
+
+        // Then
+        assert_eq!(retried.status, IngestionJobStatus::Queued);
+        assert!(retried.error.unwrap().contains("invalid JSON"));
+        assert_eq!(last.status, IngestionJobStatus::Succeeded);
+        let document = document_use_cases
+            .document_repository
+            .get_document(&last.user_id, last.document_id.unwrap())
+            .await
+            .unwrap();
+        assert!(document.fields.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_upload_is_classified_by_rules_when_the_classifier_fails() {
+        // Given
+        let document_use_cases = Arc::new(DocumentUseCases {
+            classifier: Arc::new(UnreachableClassifier),
+            ..(*given_document_use_cases(None)).clone()
+        });
+        let job = given_queued_file(
+            &document_use_cases,
+            "power.txt",
+            "text/plain",
+            b"City Power\nElectricity used: 312 kWh",
+        )
+        .await;
+        let category = Category::new(
+            job.user_id,
+            "Utility bill",
+            "Power, water and gas bills",
+            &["kwh".to_string()],
+            &["bills".to_string()],
+        )
+        .unwrap();
+        document_use_cases
+            .categories
+            .save_category(&category)
+            .await
+            .unwrap();
+        let job = document_use_cases
+            .ingestion_queue
+            .next_job(Duration::from_millis(10))
+            .await;
+
+        // When
+        let job = IngestDocumentCommand::new(document_use_cases.clone(), given_retry_policy())
+            .execute(job)
+            .await;
+
+        // Then
+        assert_eq!(job.status, IngestionJobStatus::Succeeded);
+        let document = document_use_cases
+            .document_repository
+            .get_document(&job.user_id, job.document_id.unwrap())
+            .await
+            .unwrap();
+        let classification = document.classification.unwrap();
+        assert_eq!(classification.category_id, category.id);
+        assert_eq!(classification.tags, ["bills"]);
+        assert_eq!(classification.confidence, 0.5);
+        assert_eq!(classification.status, ClassificationStatus::Suggested);
+        assert!(document.tags.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recover_requeues_running_jobs() {
+        // Given
+        let GivenUpload {
+            document_use_cases,
+            job,
+        } = given_queued_upload(None).await;
+        let queue = &document_use_cases.ingestion_queue;
+        queue.next_job(Duration::from_millis(10)).await;
+        assert!(queue.jobs.claim_next_job().await.unwrap().is_none());
+
+        // When
+        queue.recover().await;
+
+        // Then
+        let claimed = queue.jobs.claim_next_job().await.unwrap().unwrap();
+        assert_eq!(claimed.id, job.id);
+        assert_eq!(claimed.status, IngestionJobStatus::Running);
+    }
+}
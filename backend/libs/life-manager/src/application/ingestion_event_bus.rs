@@ -0,0 +1,38 @@
+use tokio::sync::broadcast;
+
+use crate::domain::ingestion_event::IngestionEvent;
+
+/// Events kept for a subscriber that falls behind before the oldest are dropped.
+const EVENT_BUS_CAPACITY: usize = 256;
+
+/**
+ * In-process broadcast of ingestion progress. The pipeline publishes every stage; each open
+ * event stream subscribes and picks out its user's events. Events are not stored, so a
+ * subscriber only sees what happens while it is connected.
+ */
+#[derive(Clone)]
+pub struct IngestionEventBus {
+    sender: broadcast::Sender<IngestionEvent>,
+}
+
+impl IngestionEventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        IngestionEventBus { sender }
+    }
+
+    pub fn publish(&self, event: IngestionEvent) {
+        // Sending only fails when nobody is listening, which is fine.
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<IngestionEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for IngestionEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,39 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::ingestion_job::{IngestionJob, IngestionJobStatus};
+
+/**
+ * Port for the persistent queue of ingestion jobs. Jobs are claimed oldest first, and claiming
+ * is atomic so two workers never run the same job.
+ */
+#[async_trait]
+pub trait IngestionJobRepository: Sync + Send {
+    async fn save_job(
+        &self,
+        job: IngestionJob,
+    ) -> Result<IngestionJob, Box<dyn Error + Send + Sync>>;
+    /// The user's job with the given ID.
+    async fn get_job(&self, user_id: &Uuid, id: Uuid) -> Option<IngestionJob>;
+    /// The user's oldest queued or running job for the file with the SHA-256.
+    async fn find_pending_job(&self, user_id: &Uuid, sha256: &str) -> Option<IngestionJob>;
+    /// The user's jobs, optionally only those with `status`, most recently updated first.
+    async fn list_jobs(
+        &self,
+        user_id: &Uuid,
+        status: Option<IngestionJobStatus>,
+        limit: &u32,
+    ) -> Vec<IngestionJob>;
+    /// Marks the oldest queued job whose next attempt is due as running, counts the attempt and
+    /// returns the job, or [`None`] if no job is due.
+    async fn claim_next_job(&self) -> Result<Option<IngestionJob>, Box<dyn Error + Send + Sync>>;
+    /// Writes the job's status, document and similar documents, error and next attempt.
+    async fn update_job(&self, job: &IngestionJob) -> Result<(), Box<dyn Error + Send + Sync>>;
+    /// Puts jobs that were left running, e.g. by a crash, back in the queue. Returns how many.
+    async fn requeue_running_jobs(&self) -> Result<usize, Box<dyn Error + Send + Sync>>;
+    /// Whether a job of any user may still read the blob: one that has not succeeded, since
+    /// failed jobs can be retried.
+    async fn references_blob(&self, sha256: &str) -> Result<bool, Box<dyn Error + Send + Sync>>;
+}
@@ -0,0 +1,82 @@
+use std::{error::Error, sync::Arc, time::Duration};
+
+use tokio::sync::Notify;
+
+use crate::{
+    application::{
+        ingestion_event_bus::IngestionEventBus, ingestion_job_repository::IngestionJobRepository,
+    },
+    domain::{
+        ingestion_event::{IngestionEvent, IngestionStage},
+        ingestion_job::IngestionJob,
+    },
+};
+
+/**
+ * Hands ingestion jobs from the upload handler to the workers. Jobs are persisted before a worker
+ * is woken up, so nothing is lost if the process stops in between. Progress of the jobs is
+ * published on `events`.
+ */
+#[derive(Clone)]
+pub struct IngestionQueue {
+    pub jobs: Arc<dyn IngestionJobRepository>,
+    pub events: IngestionEventBus,
+    wakeup: Arc<Notify>,
+}
+
+impl IngestionQueue {
+    pub fn new(jobs: Arc<dyn IngestionJobRepository>) -> Self {
+        IngestionQueue {
+            jobs,
+            events: IngestionEventBus::new(),
+            wakeup: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Stores the job and wakes up a worker.
+    pub async fn enqueue(
+        &self,
+        job: IngestionJob,
+    ) -> Result<IngestionJob, Box<dyn Error + Send + Sync>> {
+        let job = self.jobs.save_job(job).await?;
+        self.events
+            .publish(IngestionEvent::new(&job, IngestionStage::Received));
+        self.wakeup.notify_one();
+        Ok(job)
+    }
+
+    /// Waits for the next queued job that is due and claims it. The queue is also checked every
+    /// `poll_interval`, so recovered jobs and retries are picked up without a wake-up.
+    pub async fn next_job(&self, poll_interval: Duration) -> IngestionJob {
+        loop {
+            match self.jobs.claim_next_job().await {
+                Ok(Some(job)) => return job,
+                Ok(None) => {}
+                Err(e) => tracing::error!("Error claiming ingestion job: {}", e),
+            }
+            let _ = tokio::time::timeout(poll_interval, self.wakeup.notified()).await;
+        }
+    }
+
+    /// Queues a failed job again with a fresh set of attempts and wakes up a worker.
+    pub async fn retry(
+        &self,
+        mut job: IngestionJob,
+    ) -> Result<IngestionJob, Box<dyn Error + Send + Sync>> {
+        job.retry_now();
+        self.jobs.update_job(&job).await?;
+        self.events
+            .publish(IngestionEvent::new(&job, IngestionStage::Received));
+        self.wakeup.notify_one();
+        Ok(job)
+    }
+
+    /// Requeues the jobs an earlier process did not finish.
+    pub async fn recover(&self) {
+        match self.jobs.requeue_running_jobs().await {
+            Ok(0) => {}
+            Ok(requeued) => tracing::info!("Requeued {} unfinished ingestion jobs", requeued),
+            Err(e) => tracing::error!("Error requeuing ingestion jobs: {}", e),
+        }
+    }
+}
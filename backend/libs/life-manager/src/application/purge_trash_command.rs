@@ -0,0 +1,170 @@
+use std::{collections::BTreeSet, error::Error, sync::Arc, time::Duration};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use tokio::task::JoinHandle;
+
+use crate::application::document_use_cases::DocumentUseCases;
+
+/**
+ * Permanently deletes documents that have been in the trash for longer than the retention
+ * period, along with their files and thumbnails once nothing else uses them.
+ */
+pub struct PurgeTrashCommand {
+    document_use_cases: Arc<DocumentUseCases>,
+    retention: TimeDelta,
+}
+
+impl PurgeTrashCommand {
+    pub fn new(document_use_cases: Arc<DocumentUseCases>, retention: TimeDelta) -> Self {
+        PurgeTrashCommand {
+            document_use_cases,
+            retention,
+        }
+    }
+
+    /// Purges the expired documents and returns how many were removed. A blob that cannot be
+    /// deleted is logged and left in the store; it does not fail the purge.
+    pub async fn execute(&self, now: DateTime<Utc>) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let purged = self
+            .document_use_cases
+            .document_repository
+            .purge_trash(now - self.retention)
+            .await?;
+        let blobs: BTreeSet<&str> = purged
+            .iter()
+            .flat_map(|document| {
+                let file = document.blob.as_ref().map(|blob| blob.sha256.as_str());
+                file.into_iter().chain(document.thumbnail_sha256.as_deref())
+            })
+            .collect();
+        for sha256 in blobs {
+            if let Err(e) = self.delete_unused_blob(sha256).await {
+                tracing::error!("Error deleting blob {}: {}", sha256, e);
+            }
+        }
+        Ok(purged.len())
+    }
+
+    /// Deletes the blob unless a document has it or a job may still read it.
+    async fn delete_unused_blob(&self, sha256: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let use_cases = &self.document_use_cases;
+        if use_cases
+            .document_repository
+            .references_blob(sha256)
+            .await?
+            || use_cases
+                .ingestion_queue
+                .jobs
+                .references_blob(sha256)
+                .await?
+        {
+            return Ok(());
+        }
+        use_cases.blob_store.delete(sha256).await
+    }
+
+    /// Runs the purge in the background every `interval`, starting immediately.
+    pub fn spawn(self, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match self.execute(Utc::now()).await {
+                    Ok(0) => {}
+                    Ok(purged) => tracing::info!("Purged {} documents from the trash", purged),
+                    Err(e) => tracing::error!("Error purging trash: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::domain::blob_store::DocumentBlob;
+    use crate::domain::document::Document;
+    use crate::domain::ingestion_job::IngestionJob;
+
+    fn blob(sha256: &str) -> DocumentBlob {
+        DocumentBlob {
+            sha256: sha256.to_string(),
+            file_name: "scan.png".to_string(),
+            mime_type: "image/png".to_string(),
+            size_bytes: 4,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_purges_only_documents_past_retention() {
+        // Given
+        let use_cases = Arc::new(DocumentUseCases::for_tests());
+        let repo = use_cases.document_repository.clone();
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+        for (title, days_in_trash) in [("Old", 31), ("Recent", 29)] {
+            let document = repo
+                .save_document(Document::new(title, "", user_id))
+                .await
+                .unwrap();
+            repo.trash_document(&document, now - TimeDelta::days(days_in_trash))
+                .await
+                .unwrap();
+        }
+        let command = PurgeTrashCommand::new(use_cases.clone(), TimeDelta::days(30));
+
+        // When
+        let purged = command.execute(now).await.unwrap();
+
+        // Then
+        assert_eq!(purged, 1);
+        let trash = repo.get_trash(&user_id, &10).await;
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].title, "Recent");
+    }
+
+    #[tokio::test]
+    async fn test_deletes_the_blobs_nothing_else_uses() {
+        // Given
+        let use_cases = Arc::new(DocumentUseCases::for_tests());
+        let repo = use_cases.document_repository.clone();
+        let now = Utc::now();
+        let mut sha256s = Vec::new();
+        for data in ["scan", "thumbnail", "shared", "retried"] {
+            sha256s.push(use_cases.blob_store.put(data.as_bytes()).await.unwrap());
+        }
+        let [scan, thumbnail, shared, retried] = sha256s.as_slice() else {
+            unreachable!()
+        };
+        for (file, thumbnail) in [(scan, Some(thumbnail)), (shared, None), (retried, None)] {
+            let mut document = Document::new("Old", "", Uuid::new_v4());
+            document.blob = Some(blob(file));
+            document.thumbnail_sha256 = thumbnail.cloned();
+            let document = repo.save_document(document).await.unwrap();
+            repo.trash_document(&document, now - TimeDelta::days(31))
+                .await
+                .unwrap();
+        }
+        // Another user uploaded the same file, and a failed upload of another may be retried
+        let mut copy = Document::new("Copy", "", Uuid::new_v4());
+        copy.blob = Some(blob(shared));
+        repo.save_document(copy).await.unwrap();
+        let mut job = IngestionJob::new(Uuid::new_v4(), blob(retried));
+        job.fail("Tesseract is unavailable".to_string());
+        use_cases.ingestion_queue.jobs.save_job(job).await.unwrap();
+        let command = PurgeTrashCommand::new(use_cases.clone(), TimeDelta::days(30));
+
+        // When
+        let purged = command.execute(now).await.unwrap();
+
+        // Then
+        assert_eq!(purged, 3);
+        let blob_store = &use_cases.blob_store;
+        assert!(blob_store.get(scan).await.unwrap().is_none());
+        assert!(blob_store.get(thumbnail).await.unwrap().is_none());
+        assert!(blob_store.get(shared).await.unwrap().is_some());
+        assert!(blob_store.get(retried).await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use crate::domain::ingestion_failure::IngestionFailure;
+
+/**
+ * When an ingestion job that failed is run again. Retryable failures back off exponentially from
+ * `base_delay` up to `max_delay`; after `max_attempts` the job is left failed.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            base_delay,
+            max_delay,
+        }
+    }
+
+    /// Whether a job that has been started `attempts` times should run again after `failure`.
+    pub fn should_retry(&self, failure: &IngestionFailure, attempts: u32) -> bool {
+        failure.retryable && attempts < self.max_attempts
+    }
+
+    /// Delay before the next attempt. Up to half of the backoff is taken off at random, so jobs
+    /// that failed together do not hit a recovering service together.
+    pub fn delay_after(&self, attempts: u32) -> Duration {
+        self.backoff(attempts)
+            .mul_f64(rand::random_range(0.5..=1.0))
+    }
+
+    /// `base_delay * 2^(attempts - 1)`, capped at `max_delay`.
+    fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(attempts.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(5, Duration::from_secs(30), Duration::from_secs(60 * 60))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_max_delay() {
+        let policy = RetryPolicy::new(10, Duration::from_secs(30), Duration::from_secs(300));
+
+        let backoffs: Vec<u64> = (1..=6)
+            .map(|attempts| policy.backoff(attempts).as_secs())
+            .collect();
+
+        assert_eq!(backoffs, [30, 60, 120, 240, 300, 300]);
+    }
+
+    #[test]
+    fn test_delay_after_keeps_at_least_half_of_the_backoff() {
+        let policy = RetryPolicy::default();
+
+        for attempts in 1..=5 {
+            let delay = policy.delay_after(attempts);
+            assert!(delay <= policy.backoff(attempts));
+            assert!(delay >= policy.backoff(attempts) / 2);
+        }
+    }
+
+    #[test]
+    fn test_only_retryable_failures_are_retried_until_the_last_attempt() {
+        let policy = RetryPolicy::new(3, Duration::from_secs(1), Duration::from_secs(1));
+        let timeout = IngestionFailure::retryable("Ollama timed out");
+        let corrupt = IngestionFailure::permanent("Could not read PDF");
+
+        assert!(policy.should_retry(&timeout, 2));
+        assert!(!policy.should_retry(&timeout, 3));
+        assert!(!policy.should_retry(&corrupt, 1));
+    }
+}
@@ -0,0 +1,24 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::user_settings::UserSettings;
+
+/**
+ * Port for the settings of each user.
+ */
+#[async_trait]
+pub trait UserSettingsRepository: Sync + Send {
+    /// The user's settings, or the defaults if the user never saved any.
+    async fn get_settings(
+        &self,
+        user_id: &Uuid,
+    ) -> Result<UserSettings, Box<dyn Error + Send + Sync>>;
+    /// Replaces the user's settings.
+    async fn save_settings(
+        &self,
+        user_id: &Uuid,
+        settings: &UserSettings,
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
@@ -1,4 +1,29 @@
+pub mod answer_generator;
+pub mod assistant_answer;
+pub mod blob_store;
+pub mod category;
+pub mod content_type;
 pub mod document;
+pub mod document_chunk;
+pub mod document_classification;
+pub mod document_classifier;
+pub mod document_field_extractor;
+pub mod document_fields;
+pub mod document_listing;
+pub mod document_search;
+pub mod document_similarity;
 pub mod document_summarizer;
 pub mod document_text_reader;
+pub mod embedding_provider;
+pub mod image_preprocessing;
+pub mod ingestion_event;
+pub mod ingestion_failure;
+pub mod ingestion_job;
+pub mod ocr_language;
+pub mod ocr_word;
+pub mod pdf_page_renderer;
+pub mod tag;
+pub mod thumbnail;
 pub mod uploaded_document_input;
+pub mod uploaded_file;
+pub mod user_settings;
@@ -0,0 +1,24 @@
+use std::pin::Pin;
+
+use async_trait::async_trait;
+use tokio_stream::Stream;
+
+use crate::domain::assistant_answer::ContextPassage;
+
+/// Pieces of an answer in the order they are generated.
+pub type AnswerStream =
+    Pin<Box<dyn Stream<Item = Result<String, Box<dyn std::error::Error + Send + Sync>>> + Send>>;
+
+/**
+* Port for answering a question from passages of the user's documents.
+*/
+#[async_trait]
+pub trait AnswerGenerator: Sync + Send {
+    /// Streams an answer to the question based only on the passages, referring to them as `[1]`,
+    /// `[2]` and so on in their order.
+    async fn answer(
+        &self,
+        question: &str,
+        passages: &[ContextPassage],
+    ) -> Result<AnswerStream, Box<dyn std::error::Error + Send + Sync>>;
+}
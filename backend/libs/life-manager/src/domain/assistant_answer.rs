@@ -0,0 +1,401 @@
+use std::{collections::HashSet, sync::LazyLock};
+
+use regex::Regex;
+use uuid::Uuid;
+
+use crate::domain::document_chunk::SemanticHit;
+
+/// Answer given without asking the model when none of the user's documents relate to the
+/// question.
+pub const NO_CONTEXT_ANSWER: &str = "I could not find anything about that in your documents.";
+
+/// Passages given to the model, the best chunk of each of the closest documents.
+pub const CONTEXT_PASSAGES: u32 = 5;
+
+/// Least cosine similarity of a passage to the question for it to be given to the model. With
+/// real embeddings unrelated text still scores well above 0, so this keeps the model from being
+/// asked about passages that have nothing to do with the question.
+pub const DEFAULT_MIN_CONTEXT_SIMILARITY: f32 = 0.5;
+
+/// Longest quote in a citation. Longer sentences are cut at a word boundary.
+pub const MAX_QUOTE_CHARS: usize = 300;
+
+/// Characters of a word compared when matching sentences, a crude stand-in for stemming.
+const MATCH_CHARS: usize = 5;
+
+/// Words an answer without markers must share with a passage to cite it. One common word such
+/// as "your" does not tell that the answer is based on the passage.
+const MIN_SHARED_WORDS: usize = 2;
+
+/// How the model refers to a passage in its answer, e.g. `[2]` for the second one.
+static SOURCE_MARKER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[(\d+)\]").expect("marker pattern is valid"));
+
+/**
+ * A passage of one of the user's documents given to the model to answer from. Passages are
+ * numbered from 1 in the prompt, in order.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContextPassage {
+    pub document_id: Uuid,
+    pub title: String,
+    pub page: Option<u32>,
+    /// The chunk given to the model, with runs of whitespace made single spaces.
+    pub text: String,
+    /// The stored text the chunk was cut from, as it was read: the text of its page, or the
+    /// content and any text read without pages. Quotes are taken from it.
+    pub source_text: String,
+}
+
+impl ContextPassage {
+    pub fn from_hit(hit: &SemanticHit) -> Self {
+        let document = &hit.document;
+        let source_text = match (hit.page, &document.extracted_text) {
+            (Some(page), Some(extracted)) => extracted
+                .pages
+                .iter()
+                .find(|text| text.page == page)
+                .map(|text| text.text.clone())
+                .unwrap_or_default(),
+            (None, Some(extracted)) if extracted.pages.is_empty() => {
+                format!("{}\n{}", document.content, extracted.text)
+            }
+            _ => document.content.clone(),
+        };
+        Self {
+            document_id: document.id,
+            title: document.title.clone(),
+            page: hit.page,
+            text: hit.snippet.clone(),
+            source_text,
+        }
+    }
+
+    /// The sentence of the stored text sharing most [`words`] with `words_to_match`, the first
+    /// one on a tie, cut to [`MAX_QUOTE_CHARS`]. Only sentences whole in the passage are quoted,
+    /// unless there are none.
+    pub fn quote(&self, words_to_match: &HashSet<String>) -> String {
+        let passage = collapse_whitespace(&self.text);
+        let sentences: Vec<&str> = sentences(&self.source_text)
+            .into_iter()
+            .map(|(_, sentence)| sentence)
+            .collect();
+        let in_passage: Vec<&str> = sentences
+            .iter()
+            .copied()
+            .filter(|sentence| passage.contains(&collapse_whitespace(sentence)))
+            .collect();
+        let candidates = if in_passage.is_empty() {
+            sentences
+        } else {
+            in_passage
+        };
+        let best = candidates
+            .into_iter()
+            .rev()
+            .max_by_key(|sentence| shared_words(sentence, words_to_match))
+            .unwrap_or_default();
+        truncate_at_word(best, MAX_QUOTE_CHARS).to_string()
+    }
+}
+
+/**
+ * A passage the answer relies on. `source` is the number the answer refers to it by and `quote`
+ * the sentence of the passage that backs the answer, word for word.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Citation {
+    pub source: u32,
+    pub document_id: Uuid,
+    pub title: String,
+    pub page: Option<u32>,
+    pub quote: String,
+}
+
+/**
+ * An answer to a question about the user's documents with the passages it cites.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssistantAnswer {
+    pub answer: String,
+    pub citations: Vec<Citation>,
+}
+
+impl AssistantAnswer {
+    pub fn new(answer: &str, passages: &[ContextPassage]) -> Self {
+        Self {
+            answer: answer.trim().to_string(),
+            citations: cite(answer, passages),
+        }
+    }
+}
+
+/// Citations for the `[n]` markers of an answer, in order of first mention. The quote of each
+/// is the sentence of the passage sharing most words with the sentences of the answer citing
+/// it. Markers without a passage are ignored. An answer without any valid marker cites the
+/// passages sharing at least [`MIN_SHARED_WORDS`] with it, in order.
+pub fn cite(answer: &str, passages: &[ContextPassage]) -> Vec<Citation> {
+    let sentences = sentences(answer);
+    let mut sources: Vec<u32> = Vec::new();
+    let mut citing_words: Vec<HashSet<String>> = vec![HashSet::new(); passages.len()];
+    for marker in SOURCE_MARKER.captures_iter(answer) {
+        let Some(source) = marker[1]
+            .parse::<u32>()
+            .ok()
+            .filter(|source| *source >= 1 && (*source as usize) <= passages.len())
+        else {
+            continue;
+        };
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+        let position = marker.get(0).expect("whole match").start();
+        let Some(mut index) = sentences
+            .iter()
+            .position(|(start, sentence)| position < start + sentence.len())
+        else {
+            continue;
+        };
+        // A marker after the full stop stands alone; it cites the sentence before.
+        if index > 0 && words(sentences[index].1).next().is_none() {
+            index -= 1;
+        }
+        citing_words[source as usize - 1].extend(words(sentences[index].1));
+    }
+    if sources.is_empty() {
+        // The model did not cite its passages, so find those the answer is based on.
+        let answer_words: HashSet<String> = words(answer).collect();
+        return passages
+            .iter()
+            .zip(1..)
+            .filter_map(|(passage, source)| {
+                let quote = passage.quote(&answer_words);
+                (shared_words(&quote, &answer_words) >= MIN_SHARED_WORDS)
+                    .then(|| citation(source, passage, quote))
+            })
+            .collect();
+    }
+    sources
+        .into_iter()
+        .map(|source| {
+            let passage = &passages[source as usize - 1];
+            let quote = passage.quote(&citing_words[source as usize - 1]);
+            citation(source, passage, quote)
+        })
+        .collect()
+}
+
+fn citation(source: u32, passage: &ContextPassage, quote: String) -> Citation {
+    Citation {
+        source,
+        document_id: passage.document_id,
+        title: passage.title.clone(),
+        page: passage.page,
+        quote,
+    }
+}
+
+fn shared_words(text: &str, words_to_match: &HashSet<String>) -> usize {
+    words(text)
+        .collect::<HashSet<_>>()
+        .intersection(words_to_match)
+        .count()
+}
+
+/// Words of more than two characters, the ones worth matching on, in lowercase and cut to
+/// their first [`MATCH_CHARS`] characters so that "expires" matches "expiry".
+pub fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 2)
+        .map(|word| word.to_lowercase().chars().take(MATCH_CHARS).collect())
+}
+
+/// Sentences of the text with their byte offsets. A sentence ends after `.`, `!` or `?`
+/// followed by whitespace, or at a blank line; read text often breaks lines mid-sentence.
+fn sentences(text: &str) -> Vec<(usize, &str)> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let end = match (c, chars.peek()) {
+            ('\n', Some((_, '\n'))) => Some(index),
+            ('.' | '!' | '?', Some((_, next))) if next.is_whitespace() => Some(index + 1),
+            _ => None,
+        };
+        if let Some(end) = end {
+            push_sentence(&mut sentences, text, start, end);
+            start = end;
+        }
+    }
+    push_sentence(&mut sentences, text, start, text.len());
+    sentences
+}
+
+fn push_sentence<'a>(
+    sentences: &mut Vec<(usize, &'a str)>,
+    text: &'a str,
+    start: usize,
+    end: usize,
+) {
+    let raw = &text[start..end];
+    let trimmed = raw.trim_start();
+    let sentence = trimmed.trim_end();
+    if !sentence.is_empty() {
+        sentences.push((start + raw.len() - trimmed.len(), sentence));
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn truncate_at_word(text: &str, max_chars: usize) -> &str {
+    if text.chars().count() <= max_chars {
+        return text;
+    }
+    let cut = text
+        .char_indices()
+        .nth(max_chars)
+        .map_or(text.len(), |(index, _)| index);
+    match text[..cut].rfind(char::is_whitespace) {
+        Some(space) => text[..space].trim_end(),
+        None => &text[..cut],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::document::Document;
+    use crate::domain::document_text_reader::{ExtractedText, PageText};
+
+    fn passage(title: &str, text: &str) -> ContextPassage {
+        ContextPassage {
+            document_id: Uuid::new_v4(),
+            title: title.to_string(),
+            page: Some(1),
+            text: collapse_whitespace(text),
+            source_text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_cite_quotes_the_sentence_backing_each_marker() {
+        let passages = [
+            passage(
+                "Passport",
+                "Republic of Utopia. Passport no. X123. Date of expiry: 12 March 2031. \
+                 Issued in Springfield.",
+            ),
+            passage(
+                "Power bill",
+                "Electricity used: 312 kWh. Total due: 84.20 EUR.",
+            ),
+        ];
+
+        let answer = AssistantAnswer::new(
+            "Your passport expires on 12 March 2031 [1]. \
+             You owe 84.20 EUR for power [2][1]. See [7].",
+            &passages,
+        );
+
+        let cited: Vec<(u32, &str, &str)> = answer
+            .citations
+            .iter()
+            .map(|c| (c.source, c.title.as_str(), c.quote.as_str()))
+            .collect();
+        assert_eq!(
+            cited,
+            [
+                (1, "Passport", "Date of expiry: 12 March 2031."),
+                (2, "Power bill", "Total due: 84.20 EUR."),
+            ]
+        );
+        assert_eq!(answer.citations[0].document_id, passages[0].document_id);
+    }
+
+    #[test]
+    fn test_a_marker_after_the_full_stop_cites_the_sentence_before() {
+        let passages = [passage(
+            "Passport",
+            "Passport no. X123. Date of expiry: 12 March 2031.",
+        )];
+
+        let citations = cite("It expires on 12 March 2031. [1]", &passages);
+
+        assert_eq!(citations.len(), 1);
+        assert_eq!(citations[0].quote, "Date of expiry: 12 March 2031.");
+    }
+
+    #[test]
+    fn test_an_answer_without_markers_cites_the_passages_it_is_based_on() {
+        let passages = [
+            passage(
+                "Power bill",
+                "Electricity used: 312 kWh. Total due: 84.20 EUR.",
+            ),
+            passage(
+                "Passport",
+                "Passport no. X123. Date of expiry: 12 March 2031.",
+            ),
+        ];
+
+        let citations = cite("Your passport expires on 12 March 2031.", &passages);
+
+        let cited: Vec<(u32, &str)> = citations
+            .iter()
+            .map(|c| (c.source, c.quote.as_str()))
+            .collect();
+        assert_eq!(cited, [(2, "Date of expiry: 12 March 2031.")]);
+        assert!(cite("I do not know.", &passages).is_empty());
+        assert!(cite("It expires in 2031 [1].", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_quotes_are_stored_text_of_the_passage() {
+        let mut document = Document::new(
+            "Passport",
+            "Passport no. X123.\nYour passport expires on\n12 March 2031.",
+            Uuid::new_v4(),
+        );
+        document.extracted_text = Some(ExtractedText::from_pages(
+            vec![
+                PageText::read(1, "Date of issue: 12 March 2021."),
+                PageText::read(2, "Date  of expiry:  12 March 2031.\nSignature."),
+            ],
+            "tesseract",
+            true,
+        ));
+        let words: HashSet<String> = words("When does the passport expire?").collect();
+        let hit = |page: Option<u32>, snippet: &str| SemanticHit {
+            document: document.clone(),
+            snippet: snippet.to_string(),
+            page,
+            score: 0.5,
+        };
+
+        let from_title_chunk = ContextPassage::from_hit(&hit(
+            None,
+            "Passport Passport no. X123. Your passport expires on 12 March 2031.",
+        ));
+        let from_page =
+            ContextPassage::from_hit(&hit(Some(2), "Date of expiry: 12 March 2031. Signature."));
+
+        assert_eq!(
+            from_title_chunk.quote(&words),
+            "Your passport expires on\n12 March 2031."
+        );
+        assert_eq!(from_page.quote(&words), "Date  of expiry:  12 March 2031.");
+    }
+
+    #[test]
+    fn test_long_quotes_are_cut_at_a_word() {
+        let text = "word ".repeat(200);
+
+        let quote = passage("Words", &text).quote(&HashSet::new());
+
+        assert!(quote.chars().count() <= MAX_QUOTE_CHARS);
+        assert!(quote.ends_with("word"));
+        assert!(text.starts_with(&quote));
+    }
+}
@@ -0,0 +1,72 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::domain::uploaded_file::UploadedFile;
+
+/**
+ * Port for storing the original bytes of uploaded documents.
+ *
+ * Blobs are content-addressed: the key of a blob is the hex encoded SHA-256 of its bytes, so
+ * storing the same file twice only keeps one copy.
+ */
+#[async_trait]
+pub trait BlobStore: Sync + Send {
+    /// Stores the bytes and returns their content address.
+    async fn put(&self, data: &[u8]) -> Result<String, Box<dyn Error + Send + Sync>>;
+    /// Loads the bytes for a content address. Returns [`None`] if no such blob exists.
+    async fn get(&self, sha256: &str) -> Result<Option<Vec<u8>>, Box<dyn Error + Send + Sync>>;
+    /// Stores the content of the file without loading it into memory and returns its content
+    /// address.
+    async fn put_file(&self, file: &UploadedFile) -> Result<String, Box<dyn Error + Send + Sync>>;
+    /// Loads a blob as a file. Returns [`None`] if no such blob exists.
+    async fn get_file(
+        &self,
+        sha256: &str,
+    ) -> Result<Option<UploadedFile>, Box<dyn Error + Send + Sync>>;
+    /// Removes the blob. Removing a blob that does not exist is not an error.
+    async fn delete(&self, sha256: &str) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/**
+ * Reference from a document to the original file stored in a [`BlobStore`].
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DocumentBlob {
+    pub sha256: String,
+    pub file_name: String,
+    pub mime_type: String,
+    pub size_bytes: u64,
+}
+
+/// Hex encoded SHA-256 of the given bytes, used as the key of a blob.
+pub fn content_address(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// Whether the value looks like a key produced by [`content_address`].
+pub fn is_content_address(value: &str) -> bool {
+    value.len() == 64 && value.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_address_is_sha256_hex() {
+        assert_eq!(
+            content_address(b"hello world"),
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+    }
+
+    #[test]
+    fn test_is_content_address() {
+        assert!(is_content_address(&content_address(b"")));
+        assert!(!is_content_address("../../etc/passwd"));
+        assert!(!is_content_address("abc"));
+    }
+}
@@ -0,0 +1,228 @@
+use std::{error::Error, fmt};
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::tag::normalize_tags;
+
+/// Longest category name; longer ones are descriptions.
+const MAX_NAME_LENGTH: usize = 100;
+
+/**
+ * A rule of the offline classifier: a keyword, matched case-insensitively as a whole word, or
+ * a regular expression written between slashes, such as `/\bkwh\b/`.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(try_from = "String", into = "String")]
+pub struct CategoryRule {
+    spec: String,
+    pattern: Regex,
+}
+
+impl CategoryRule {
+    pub fn parse(spec: &str) -> Result<Self, InvalidCategory> {
+        let spec = spec.trim();
+        match spec.strip_prefix('/').and_then(|s| s.strip_suffix('/')) {
+            Some(regex) if !regex.is_empty() => Self::build(spec, regex),
+            _ => Self::keyword(spec),
+        }
+    }
+
+    /// A rule matching `keyword` as a whole word, even if it looks like a regular expression.
+    pub fn keyword(keyword: &str) -> Result<Self, InvalidCategory> {
+        let keyword = keyword.trim();
+        if keyword.is_empty() {
+            return Err(InvalidCategory::InvalidRule {
+                rule: keyword.to_string(),
+                reason: "the rule is blank".to_string(),
+            });
+        }
+        // Unlike `\b`, also bounds keywords starting or ending with punctuation.
+        Self::build(
+            keyword,
+            &format!(r"(?:^|\W){}(?:\W|$)", regex::escape(keyword)),
+        )
+    }
+
+    fn build(spec: &str, pattern: &str) -> Result<Self, InvalidCategory> {
+        let pattern = RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| InvalidCategory::InvalidRule {
+                rule: spec.to_string(),
+                reason: e.to_string(),
+            })?;
+        Ok(Self {
+            spec: spec.to_string(),
+            pattern,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.spec
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        self.pattern.is_match(text)
+    }
+}
+
+impl PartialEq for CategoryRule {
+    fn eq(&self, other: &Self) -> bool {
+        self.spec == other.spec
+    }
+}
+
+impl Eq for CategoryRule {}
+
+impl TryFrom<String> for CategoryRule {
+    type Error = InvalidCategory;
+
+    fn try_from(spec: String) -> Result<Self, Self::Error> {
+        Self::parse(&spec)
+    }
+}
+
+impl From<CategoryRule> for String {
+    fn from(rule: CategoryRule) -> Self {
+        rule.spec
+    }
+}
+
+/**
+ * A category the user files documents under, such as "utility bill" or "tax".
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Category {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    /// What belongs in the category, in the user's words. The model classifies by it.
+    pub description: String,
+    /// Rules classifying documents without the model. The name is a keyword too.
+    pub rules: Vec<CategoryRule>,
+    /// Tags suggested for documents of the category.
+    pub tags: Vec<String>,
+}
+
+impl Category {
+    pub fn new(
+        user_id: Uuid,
+        name: &str,
+        description: &str,
+        rules: &[String],
+        tags: &[String],
+    ) -> Result<Self, InvalidCategory> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+            return Err(InvalidCategory::InvalidName(name.to_string()));
+        }
+        Ok(Self {
+            id: Uuid::new_v4(),
+            user_id,
+            name: name.to_string(),
+            description: description.trim().to_string(),
+            rules: rules
+                .iter()
+                .map(|rule| CategoryRule::parse(rule))
+                .collect::<Result<_, _>>()?,
+            tags: normalize_tags(tags),
+        })
+    }
+
+    /// Whether the category is called `name`, ignoring case.
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name.to_lowercase() == name.trim().to_lowercase()
+    }
+
+    /// How many of the category's rules, counting its name, match the text, and out of how many.
+    pub fn rule_matches(&self, text: &str) -> (usize, usize) {
+        let name_matches = CategoryRule::keyword(&self.name).is_ok_and(|name| name.matches(text));
+        let matched = self.rules.iter().filter(|rule| rule.matches(text)).count();
+        (usize::from(name_matches) + matched, self.rules.len() + 1)
+    }
+}
+
+/// Why a category is not accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidCategory {
+    InvalidName(String),
+    InvalidRule { rule: String, reason: String },
+}
+
+impl fmt::Display for InvalidCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidCategory::InvalidName(name) => write!(
+                f,
+                "'{}' is not a valid category name: it must have 1 to {} characters",
+                name, MAX_NAME_LENGTH
+            ),
+            InvalidCategory::InvalidRule { rule, reason } => {
+                write!(f, "'{}' is not a valid rule: {}", rule, reason)
+            }
+        }
+    }
+}
+
+impl Error for InvalidCategory {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_keyword_rules_match_whole_words_in_any_case() {
+        let rule = CategoryRule::parse(" kWh ").unwrap();
+
+        assert!(rule.matches("Usage: 312 KWH this month"));
+        assert!(rule.matches("kwh"));
+        assert!(!rule.matches("kwhatever"));
+        assert_eq!(rule.as_str(), "kWh");
+    }
+
+    #[test]
+    fn test_regex_rules_are_written_between_slashes() {
+        let rule = CategoryRule::parse(r"/policy (no|number)\.?\s*\d+/").unwrap();
+
+        assert!(rule.matches("Policy No. 123456"));
+        assert!(!rule.matches("Privacy policy"));
+        assert!(matches!(
+            CategoryRule::parse("/(unclosed/"),
+            Err(InvalidCategory::InvalidRule { .. })
+        ));
+        assert!(CategoryRule::parse("  ").is_err());
+    }
+
+    #[test]
+    fn test_rule_matches_count_the_name() {
+        let category = Category::new(
+            Uuid::new_v4(),
+            "Utility bill",
+            "Power, water and gas bills",
+            &strings(&["kwh", "/meter (reading|number)/"]),
+            &strings(&["Bills", "utilities"]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            category.rule_matches("Your utility bill: 312 kWh, meter reading 4411"),
+            (3, 3)
+        );
+        assert_eq!(category.rule_matches("312 kWh"), (1, 3));
+        assert_eq!(category.tags, strings(&["bills", "utilities"]));
+    }
+
+    #[test]
+    fn test_new_rejects_blank_names() {
+        assert_eq!(
+            Category::new(Uuid::new_v4(), " ", "", &[], &[]),
+            Err(InvalidCategory::InvalidName("".to_string()))
+        );
+    }
+}
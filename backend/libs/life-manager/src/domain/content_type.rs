@@ -0,0 +1,215 @@
+use std::{error::Error, fmt};
+
+pub const PDF: &str = "application/pdf";
+pub const PNG: &str = "image/png";
+pub const JPEG: &str = "image/jpeg";
+pub const TIFF: &str = "image/tiff";
+pub const BMP: &str = "image/bmp";
+pub const GIF: &str = "image/gif";
+pub const WEBP: &str = "image/webp";
+pub const ZIP: &str = "application/zip";
+pub const TEXT: &str = "text/plain";
+
+/// Types documents can be made from.
+pub const ACCEPTED_MIME_TYPES: [&str; 7] = [PDF, PNG, JPEG, TIFF, BMP, GIF, TEXT];
+
+/// Extensions of document formats stored as ZIP archives, which are not archives of files.
+const ZIP_BASED_EXTENSIONS: [&str; 7] = ["docx", "xlsx", "pptx", "odt", "ods", "odp", "epub"];
+
+/// Signatures at the start of a file, most specific first.
+const MAGIC_BYTES: [(&[u8], &str); 8] = [
+    (b"%PDF-", PDF),
+    (b"\x89PNG\r\n\x1a\n", PNG),
+    (b"\xff\xd8\xff", JPEG),
+    (b"II*\0", TIFF),
+    (b"MM\0*", TIFF),
+    (b"GIF87a", GIF),
+    (b"GIF89a", GIF),
+    (b"PK\x03\x04", ZIP),
+];
+
+/// Sizes of the DIB headers that may follow a bitmap's file header.
+const BMP_DIB_HEADER_SIZES: [u32; 6] = [12, 40, 52, 56, 108, 124];
+
+/// MIME type of the file's content, from its first bytes. Valid UTF-8 without control
+/// characters other than whitespace counts as plain text; a character cut off at the end is
+/// allowed, since only the head of a file may be given. Returns [`None`] for content that is
+/// not recognized.
+pub fn sniff_mime_type(data: &[u8]) -> Option<&'static str> {
+    if let Some((_, mime_type)) = MAGIC_BYTES
+        .iter()
+        .find(|(magic, _)| data.starts_with(magic))
+    {
+        return Some(mime_type);
+    }
+    if data.len() >= 12 && data.starts_with(b"RIFF") && &data[8..12] == b"WEBP" {
+        return Some(WEBP);
+    }
+    if is_bmp(data) {
+        return Some(BMP);
+    }
+    let text = match std::str::from_utf8(data) {
+        Ok(text) => text,
+        Err(error) if error.error_len().is_none() => {
+            std::str::from_utf8(&data[..error.valid_up_to()]).ok()?
+        }
+        Err(_) => return None,
+    };
+    let is_text = !text.is_empty() && !text.chars().any(|c| c.is_control() && !c.is_whitespace());
+    is_text.then_some(TEXT)
+}
+
+/// Whether the data starts with a bitmap file header. "BM" alone is too common at the start
+/// of text, so the reserved bytes must be zero and a known DIB header must follow.
+fn is_bmp(data: &[u8]) -> bool {
+    if data.len() < 26 || !data.starts_with(b"BM") || data[6..10] != [0; 4] {
+        return false;
+    }
+    let dib_header_size = u32::from_le_bytes([data[14], data[15], data[16], data[17]]);
+    BMP_DIB_HEADER_SIZES.contains(&dib_header_size)
+}
+
+/// MIME type a file name extension stands for, if it is one of the known types.
+pub fn mime_type_for_extension(extension: &str) -> Option<&'static str> {
+    match extension {
+        "pdf" => Some(PDF),
+        "png" => Some(PNG),
+        "jpg" | "jpeg" => Some(JPEG),
+        "tif" | "tiff" => Some(TIFF),
+        "bmp" => Some(BMP),
+        "gif" => Some(GIF),
+        "webp" => Some(WEBP),
+        "zip" => Some(ZIP),
+        "txt" => Some(TEXT),
+        _ => None,
+    }
+}
+
+/// Checks the detected type of a file against [`ACCEPTED_MIME_TYPES`] and the type its
+/// extension claims. Unknown extensions, or none at all, are judged by the content alone.
+pub fn check_content_type(
+    extension: &str,
+    detected: Option<&'static str>,
+) -> Result<&'static str, UnsupportedContentType> {
+    let detected = detected.ok_or(UnsupportedContentType::Unrecognized)?;
+    if !ACCEPTED_MIME_TYPES.contains(&detected) {
+        return Err(UnsupportedContentType::NotAccepted { detected });
+    }
+    match mime_type_for_extension(extension) {
+        Some(claimed) if claimed != detected => Err(UnsupportedContentType::Mismatch {
+            extension: extension.to_string(),
+            detected,
+        }),
+        _ => Ok(detected),
+    }
+}
+
+/// Whether the content is a ZIP archive of files, whatever the file is named, unless its
+/// extension names a document format stored as ZIP such as `.docx`.
+pub fn is_zip_archive(extension: &str, detected: Option<&'static str>) -> bool {
+    detected == Some(ZIP) && !ZIP_BASED_EXTENSIONS.contains(&extension)
+}
+
+/**
+ * Why an uploaded file is not accepted.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnsupportedContentType {
+    /// The content is none of the types documents are made from.
+    Unrecognized,
+    NotAccepted {
+        detected: &'static str,
+    },
+    /// The file name claims a different type than the content has.
+    Mismatch {
+        extension: String,
+        detected: &'static str,
+    },
+}
+
+impl fmt::Display for UnsupportedContentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnsupportedContentType::Unrecognized => write!(
+                f,
+                "The file's content is not a supported type; upload {}",
+                ACCEPTED_MIME_TYPES.join(", ")
+            ),
+            UnsupportedContentType::NotAccepted { detected } => write!(
+                f,
+                "Files of type {} are not supported; upload {}",
+                detected,
+                ACCEPTED_MIME_TYPES.join(", ")
+            ),
+            UnsupportedContentType::Mismatch {
+                extension,
+                detected,
+            } => write!(
+                f,
+                "The file is named .{} but its content is {}",
+                extension, detected
+            ),
+        }
+    }
+}
+
+impl Error for UnsupportedContentType {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_mime_type() {
+        assert_eq!(sniff_mime_type(b"%PDF-1.7\n"), Some(PDF));
+        assert_eq!(sniff_mime_type(b"\x89PNG\r\n\x1a\n\0\0"), Some(PNG));
+        assert_eq!(sniff_mime_type(b"II*\0\x08\0"), Some(TIFF));
+        assert_eq!(sniff_mime_type(b"RIFF\0\0\0\0WEBPVP8 "), Some(WEBP));
+        assert_eq!(sniff_mime_type("Total: 12,50 €".as_bytes()), Some(TEXT));
+        assert_eq!(
+            sniff_mime_type(&"Total: 12,50 €".as_bytes()[..14]),
+            Some(TEXT)
+        );
+        assert_eq!(sniff_mime_type(b"\0\x01\x02"), None);
+        assert_eq!(
+            sniff_mime_type(b"BMW invoice no. 4711, total 99,00"),
+            Some(TEXT)
+        );
+        let mut bitmap = b"BM\x46\0\0\0\0\0\0\0\x36\0\0\0\x28\0\0\0".to_vec();
+        bitmap.resize(26, 1);
+        assert_eq!(sniff_mime_type(&bitmap), Some(BMP));
+        assert_eq!(sniff_mime_type(b""), None);
+    }
+
+    #[test]
+    fn test_check_content_type() {
+        assert_eq!(check_content_type("jpg", Some(JPEG)), Ok(JPEG));
+        assert_eq!(check_content_type("", Some(PDF)), Ok(PDF));
+        assert_eq!(check_content_type("csv", Some(TEXT)), Ok(TEXT));
+        assert_eq!(
+            check_content_type("pdf", Some(PNG)),
+            Err(UnsupportedContentType::Mismatch {
+                extension: "pdf".to_string(),
+                detected: PNG,
+            })
+        );
+        assert_eq!(
+            check_content_type("docx", Some(ZIP)),
+            Err(UnsupportedContentType::NotAccepted { detected: ZIP })
+        );
+        assert_eq!(
+            check_content_type("png", None),
+            Err(UnsupportedContentType::Unrecognized)
+        );
+    }
+
+    #[test]
+    fn test_is_zip_archive() {
+        assert!(is_zip_archive("zip", Some(ZIP)));
+        assert!(is_zip_archive("", Some(ZIP)));
+        assert!(is_zip_archive("bak", Some(ZIP)));
+        assert!(!is_zip_archive("docx", Some(ZIP)));
+        assert!(!is_zip_archive("zip", Some(TEXT)));
+        assert!(!is_zip_archive("zip", None));
+    }
+}
@@ -1,12 +1,19 @@
 use std::sync::Arc;
 
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::Deserialize;
 use serde::Serialize;
 use uuid::Uuid;
 
+use crate::domain::blob_store::DocumentBlob;
+use crate::domain::document_chunk::DocumentChunk;
+use crate::domain::document_classification::DocumentClassification;
+use crate::domain::document_fields::DocumentFields;
+use crate::domain::document_similarity::simhash;
 use crate::domain::document_summarizer::DocumentSummarizer;
 use crate::domain::document_summarizer::DocumentSummaryResult;
-use crate::domain::document_text_reader::DocumentTextReader;
+use crate::domain::document_text_reader::{DocumentTextReader, ExtractedText};
+use crate::domain::ingestion_failure::IngestionFailure;
 use crate::domain::uploaded_document_input::UploadedDocumentInput;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -16,28 +23,83 @@ pub struct Document {
     pub content: String,
     pub tags: Vec<String>,
     pub user_id: Uuid,
+    /// Original uploaded file, if the document was created from one.
+    pub blob: Option<DocumentBlob>,
+    /// Raw text read from the uploaded file. Served by its own endpoint, so it is left out of
+    /// serialized documents.
+    #[serde(skip_serializing, default)]
+    pub extracted_text: Option<ExtractedText>,
+    /// [`simhash`] of the extracted text, for finding near duplicates.
+    #[serde(skip_serializing, default)]
+    pub text_simhash: Option<u64>,
+    /// Content address of a small preview of the uploaded file in the blob store.
+    #[serde(default)]
+    pub thumbnail_sha256: Option<String>,
+    /// When the document was moved to the trash; [`None`] for live documents.
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    /// Last change to the title, content, tags or document date.
+    pub updated_at: DateTime<Utc>,
+    /// Date printed on the document itself, e.g. the issue date of a bill, when known.
+    #[serde(default)]
+    pub document_date: Option<NaiveDate>,
+    /// Typed fields read from the extracted text, such as the amount of a bill.
+    #[serde(default)]
+    pub fields: Option<DocumentFields>,
+    /// Category the document is filed under, suggested at ingestion or chosen by the user.
+    #[serde(default)]
+    pub classification: Option<DocumentClassification>,
+    /// Embedded passages of the text. Stored apart from the document and searched on their own,
+    /// so they are only set on freshly ingested documents.
+    #[serde(skip)]
+    pub chunks: Vec<DocumentChunk>,
 }
 
 impl Document {
     /// Creates a new document with an auto-generated UUID
     pub fn new(title: &str, content: &str, user_id: Uuid) -> Self {
+        let now = Utc::now();
         Self {
             id: Uuid::new_v4(),
             title: title.to_string(),
             content: String::from(content),
             tags: vec![],
             user_id,
+            blob: None,
+            extracted_text: None,
+            text_simhash: None,
+            thumbnail_sha256: None,
+            deleted_at: None,
+            created_at: now,
+            updated_at: now,
+            document_date: None,
+            fields: None,
+            classification: None,
+            chunks: vec![],
         }
     }
 
     /// Creates a document with a known ID (for loading from database)
     pub fn with_id(id: Uuid, title: &str, content: &str, user_id: Uuid) -> Self {
+        let now = Utc::now();
         Self {
             id,
             title: title.to_string(),
             content: String::from(content),
             tags: vec![],
             user_id,
+            blob: None,
+            extracted_text: None,
+            text_simhash: None,
+            thumbnail_sha256: None,
+            deleted_at: None,
+            created_at: now,
+            updated_at: now,
+            document_date: None,
+            fields: None,
+            classification: None,
+            chunks: vec![],
         }
     }
 
@@ -48,35 +110,60 @@ impl Document {
         uploaded_document_input: &UploadedDocumentInput,
         reader: Arc<dyn DocumentTextReader>,
         summarizer: Arc<dyn DocumentSummarizer>,
-    ) -> Option<Document> {
+    ) -> Result<Document, IngestionFailure> {
         tracing::info!("Document::from_file");
-        let text = match reader.read_image(uploaded_document_input).await {
+        let extracted = Document::read_text(uploaded_document_input, reader).await?;
+        Document::from_text(extracted, uploaded_document_input.user_id, summarizer).await
+    }
+
+    /// Reads the text of an uploaded file, the first half of [`Document::from_file`].
+    pub async fn read_text(
+        uploaded_document_input: &UploadedDocumentInput,
+        reader: Arc<dyn DocumentTextReader>,
+    ) -> Result<ExtractedText, IngestionFailure> {
+        let extracted = match reader.read_image(uploaded_document_input).await {
             Ok(t) => t,
             Err(e) => {
                 tracing::error!("Error reading document text: {}", e);
-                return None;
+                return Err(IngestionFailure::from_error(
+                    "Error reading document text",
+                    &*e,
+                ));
             }
         };
 
-        tracing::info!("Document text read successfully, text: {}", text);
+        tracing::info!(
+            "Document text read successfully by {} (pages: {:?}, ocr: {}), text: {}",
+            extracted.reader,
+            extracted.page_count,
+            extracted.ocr_used,
+            extracted.text
+        );
+        Ok(extracted)
+    }
 
-        let summary_result = match (summarizer.summarize(&text)).await {
+    /// Summarizes extracted text into a new document, the second half of [`Document::from_file`].
+    pub async fn from_text(
+        extracted: ExtractedText,
+        user_id: Uuid,
+        summarizer: Arc<dyn DocumentSummarizer>,
+    ) -> Result<Document, IngestionFailure> {
+        let summary_result = match (summarizer.summarize(&extracted.text)).await {
             Ok(s) => s,
             Err(e) => {
                 tracing::error!("Error summarizing document text: {}", e);
-                return None;
+                return Err(IngestionFailure::from_error(
+                    "Error summarizing document text",
+                    &*e,
+                ));
             }
         };
 
         let DocumentSummaryResult { summary, title } = summary_result;
-        let document = Document {
-            id: Uuid::new_v4(),
-            title,
-            content: summary,
-            tags: vec![],
-            user_id: uploaded_document_input.user_id,
-        };
-        Some(document)
+        let mut document = Document::new(&title, &summary, user_id);
+        document.text_simhash = simhash(&extracted.text);
+        document.extracted_text = Some(extracted);
+        Ok(document)
     }
 
     // Prints the document details
@@ -86,6 +173,24 @@ impl Document {
         tracing::info!("Content: {}", self.content);
     }
 
+    pub fn is_trashed(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// Whether OCR read the document with too little confidence to trust its text.
+    pub fn needs_review(&self) -> bool {
+        self.extracted_text
+            .as_ref()
+            .is_some_and(ExtractedText::needs_review)
+    }
+
+    /// The date listings filter on: [`Document::document_date`] when known, otherwise the day the
+    /// document was added.
+    pub fn date(&self) -> NaiveDate {
+        self.document_date
+            .unwrap_or_else(|| self.created_at.date_naive())
+    }
+
     pub fn content(&self) -> &String {
         &self.content
     }
@@ -108,6 +213,19 @@ mod tests {
         assert_eq!(doc.title, "Test Document");
         assert_eq!(doc.content, "This is a test content.");
         assert!(doc.tags.is_empty());
+        assert!(!doc.is_trashed());
+        assert_eq!(doc.created_at, doc.updated_at);
+        assert!(doc.document_date.is_none());
+    }
+
+    #[test]
+    fn test_document_date_falls_back_to_created_at() {
+        let mut doc = Document::new("Bill", "", Uuid::new_v4());
+        assert_eq!(doc.date(), doc.created_at.date_naive());
+
+        let printed = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        doc.document_date = Some(printed);
+        assert_eq!(doc.date(), printed);
     }
 
     #[test]
@@ -276,9 +394,18 @@ mod tests {
         async fn read_image(
             &self,
             _uploaded_document_input: &UploadedDocumentInput,
-        ) -> Result<String, Box<dyn std::error::Error>> {
+        ) -> Result<ExtractedText, Box<dyn std::error::Error>> {
             if self.should_succeed {
-                Ok(self.text.clone())
+                Ok(ExtractedText {
+                    text: self.text.clone(),
+                    reader: "mock".to_string(),
+                    page_count: Some(1),
+                    ocr_used: true,
+                    pages: vec![],
+                    languages: vec![],
+                    words: vec![],
+                    confidence: None,
+                })
             } else {
                 Err(Box::new(MockError(self.error_message.clone())))
             }
@@ -332,7 +459,8 @@ mod tests {
     #[tokio::test]
     async fn test_from_file_success() {
         let user_id = Uuid::new_v4();
-        let input = UploadedDocumentInput::new("test.pdf".to_string(), vec![1, 2, 3], user_id);
+        let input =
+            UploadedDocumentInput::from_bytes("test.pdf".to_string(), &[1, 2, 3], user_id).unwrap();
 
         let reader = Arc::new(MockTextReader::success(
             "Extracted text from document".to_string(),
@@ -352,12 +480,19 @@ mod tests {
         assert_eq!(doc.content, "This is a summary");
         assert_eq!(doc.user_id, user_id);
         assert!(doc.tags.is_empty());
+        let extracted = doc.extracted_text.expect("Should keep the extracted text");
+        assert_eq!(extracted.text, "Extracted text from document");
+        assert_eq!(extracted.reader, "mock");
+        assert_eq!(extracted.page_count, Some(1));
+        assert!(extracted.ocr_used);
+        assert_eq!(doc.text_simhash, simhash("Extracted text from document"));
     }
 
     #[tokio::test]
     async fn test_from_file_reader_error() {
         let user_id = Uuid::new_v4();
-        let input = UploadedDocumentInput::new("test.pdf".to_string(), vec![1, 2, 3], user_id);
+        let input =
+            UploadedDocumentInput::from_bytes("test.pdf".to_string(), &[1, 2, 3], user_id).unwrap();
 
         let reader = Arc::new(MockTextReader::error("Failed to read document".to_string()));
 
@@ -367,26 +502,28 @@ mod tests {
         ));
 
         let result = Document::from_file(&input, reader, summarizer).await;
-        assert!(result.is_none());
+        assert!(result.is_err());
     }
 
     #[tokio::test]
     async fn test_from_file_summarizer_error() {
         let user_id = Uuid::new_v4();
-        let input = UploadedDocumentInput::new("test.pdf".to_string(), vec![1, 2, 3], user_id);
+        let input =
+            UploadedDocumentInput::from_bytes("test.pdf".to_string(), &[1, 2, 3], user_id).unwrap();
 
         let reader = Arc::new(MockTextReader::success("Extracted text".to_string()));
 
         let summarizer = Arc::new(MockSummarizer::error("Failed to summarize".to_string()));
 
         let result = Document::from_file(&input, reader, summarizer).await;
-        assert!(result.is_none());
+        assert!(result.is_err());
     }
 
     #[tokio::test]
     async fn test_from_file_with_empty_text() {
         let user_id = Uuid::new_v4();
-        let input = UploadedDocumentInput::new("empty.pdf".to_string(), vec![], user_id);
+        let input =
+            UploadedDocumentInput::from_bytes("empty.pdf".to_string(), &[], user_id).unwrap();
 
         let reader = Arc::new(MockTextReader::success(String::new()));
 
@@ -398,12 +535,15 @@ mod tests {
 
         assert_eq!(doc.title, "");
         assert_eq!(doc.content, "");
+        assert!(doc.text_simhash.is_none());
     }
 
     #[tokio::test]
     async fn test_from_file_with_very_long_text() {
         let user_id = Uuid::new_v4();
-        let input = UploadedDocumentInput::new("large.pdf".to_string(), vec![1; 10000], user_id);
+        let input =
+            UploadedDocumentInput::from_bytes("large.pdf".to_string(), &[1; 10000], user_id)
+                .unwrap();
 
         let long_text = "A".repeat(1_000_000);
         let long_summary = "B".repeat(500_000);
@@ -427,7 +567,9 @@ mod tests {
     #[tokio::test]
     async fn test_from_file_with_unicode() {
         let user_id = Uuid::new_v4();
-        let input = UploadedDocumentInput::new("unicode.pdf".to_string(), vec![1, 2, 3], user_id);
+        let input =
+            UploadedDocumentInput::from_bytes("unicode.pdf".to_string(), &[1, 2, 3], user_id)
+                .unwrap();
 
         let reader = Arc::new(MockTextReader::success(
             "Text with émojis 🚀 and 中文字符".to_string(),
@@ -0,0 +1,204 @@
+use crate::domain::document::Document;
+
+/// Most characters in a chunk. Long enough to carry a topic, short enough that one page holds
+/// a few of them and a match points to the right part of it.
+pub const CHUNK_CHARS: usize = 1000;
+
+/// Characters a chunk repeats from the end of the one before, so a sentence cut in two is still
+/// whole in one of them.
+pub const CHUNK_OVERLAP_CHARS: usize = 200;
+
+/**
+ * A passage of a document with its embedding. The first chunk holds the title and the summary;
+ * the others split the extracted text, page by page when it was read that way.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocumentChunk {
+    /// 1-based page the passage was read from, if known.
+    pub page: Option<u32>,
+    pub text: String,
+    /// Model that computed the embedding; empty until embedded.
+    pub model: String,
+    pub embedding: Vec<f32>,
+}
+
+impl DocumentChunk {
+    /// Splits the title, content and any extracted text of the document into chunks to embed.
+    pub fn split(document: &Document) -> Vec<DocumentChunk> {
+        let mut chunks = Vec::new();
+        let summary = format!("{}\n{}", document.title, document.content);
+        chunks.extend(
+            chunk_text(&summary)
+                .into_iter()
+                .map(|text| Self::new(None, text)),
+        );
+        if let Some(extracted) = &document.extracted_text {
+            if extracted.pages.is_empty() {
+                chunks.extend(
+                    chunk_text(&extracted.text)
+                        .into_iter()
+                        .map(|text| Self::new(None, text)),
+                );
+            }
+            for page in &extracted.pages {
+                chunks.extend(
+                    chunk_text(&page.text)
+                        .into_iter()
+                        .map(|text| Self::new(Some(page.page), text)),
+                );
+            }
+        }
+        chunks
+    }
+
+    fn new(page: Option<u32>, text: String) -> Self {
+        Self {
+            page,
+            text,
+            model: String::new(),
+            embedding: vec![],
+        }
+    }
+
+    /// Stands in for the chunks of a document without any text, so that it is not picked to be
+    /// embedded again. It has no embedding and never matches a search.
+    pub fn nothing_to_embed(model: &str) -> Self {
+        Self::new(None, String::new()).embedded(model, vec![])
+    }
+
+    pub fn embedded(self, model: &str, embedding: Vec<f32>) -> Self {
+        Self {
+            model: model.to_string(),
+            embedding,
+            ..self
+        }
+    }
+}
+
+/**
+ * One of the user's documents that means about the same as a semantic search query, with the
+ * chunk that came closest. `score` is the cosine similarity of the two, at most 1.
+ */
+#[derive(Clone, Debug)]
+pub struct SemanticHit {
+    pub document: Document,
+    pub snippet: String,
+    pub page: Option<u32>,
+    pub score: f32,
+}
+
+/// Splits text at whitespace into chunks of at most [`CHUNK_CHARS`] characters, each starting
+/// with about the last [`CHUNK_OVERLAP_CHARS`] characters of the one before. Runs of whitespace
+/// become single spaces; a word longer than a chunk gets a chunk of its own.
+pub fn chunk_text(text: &str) -> Vec<String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let length = |word: &&str| word.chars().count();
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < words.len() {
+        let mut end = start + 1;
+        let mut chars = length(&words[start]);
+        while end < words.len() && chars + 1 + length(&words[end]) <= CHUNK_CHARS {
+            chars += 1 + length(&words[end]);
+            end += 1;
+        }
+        chunks.push(words[start..end].join(" "));
+        if end == words.len() {
+            break;
+        }
+        let mut next = end;
+        let mut overlap = 0;
+        while next > start + 1 && overlap + length(&words[next - 1]) < CHUNK_OVERLAP_CHARS {
+            overlap += 1 + length(&words[next - 1]);
+            next -= 1;
+        }
+        start = next;
+    }
+    chunks
+}
+
+/// Cosine of the angle between two vectors, from -1 to 1. Vectors of different lengths or
+/// without direction have 0.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::domain::document_text_reader::{ExtractedText, PageText};
+
+    #[test]
+    fn test_chunk_text_overlaps_and_keeps_words_whole() {
+        let text = (0..400)
+            .map(|i| format!("word{:03}", i))
+            .collect::<Vec<_>>()
+            .join(" \n ");
+
+        let chunks = chunk_text(&text);
+
+        assert_eq!(chunks.len(), 4);
+        assert!(
+            chunks
+                .iter()
+                .all(|chunk| chunk.chars().count() <= CHUNK_CHARS)
+        );
+        assert!(chunks[0].starts_with("word000 word001"));
+        assert!(chunks[3].ends_with("word399"));
+        let first_of_second = chunks[1].split(' ').next().unwrap();
+        assert!(chunks[0].contains(first_of_second));
+        assert!(chunk_text(" \n\t").is_empty());
+    }
+
+    #[test]
+    fn test_split_keeps_the_page_of_each_chunk() {
+        let mut document = Document::new("Auto policy", "Insurance for the car", Uuid::new_v4());
+        document.extracted_text = Some(ExtractedText {
+            text: "ignored when read page by page".to_string(),
+            reader: "tesseract".to_string(),
+            page_count: Some(2),
+            ocr_used: true,
+            pages: vec![
+                PageText::read(1, "Policy number 42"),
+                PageText::read(2, "  "),
+            ],
+            languages: vec![],
+            words: vec![],
+            confidence: None,
+        });
+
+        let chunks = DocumentChunk::split(&document);
+
+        let passages: Vec<(Option<u32>, &str)> = chunks
+            .iter()
+            .map(|chunk| (chunk.page, chunk.text.as_str()))
+            .collect();
+        assert_eq!(
+            passages,
+            [
+                (None, "Auto policy Insurance for the car"),
+                (Some(1), "Policy number 42"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 3.0]).abs() < 1e-6);
+        assert!((cosine_similarity(&[1.0, 1.0], &[-1.0, -1.0]) + 1.0).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+    }
+}
@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{category::Category, tag::normalize_tags};
+
+/**
+ * Whether the user has looked at a document's category.
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClassificationStatus {
+    /// Chosen at ingestion; its tags are not on the document yet.
+    #[default]
+    Suggested,
+    /// The user agreed with the suggestion and its tags were added to the document.
+    Accepted,
+    /// The user chose the category.
+    Overridden,
+}
+
+impl ClassificationStatus {
+    pub const ALL: [ClassificationStatus; 3] = [
+        ClassificationStatus::Suggested,
+        ClassificationStatus::Accepted,
+        ClassificationStatus::Overridden,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClassificationStatus::Suggested => "suggested",
+            ClassificationStatus::Accepted => "accepted",
+            ClassificationStatus::Overridden => "overridden",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.as_str() == name)
+    }
+}
+
+/**
+ * The category a document was filed under, with the tags that go with it.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DocumentClassification {
+    pub category_id: Uuid,
+    /// Name of the category, for display.
+    pub category: String,
+    pub tags: Vec<String>,
+    /// How sure the classifier was, from 0 to 1. Categories the user chose have 1.
+    pub confidence: f32,
+    pub status: ClassificationStatus,
+}
+
+impl DocumentClassification {
+    /// A suggestion of the category with its tags and any others the classifier came up with.
+    pub fn suggest(category: &Category, tags: &[String], confidence: f32) -> Self {
+        let tags: Vec<String> = category.tags.iter().chain(tags).cloned().collect();
+        Self {
+            category_id: category.id,
+            category: category.name.clone(),
+            tags: normalize_tags(&tags),
+            confidence: if confidence.is_finite() {
+                confidence.clamp(0.0, 1.0)
+            } else {
+                0.0
+            },
+            status: ClassificationStatus::Suggested,
+        }
+    }
+
+    /// The category and tags the user chose instead of the suggestion.
+    pub fn choose(category: &Category, tags: &[String]) -> Self {
+        Self {
+            category_id: category.id,
+            category: category.name.clone(),
+            tags: normalize_tags(tags),
+            confidence: 1.0,
+            status: ClassificationStatus::Overridden,
+        }
+    }
+
+    pub fn accept(self) -> Self {
+        Self {
+            status: ClassificationStatus::Accepted,
+            ..self
+        }
+    }
+}
+
+/// Classifies text by the rules of the categories alone, so without a model. The category with
+/// the most matching rules wins, ties going to the one with the fewest rules; its confidence is
+/// the share of its rules that match. [`None`] if no rule matches.
+pub fn classify_by_rules(text: &str, categories: &[Category]) -> Option<DocumentClassification> {
+    categories
+        .iter()
+        .map(|category| (category, category.rule_matches(text)))
+        .filter(|(_, (matched, _))| *matched > 0)
+        .max_by(|(_, (a, a_total)), (_, (b, b_total))| a.cmp(b).then(b_total.cmp(a_total)))
+        .map(|(category, (matched, total))| {
+            DocumentClassification::suggest(category, &[], matched as f32 / total as f32)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(name: &str, rules: &[&str], tags: &[&str]) -> Category {
+        let strings = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        Category::new(Uuid::new_v4(), name, "", &strings(rules), &strings(tags)).unwrap()
+    }
+
+    #[test]
+    fn test_classify_by_rules_picks_the_category_with_most_matches() {
+        let categories = [
+            category("Medical", &["doctor", "/prescri(be|ption)/"], &["health"]),
+            category("Utility bill", &["kwh", "meter reading"], &["bills"]),
+            category("Tax", &["irs"], &[]),
+        ];
+
+        let classification = classify_by_rules(
+            "City Power: meter reading 4411, 312 kWh. Tax included.",
+            &categories,
+        )
+        .unwrap();
+
+        assert_eq!(classification.category_id, categories[1].id);
+        assert_eq!(classification.category, "Utility bill");
+        assert_eq!(classification.tags, ["bills"]);
+        assert!((classification.confidence - 2.0 / 3.0).abs() < 1e-6);
+        assert_eq!(classification.status, ClassificationStatus::Suggested);
+    }
+
+    #[test]
+    fn test_classify_by_rules_without_matches_is_none() {
+        let categories = [category("Insurance", &["/policy (no|number)/"], &[])];
+
+        assert_eq!(classify_by_rules("Dear Ms Lee", &categories), None);
+        assert_eq!(classify_by_rules("Dear Ms Lee", &[]), None);
+    }
+
+    #[test]
+    fn test_suggest_merges_tags_and_clamps_the_confidence() {
+        let category = category("Tax", &[], &["tax"]);
+
+        let classification = DocumentClassification::suggest(
+            &category,
+            &["2025".to_string(), "Tax".to_string()],
+            7.0,
+        );
+
+        assert_eq!(classification.tags, ["2025", "tax"]);
+        assert_eq!(classification.confidence, 1.0);
+        assert_eq!(
+            classification.accept().status,
+            ClassificationStatus::Accepted
+        );
+    }
+}
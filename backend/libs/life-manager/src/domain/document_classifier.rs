@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+use crate::domain::{category::Category, document_classification::DocumentClassification};
+
+/**
+* Port for filing document text under one of the user's categories.
+*/
+#[async_trait]
+pub trait DocumentClassifier: Sync + Send {
+    /// The category of `categories` the text belongs to, with suggested tags, or [`None`] if
+    /// none fits.
+    async fn classify(
+        &self,
+        text: &str,
+        categories: &[Category],
+    ) -> Result<Option<DocumentClassification>, Box<dyn std::error::Error>>;
+}
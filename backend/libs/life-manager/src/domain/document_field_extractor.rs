@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::domain::document_fields::DocumentFields;
+
+/**
+* Port for reading typed fields, such as the amount and due date of a bill, from document text.
+*/
+#[async_trait]
+pub trait DocumentFieldExtractor: Sync + Send {
+    /// The type of the document and the fields of that type found in its text.
+    async fn extract_fields(
+        &self,
+        text: &str,
+    ) -> Result<DocumentFields, Box<dyn std::error::Error>>;
+}
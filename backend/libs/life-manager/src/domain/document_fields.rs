@@ -0,0 +1,363 @@
+use std::{error::Error, fmt};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+/// Longest text field kept from an answer; anything longer is not a name or a number.
+const MAX_TEXT_FIELD_LENGTH: usize = 200;
+
+/**
+ * Kind of document, which decides the fields read from it.
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentType {
+    /// An invoice, utility bill or statement asking for a payment.
+    Bill,
+    /// Proof of a payment already made.
+    Receipt,
+    Other,
+}
+
+impl DocumentType {
+    pub const ALL: [DocumentType; 3] = [
+        DocumentType::Bill,
+        DocumentType::Receipt,
+        DocumentType::Other,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocumentType::Bill => "bill",
+            DocumentType::Receipt => "receipt",
+            DocumentType::Other => "other",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|document_type| document_type.as_str().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Fields read from documents of this type.
+    pub fn fields(&self) -> &'static [DocumentField] {
+        match self {
+            DocumentType::Bill => &[
+                DocumentField::Vendor,
+                DocumentField::TotalAmount,
+                DocumentField::Currency,
+                DocumentField::DueDate,
+                DocumentField::AccountNumber,
+                DocumentField::InvoiceNumber,
+            ],
+            DocumentType::Receipt => &[
+                DocumentField::Vendor,
+                DocumentField::TotalAmount,
+                DocumentField::Currency,
+            ],
+            DocumentType::Other => &[],
+        }
+    }
+
+    /// JSON schema of the fields of this type, each of which may be `null`.
+    pub fn json_schema(&self) -> Value {
+        let properties: Map<String, Value> = self
+            .fields()
+            .iter()
+            .map(|field| {
+                let json_type = match field {
+                    DocumentField::TotalAmount => "number",
+                    _ => "string",
+                };
+                (
+                    field.name().to_string(),
+                    json!({ "type": [json_type, "null"], "description": field.description() }),
+                )
+            })
+            .collect();
+        let required: Vec<&str> = self.fields().iter().map(DocumentField::name).collect();
+        json!({ "type": "object", "properties": properties, "required": required })
+    }
+}
+
+/// A field read from documents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocumentField {
+    Vendor,
+    TotalAmount,
+    Currency,
+    DueDate,
+    AccountNumber,
+    InvoiceNumber,
+}
+
+impl DocumentField {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DocumentField::Vendor => "vendor",
+            DocumentField::TotalAmount => "total_amount",
+            DocumentField::Currency => "currency",
+            DocumentField::DueDate => "due_date",
+            DocumentField::AccountNumber => "account_number",
+            DocumentField::InvoiceNumber => "invoice_number",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            DocumentField::Vendor => "Company or person that issued the document",
+            DocumentField::TotalAmount => "Total amount to pay or paid, as a plain number",
+            DocumentField::Currency => "ISO 4217 code of the currency, e.g. EUR or USD",
+            DocumentField::DueDate => "Date the payment is due, as YYYY-MM-DD",
+            DocumentField::AccountNumber => "Customer or account number at the vendor",
+            DocumentField::InvoiceNumber => "Number of the invoice or bill",
+        }
+    }
+}
+
+/**
+ * Typed fields read from a document's text. Only the fields of its
+ * [`DocumentType::fields`] are ever set.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DocumentFields {
+    pub document_type: DocumentType,
+    pub vendor: Option<String>,
+    pub total_amount: Option<f64>,
+    pub currency: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    pub account_number: Option<String>,
+    pub invoice_number: Option<String>,
+}
+
+impl DocumentFields {
+    pub fn new(document_type: DocumentType) -> Self {
+        Self {
+            document_type,
+            vendor: None,
+            total_amount: None,
+            currency: None,
+            due_date: None,
+            account_number: None,
+            invoice_number: None,
+        }
+    }
+
+    /// Validates an answer giving the fields of `document_type` by name. Missing fields, `null`
+    /// and empty strings leave a field unset; fields the type does not have are ignored.
+    pub fn from_answer(
+        document_type: DocumentType,
+        answer: &Value,
+    ) -> Result<Self, InvalidDocumentFields> {
+        let answer = answer
+            .as_object()
+            .ok_or(InvalidDocumentFields::NotAnObject)?;
+        let mut fields = Self::new(document_type);
+        for field in document_type.fields() {
+            let Some(value) = answer.get(field.name()).filter(|value| !is_blank(value)) else {
+                continue;
+            };
+            let invalid = || InvalidDocumentFields::InvalidField {
+                field: field.name(),
+                value: value.to_string(),
+            };
+            match field {
+                DocumentField::Vendor => {
+                    fields.vendor = Some(text_field(value).ok_or_else(invalid)?)
+                }
+                DocumentField::TotalAmount => {
+                    fields.total_amount = Some(amount_field(value).ok_or_else(invalid)?)
+                }
+                DocumentField::Currency => {
+                    fields.currency = Some(currency_field(value).ok_or_else(invalid)?)
+                }
+                DocumentField::DueDate => {
+                    fields.due_date = Some(date_field(value).ok_or_else(invalid)?)
+                }
+                DocumentField::AccountNumber => {
+                    fields.account_number = Some(text_field(value).ok_or_else(invalid)?)
+                }
+                DocumentField::InvoiceNumber => {
+                    fields.invoice_number = Some(text_field(value).ok_or_else(invalid)?)
+                }
+            }
+        }
+        Ok(fields)
+    }
+}
+
+fn is_blank(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(text) => text.trim().is_empty(),
+        _ => false,
+    }
+}
+
+/// A name or number; numbers are taken as their digits, e.g. an account number.
+fn text_field(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::String(text) => text.trim().to_string(),
+        Value::Number(number) => number.to_string(),
+        _ => return None,
+    };
+    (text.chars().count() <= MAX_TEXT_FIELD_LENGTH).then_some(text)
+}
+
+/// A non-negative amount, as a number or as text such as `1,024.50` or `1.024,50 €`.
+fn amount_field(value: &Value) -> Option<f64> {
+    let amount = match value {
+        Value::Number(number) => number.as_f64()?,
+        Value::String(text) => parse_amount(text)?,
+        _ => return None,
+    };
+    (amount.is_finite() && amount >= 0.0).then_some(amount)
+}
+
+/// Reads an amount written with either `.` or `,` as the decimal separator: the last separator
+/// is the decimal one when at most two digits follow it.
+pub fn parse_amount(text: &str) -> Option<f64> {
+    let number: String = text
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, '.' | ',' | '-'))
+        .collect();
+    let number = number.trim_matches(|c| c == '.' || c == ',');
+    let decimal = number
+        .rfind(['.', ','])
+        .filter(|position| number.len() - position - 1 <= 2);
+    let normalized: String = number
+        .char_indices()
+        .filter_map(|(position, c)| match c {
+            '.' | ',' if Some(position) == decimal => Some('.'),
+            '.' | ',' => None,
+            c => Some(c),
+        })
+        .collect();
+    normalized.parse().ok()
+}
+
+/// An ISO 4217 code, or one of the common currency symbols.
+fn currency_field(value: &Value) -> Option<String> {
+    let code = match value.as_str()?.trim() {
+        "€" => "EUR",
+        "$" => "USD",
+        "£" => "GBP",
+        "¥" => "JPY",
+        code => code,
+    }
+    .to_uppercase();
+    (code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase())).then_some(code)
+}
+
+fn date_field(value: &Value) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.as_str()?.trim(), "%Y-%m-%d").ok()
+}
+
+/// Why an answer with document fields is not accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidDocumentFields {
+    NotAnObject,
+    UnknownDocumentType(String),
+    InvalidField { field: &'static str, value: String },
+}
+
+impl fmt::Display for InvalidDocumentFields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidDocumentFields::NotAnObject => write!(f, "The fields are not a JSON object"),
+            InvalidDocumentFields::UnknownDocumentType(name) => {
+                write!(f, "'{}' is not a document type", name)
+            }
+            InvalidDocumentFields::InvalidField { field, value } => {
+                write!(f, "{} is not a valid {}", value, field)
+            }
+        }
+    }
+}
+
+impl Error for InvalidDocumentFields {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_answer_types_the_fields_of_the_document_type() {
+        let answer = json!({
+            "vendor": " City Power ",
+            "total_amount": "1.024,50 €",
+            "currency": "€",
+            "due_date": "2026-11-01",
+            "account_number": 88213,
+            "invoice_number": "",
+            "summary": "Ignored",
+        });
+
+        let bill = DocumentFields::from_answer(DocumentType::Bill, &answer).unwrap();
+        let receipt = DocumentFields::from_answer(DocumentType::Receipt, &answer).unwrap();
+
+        assert_eq!(
+            bill,
+            DocumentFields {
+                document_type: DocumentType::Bill,
+                vendor: Some("City Power".to_string()),
+                total_amount: Some(1024.5),
+                currency: Some("EUR".to_string()),
+                due_date: NaiveDate::from_ymd_opt(2026, 11, 1),
+                account_number: Some("88213".to_string()),
+                invoice_number: None,
+            }
+        );
+        assert_eq!(receipt.due_date, None);
+        assert_eq!(receipt.total_amount, Some(1024.5));
+    }
+
+    #[test]
+    fn test_from_answer_rejects_invalid_values() {
+        assert_eq!(
+            DocumentFields::from_answer(DocumentType::Bill, &json!({"due_date": "next Tuesday"})),
+            Err(InvalidDocumentFields::InvalidField {
+                field: "due_date",
+                value: "\"next Tuesday\"".to_string(),
+            })
+        );
+        assert!(
+            DocumentFields::from_answer(DocumentType::Receipt, &json!({"total_amount": -3}))
+                .is_err()
+        );
+        assert!(
+            DocumentFields::from_answer(DocumentType::Receipt, &json!({"currency": "euros"}))
+                .is_err()
+        );
+        assert_eq!(
+            DocumentFields::from_answer(DocumentType::Bill, &json!(["City Power"])),
+            Err(InvalidDocumentFields::NotAnObject)
+        );
+    }
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(parse_amount("1,024.50"), Some(1024.5));
+        assert_eq!(parse_amount("1.024,50"), Some(1024.5));
+        assert_eq!(parse_amount("USD 12"), Some(12.0));
+        assert_eq!(parse_amount("1,000"), Some(1000.0));
+        assert_eq!(parse_amount("total"), None);
+    }
+
+    #[test]
+    fn test_json_schema_lists_the_fields_of_the_type() {
+        let schema = DocumentType::Receipt.json_schema();
+
+        assert_eq!(
+            schema["required"],
+            json!(["vendor", "total_amount", "currency"])
+        );
+        assert_eq!(
+            schema["properties"]["total_amount"]["type"],
+            json!(["number", "null"])
+        );
+        assert_eq!(DocumentType::Other.json_schema()["required"], json!([]));
+    }
+}
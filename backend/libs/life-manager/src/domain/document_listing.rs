@@ -0,0 +1,310 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{document::Document, tag::TagFilter};
+
+/**
+ * Field a document listing is ordered by. Ties are broken by document ID so every document has
+ * a unique position.
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentSortField {
+    #[default]
+    Title,
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl DocumentSortField {
+    /// The document's value for this field, as stored in a cursor. Timestamps are rendered with a
+    /// fixed width so their keys sort like the timestamps themselves.
+    pub fn key_of(&self, document: &Document) -> String {
+        match self {
+            DocumentSortField::Title => document.title.clone(),
+            DocumentSortField::CreatedAt => timestamp_key(&document.created_at),
+            DocumentSortField::UpdatedAt => timestamp_key(&document.updated_at),
+        }
+    }
+
+    /// Whether `key` could have been produced by [`DocumentSortField::key_of`].
+    fn accepts_key(&self, key: &str) -> bool {
+        match self {
+            DocumentSortField::Title => true,
+            DocumentSortField::CreatedAt | DocumentSortField::UpdatedAt => {
+                parse_timestamp_key(key).is_some()
+            }
+        }
+    }
+}
+
+fn timestamp_key(timestamp: &DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+/// Reads back the sort key of a timestamp field.
+pub fn parse_timestamp_key(key: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(key)
+        .ok()
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DocumentSort {
+    pub field: DocumentSortField,
+    pub direction: SortDirection,
+}
+
+impl DocumentSort {
+    /// Orders two documents by the sort field, then by ID.
+    pub fn compare(&self, a: &Document, b: &Document) -> Ordering {
+        let ordering = self
+            .field
+            .key_of(a)
+            .cmp(&self.field.key_of(b))
+            .then_with(|| a.id.cmp(&b.id));
+        match self.direction {
+            SortDirection::Asc => ordering,
+            SortDirection::Desc => ordering.reverse(),
+        }
+    }
+}
+
+/**
+ * Position of the last document of a page. Clients receive it as an opaque string and send it
+ * back to get the next page.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DocumentCursor {
+    #[serde(rename = "f")]
+    pub field: DocumentSortField,
+    #[serde(rename = "d")]
+    pub direction: SortDirection,
+    #[serde(rename = "k")]
+    pub sort_key: String,
+    pub id: Uuid,
+}
+
+impl DocumentCursor {
+    pub fn after(document: &Document, sort: &DocumentSort) -> Self {
+        Self {
+            field: sort.field,
+            direction: sort.direction,
+            sort_key: sort.field.key_of(document),
+            id: document.id,
+        }
+    }
+
+    pub fn sort(&self) -> DocumentSort {
+        DocumentSort {
+            field: self.field,
+            direction: self.direction,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).expect("cursor serializes to JSON"))
+    }
+
+    /// Decodes a cursor handed out for the same sort order.
+    pub fn decode(value: &str, sort: &DocumentSort) -> Result<Self, InvalidCursor> {
+        let bytes = URL_SAFE_NO_PAD.decode(value).map_err(|_| InvalidCursor)?;
+        let cursor: Self = serde_json::from_slice(&bytes).map_err(|_| InvalidCursor)?;
+        if cursor.sort() != *sort || !cursor.field.accepts_key(&cursor.sort_key) {
+            return Err(InvalidCursor);
+        }
+        Ok(cursor)
+    }
+
+    /// Whether the document comes after this cursor in the cursor's sort order.
+    pub fn precedes(&self, document: &Document) -> bool {
+        let ordering = self
+            .field
+            .key_of(document)
+            .as_str()
+            .cmp(self.sort_key.as_str())
+            .then_with(|| document.id.cmp(&self.id));
+        match self.direction {
+            SortDirection::Asc => ordering == Ordering::Greater,
+            SortDirection::Desc => ordering == Ordering::Less,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidCursor;
+
+impl fmt::Display for InvalidCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid or mismatched page cursor")
+    }
+}
+
+impl std::error::Error for InvalidCursor {}
+
+/**
+ * Inclusive range of [`Document::date`]s. Either end may be open.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DateRange {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl DateRange {
+    pub fn new(from: Option<NaiveDate>, to: Option<NaiveDate>) -> Self {
+        Self { from, to }
+    }
+
+    /// A range whose start is after its end matches nothing.
+    pub fn is_empty(&self) -> bool {
+        matches!((self.from, self.to), (Some(from), Some(to)) if from > to)
+    }
+
+    pub fn contains(&self, document: &Document) -> bool {
+        let date = document.date();
+        self.from.is_none_or(|from| date >= from) && self.to.is_none_or(|to| date <= to)
+    }
+}
+
+/**
+ * One page request for a user's documents.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct DocumentListing {
+    pub sort: DocumentSort,
+    pub after: Option<DocumentCursor>,
+    pub tags: TagFilter,
+    pub dates: DateRange,
+    pub limit: u32,
+}
+
+/**
+ * A page of documents and the cursor for the following page, if there is one.
+ */
+#[derive(Clone, Debug)]
+pub struct DocumentPage {
+    pub items: Vec<Document>,
+    pub next_cursor: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sort(direction: SortDirection) -> DocumentSort {
+        DocumentSort {
+            field: DocumentSortField::Title,
+            direction,
+        }
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let document = Document::new("Power bill", "", Uuid::new_v4());
+        let cursor = DocumentCursor::after(&document, &sort(SortDirection::Desc));
+
+        let decoded = DocumentCursor::decode(&cursor.encode(), &sort(SortDirection::Desc));
+
+        assert_eq!(decoded, Ok(cursor));
+    }
+
+    #[test]
+    fn test_cursor_for_other_sort_is_invalid() {
+        let document = Document::new("Power bill", "", Uuid::new_v4());
+        let cursor = DocumentCursor::after(&document, &sort(SortDirection::Asc));
+
+        assert_eq!(
+            DocumentCursor::decode(&cursor.encode(), &sort(SortDirection::Desc)),
+            Err(InvalidCursor)
+        );
+        assert_eq!(
+            DocumentCursor::decode("not a cursor", &sort(SortDirection::Asc)),
+            Err(InvalidCursor)
+        );
+    }
+
+    #[test]
+    fn test_cursor_breaks_title_ties_by_id() {
+        let user_id = Uuid::new_v4();
+        let mut documents = [
+            Document::new("Bill", "", user_id),
+            Document::new("Bill", "", user_id),
+            Document::new("Bill", "", user_id),
+        ];
+        let sort = sort(SortDirection::Asc);
+        documents.sort_by(|a, b| sort.compare(a, b));
+
+        let cursor = DocumentCursor::after(&documents[0], &sort);
+
+        assert!(!cursor.precedes(&documents[0]));
+        assert!(cursor.precedes(&documents[1]));
+        assert!(cursor.precedes(&documents[2]));
+    }
+
+    #[test]
+    fn test_cursor_orders_by_timestamp() {
+        let user_id = Uuid::new_v4();
+        let older = Document::new("Zebra", "", user_id);
+        let mut newer = Document::new("Aardvark", "", user_id);
+        newer.created_at = older.created_at + chrono::TimeDelta::seconds(1);
+        let sort = DocumentSort {
+            field: DocumentSortField::CreatedAt,
+            direction: SortDirection::Desc,
+        };
+
+        let cursor = DocumentCursor::after(&newer, &sort);
+
+        assert_eq!(sort.compare(&newer, &older), Ordering::Less);
+        assert!(cursor.precedes(&older));
+        assert_eq!(
+            DocumentCursor::decode(&cursor.encode(), &sort).map(|c| c.sort_key),
+            Ok(timestamp_key(&newer.created_at))
+        );
+    }
+
+    #[test]
+    fn test_timestamp_cursor_with_bad_key_is_invalid() {
+        let document = Document::new("Power bill", "", Uuid::new_v4());
+        let created_at = DocumentSort {
+            field: DocumentSortField::CreatedAt,
+            direction: SortDirection::Asc,
+        };
+        let mut cursor = DocumentCursor::after(&document, &created_at);
+        cursor.sort_key = "yesterday".to_string();
+
+        assert_eq!(
+            DocumentCursor::decode(&cursor.encode(), &created_at),
+            Err(InvalidCursor)
+        );
+    }
+
+    #[test]
+    fn test_date_range_uses_document_date() {
+        let mut document = Document::new("Power bill", "", Uuid::new_v4());
+        document.document_date = NaiveDate::from_ymd_opt(2025, 3, 14);
+        let march = DateRange::new(
+            NaiveDate::from_ymd_opt(2025, 3, 1),
+            NaiveDate::from_ymd_opt(2025, 3, 31),
+        );
+        let april = DateRange::new(NaiveDate::from_ymd_opt(2025, 4, 1), None);
+
+        assert!(march.contains(&document));
+        assert!(!april.contains(&document));
+        assert!(DateRange::default().contains(&document));
+        assert!(DateRange::new(march.to, march.from).is_empty());
+    }
+}
@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::document::Document;
+
+/// Marker placed around matched terms in search snippets.
+pub const HIGHLIGHT_START: &str = "<mark>";
+/// Closing marker placed around matched terms in search snippets.
+pub const HIGHLIGHT_END: &str = "</mark>";
+
+/**
+ * A single term of a full-text search. All terms of a query must match.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SearchTerm {
+    /// A whole word, e.g. `invoice`.
+    Word(String),
+    /// Words starting with the given text, written as `inv*`.
+    Prefix(String),
+    /// Words that must appear next to each other, written as `"power bill"`.
+    Phrase(Vec<String>),
+}
+
+/**
+ * Parsed full-text search query. Parsing never fails on user input: characters that have a
+ * special meaning to the search engine are treated as word separators.
+ */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    pub terms: Vec<SearchTerm>,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Self {
+        let mut terms = Vec::new();
+        for (index, part) in input.split('"').enumerate() {
+            let quoted = index % 2 == 1;
+            if quoted {
+                let words = words(part);
+                if !words.is_empty() {
+                    terms.push(SearchTerm::Phrase(words));
+                }
+                continue;
+            }
+            for token in part.split_whitespace() {
+                let prefix = token.ends_with('*');
+                let mut token_words = words(token);
+                match (prefix, token_words.len()) {
+                    (_, 0) => {}
+                    (true, _) => {
+                        let last = token_words.pop().expect("checked non-empty");
+                        terms.extend(token_words.into_iter().map(SearchTerm::Word));
+                        terms.push(SearchTerm::Prefix(last));
+                    }
+                    (false, 1) => terms.push(SearchTerm::Word(token_words.remove(0))),
+                    (false, _) => terms.push(SearchTerm::Phrase(token_words)),
+                }
+            }
+        }
+        Self { terms }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Scores a document by counting term occurrences, weighting the title above the content.
+    /// Returns [`None`] unless every term matches. Used where no search index is available.
+    pub fn score(&self, title: &str, content: &str) -> Option<f64> {
+        let title_words = words(title);
+        let content_words = words(content);
+        let mut score = 0.0;
+        for term in &self.terms {
+            let hits = 10 * term.count_in(&title_words) + term.count_in(&content_words);
+            if hits == 0 {
+                return None;
+            }
+            score += hits as f64;
+        }
+        Some(score)
+    }
+
+    /// Wraps every word of the text that matches one of the terms in highlight markers.
+    pub fn highlight(&self, text: &str) -> String {
+        let mut highlighted = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find(char::is_alphanumeric) {
+            highlighted.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let end = rest
+                .find(|c: char| !c.is_alphanumeric())
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            if self
+                .terms
+                .iter()
+                .any(|t| t.matches_word(&word.to_lowercase()))
+            {
+                highlighted.push_str(HIGHLIGHT_START);
+                highlighted.push_str(word);
+                highlighted.push_str(HIGHLIGHT_END);
+            } else {
+                highlighted.push_str(word);
+            }
+            rest = &rest[end..];
+        }
+        highlighted.push_str(rest);
+        highlighted
+    }
+}
+
+impl SearchTerm {
+    fn matches_word(&self, word: &str) -> bool {
+        match self {
+            SearchTerm::Word(w) => w == word,
+            SearchTerm::Prefix(p) => word.starts_with(p.as_str()),
+            SearchTerm::Phrase(ws) => ws.iter().any(|w| w == word),
+        }
+    }
+
+    fn count_in(&self, words: &[String]) -> usize {
+        match self {
+            SearchTerm::Phrase(phrase) => words
+                .windows(phrase.len())
+                .filter(|window| window == &phrase.as_slice())
+                .count(),
+            term => words.iter().filter(|w| term.matches_word(w)).count(),
+        }
+    }
+}
+
+/**
+ * A document matching a search, with a highlighted excerpt. Higher scores are better matches.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SearchHit {
+    pub document: Document,
+    pub snippet: String,
+    pub score: f64,
+}
+
+/// Lowercased alphanumeric words of the text, mirroring how the search index tokenizes.
+pub fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_words() {
+        let query = SearchQuery::parse("Power  bill");
+        assert_eq!(
+            query.terms,
+            vec![
+                SearchTerm::Word("power".to_string()),
+                SearchTerm::Word("bill".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_phrase_and_prefix() {
+        let query = SearchQuery::parse("\"Car Insurance\" renew*");
+        assert_eq!(
+            query.terms,
+            vec![
+                SearchTerm::Phrase(vec!["car".to_string(), "insurance".to_string()]),
+                SearchTerm::Prefix("renew".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_strips_search_syntax() {
+        let query = SearchQuery::parse("NEAR(a b) OR - * \"");
+        assert_eq!(
+            query.terms,
+            vec![
+                SearchTerm::Phrase(vec!["near".to_string(), "a".to_string()]),
+                SearchTerm::Word("b".to_string()),
+                SearchTerm::Word("or".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_score_requires_every_term() {
+        let query = SearchQuery::parse("\"power bill\" due*");
+        assert!(query.score("Power bill", "Due on the 5th").is_some());
+        assert!(query.score("Power", "bill due").is_none());
+    }
+
+    #[test]
+    fn test_highlight() {
+        let query = SearchQuery::parse("bill due*");
+        assert_eq!(
+            query.highlight("Power bill, due Friday."),
+            "Power <mark>bill</mark>, <mark>due</mark> Friday."
+        );
+    }
+
+    #[test]
+    fn test_parse_blank_query_is_empty() {
+        assert!(SearchQuery::parse("  \"\" ").is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+use sha2::{Digest, Sha256};
+
+use crate::domain::document::Document;
+
+/// Documents whose fingerprints differ in at most this many bits are likely the same paper.
+/// Unrelated texts differ in about 32 bits; a few misread words in a short page can flip close
+/// to 10.
+pub const DEFAULT_MAX_DISTANCE: u32 = 10;
+
+/// Words per shingle. Shingles keep some word order, while one misread word only changes the
+/// few shingles it is part of.
+const SHINGLE_WORDS: usize = 3;
+
+/**
+ * A document that reads almost the same as another one, e.g. a second scan of the same paper.
+ * `distance` is the number of bits in which their fingerprints differ.
+ */
+#[derive(Clone, Debug)]
+pub struct SimilarDocument {
+    pub document: Document,
+    pub distance: u32,
+}
+
+/// 64 bit SimHash of the words of the text: similar texts get fingerprints that differ in few
+/// bits. Case and punctuation are ignored. Returns [`None`] for text without words.
+pub fn simhash(text: &str) -> Option<u64> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+
+    let mut weights = [0_i32; 64];
+    for shingle in words.windows(SHINGLE_WORDS.min(words.len())) {
+        let hash = feature_hash(&shingle.join(" "));
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            if hash & (1 << bit) != 0 {
+                *weight += 1;
+            } else {
+                *weight -= 1;
+            }
+        }
+    }
+    let fingerprint = weights
+        .iter()
+        .enumerate()
+        .filter(|(_, weight)| **weight > 0)
+        .fold(0, |fingerprint, (bit, _)| fingerprint | (1 << bit));
+    Some(fingerprint)
+}
+
+/// Number of bits in which two fingerprints differ.
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// A stable hash, so fingerprints stored by one build still compare with the next.
+pub(crate) fn feature_hash(feature: &str) -> u64 {
+    let digest = Sha256::digest(feature.as_bytes());
+    u64::from_le_bytes(
+        digest[..8]
+            .try_into()
+            .expect("SHA-256 has at least 8 bytes"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BILL: &str = "City Power and Light. Invoice 2025-0314 for account 88213. Billing period \
+        February 1 to February 28. Electricity used 412 kWh at 0.21 per kWh. Standing charge \
+        12.00. Total due 98.52 by March 28. Pay online or at any post office. Questions about \
+        your bill? Call our customer service line Monday to Friday between 8 and 18.";
+
+    #[test]
+    fn test_rescan_with_ocr_errors_is_close() {
+        let rescan = BILL
+            .replace("Electricity", "Electrlcity")
+            .replace("Standing", "standing");
+
+        let distance = hamming_distance(simhash(BILL).unwrap(), simhash(&rescan).unwrap());
+
+        assert!(distance <= DEFAULT_MAX_DISTANCE, "distance {distance}");
+    }
+
+    #[test]
+    fn test_unrelated_text_is_far() {
+        let letter = "Dear Anna, thank you for the lovely flowers you sent for my birthday. The \
+            garden is in full bloom and the cat sleeps in the sun all day. We hope to visit you \
+            in the summer, when the children are out of school. Love, grandma.";
+
+        let distance = hamming_distance(simhash(BILL).unwrap(), simhash(letter).unwrap());
+
+        assert!(distance > DEFAULT_MAX_DISTANCE, "distance {distance}");
+    }
+
+    #[test]
+    fn test_simhash_ignores_case_and_punctuation() {
+        assert_eq!(simhash("Total due: 98.52"), simhash("TOTAL DUE 98 52"));
+        assert!(simhash("Paid").is_some());
+        assert_eq!(simhash(" -- "), None);
+    }
+}
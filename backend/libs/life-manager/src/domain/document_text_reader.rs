@@ -1,9 +1,137 @@
 use std::error::Error;
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
+use crate::domain::ocr_word::{OcrWord, REVIEW_CONFIDENCE, mean_confidence};
 use crate::domain::uploaded_document_input::UploadedDocumentInput;
 
+/// Reader name recorded for files that have no text to extract.
+pub const NO_TEXT_READER: &str = "none";
+
+/**
+ * Text read from an uploaded file, with a record of how it was obtained.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExtractedText {
+    pub text: String,
+    /// Name of the extractor that produced the text, e.g. `pdf-extract` or `tesseract`.
+    pub reader: String,
+    /// Number of pages read, if the extractor knows it.
+    pub page_count: Option<u32>,
+    pub ocr_used: bool,
+    /// Text of each page, when the extractor reads page by page. `text` joins them with page
+    /// markers.
+    #[serde(default)]
+    pub pages: Vec<PageText>,
+    /// Languages OCR read the text in, e.g. `["eng", "deu"]`.
+    #[serde(default)]
+    pub languages: Vec<String>,
+    /// Words OCR found, with their bounding boxes. Stored apart from the text and served by
+    /// their own endpoint, so they are only set on freshly read text.
+    #[serde(skip)]
+    pub words: Vec<OcrWord>,
+    /// Mean confidence of the OCR words, from 0 to 100.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+/**
+ * Text read from one page of a document. A page that could not be read has empty text and the
+ * error, so the other pages are kept.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PageText {
+    /// 1-based page number.
+    pub page: u32,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Size in pixels of the image OCR read, which word boxes are relative to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+}
+
+impl PageText {
+    pub fn read(page: u32, text: impl Into<String>) -> Self {
+        Self {
+            page,
+            text: text.into(),
+            error: None,
+            width: None,
+            height: None,
+        }
+    }
+
+    pub fn failed(page: u32, error: impl Into<String>) -> Self {
+        Self {
+            page,
+            text: String::new(),
+            error: Some(error.into()),
+            width: None,
+            height: None,
+        }
+    }
+}
+
+impl ExtractedText {
+    /// Joins the text of the pages, marking where each page starts when there is more than one.
+    pub fn from_pages(pages: Vec<PageText>, reader: &str, ocr_used: bool) -> Self {
+        let text = match pages.as_slice() {
+            [page] => page.text.trim().to_string(),
+            _ => pages
+                .iter()
+                .map(|page| format!("{}\n{}", page_marker(page.page), page.text.trim()))
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        };
+        Self {
+            text,
+            reader: reader.to_string(),
+            page_count: Some(pages.len() as u32),
+            ocr_used,
+            pages,
+            languages: vec![],
+            words: vec![],
+            confidence: None,
+        }
+    }
+
+    /// Sets the words OCR found and the confidence of the text from them.
+    pub fn with_words(mut self, words: Vec<OcrWord>) -> Self {
+        self.confidence = mean_confidence(&words);
+        self.words = words;
+        self
+    }
+
+    /// Whether OCR read the text with a mean confidence below [`REVIEW_CONFIDENCE`].
+    pub fn needs_review(&self) -> bool {
+        self.confidence
+            .is_some_and(|confidence| confidence < REVIEW_CONFIDENCE)
+    }
+
+    /// Result for files that do not contain any readable text.
+    pub fn empty() -> Self {
+        Self {
+            text: String::new(),
+            reader: NO_TEXT_READER.to_string(),
+            page_count: None,
+            ocr_used: false,
+            pages: vec![],
+            languages: vec![],
+            words: vec![],
+            confidence: None,
+        }
+    }
+}
+
+/// Line put before the text of each page of a multi-page document.
+pub fn page_marker(page: u32) -> String {
+    format!("--- Page {} ---", page)
+}
+
 /**
  * Port for reading text from documents.
  */
@@ -12,5 +140,40 @@ pub trait DocumentTextReader: Sync + Send {
     async fn read_image(
         &self,
         uploaded_document_input: &UploadedDocumentInput,
-    ) -> Result<String, Box<dyn Error>>;
+    ) -> Result<ExtractedText, Box<dyn Error>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_pages_marks_pages_and_keeps_failed_ones() {
+        let pages = vec![
+            PageText::read(1, "Statement for March\n"),
+            PageText::failed(2, "Tesseract service answered 503"),
+            PageText::read(3, "Closing balance 1200.00"),
+        ];
+
+        let extracted = ExtractedText::from_pages(pages, "tesseract", true);
+
+        assert_eq!(
+            extracted.text,
+            "--- Page 1 ---\nStatement for March\n\n--- Page 2 ---\n\n\n--- Page 3 ---\nClosing \
+             balance 1200.00"
+        );
+        assert_eq!(extracted.page_count, Some(3));
+        assert_eq!(
+            extracted.pages[1].error.as_deref(),
+            Some("Tesseract service answered 503")
+        );
+    }
+
+    #[test]
+    fn test_from_pages_leaves_a_single_page_unmarked() {
+        let extracted = ExtractedText::from_pages(vec![PageText::read(1, " Hello ")], "mock", true);
+
+        assert_eq!(extracted.text, "Hello");
+        assert_eq!(extracted.page_count, Some(1));
+    }
 }
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+/**
+* Port for turning text into vectors whose cosine similarity follows the similarity of meaning.
+*/
+#[async_trait]
+pub trait EmbeddingProvider: Sync + Send {
+    /// Name of the model, stored with each vector. Vectors of different models are not compared.
+    fn model(&self) -> &str;
+
+    /// One vector per text, in the same order.
+    async fn embed(
+        &self,
+        texts: &[String],
+    ) -> Result<Vec<Vec<f32>>, Box<dyn std::error::Error + Send + Sync>>;
+}
@@ -0,0 +1,608 @@
+use std::{
+    error::Error,
+    fmt,
+    io::Cursor,
+    sync::{Arc, LazyLock},
+};
+
+use image::{
+    DynamicImage, GrayImage, ImageDecoder, ImageError, ImageReader, Luma,
+    codecs::png::PngEncoder,
+    imageops::{self, FilterType},
+    metadata::Orientation,
+};
+
+/// Long side of an A4 page in inches. Images carry no reliable DPI, so their resolution is
+/// estimated as if they showed a whole page.
+const PAGE_LONG_SIDE_INCHES: f32 = 11.69;
+/// Images are never scaled up more than this, however small they are.
+const MAX_UPSCALE_FACTOR: f32 = 4.0;
+/// Longest side of the copy the skew is measured on.
+const SKEW_SAMPLE_SIZE: u32 = 1000;
+
+/**
+ * An image on its way to OCR, with the EXIF orientation of the file it came from.
+ */
+#[derive(Clone, Debug)]
+pub struct OcrImage {
+    pub image: DynamicImage,
+    /// How the image has to be turned to be upright. [`Orientation::NoTransforms`] once applied.
+    pub orientation: Orientation,
+}
+
+impl OcrImage {
+    pub fn new(image: DynamicImage) -> Self {
+        Self {
+            image,
+            orientation: Orientation::NoTransforms,
+        }
+    }
+
+    /// Decodes an image file, keeping its EXIF orientation for [`FixOrientation`].
+    pub fn decode(data: &[u8]) -> Result<Self, ImageError> {
+        let mut decoder = ImageReader::new(Cursor::new(data))
+            .with_guessed_format()?
+            .into_decoder()?;
+        let orientation = decoder.orientation()?;
+        Ok(Self {
+            image: DynamicImage::from_decoder(decoder)?,
+            orientation,
+        })
+    }
+}
+
+/**
+ * One step of a [`PreprocessingChain`], e.g. turning the image to grayscale. Steps take any
+ * image; a step that needs grayscale converts it itself.
+ */
+pub trait PreprocessingStep: Send + Sync {
+    /// Name the step is selected by, e.g. in `?preprocessing=grayscale,binarize`.
+    fn name(&self) -> &'static str;
+    fn apply(&self, image: OcrImage) -> OcrImage;
+}
+
+/// Turns the image upright as its EXIF orientation says; phone cameras store photos sideways.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FixOrientation;
+
+impl PreprocessingStep for FixOrientation {
+    fn name(&self) -> &'static str {
+        "orient"
+    }
+
+    fn apply(&self, mut image: OcrImage) -> OcrImage {
+        image.image.apply_orientation(image.orientation);
+        image.orientation = Orientation::NoTransforms;
+        image
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Grayscale;
+
+impl PreprocessingStep for Grayscale {
+    fn name(&self) -> &'static str {
+        "grayscale"
+    }
+
+    fn apply(&self, image: OcrImage) -> OcrImage {
+        OcrImage {
+            image: DynamicImage::ImageLuma8(image.image.to_luma8()),
+            ..image
+        }
+    }
+}
+
+/// Stretches the gray levels so the darkest and lightest `clip` share of pixels become black
+/// and white, which brings out text on dim photos.
+#[derive(Clone, Copy, Debug)]
+pub struct NormalizeContrast {
+    pub clip: f32,
+}
+
+impl Default for NormalizeContrast {
+    fn default() -> Self {
+        Self { clip: 0.01 }
+    }
+}
+
+impl PreprocessingStep for NormalizeContrast {
+    fn name(&self) -> &'static str {
+        "normalize_contrast"
+    }
+
+    fn apply(&self, image: OcrImage) -> OcrImage {
+        let mut gray = image.image.to_luma8();
+        let histogram = histogram(&gray);
+        let clipped = (gray.len() as f32 * self.clip) as u64;
+        let low = percentile_level(&histogram, clipped + 1);
+        let high = percentile_level(&histogram, gray.len() as u64 - clipped);
+        if high > low {
+            let range = f32::from(high - low);
+            for Luma([value]) in gray.pixels_mut() {
+                let stretched = f32::from(value.saturating_sub(low)) * 255.0 / range;
+                *value = stretched.min(255.0) as u8;
+            }
+        }
+        OcrImage {
+            image: DynamicImage::ImageLuma8(gray),
+            ..image
+        }
+    }
+}
+
+/// Turns every pixel black or white at the threshold Otsu's method finds for the image.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Binarize;
+
+impl PreprocessingStep for Binarize {
+    fn name(&self) -> &'static str {
+        "binarize"
+    }
+
+    fn apply(&self, image: OcrImage) -> OcrImage {
+        let mut gray = image.image.to_luma8();
+        let threshold = otsu_threshold(&histogram(&gray));
+        for Luma([value]) in gray.pixels_mut() {
+            *value = if *value > threshold { 255 } else { 0 };
+        }
+        OcrImage {
+            image: DynamicImage::ImageLuma8(gray),
+            ..image
+        }
+    }
+}
+
+/// Rotates tilted text back to horizontal. The skew is the angle, up to `max_degrees` either
+/// way, at which the rows of dark pixels line up best.
+#[derive(Clone, Copy, Debug)]
+pub struct Deskew {
+    pub max_degrees: f32,
+    pub step_degrees: f32,
+}
+
+impl Default for Deskew {
+    fn default() -> Self {
+        Self {
+            max_degrees: 10.0,
+            step_degrees: 0.25,
+        }
+    }
+}
+
+impl PreprocessingStep for Deskew {
+    fn name(&self) -> &'static str {
+        "deskew"
+    }
+
+    fn apply(&self, image: OcrImage) -> OcrImage {
+        let gray = image.image.to_luma8();
+        let skew = estimate_skew(&gray, self.max_degrees, self.step_degrees);
+        if skew.abs() < self.step_degrees {
+            return OcrImage {
+                image: DynamicImage::ImageLuma8(gray),
+                ..image
+            };
+        }
+        OcrImage {
+            image: DynamicImage::ImageLuma8(rotate(&gray, -skew)),
+            ..image
+        }
+    }
+}
+
+/// Scales small images up until a page would have `min_dpi`, since Tesseract reads best at
+/// about 300 DPI.
+#[derive(Clone, Copy, Debug)]
+pub struct Upscale {
+    pub min_dpi: f32,
+}
+
+impl Default for Upscale {
+    fn default() -> Self {
+        Self { min_dpi: 300.0 }
+    }
+}
+
+impl PreprocessingStep for Upscale {
+    fn name(&self) -> &'static str {
+        "upscale"
+    }
+
+    fn apply(&self, image: OcrImage) -> OcrImage {
+        let (width, height) = (image.image.width(), image.image.height());
+        let dpi = width.max(height) as f32 / PAGE_LONG_SIDE_INCHES;
+        if dpi >= self.min_dpi || width == 0 || height == 0 {
+            return image;
+        }
+        let factor = (self.min_dpi / dpi).min(MAX_UPSCALE_FACTOR);
+        let scaled = image.image.resize_exact(
+            (width as f32 * factor).round() as u32,
+            (height as f32 * factor).round() as u32,
+            FilterType::CatmullRom,
+        );
+        OcrImage {
+            image: scaled,
+            ..image
+        }
+    }
+}
+
+/// Every step, by name.
+static STEPS: LazyLock<Vec<Arc<dyn PreprocessingStep>>> = LazyLock::new(|| {
+    vec![
+        Arc::new(FixOrientation),
+        Arc::new(Grayscale),
+        Arc::new(Upscale::default()),
+        Arc::new(NormalizeContrast::default()),
+        Arc::new(Deskew::default()),
+        Arc::new(Binarize),
+    ]
+});
+
+/**
+ * Steps an image goes through before OCR, in order. The default chain runs every step: it
+ * turns the image upright and gray, scales it up while it still has gray levels to interpolate,
+ * stretches its contrast, straightens it and finally makes it black and white.
+ */
+#[derive(Clone)]
+pub struct PreprocessingChain {
+    steps: Vec<Arc<dyn PreprocessingStep>>,
+}
+
+impl PreprocessingChain {
+    pub fn new(steps: Vec<Arc<dyn PreprocessingStep>>) -> Self {
+        Self { steps }
+    }
+
+    /// A chain that sends images to OCR as they are.
+    pub fn none() -> Self {
+        Self::new(vec![])
+    }
+
+    /// Parses `none`, `default` or comma separated step names, e.g. `grayscale,binarize`.
+    pub fn parse(spec: &str) -> Result<Self, UnknownPreprocessingStep> {
+        match spec.trim() {
+            "none" => return Ok(Self::none()),
+            "default" => return Ok(Self::default()),
+            _ => {}
+        }
+        spec.split(',')
+            .map(str::trim)
+            .map(|name| {
+                STEPS
+                    .iter()
+                    .find(|step| step.name() == name)
+                    .cloned()
+                    .ok_or_else(|| UnknownPreprocessingStep(name.to_string()))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self::new)
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.steps.iter().map(|step| step.name()).collect()
+    }
+
+    /// The chain in the form [`PreprocessingChain::parse`] reads.
+    pub fn to_spec(&self) -> String {
+        if self.steps.is_empty() {
+            "none".to_string()
+        } else {
+            self.names().join(",")
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn apply(&self, image: OcrImage) -> OcrImage {
+        self.steps
+            .iter()
+            .fold(image, |image, step| step.apply(image))
+    }
+
+    /// Runs the chain on an image file and encodes the result as a PNG. An empty chain gives
+    /// the file back untouched.
+    pub fn apply_to_file(&self, image_data: &[u8]) -> Result<Vec<u8>, ImageError> {
+        if self.steps.is_empty() {
+            return Ok(image_data.to_vec());
+        }
+        let image = self.apply(OcrImage::decode(image_data)?).image;
+        let mut png = Cursor::new(Vec::new());
+        image.write_with_encoder(PngEncoder::new(&mut png))?;
+        Ok(png.into_inner())
+    }
+}
+
+impl Default for PreprocessingChain {
+    fn default() -> Self {
+        Self::new(STEPS.clone())
+    }
+}
+
+impl fmt::Debug for PreprocessingChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+impl PartialEq for PreprocessingChain {
+    fn eq(&self, other: &Self) -> bool {
+        self.names() == other.names()
+    }
+}
+
+/// A step name [`PreprocessingChain::parse`] does not know.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownPreprocessingStep(pub String);
+
+impl fmt::Display for UnknownPreprocessingStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = STEPS.iter().map(|step| step.name()).collect();
+        write!(
+            f,
+            "Unknown preprocessing step '{}'; use none, default or some of {}",
+            self.0,
+            names.join(", ")
+        )
+    }
+}
+
+impl Error for UnknownPreprocessingStep {}
+
+fn histogram(gray: &GrayImage) -> [u64; 256] {
+    let mut histogram = [0; 256];
+    for Luma([value]) in gray.pixels() {
+        histogram[*value as usize] += 1;
+    }
+    histogram
+}
+
+/// Lowest gray level with at least `count` pixels at or below it.
+fn percentile_level(histogram: &[u64; 256], count: u64) -> u8 {
+    let mut seen = 0;
+    for (level, pixels) in histogram.iter().enumerate() {
+        seen += pixels;
+        if seen >= count {
+            return level as u8;
+        }
+    }
+    u8::MAX
+}
+
+/// Gray level that splits the histogram into two classes with the largest variance between
+/// them.
+fn otsu_threshold(histogram: &[u64; 256]) -> u8 {
+    let total: u64 = histogram.iter().sum();
+    let weighted_total: f64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(level, pixels)| level as f64 * *pixels as f64)
+        .sum();
+    let (mut background, mut weighted_background) = (0_u64, 0_f64);
+    let (mut best_threshold, mut best_variance) = (0_u8, 0_f64);
+    for (level, pixels) in histogram.iter().enumerate() {
+        background += pixels;
+        weighted_background += level as f64 * *pixels as f64;
+        let foreground = total - background;
+        if background == 0 || foreground == 0 {
+            continue;
+        }
+        let mean_background = weighted_background / background as f64;
+        let mean_foreground = (weighted_total - weighted_background) / foreground as f64;
+        let variance =
+            background as f64 * foreground as f64 * (mean_background - mean_foreground).powi(2);
+        if variance > best_variance {
+            best_variance = variance;
+            best_threshold = level as u8;
+        }
+    }
+    best_threshold
+}
+
+/// Angle in degrees by which the lines of text in the image rise to the right, found by
+/// projecting the dark pixels onto rows at each candidate angle and keeping the sharpest
+/// projection.
+pub fn estimate_skew(gray: &GrayImage, max_degrees: f32, step_degrees: f32) -> f32 {
+    let sample = if gray.width().max(gray.height()) > SKEW_SAMPLE_SIZE {
+        imageops::thumbnail(gray, SKEW_SAMPLE_SIZE, SKEW_SAMPLE_SIZE)
+    } else {
+        gray.clone()
+    };
+    let threshold = otsu_threshold(&histogram(&sample));
+    let (center_x, center_y) = (sample.width() as f32 / 2.0, sample.height() as f32 / 2.0);
+    let dark: Vec<(f32, f32)> = sample
+        .enumerate_pixels()
+        .filter(|(_, _, Luma([value]))| *value <= threshold)
+        .map(|(x, y, _)| (x as f32 - center_x, y as f32 - center_y))
+        .collect();
+    // A blank page, or one that is all ink, has no lines to measure.
+    if dark.is_empty() || dark.len() as u64 * 2 > sample.len() as u64 {
+        return 0.0;
+    }
+    let diagonal = (center_x.hypot(center_y).ceil() as usize) * 2 + 1;
+    let half = (diagonal / 2) as f32;
+
+    let steps = (max_degrees / step_degrees).round() as i32;
+    let mut best = (0.0, 0_u64);
+    for step in -steps..=steps {
+        let angle = step as f32 * step_degrees;
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let mut rows = vec![0_u64; diagonal];
+        for (x, y) in &dark {
+            let row = (y * cos - x * sin + half).round() as usize;
+            rows[row.min(diagonal - 1)] += 1;
+        }
+        let sharpness = rows.iter().map(|count| count * count).sum();
+        if sharpness > best.1 || (sharpness == best.1 && angle.abs() < f32::abs(best.0)) {
+            best = (angle, sharpness);
+        }
+    }
+    best.0
+}
+
+/// Rotates the image by `degrees` about its center onto a canvas large enough to hold it,
+/// filling the corners with white. Lines that rise by `a` degrees rise by `a + degrees` after.
+pub fn rotate(gray: &GrayImage, degrees: f32) -> GrayImage {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let (width, height) = (gray.width() as f32, gray.height() as f32);
+    let out_width = (width * cos.abs() + height * sin.abs()).ceil() as u32;
+    let out_height = (width * sin.abs() + height * cos.abs()).ceil() as u32;
+    let (center_x, center_y) = (width / 2.0, height / 2.0);
+    let (out_center_x, out_center_y) = (out_width as f32 / 2.0, out_height as f32 / 2.0);
+    GrayImage::from_fn(out_width, out_height, |u, v| {
+        let (u, v) = (u as f32 - out_center_x, v as f32 - out_center_y);
+        let x = u * cos + v * sin + center_x;
+        let y = -u * sin + v * cos + center_y;
+        Luma([sample_bilinear(gray, x, y)])
+    })
+}
+
+fn sample_bilinear(gray: &GrayImage, x: f32, y: f32) -> u8 {
+    let pixel = |x: i64, y: i64| -> f32 {
+        if x < 0 || y < 0 || x >= gray.width() as i64 || y >= gray.height() as i64 {
+            255.0
+        } else {
+            f32::from(gray.get_pixel(x as u32, y as u32).0[0])
+        }
+    };
+    let (x, y) = (x - 0.5, y - 0.5);
+    let (x0, y0) = (x.floor(), y.floor());
+    let (dx, dy) = (x - x0, y - y0);
+    let (x0, y0) = (x0 as i64, y0 as i64);
+    let top = pixel(x0, y0) * (1.0 - dx) + pixel(x0 + 1, y0) * dx;
+    let bottom = pixel(x0, y0 + 1) * (1.0 - dx) + pixel(x0 + 1, y0 + 1) * dx;
+    (top * (1.0 - dy) + bottom * dy).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use image::{GenericImageView, ImageFormat};
+
+    use super::*;
+
+    fn hello_world_png() -> Vec<u8> {
+        let path =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/resources/hello_world.png");
+        std::fs::read(path).unwrap()
+    }
+
+    fn hello_world() -> OcrImage {
+        OcrImage::decode(&hello_world_png()).unwrap()
+    }
+
+    fn gray_levels(image: &DynamicImage) -> usize {
+        let histogram = histogram(&image.to_luma8());
+        histogram.iter().filter(|pixels| **pixels > 0).count()
+    }
+
+    #[test]
+    fn test_default_chain_gives_a_larger_black_and_white_image() {
+        // Given
+        let before = hello_world();
+
+        // When
+        let after = PreprocessingChain::default().apply(before.clone());
+
+        // Then
+        assert_eq!(before.image.dimensions(), (400, 200));
+        assert_eq!(after.image.dimensions(), (1600, 800));
+        assert!(gray_levels(&before.image.resize_exact(1600, 800, FilterType::CatmullRom)) > 2);
+        assert_eq!(gray_levels(&after.image), 2);
+        let ink = after
+            .image
+            .to_luma8()
+            .pixels()
+            .filter(|Luma([value])| *value == 0)
+            .count();
+        assert!(ink > 1000, "{ink} black pixels");
+    }
+
+    #[test]
+    fn test_apply_to_file_encodes_a_png_and_none_keeps_the_file() {
+        let original = hello_world_png();
+
+        let processed = PreprocessingChain::default()
+            .apply_to_file(&original)
+            .unwrap();
+        let untouched = PreprocessingChain::none().apply_to_file(&original).unwrap();
+
+        assert_eq!(image::guess_format(&processed).unwrap(), ImageFormat::Png);
+        assert_ne!(processed, original);
+        assert_eq!(untouched, original);
+    }
+
+    #[test]
+    fn test_deskew_straightens_tilted_text() {
+        // Given
+        let upright = hello_world().image.to_luma8();
+        let tilted = rotate(&upright, 5.0);
+        assert!((estimate_skew(&upright, 10.0, 0.25)).abs() < 0.5);
+        assert!((estimate_skew(&tilted, 10.0, 0.25) - 5.0).abs() < 1.0);
+
+        // When
+        let straightened = Deskew::default().apply(OcrImage::new(DynamicImage::ImageLuma8(tilted)));
+
+        // Then
+        let skew = estimate_skew(&straightened.image.to_luma8(), 10.0, 0.25);
+        assert!(skew.abs() < 1.0, "still tilted by {skew} degrees");
+    }
+
+    #[test]
+    fn test_fix_orientation_turns_the_image_upright() {
+        let image = OcrImage {
+            orientation: Orientation::Rotate90,
+            ..hello_world()
+        };
+
+        let upright = FixOrientation.apply(image);
+
+        assert_eq!(upright.image.dimensions(), (200, 400));
+        assert_eq!(upright.orientation, Orientation::NoTransforms);
+    }
+
+    #[test]
+    fn test_normalize_contrast_stretches_dim_images() {
+        let dim = GrayImage::from_fn(10, 10, |x, _| Luma([100 + x as u8 * 5]));
+
+        let normalized = NormalizeContrast { clip: 0.0 }
+            .apply(OcrImage::new(DynamicImage::ImageLuma8(dim)))
+            .image
+            .to_luma8();
+
+        assert_eq!(normalized.get_pixel(0, 0).0, [0]);
+        assert_eq!(normalized.get_pixel(9, 0).0, [255]);
+    }
+
+    #[test]
+    fn test_otsu_threshold_splits_two_levels() {
+        let mut histogram = [0; 256];
+        histogram[40] = 100;
+        histogram[200] = 300;
+
+        let threshold = otsu_threshold(&histogram);
+
+        assert!((40..200).contains(&threshold), "{threshold}");
+    }
+
+    #[test]
+    fn test_parse_chain() {
+        let chain = PreprocessingChain::parse("grayscale, binarize").unwrap();
+
+        assert_eq!(chain.names(), ["grayscale", "binarize"]);
+        assert_eq!(chain.to_spec(), "grayscale,binarize");
+        assert!(PreprocessingChain::parse("none").unwrap().is_empty());
+        assert_eq!(
+            PreprocessingChain::parse("default").unwrap(),
+            PreprocessingChain::default()
+        );
+        assert_eq!(
+            PreprocessingChain::parse("grayscale,sharpen").unwrap_err(),
+            UnknownPreprocessingStep("sharpen".to_string())
+        );
+    }
+}
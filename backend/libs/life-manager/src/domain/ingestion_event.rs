@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::ingestion_job::IngestionJob;
+
+/**
+ * Progress of an upload through the ingestion pipeline, pushed to the user who uploaded it.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct IngestionEvent {
+    pub job_id: Uuid,
+    pub user_id: Uuid,
+    pub file_name: String,
+    pub stage: IngestionStage,
+    /// The created document, once it is saved.
+    pub document_id: Option<Uuid>,
+    /// Existing documents that read almost the same as the saved one.
+    pub similar_document_ids: Vec<Uuid>,
+    /// Why the attempt failed.
+    pub error: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+impl IngestionEvent {
+    pub fn new(job: &IngestionJob, stage: IngestionStage) -> Self {
+        Self {
+            job_id: job.id,
+            user_id: job.user_id,
+            file_name: job.blob.file_name.clone(),
+            stage,
+            document_id: job.document_id,
+            similar_document_ids: job.similar_document_ids.clone(),
+            error: job.error.clone(),
+            at: Utc::now(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestionStage {
+    /// The upload is stored and queued.
+    Received,
+    TextExtracted,
+    Summarizing,
+    /// The document is saved.
+    Saved,
+    /// An attempt failed. The job may still be retried, see its `next_attempt_at`.
+    Failed,
+}
+
+impl IngestionStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IngestionStage::Received => "received",
+            IngestionStage::TextExtracted => "text_extracted",
+            IngestionStage::Summarizing => "summarizing",
+            IngestionStage::Saved => "saved",
+            IngestionStage::Failed => "failed",
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::{error::Error, fmt};
+
+/**
+ * Error from a stage of ingestion that says whether running the job again can help. Timeouts,
+ * refused connections and 5xx answers are retryable; a file that cannot be read is permanent.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IngestionFailure {
+    pub message: String,
+    pub retryable: bool,
+}
+
+impl IngestionFailure {
+    pub fn retryable(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            retryable: true,
+        }
+    }
+
+    pub fn permanent(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            retryable: false,
+        }
+    }
+
+    /// Wraps any error with `context`. Errors that are not an [`IngestionFailure`] are treated as
+    /// retryable, since the attempts are bounded anyway.
+    pub fn from_error(context: &str, error: &(dyn Error + 'static)) -> Self {
+        let retryable = error
+            .downcast_ref::<IngestionFailure>()
+            .is_none_or(|failure| failure.retryable);
+        Self {
+            message: format!("{}: {}", context, error),
+            retryable,
+        }
+    }
+}
+
+impl fmt::Display for IngestionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for IngestionFailure {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_error_keeps_the_classification() {
+        let permanent: Box<dyn Error> = Box::new(IngestionFailure::permanent("not a PDF"));
+        let unknown: Box<dyn Error> = "connection reset".into();
+
+        let permanent = IngestionFailure::from_error("Error reading document text", &*permanent);
+        let unknown = IngestionFailure::from_error("Error reading document text", &*unknown);
+
+        assert_eq!(
+            permanent,
+            IngestionFailure::permanent("Error reading document text: not a PDF")
+        );
+        assert_eq!(
+            unknown,
+            IngestionFailure::retryable("Error reading document text: connection reset")
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{
+    blob_store::DocumentBlob, image_preprocessing::PreprocessingChain, ocr_language::OcrLanguages,
+};
+
+/**
+ * Uploaded file waiting to be turned into a document. The file is already in the blob store when
+ * the job is queued, so a job survives a restart of the server.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct IngestionJob {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub status: IngestionJobStatus,
+    pub blob: DocumentBlob,
+    /// The created document, once the job has succeeded.
+    pub document_id: Option<Uuid>,
+    /// Why the last attempt failed.
+    pub error: Option<String>,
+    /// Existing documents that read almost the same as the created one.
+    pub similar_document_ids: Vec<Uuid>,
+    /// Preprocessing of images before OCR chosen for the upload. [`None`] leaves it to the reader.
+    pub preprocessing: Option<PreprocessingChain>,
+    /// Languages chosen for the upload, by the user or their settings. [`None`] lets the reader
+    /// detect them.
+    pub languages: Option<OcrLanguages>,
+    /// How many times a worker has started the job.
+    pub attempts: u32,
+    /// A queued job is not claimed before this time; pushed back after a retryable failure.
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl IngestionJob {
+    /// A queued job for a file that is already in the blob store.
+    pub fn new(user_id: Uuid, blob: DocumentBlob) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            status: IngestionJobStatus::Queued,
+            blob,
+            document_id: None,
+            error: None,
+            similar_document_ids: vec![],
+            preprocessing: None,
+            languages: None,
+            attempts: 0,
+            next_attempt_at: now,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn with_preprocessing(mut self, preprocessing: Option<PreprocessingChain>) -> Self {
+        self.preprocessing = preprocessing;
+        self
+    }
+
+    pub fn with_languages(mut self, languages: Option<OcrLanguages>) -> Self {
+        self.languages = languages;
+        self
+    }
+
+    pub fn succeed(&mut self, document_id: Uuid, similar_document_ids: Vec<Uuid>) {
+        self.status = IngestionJobStatus::Succeeded;
+        self.document_id = Some(document_id);
+        self.similar_document_ids = similar_document_ids;
+        self.error = None;
+        self.updated_at = Utc::now();
+    }
+
+    /// Gives up on the job. The file stays in the blob store so the job can be retried by hand.
+    pub fn fail(&mut self, error: String) {
+        self.status = IngestionJobStatus::Failed;
+        self.error = Some(error);
+        self.updated_at = Utc::now();
+    }
+
+    /// Puts the job back in the queue after a retryable failure, to run again at `next_attempt_at`.
+    pub fn retry_at(&mut self, error: String, next_attempt_at: DateTime<Utc>) {
+        self.status = IngestionJobStatus::Queued;
+        self.error = Some(error);
+        self.next_attempt_at = next_attempt_at;
+        self.updated_at = Utc::now();
+    }
+
+    /// Queues a failed job again with a fresh set of attempts.
+    pub fn retry_now(&mut self) {
+        let now = Utc::now();
+        self.status = IngestionJobStatus::Queued;
+        self.error = None;
+        self.attempts = 0;
+        self.next_attempt_at = now;
+        self.updated_at = now;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self.status,
+            IngestionJobStatus::Succeeded | IngestionJobStatus::Failed
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestionJobStatus {
+    /// Waiting for a worker.
+    Queued,
+    /// A worker is reading and summarizing the file.
+    Running,
+    Succeeded,
+    /// Failed permanently or ran out of attempts.
+    Failed,
+}
+
+impl IngestionJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IngestionJobStatus::Queued => "queued",
+            IngestionJobStatus::Running => "running",
+            IngestionJobStatus::Succeeded => "succeeded",
+            IngestionJobStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "queued" => Some(IngestionJobStatus::Queued),
+            "running" => Some(IngestionJobStatus::Running),
+            "succeeded" => Some(IngestionJobStatus::Succeeded),
+            "failed" => Some(IngestionJobStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blob() -> DocumentBlob {
+        DocumentBlob {
+            sha256: "abc".to_string(),
+            file_name: "bill.pdf".to_string(),
+            mime_type: "application/pdf".to_string(),
+            size_bytes: 3,
+        }
+    }
+
+    #[test]
+    fn test_new_job_is_queued() {
+        let job = IngestionJob::new(Uuid::new_v4(), blob());
+
+        assert_eq!(job.status, IngestionJobStatus::Queued);
+        assert!(job.document_id.is_none());
+        assert!(!job.is_finished());
+    }
+
+    #[test]
+    fn test_succeed_and_fail_finish_the_job() {
+        let document_id = Uuid::new_v4();
+        let mut succeeded = IngestionJob::new(Uuid::new_v4(), blob());
+        let mut failed = succeeded.clone();
+
+        succeeded.succeed(document_id, vec![]);
+        failed.fail("Ollama is down".to_string());
+
+        assert_eq!(succeeded.status, IngestionJobStatus::Succeeded);
+        assert_eq!(succeeded.document_id, Some(document_id));
+        assert!(succeeded.is_finished());
+        assert_eq!(failed.status, IngestionJobStatus::Failed);
+        assert_eq!(failed.error.as_deref(), Some("Ollama is down"));
+        assert!(failed.is_finished());
+    }
+
+    #[test]
+    fn test_retry_at_requeues_and_retry_now_resets_the_attempts() {
+        let mut job = IngestionJob::new(Uuid::new_v4(), blob());
+        job.attempts = 3;
+        let later = job.created_at + chrono::Duration::minutes(5);
+
+        job.retry_at("Ollama timed out".to_string(), later);
+
+        assert_eq!(job.status, IngestionJobStatus::Queued);
+        assert_eq!(job.next_attempt_at, later);
+        assert_eq!(job.error.as_deref(), Some("Ollama timed out"));
+
+        job.fail("Ollama timed out".to_string());
+        job.retry_now();
+
+        assert_eq!(job.status, IngestionJobStatus::Queued);
+        assert_eq!(job.attempts, 0);
+        assert!(job.error.is_none());
+        assert!(job.next_attempt_at < later);
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            IngestionJobStatus::Queued,
+            IngestionJobStatus::Running,
+            IngestionJobStatus::Succeeded,
+            IngestionJobStatus::Failed,
+        ] {
+            assert_eq!(IngestionJobStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(IngestionJobStatus::parse("lost"), None);
+    }
+}
@@ -0,0 +1,233 @@
+use std::{error::Error, fmt};
+
+/// Languages read when none are chosen and the script cannot be detected.
+pub const DEFAULT_OCR_LANGUAGES: [&str; 3] = ["eng", "deu", "spa"];
+
+/// Languages written in each script Tesseract's orientation and script detection reports.
+const SCRIPT_LANGUAGES: [(&str, &[&str]); 10] = [
+    (
+        "Latin",
+        &[
+            "eng", "deu", "spa", "fra", "ita", "por", "nld", "pol", "ces", "swe", "dan", "nor",
+            "fin", "tur",
+        ],
+    ),
+    ("Cyrillic", &["rus", "ukr", "bul", "srp"]),
+    ("Greek", &["ell"]),
+    ("Arabic", &["ara", "fas"]),
+    ("Hebrew", &["heb"]),
+    ("Devanagari", &["hin", "mar", "nep"]),
+    ("Han", &["chi_sim", "chi_tra"]),
+    ("Japanese", &["jpn"]),
+    ("Hangul", &["kor"]),
+    ("Thai", &["tha"]),
+];
+
+/**
+ * Languages Tesseract reads a document in, by the names of their traineddata, e.g. `eng` or
+ * `chi_sim`. Never empty and without repeats.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OcrLanguages(Vec<String>);
+
+impl OcrLanguages {
+    pub fn new<S: AsRef<str>>(
+        codes: impl IntoIterator<Item = S>,
+    ) -> Result<Self, InvalidOcrLanguages> {
+        let mut languages: Vec<String> = Vec::new();
+        for code in codes {
+            let code = code.as_ref().trim().to_lowercase();
+            if !is_language_code(&code) {
+                return Err(InvalidOcrLanguages::Unknown(code));
+            }
+            if !languages.contains(&code) {
+                languages.push(code);
+            }
+        }
+        if languages.is_empty() {
+            return Err(InvalidOcrLanguages::Empty);
+        }
+        Ok(Self(languages))
+    }
+
+    /// Reads languages separated by `,` or `+`, e.g. `eng,deu` or Tesseract's own `eng+deu`.
+    pub fn parse(spec: &str) -> Result<Self, InvalidOcrLanguages> {
+        Self::new(
+            spec.split([',', '+'])
+                .filter(|code| !code.trim().is_empty()),
+        )
+    }
+
+    pub fn codes(&self) -> &[String] {
+        &self.0
+    }
+
+    /// The languages in the form Tesseract's `-l` takes, e.g. `eng+deu`.
+    pub fn to_spec(&self) -> String {
+        self.0.join("+")
+    }
+
+    /// The languages, if each of them is one of the `available` ones, e.g. those the OCR service
+    /// has the traineddata of.
+    pub fn available_in(self, available: &OcrLanguages) -> Result<Self, InvalidOcrLanguages> {
+        match self.0.iter().find(|code| !available.0.contains(code)) {
+            Some(code) => Err(InvalidOcrLanguages::Unavailable {
+                code: code.clone(),
+                available: available.0.clone(),
+            }),
+            None => Ok(self),
+        }
+    }
+
+    /// Those of the languages that are written in `script`, or [`None`] if none of them is.
+    pub fn for_script(&self, script: &str) -> Option<Self> {
+        let (_, written) = SCRIPT_LANGUAGES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(script))?;
+        let languages: Vec<String> = self
+            .0
+            .iter()
+            .filter(|code| written.contains(&code.as_str()))
+            .cloned()
+            .collect();
+        (!languages.is_empty()).then_some(Self(languages))
+    }
+}
+
+impl Default for OcrLanguages {
+    fn default() -> Self {
+        Self(DEFAULT_OCR_LANGUAGES.map(str::to_string).to_vec())
+    }
+}
+
+/// Tesseract names languages with three letters, optionally followed by a variant, e.g.
+/// `chi_sim` or `deu_latf`.
+fn is_language_code(code: &str) -> bool {
+    let is_word = |word: &str, lengths: std::ops::RangeInclusive<usize>| {
+        lengths.contains(&word.len()) && word.chars().all(|c| c.is_ascii_lowercase())
+    };
+    match code.split_once('_') {
+        Some((language, variant)) => is_word(language, 3..=3) && is_word(variant, 1..=8),
+        None => is_word(code, 3..=3),
+    }
+}
+
+/// The script named in the output of Tesseract's orientation and script detection
+/// (`--psm 0`), e.g. `Latin` from a `Script: Latin` line.
+pub fn detected_script(osd_output: &str) -> Option<&str> {
+    osd_output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Script:"))
+        .map(str::trim)
+        .filter(|script| !script.is_empty())
+}
+
+/// Why a list of OCR languages is not accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidOcrLanguages {
+    Empty,
+    /// Not a Tesseract language code.
+    Unknown(String),
+    /// A language code OCR cannot read in.
+    Unavailable {
+        code: String,
+        available: Vec<String>,
+    },
+}
+
+impl fmt::Display for InvalidOcrLanguages {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidOcrLanguages::Empty => write!(f, "Choose at least one OCR language"),
+            InvalidOcrLanguages::Unknown(code) => write!(
+                f,
+                "'{}' is not an OCR language; use Tesseract language codes such as {}",
+                code,
+                DEFAULT_OCR_LANGUAGES.join(", ")
+            ),
+            InvalidOcrLanguages::Unavailable { code, available } => write!(
+                f,
+                "OCR language '{}' is not installed; choose among {}",
+                code,
+                available.join(", ")
+            ),
+        }
+    }
+}
+
+impl Error for InvalidOcrLanguages {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_languages() {
+        let languages = OcrLanguages::parse("eng, DEU+chi_sim,eng").unwrap();
+
+        assert_eq!(languages.codes(), ["eng", "deu", "chi_sim"]);
+        assert_eq!(languages.to_spec(), "eng+deu+chi_sim");
+        assert_eq!(OcrLanguages::parse(" , "), Err(InvalidOcrLanguages::Empty));
+        assert_eq!(
+            OcrLanguages::parse("eng,english"),
+            Err(InvalidOcrLanguages::Unknown("english".to_string()))
+        );
+        assert_eq!(
+            OcrLanguages::parse("../eng"),
+            Err(InvalidOcrLanguages::Unknown("../eng".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_available_in_rejects_languages_that_are_not_installed() {
+        let installed = OcrLanguages::parse("eng,deu,spa").unwrap();
+
+        assert_eq!(
+            OcrLanguages::parse("deu,eng")
+                .unwrap()
+                .available_in(&installed),
+            Ok(OcrLanguages::parse("deu,eng").unwrap())
+        );
+        assert_eq!(
+            OcrLanguages::parse("eng,xyz")
+                .unwrap()
+                .available_in(&installed),
+            Err(InvalidOcrLanguages::Unavailable {
+                code: "xyz".to_string(),
+                available: installed.codes().to_vec(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_for_script_keeps_the_languages_written_in_it() {
+        let languages = OcrLanguages::parse("eng,rus,deu").unwrap();
+
+        assert_eq!(
+            languages.for_script("Latin"),
+            Some(OcrLanguages::parse("eng,deu").unwrap())
+        );
+        assert_eq!(
+            languages.for_script("Cyrillic"),
+            Some(OcrLanguages::parse("rus").unwrap())
+        );
+        assert_eq!(languages.for_script("Greek"), None);
+        assert_eq!(languages.for_script("Klingon"), None);
+    }
+
+    #[test]
+    fn test_detected_script() {
+        let osd = "Page number: 0\n\
+            Orientation in degrees: 0\n\
+            Rotate: 0\n\
+            Orientation confidence: 11.14\n\
+            Script: Cyrillic\n\
+            Script confidence: 2.57\n";
+
+        assert_eq!(detected_script(osd), Some("Cyrillic"));
+        assert_eq!(
+            detected_script("Too few characters. Skipping this page"),
+            None
+        );
+    }
+}
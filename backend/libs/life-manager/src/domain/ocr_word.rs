@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+/// Mean word confidence, from 0 to 100, below which OCR text is flagged for review.
+pub const REVIEW_CONFIDENCE: f32 = 60.0;
+
+/**
+ * A word OCR found on a page, with its bounding box in pixels of the page image Tesseract read.
+ * Boxes are relative to the page size recorded in
+ * [`crate::domain::document_text_reader::PageText`], since pages may be scaled before OCR.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OcrWord {
+    /// 1-based page number.
+    pub page: u32,
+    pub text: String,
+    pub left: u32,
+    pub top: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Tesseract's confidence in the word, from 0 to 100.
+    pub confidence: f32,
+}
+
+impl OcrWord {
+    /// Whether the word contains one of the terms, ignoring case and surrounding punctuation.
+    pub fn matches_any(&self, terms: &[String]) -> bool {
+        let word = self
+            .text
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase();
+        !word.is_empty() && terms.iter().any(|term| word.contains(term.as_str()))
+    }
+}
+
+/// Mean confidence of the words, or [`None`] if there are none.
+pub fn mean_confidence(words: &[OcrWord]) -> Option<f32> {
+    if words.is_empty() {
+        return None;
+    }
+    Some(words.iter().map(|word| word.confidence).sum::<f32>() / words.len() as f32)
+}
+
+/// Lowercased terms of a query to look for with [`OcrWord::matches_any`].
+pub fn query_terms(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, confidence: f32) -> OcrWord {
+        OcrWord {
+            page: 1,
+            text: text.to_string(),
+            left: 0,
+            top: 0,
+            width: 10,
+            height: 10,
+            confidence,
+        }
+    }
+
+    #[test]
+    fn test_mean_confidence() {
+        assert_eq!(
+            mean_confidence(&[word("Total", 90.0), word("12.50", 40.0)]),
+            Some(65.0)
+        );
+        assert_eq!(mean_confidence(&[]), None);
+    }
+
+    #[test]
+    fn test_matches_any_ignores_case_and_punctuation() {
+        let terms = query_terms("Invoice, total");
+
+        assert_eq!(terms, ["invoice", "total"]);
+        assert!(word("INVOICE:", 90.0).matches_any(&terms));
+        assert!(word("(Subtotal)", 90.0).matches_any(&terms));
+        assert!(!word("Date", 90.0).matches_any(&terms));
+        assert!(!word("--", 90.0).matches_any(&terms));
+    }
+}
@@ -0,0 +1,17 @@
+use std::{error::Error, path::Path};
+
+use async_trait::async_trait;
+
+/**
+ * Port for rasterizing pages of a PDF, e.g. to preview a scanned document.
+ */
+#[async_trait]
+pub trait PdfPageRenderer: Sync + Send {
+    /// Renders `page` (1-based) as a PNG that fits in `max_size` × `max_size` pixels.
+    async fn render_page(
+        &self,
+        pdf: &Path,
+        page: u32,
+        max_size: u32,
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>>;
+}
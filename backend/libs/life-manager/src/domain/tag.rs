@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+/**
+ * How several tags in a filter are combined.
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMatch {
+    /// Documents must carry every tag (AND).
+    #[default]
+    All,
+    /// Documents must carry at least one of the tags (OR).
+    Any,
+}
+
+/**
+ * Restricts a document listing to documents carrying some tags.
+ */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TagFilter {
+    pub tags: Vec<String>,
+    pub mode: TagMatch,
+}
+
+impl TagFilter {
+    pub fn new(tags: &[String], mode: TagMatch) -> Self {
+        Self {
+            tags: normalize_tags(tags),
+            mode,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Whether a document with the given (normalized) tags passes the filter.
+    pub fn matches(&self, document_tags: &[String]) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        match self.mode {
+            TagMatch::All => self.tags.iter().all(|t| document_tags.contains(t)),
+            TagMatch::Any => self.tags.iter().any(|t| document_tags.contains(t)),
+        }
+    }
+}
+
+/**
+ * A tag together with how many of the user's documents carry it.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TagCount {
+    pub name: String,
+    pub document_count: i64,
+}
+
+/// Tags are compared case-insensitively and without surrounding whitespace. Returns [`None`] for
+/// blank names.
+pub fn normalize_tag(name: &str) -> Option<String> {
+    let name = name.trim().to_lowercase();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Normalizes, de-duplicates and sorts a list of tag names.
+pub fn normalize_tags(names: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = names.iter().filter_map(|n| normalize_tag(n)).collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_normalize_tags() {
+        let normalized = normalize_tags(&tags(&[" Bills ", "tax", "bills", "  "]));
+        assert_eq!(normalized, tags(&["bills", "tax"]));
+    }
+
+    #[test]
+    fn test_filter_all_requires_every_tag() {
+        let filter = TagFilter::new(&tags(&["bills", "tax"]), TagMatch::All);
+        assert!(filter.matches(&tags(&["bills", "tax", "2025"])));
+        assert!(!filter.matches(&tags(&["bills"])));
+    }
+
+    #[test]
+    fn test_filter_any_requires_one_tag() {
+        let filter = TagFilter::new(&tags(&["bills", "tax"]), TagMatch::Any);
+        assert!(filter.matches(&tags(&["tax"])));
+        assert!(!filter.matches(&tags(&["medical"])));
+    }
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        assert!(TagFilter::default().matches(&[]));
+    }
+}
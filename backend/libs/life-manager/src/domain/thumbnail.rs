@@ -0,0 +1,76 @@
+use std::io::Cursor;
+
+use image::{ImageError, ImageFormat, codecs::webp::WebPEncoder};
+
+/// Thumbnails fit in a square of this many pixels.
+pub const THUMBNAIL_SIZE: u32 = 256;
+pub const THUMBNAIL_MIME_TYPE: &str = "image/webp";
+
+/// Scales an image down to fit in [`THUMBNAIL_SIZE`], keeping its aspect ratio, and encodes it
+/// as lossless WebP. Smaller images keep their size.
+pub fn make_thumbnail(image_data: &[u8]) -> Result<Vec<u8>, ImageError> {
+    let image = image::load_from_memory(image_data)?;
+    let image = if image.width() > THUMBNAIL_SIZE || image.height() > THUMBNAIL_SIZE {
+        image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+    } else {
+        image
+    };
+    let mut webp = Cursor::new(Vec::new());
+    image
+        .to_rgba8()
+        .write_with_encoder(WebPEncoder::new_lossless(&mut webp))?;
+    Ok(webp.into_inner())
+}
+
+/// Whether the bytes are an image format thumbnails can be made from.
+pub fn is_thumbnailable_image(image_data: &[u8]) -> bool {
+    image::guess_format(image_data).is_ok_and(|format| {
+        matches!(
+            format,
+            ImageFormat::Png
+                | ImageFormat::Jpeg
+                | ImageFormat::Gif
+                | ImageFormat::WebP
+                | ImageFormat::Tiff
+                | ImageFormat::Bmp
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{DynamicImage, GenericImageView, RgbImage};
+
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut png = Cursor::new(Vec::new());
+        DynamicImage::ImageRgb8(RgbImage::new(width, height))
+            .write_to(&mut png, ImageFormat::Png)
+            .unwrap();
+        png.into_inner()
+    }
+
+    #[test]
+    fn test_make_thumbnail_fits_the_image_in_the_square() {
+        let thumbnail = make_thumbnail(&png(1000, 500)).unwrap();
+
+        let decoded = image::load_from_memory_with_format(&thumbnail, ImageFormat::WebP).unwrap();
+        assert_eq!(decoded.dimensions(), (256, 128));
+    }
+
+    #[test]
+    fn test_make_thumbnail_keeps_small_images() {
+        let thumbnail = make_thumbnail(&png(40, 30)).unwrap();
+
+        let decoded = image::load_from_memory_with_format(&thumbnail, ImageFormat::WebP).unwrap();
+        assert_eq!(decoded.dimensions(), (40, 30));
+    }
+
+    #[test]
+    fn test_is_thumbnailable_image() {
+        assert!(is_thumbnailable_image(&png(1, 1)));
+        assert!(!is_thumbnailable_image(b"%PDF-1.4"));
+        assert!(!is_thumbnailable_image(b"Total: 12.50"));
+    }
+}
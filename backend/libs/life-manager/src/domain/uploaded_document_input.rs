@@ -1,15 +1,34 @@
+use std::io;
+
 use uuid::Uuid;
 
+use crate::domain::{
+    content_type::{
+        self, UnsupportedContentType, check_content_type, is_zip_archive, sniff_mime_type,
+    },
+    image_preprocessing::PreprocessingChain,
+    ocr_language::OcrLanguages,
+    uploaded_file::UploadedFile,
+};
+
 /**
 * Structure representing an uploaded document input.
 */
+#[derive(Clone)]
 pub struct UploadedDocumentInput {
     /** Name of the uploaded file. */
     pub file_name: String,
-    /** Raw binary data of the uploaded file. */
-    pub file_data: Vec<u8>,
+    /** Content of the uploaded file, kept on disk. */
+    pub file: UploadedFile,
+    /// Lowercase extension of the file name; empty if it has none.
     pub extension: String,
+    /// MIME type of the content, from its magic bytes. [`None`] if it is not recognized.
+    pub detected_mime_type: Option<&'static str>,
     pub user_id: Uuid,
+    /// Preprocessing of images before OCR chosen for the upload. [`None`] leaves it to the reader.
+    pub preprocessing: Option<PreprocessingChain>,
+    /// Languages to read the text in. [`None`] lets the reader detect them.
+    pub languages: Option<OcrLanguages>,
 }
 
 impl UploadedDocumentInput {
@@ -19,37 +38,82 @@ impl UploadedDocumentInput {
      * # Arguments
      *
      * * `file_name` - The name of the uploaded file.
-     * * `file_data` - The raw binary data of the uploaded file.
+     * * `file` - The content of the uploaded file.
      * * `user_id` - The ID of the user who uploaded the document.
      *
      * # Returns
      *
-     * A new instance of `UploadedDocumentInput`.
+     * A new instance of `UploadedDocumentInput`, or the error reading the start of the file to
+     * detect its type.
      */
-    pub fn new(file_name: String, file_data: Vec<u8>, user_id: Uuid) -> Self {
-        let extension = file_name.rsplit('.').next().unwrap_or("").to_lowercase();
-        UploadedDocumentInput {
+    pub fn new(file_name: String, file: UploadedFile, user_id: Uuid) -> io::Result<Self> {
+        let extension = file_name
+            .rsplit_once('.')
+            .map(|(_, extension)| extension.to_lowercase())
+            .unwrap_or_default();
+        let detected_mime_type = sniff_mime_type(&file.head()?);
+        Ok(UploadedDocumentInput {
             file_name,
-            file_data,
+            file,
             extension,
+            detected_mime_type,
             user_id,
-        }
+            preprocessing: None,
+            languages: None,
+        })
+    }
+
+    pub fn with_preprocessing(mut self, preprocessing: Option<PreprocessingChain>) -> Self {
+        self.preprocessing = preprocessing;
+        self
+    }
+
+    pub fn with_languages(mut self, languages: Option<OcrLanguages>) -> Self {
+        self.languages = languages;
+        self
+    }
+
+    /// Writes the bytes to a temp file first.
+    pub fn from_bytes(file_name: String, file_data: &[u8], user_id: Uuid) -> io::Result<Self> {
+        Self::new(file_name, UploadedFile::from_bytes(file_data)?, user_id)
     }
 
     pub fn is_pdf(&self) -> bool {
-        self.file_name.to_lowercase().ends_with(".pdf")
+        self.detected_mime_type == Some(content_type::PDF)
+    }
+
+    /// Whether the content is a ZIP archive whose files are uploaded one by one.
+    pub fn is_zip(&self) -> bool {
+        is_zip_archive(&self.extension, self.detected_mime_type)
+    }
+
+    /// Whether the content is an image text can only be read from with OCR.
+    pub fn is_image(&self) -> bool {
+        self.detected_mime_type
+            .is_some_and(|mime_type| mime_type.starts_with("image/"))
+    }
+
+    /// MIME type detected from the content. Falls back to `application/octet-stream`.
+    pub fn mime_type(&self) -> &'static str {
+        self.detected_mime_type
+            .unwrap_or("application/octet-stream")
+    }
+
+    /// The detected MIME type, if documents can be made from it and the file name does not
+    /// claim another type.
+    pub fn check_content_type(&self) -> Result<&'static str, UnsupportedContentType> {
+        check_content_type(&self.extension, self.detected_mime_type)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs::File;
-    use std::io::Read;
     use std::path::PathBuf;
 
     use uuid::Uuid;
 
     use super::UploadedDocumentInput;
+    use crate::domain::uploaded_file::UploadedFile;
 
     fn test_resources_path(file_name: &str) -> PathBuf {
         PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -61,15 +125,42 @@ mod tests {
     pub fn test_new() {
         let file_name = "hello_world.png";
         let path = test_resources_path(file_name);
-        let mut file = File::open(path).expect("Failed to open the file");
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer)
-            .expect("Failed to read the file");
-        let buffer_length = buffer.len();
+        let file_length = std::fs::metadata(&path).unwrap().len();
+        let file = UploadedFile::existing(path).expect("Failed to open the file");
         let uploaded_document_input =
-            UploadedDocumentInput::new(file_name.to_string(), buffer, Uuid::new_v4());
+            UploadedDocumentInput::new(file_name.to_string(), file, Uuid::new_v4()).unwrap();
         assert_eq!(uploaded_document_input.extension, "png");
         assert_eq!(uploaded_document_input.file_name, file_name);
-        assert_eq!(uploaded_document_input.file_data.len(), buffer_length);
+        assert_eq!(uploaded_document_input.file.size_bytes(), file_length);
+        assert_eq!(uploaded_document_input.mime_type(), "image/png");
+    }
+
+    #[test]
+    pub fn test_type_comes_from_the_content() {
+        let uploaded_document_input =
+            UploadedDocumentInput::from_bytes("scan".to_string(), b"%PDF-1.4", Uuid::new_v4())
+                .unwrap();
+        assert!(uploaded_document_input.is_pdf());
+        assert_eq!(uploaded_document_input.extension, "");
+        assert_eq!(
+            uploaded_document_input.check_content_type(),
+            Ok("application/pdf")
+        );
+
+        let renamed =
+            UploadedDocumentInput::from_bytes("scan.png".to_string(), b"%PDF-1.4", Uuid::new_v4())
+                .unwrap();
+        assert!(renamed.check_content_type().is_err());
+    }
+
+    #[test]
+    pub fn test_mime_type_unknown_extension() {
+        let uploaded_document_input =
+            UploadedDocumentInput::from_bytes("receipt".to_string(), &[1, 2, 3], Uuid::new_v4())
+                .unwrap();
+        assert_eq!(
+            uploaded_document_input.mime_type(),
+            "application/octet-stream"
+        );
     }
 }
@@ -0,0 +1,133 @@
+use std::{
+    fs::File,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use sha2::{Digest, Sha256};
+use tempfile::{NamedTempFile, TempPath};
+
+/// Bytes read from the start of a file to tell its type.
+const HEAD_BYTES: u64 = 8 * 1024;
+
+/**
+ * Handle to the bytes of an uploaded file, kept on disk rather than in memory. A file the handle
+ * was created for is deleted once the last clone is dropped; a file that already existed, such
+ * as a stored blob, is left alone.
+ */
+#[derive(Clone, Debug)]
+pub struct UploadedFile {
+    path: Arc<FilePath>,
+    size_bytes: u64,
+}
+
+#[derive(Debug)]
+enum FilePath {
+    Temporary(TempPath),
+    Existing(PathBuf),
+}
+
+impl UploadedFile {
+    /// Takes ownership of a temp file; it is deleted with the last clone of the handle.
+    pub fn temporary(path: TempPath) -> io::Result<Self> {
+        let size_bytes = std::fs::metadata(&path)?.len();
+        Ok(Self {
+            path: Arc::new(FilePath::Temporary(path)),
+            size_bytes,
+        })
+    }
+
+    /// Refers to a file that outlives the handle.
+    pub fn existing(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let size_bytes = std::fs::metadata(&path)?.len();
+        Ok(Self {
+            path: Arc::new(FilePath::Existing(path)),
+            size_bytes,
+        })
+    }
+
+    /// Writes the bytes to a new temp file.
+    pub fn from_bytes(data: &[u8]) -> io::Result<Self> {
+        let mut file = NamedTempFile::new()?;
+        file.write_all(data)?;
+        file.flush()?;
+        Self::temporary(file.into_temp_path())
+    }
+
+    pub fn path(&self) -> &Path {
+        match self.path.as_ref() {
+            FilePath::Temporary(path) => path,
+            FilePath::Existing(path) => path,
+        }
+    }
+
+    pub fn size_bytes(&self) -> u64 {
+        self.size_bytes
+    }
+
+    pub fn open(&self) -> io::Result<File> {
+        File::open(self.path())
+    }
+
+    /// Reads the whole file into memory. Meant for files that have to be decoded at once anyway,
+    /// such as images.
+    pub fn read(&self) -> io::Result<Vec<u8>> {
+        std::fs::read(self.path())
+    }
+
+    /// The first few kilobytes of the file, enough to tell its type.
+    pub fn head(&self) -> io::Result<Vec<u8>> {
+        let mut head = Vec::new();
+        self.open()?.take(HEAD_BYTES).read_to_end(&mut head)?;
+        Ok(head)
+    }
+
+    /// Hex encoded SHA-256 of the file, read in chunks. Same as
+    /// [`crate::domain::blob_store::content_address`] of its bytes.
+    pub fn content_address(&self) -> io::Result<String> {
+        let mut hasher = Sha256::new();
+        io::copy(&mut self.open()?, &mut hasher)?;
+        Ok(hex::encode(hasher.finalize()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::blob_store::content_address;
+
+    #[test]
+    fn test_temporary_file_is_deleted_with_the_last_handle() {
+        let file = UploadedFile::from_bytes(b"scanned receipt").unwrap();
+        let path = file.path().to_path_buf();
+        let clone = file.clone();
+
+        drop(file);
+        assert_eq!(clone.read().unwrap(), b"scanned receipt");
+        drop(clone);
+
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_existing_file_is_kept() {
+        let kept = NamedTempFile::new().unwrap();
+        let file = UploadedFile::existing(kept.path()).unwrap();
+
+        drop(file);
+
+        assert!(kept.path().exists());
+    }
+
+    #[test]
+    fn test_content_address_matches_the_bytes() {
+        let data = vec![7_u8; 100_000];
+        let file = UploadedFile::from_bytes(&data).unwrap();
+
+        assert_eq!(file.content_address().unwrap(), content_address(&data));
+        assert_eq!(file.size_bytes(), 100_000);
+        assert_eq!(file.head().unwrap().len() as u64, HEAD_BYTES);
+    }
+}
@@ -0,0 +1,11 @@
+use crate::domain::ocr_language::OcrLanguages;
+
+/**
+ * Preferences a user keeps across uploads.
+ */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserSettings {
+    /// Languages the user's uploads are read in unless an upload chooses its own. [`None`]
+    /// lets the reader detect them.
+    pub ocr_languages: Option<OcrLanguages>,
+}
@@ -1,10 +1,31 @@
 pub mod app_state;
+pub mod assistant;
 pub mod auth_integration;
+pub mod category;
 pub mod db;
 pub mod document;
 pub mod document_text_extraction;
+pub mod extractive_answer_generator;
+pub mod filesystem_blob_store;
+pub mod hashing_embedding_provider;
 pub mod http_client;
+pub mod in_memory_blob_store;
+pub mod ingestion_job;
 pub mod noop_document_text_reader;
+pub mod noop_pdf_page_renderer;
+pub mod ollama_answer_generator_adapter;
+pub mod ollama_document_classifier_adapter;
+pub mod ollama_document_field_extractor_adapter;
 pub mod ollama_document_summarizer_adapter;
+pub mod ollama_embedding_provider_adapter;
+pub mod pdftoppm_renderer;
 pub mod reqwest_http_client;
+pub mod rule_document_classifier;
+pub mod streamed_upload;
+pub mod stub_document_field_extractor;
 pub mod tesseract_adapter;
+pub mod tesseract_tsv;
+#[cfg(test)]
+pub mod test_support;
+pub mod user_settings;
+pub mod zip_upload;
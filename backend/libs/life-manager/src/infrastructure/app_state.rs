@@ -1,25 +1,55 @@
-use std::{env, sync::Arc};
+use std::{env, sync::Arc, time::Duration};
 
 use auth::{AuthState, AuthStateBuilder};
+use chrono::TimeDelta;
 use deadpool_diesel::sqlite::Pool;
 
 use crate::{
-    application::document_use_cases::DocumentUseCases,
-    domain::document_text_reader::DocumentTextReader,
+    application::{
+        document_policy::DocumentPolicy, document_use_cases::DocumentUseCases,
+        embed_documents_command::EmbedDocumentsCommand,
+        ingest_document_command::IngestDocumentCommand, ingestion_queue::IngestionQueue,
+        purge_trash_command::PurgeTrashCommand, retry_policy::RetryPolicy,
+    },
+    domain::{
+        answer_generator::AnswerGenerator, assistant_answer::DEFAULT_MIN_CONTEXT_SIMILARITY,
+        document_classifier::DocumentClassifier, document_field_extractor::DocumentFieldExtractor,
+        document_text_reader::DocumentTextReader, embedding_provider::EmbeddingProvider,
+        image_preprocessing::PreprocessingChain, ocr_language::OcrLanguages,
+        pdf_page_renderer::PdfPageRenderer,
+    },
     infrastructure::{
+        category::category_orm_collection::CategoryOrmCollection,
         db::{create_connection_pool, create_connection_pool_from_url, run_migrations},
         document::document_orm_collection::DocumentOrmCollection,
+        extractive_answer_generator::ExtractiveAnswerGenerator,
+        filesystem_blob_store::FilesystemBlobStore,
+        hashing_embedding_provider::HashingEmbeddingProvider,
+        ingestion_job::ingestion_job_orm_collection::IngestionJobOrmCollection,
         noop_document_text_reader::NoOpDocumentTextReader,
+        noop_pdf_page_renderer::NoOpPdfPageRenderer,
+        ollama_answer_generator_adapter::OllamaAnswerGeneratorAdapter,
+        ollama_document_classifier_adapter::OllamaDocumentClassifierAdapter,
+        ollama_document_field_extractor_adapter::OllamaDocumentFieldExtractorAdapter,
         ollama_document_summarizer_adapter::OllamaDocumentSummarizerAdapter,
+        ollama_embedding_provider_adapter::OllamaEmbeddingProviderAdapter,
+        pdftoppm_renderer::PdftoppmRenderer,
         reqwest_http_client::ReqwestHttpClient,
+        rule_document_classifier::RuleDocumentClassifier,
+        streamed_upload::UploadLimits,
+        stub_document_field_extractor::StubDocumentFieldExtractor,
         tesseract_adapter::TesseractAdapter,
+        user_settings::user_settings_orm_collection::UserSettingsOrmCollection,
     },
 };
 
+const TRASH_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 #[derive(Clone)]
 pub struct LifeManagerState {
     pub(crate) document_use_cases: Arc<DocumentUseCases>,
     pub(crate) auth_state: AuthState,
+    pub(crate) upload_limits: UploadLimits,
 }
 
 #[derive(Clone, Default)]
@@ -64,11 +94,19 @@ impl LifeManagerStateBuilder {
                     .await
             }
         };
+        let document_use_cases = deps
+            .document_use_cases
+            .unwrap_or_else(|| Arc::new(default_document_use_cases(pool)));
+        PurgeTrashCommand::new(document_use_cases.clone(), trash_retention_from_env())
+            .spawn(TRASH_PURGE_INTERVAL);
+        document_use_cases.ingestion_queue.recover().await;
+        IngestDocumentCommand::new(document_use_cases.clone(), retry_policy_from_env())
+            .spawn(ingestion_workers_from_env());
+        EmbedDocumentsCommand::new(document_use_cases.clone()).spawn();
         LifeManagerState {
-            document_use_cases: deps
-                .document_use_cases
-                .unwrap_or_else(|| Arc::new(default_document_use_cases(pool))),
+            document_use_cases,
             auth_state,
+            upload_limits: upload_limits_from_env(),
         }
     }
 }
@@ -85,25 +123,206 @@ fn tesseract_enabled_from_env() -> bool {
         .unwrap_or(false)
 }
 
+/// Whether PDFs get a preview of their first page, from `PDF_PREVIEWS_ENABLED`. Needs
+/// `pdftoppm` on the `PATH`.
+fn pdf_previews_enabled_from_env() -> bool {
+    env::var("PDF_PREVIEWS_ENABLED")
+        .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// Whether fields are read by keywords instead of Ollama, from `FIELD_EXTRACTOR` set to `stub`.
+fn stub_field_extractor_from_env() -> bool {
+    env::var("FIELD_EXTRACTOR").is_ok_and(|v| v.eq_ignore_ascii_case("stub"))
+}
+
+/// Whether documents are classified by the categories' rules alone instead of Ollama, from
+/// `CLASSIFIER` set to `rules`.
+fn rule_classifier_from_env() -> bool {
+    env::var("CLASSIFIER").is_ok_and(|v| v.eq_ignore_ascii_case("rules"))
+}
+
+/// Whether text is embedded by hashing its words instead of with Ollama, from
+/// `EMBEDDING_PROVIDER` set to `hashing`.
+fn hashing_embeddings_from_env() -> bool {
+    env::var("EMBEDDING_PROVIDER").is_ok_and(|v| v.eq_ignore_ascii_case("hashing"))
+}
+
+/// Whether the assistant answers by quoting the closest passage instead of asking Ollama, from
+/// `ASSISTANT` set to `extractive`.
+fn extractive_assistant_from_env() -> bool {
+    env::var("ASSISTANT").is_ok_and(|v| v.eq_ignore_ascii_case("extractive"))
+}
+
+/// Least similarity of a passage to a question for the assistant to answer from it, from
+/// `ASSISTANT_MIN_SIMILARITY`.
+fn min_context_similarity_from_env() -> f32 {
+    env::var("ASSISTANT_MIN_SIMILARITY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|similarity: &f32| similarity.is_finite())
+        .unwrap_or(DEFAULT_MIN_CONTEXT_SIMILARITY)
+}
+
+/// Pages of one document sent to Tesseract at the same time, from `OCR_CONCURRENCY`.
+fn ocr_concurrency_from_env() -> usize {
+    env::var("OCR_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|pages| *pages > 0)
+        .unwrap_or(2)
+}
+
+/// Largest uploaded file from `UPLOAD_MAX_FILE_BYTES` and largest upload request from
+/// `UPLOAD_MAX_REQUEST_BYTES`.
+fn upload_limits_from_env() -> UploadLimits {
+    let default = UploadLimits::default();
+    let bytes_from_env = |name: &str, default: u64| {
+        env::var(name)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|bytes| *bytes > 0)
+            .unwrap_or(default)
+    };
+    UploadLimits {
+        max_file_bytes: bytes_from_env("UPLOAD_MAX_FILE_BYTES", default.max_file_bytes),
+        max_request_bytes: bytes_from_env("UPLOAD_MAX_REQUEST_BYTES", default.max_request_bytes),
+    }
+}
+
+/// Preprocessing of images before OCR for uploads that do not choose their own, from
+/// `OCR_PREPROCESSING`: `default`, `none` or comma separated step names.
+fn ocr_preprocessing_from_env() -> PreprocessingChain {
+    let Ok(spec) = env::var("OCR_PREPROCESSING") else {
+        return PreprocessingChain::default();
+    };
+    PreprocessingChain::parse(&spec).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring OCR_PREPROCESSING: {}", e);
+        PreprocessingChain::default()
+    })
+}
+
+/// Languages OCR can read in, from `OCR_LANGUAGES`: Tesseract language codes separated by `,`
+/// or `+`. Uploads and user settings choose among them; script detection does for the others.
+fn ocr_languages_from_env() -> OcrLanguages {
+    let Ok(spec) = env::var("OCR_LANGUAGES") else {
+        return OcrLanguages::default();
+    };
+    OcrLanguages::parse(&spec).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring OCR_LANGUAGES: {}", e);
+        OcrLanguages::default()
+    })
+}
+
+fn blob_store_path_from_env() -> String {
+    env::var("BLOB_STORE_PATH").unwrap_or_else(|_| "./data/blobs".to_string())
+}
+
+/// How long documents stay in the trash before they are purged, from `TRASH_RETENTION_DAYS`:
+/// between a day and a hundred years, otherwise 30 days.
+fn trash_retention_from_env() -> TimeDelta {
+    let days = env::var("TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|days| (1..=36500).contains(days))
+        .unwrap_or(30);
+    TimeDelta::days(days)
+}
+
+/// Number of background workers reading and summarizing uploads, from `INGESTION_WORKERS`.
+fn ingestion_workers_from_env() -> usize {
+    env::var("INGESTION_WORKERS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|workers| *workers > 0)
+        .unwrap_or(2)
+}
+
+/// Attempts per ingestion job from `INGESTION_MAX_ATTEMPTS` and the first retry delay from
+/// `INGESTION_RETRY_BASE_SECONDS`.
+fn retry_policy_from_env() -> RetryPolicy {
+    let default = RetryPolicy::default();
+    let max_attempts = env::var("INGESTION_MAX_ATTEMPTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|attempts| *attempts > 0)
+        .unwrap_or(default.max_attempts);
+    let base_delay = env::var("INGESTION_RETRY_BASE_SECONDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(default.base_delay);
+    RetryPolicy::new(max_attempts, base_delay, default.max_delay)
+}
+
 fn default_document_use_cases(pool: Arc<Pool>) -> DocumentUseCases {
     tracing::info!("Creating default DocumentUseCases...");
+    let ocr_languages = ocr_languages_from_env();
     let reader: Arc<dyn DocumentTextReader> = if tesseract_enabled_from_env() {
-        Arc::new(TesseractAdapter::new(
-            env::var("TESSERACT_URL")
-                .expect("TESSERACT_URL must be set when TESSERACT_ENABLED is true"),
-            Arc::new(ReqwestHttpClient::new()),
-        ))
+        Arc::new(
+            TesseractAdapter::new(
+                env::var("TESSERACT_URL")
+                    .expect("TESSERACT_URL must be set when TESSERACT_ENABLED is true"),
+                Arc::new(ReqwestHttpClient::new()),
+                Arc::new(PdftoppmRenderer::default()),
+            )
+            .with_concurrency(ocr_concurrency_from_env())
+            .with_preprocessing(ocr_preprocessing_from_env())
+            .with_languages(ocr_languages.clone()),
+        )
     } else {
         Arc::new(NoOpDocumentTextReader::new())
     };
+    let pdf_renderer: Arc<dyn PdfPageRenderer> = if pdf_previews_enabled_from_env() {
+        Arc::new(PdftoppmRenderer::default())
+    } else {
+        Arc::new(NoOpPdfPageRenderer::new())
+    };
+    let ollama_url = env::var("OLLAMA_URL")
+        .ok()
+        .and_then(|url_str| url_str.parse().ok());
+    let field_extractor: Arc<dyn DocumentFieldExtractor> = if stub_field_extractor_from_env() {
+        Arc::new(StubDocumentFieldExtractor::new())
+    } else {
+        Arc::new(OllamaDocumentFieldExtractorAdapter::new(ollama_url.clone()))
+    };
+    let classifier: Arc<dyn DocumentClassifier> = if rule_classifier_from_env() {
+        Arc::new(RuleDocumentClassifier::new())
+    } else {
+        Arc::new(OllamaDocumentClassifierAdapter::new(ollama_url.clone()))
+    };
+    let embeddings: Arc<dyn EmbeddingProvider> = if hashing_embeddings_from_env() {
+        Arc::new(HashingEmbeddingProvider::new())
+    } else {
+        let adapter = OllamaEmbeddingProviderAdapter::new(ollama_url.clone());
+        match env::var("EMBEDDING_MODEL") {
+            Ok(model) if !model.is_empty() => Arc::new(adapter.with_model(&model)),
+            _ => Arc::new(adapter),
+        }
+    };
+    let answer_generator: Arc<dyn AnswerGenerator> = if extractive_assistant_from_env() {
+        Arc::new(ExtractiveAnswerGenerator::new())
+    } else {
+        Arc::new(OllamaAnswerGeneratorAdapter::new(ollama_url.clone()))
+    };
     DocumentUseCases {
-        document_repository: (Arc::new(DocumentOrmCollection::new(pool))),
+        document_repository: (Arc::new(DocumentOrmCollection::new(pool.clone()))),
         reader,
-        summarizer: Arc::new(OllamaDocumentSummarizerAdapter::new(
-            env::var("OLLAMA_URL")
-                .ok()
-                .and_then(|url_str| url_str.parse().ok()),
-        )),
+        summarizer: Arc::new(OllamaDocumentSummarizerAdapter::new(ollama_url)),
+        blob_store: Arc::new(FilesystemBlobStore::new(blob_store_path_from_env())),
+        policy: DocumentPolicy::new(),
+        ingestion_queue: IngestionQueue::new(Arc::new(IngestionJobOrmCollection::new(
+            pool.clone(),
+        ))),
+        pdf_renderer,
+        user_settings: Arc::new(UserSettingsOrmCollection::new(pool.clone())),
+        field_extractor,
+        categories: Arc::new(CategoryOrmCollection::new(pool)),
+        classifier,
+        embeddings,
+        answer_generator,
+        min_context_similarity: min_context_similarity_from_env(),
+        ocr_languages,
     }
 }
 
@@ -0,0 +1,3 @@
+pub mod assistant_dto;
+pub mod assistant_handler;
+pub mod assistant_router;
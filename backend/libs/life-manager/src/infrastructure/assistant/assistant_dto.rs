@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::assistant_answer::{AssistantAnswer, Citation};
+
+/// A question about the user's documents.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct AskCommand {
+    pub question: String,
+    /// Whether to stream the answer as server-sent events while the model writes it.
+    #[serde(default)]
+    pub stream: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CitationDto {
+    /// Number the answer refers to the passage by, as in `[1]`.
+    pub source: u32,
+    pub document_id: Uuid,
+    pub title: String,
+    pub page: Option<u32>,
+    /// Sentence of the document backing the answer, word for word.
+    pub quote: String,
+}
+
+impl CitationDto {
+    pub fn from_citation(citation: &Citation) -> Self {
+        Self {
+            source: citation.source,
+            document_id: citation.document_id,
+            title: citation.title.clone(),
+            page: citation.page,
+            quote: citation.quote.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AssistantAnswerDto {
+    pub answer: String,
+    pub citations: Vec<CitationDto>,
+}
+
+impl AssistantAnswerDto {
+    pub fn from_answer(answer: &AssistantAnswer) -> Self {
+        Self {
+            answer: answer.answer.clone(),
+            citations: answer
+                .citations
+                .iter()
+                .map(CitationDto::from_citation)
+                .collect(),
+        }
+    }
+}
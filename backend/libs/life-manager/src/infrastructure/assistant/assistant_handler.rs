@@ -0,0 +1,331 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use auth::AuthUser;
+use axum::extract::State;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::{Json, http::StatusCode};
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+use uuid::Uuid;
+
+use crate::application::document_use_cases::DocumentUseCases;
+use crate::domain::answer_generator::AnswerStream;
+use crate::domain::assistant_answer::{AssistantAnswer, NO_CONTEXT_ANSWER};
+use crate::infrastructure::assistant::assistant_dto::{AskCommand, AssistantAnswerDto};
+use crate::infrastructure::document::document_state::DocumentState;
+
+/// Server-sent events buffered for a slow client before the answer waits for it.
+const STREAM_BUFFER: usize = 32;
+
+/// Answers a question from the user's own documents, citing the passages it is based on. With
+/// `stream`, the answer comes as server-sent events: `answer` events with the next piece of
+/// text, then one `citations` event, or an `error` event if the model fails midway. A blank
+/// question gives **400**; **503** when the embedding model or the assistant's model is
+/// unavailable.
+pub async fn ask_assistant(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Json(command): Json<AskCommand>,
+) -> Response {
+    let question = command.question.trim();
+    if question.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "question must not be empty" })),
+        )
+            .into_response();
+    }
+    tracing::info!("Answering a question for user: {}", user_id);
+    if command.stream {
+        return stream_answer(document_use_cases, user_id, question.to_string()).await;
+    }
+    match document_use_cases.ask(user_id, question).await {
+        Ok(answer) => (
+            StatusCode::OK,
+            Json(json!(AssistantAnswerDto::from_answer(&answer))),
+        )
+            .into_response(),
+        Err(e) => unavailable(user_id, &*e),
+    }
+}
+
+async fn stream_answer(
+    document_use_cases: Arc<DocumentUseCases>,
+    user_id: Uuid,
+    question: String,
+) -> Response {
+    let passages = match document_use_cases.find_context(user_id, &question).await {
+        Ok(passages) => passages,
+        Err(e) => return unavailable(user_id, &*e),
+    };
+    let mut pieces: AnswerStream = if passages.is_empty() {
+        Box::pin(tokio_stream::once(Ok(NO_CONTEXT_ANSWER.to_string())))
+    } else {
+        match document_use_cases
+            .answer_generator
+            .answer(&question, &passages)
+            .await
+        {
+            Ok(pieces) => pieces,
+            Err(e) => return unavailable(user_id, &*e),
+        }
+    };
+
+    let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+    tokio::spawn(async move {
+        let mut answer = String::new();
+        while let Some(piece) = pieces.next().await {
+            let event = match piece {
+                Ok(piece) => {
+                    answer.push_str(&piece);
+                    sse_event("answer", json!({ "text": piece }))
+                }
+                Err(e) => {
+                    tracing::error!("Error streaming answer for user {}: {}", user_id, e);
+                    let _ = sender
+                        .send(sse_event(
+                            "error",
+                            json!({ "error": "The assistant stopped answering" }),
+                        ))
+                        .await;
+                    return;
+                }
+            };
+            if sender.send(event).await.is_err() {
+                // The client went away.
+                return;
+            }
+        }
+        let answer = AssistantAnswer::new(&answer, &passages);
+        let _ = sender
+            .send(sse_event(
+                "citations",
+                AssistantAnswerDto::from_answer(&answer).citations,
+            ))
+            .await;
+    });
+    Sse::new(ReceiverStream::new(receiver).map(Ok::<_, Infallible>))
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+fn sse_event(name: &str, data: impl Serialize) -> Event {
+    Event::default()
+        .event(name)
+        .json_data(data)
+        .unwrap_or_else(|e| {
+            tracing::error!("Error serializing {} event: {}", name, e);
+            Event::default().event("error")
+        })
+}
+
+fn unavailable(user_id: Uuid, e: &(dyn std::error::Error + Send + Sync)) -> Response {
+    tracing::error!("Error answering a question for user {}: {}", user_id, e);
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({ "error": "The assistant is unavailable" })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+
+    use super::*;
+    use crate::application::document_repository::DocumentRepository;
+    use crate::application::embed_documents_command::EmbedDocumentsCommand;
+    use crate::domain::answer_generator::AnswerGenerator;
+    use crate::domain::assistant_answer::ContextPassage;
+    use crate::domain::document::Document;
+    use crate::infrastructure::assistant::assistant_dto::CitationDto;
+    use crate::infrastructure::document::document_collection::DocumentCollection;
+
+    struct GivenDocuments {
+        auth_user: AuthUser,
+        state: DocumentState,
+        passport: Document,
+        others_passport: Document,
+    }
+
+    async fn given_documents() -> GivenDocuments {
+        let auth_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let repo = DocumentCollection::new();
+        let passport = repo
+            .save_document(Document::new(
+                "Passport",
+                "Passport no. X123. Your passport expires on 12 March 2031.",
+                auth_user.user_id,
+            ))
+            .await
+            .unwrap();
+        repo.save_document(Document::new(
+            "Power bill",
+            "Electricity used: 312 kWh. Total due: 84.20 EUR.",
+            auth_user.user_id,
+        ))
+        .await
+        .unwrap();
+        let others_passport = repo
+            .save_document(Document::new(
+                "Passport",
+                "When does my passport expire? Your passport expires on 1 May 2027.",
+                Uuid::new_v4(),
+            ))
+            .await
+            .unwrap();
+        let document_use_cases = Arc::new(DocumentUseCases {
+            document_repository: Arc::new(repo),
+            ..DocumentUseCases::for_tests()
+        });
+        EmbedDocumentsCommand::new(document_use_cases.clone())
+            .execute()
+            .await
+            .unwrap();
+        GivenDocuments {
+            auth_user,
+            state: DocumentState(document_use_cases),
+            passport,
+            others_passport,
+        }
+    }
+
+    fn question(text: &str, stream: bool) -> Json<AskCommand> {
+        Json(AskCommand {
+            question: text.to_string(),
+            stream,
+        })
+    }
+
+    async fn read_body(response: Response) -> (StatusCode, String) {
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read body");
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_answers_from_the_users_own_documents() {
+        // Given
+        let GivenDocuments {
+            auth_user,
+            state,
+            passport,
+            others_passport,
+        } = given_documents().await;
+
+        // When
+        let (status, body) = read_body(
+            ask_assistant(
+                auth_user,
+                State(state),
+                question("When does my passport expire?", false),
+            )
+            .await,
+        )
+        .await;
+
+        // Then
+        assert_eq!(status, StatusCode::OK);
+        let answer: AssistantAnswerDto = serde_json::from_str(&body).unwrap();
+        assert_eq!(answer.answer, "Your passport expires on 12 March 2031. [1]");
+        assert_eq!(
+            answer.citations,
+            [CitationDto {
+                source: 1,
+                document_id: passport.id,
+                title: "Passport".to_string(),
+                page: None,
+                quote: "Your passport expires on 12 March 2031.".to_string(),
+            }]
+        );
+        assert!(!body.contains(&others_passport.id.to_string()));
+    }
+
+    struct UnreachableGenerator;
+
+    #[async_trait::async_trait]
+    impl AnswerGenerator for UnreachableGenerator {
+        async fn answer(
+            &self,
+            _question: &str,
+            _passages: &[ContextPassage],
+        ) -> Result<AnswerStream, Box<dyn std::error::Error + Send + Sync>> {
+            Err("Connection refused".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unrelated_questions_are_not_sent_to_the_model() {
+        // Given
+        let GivenDocuments {
+            auth_user, state, ..
+        } = given_documents().await;
+        let state = DocumentState(Arc::new(DocumentUseCases {
+            answer_generator: Arc::new(UnreachableGenerator),
+            ..(*state.0).clone()
+        }));
+
+        // When
+        let (status, body) = read_body(
+            ask_assistant(
+                auth_user,
+                State(state),
+                question("What is the weather like on Mars?", false),
+            )
+            .await,
+        )
+        .await;
+
+        // Then
+        assert_eq!(status, StatusCode::OK);
+        let answer: AssistantAnswerDto = serde_json::from_str(&body).unwrap();
+        assert_eq!(answer.answer, NO_CONTEXT_ANSWER);
+        assert!(answer.citations.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_streams_the_answer_then_the_citations() {
+        // Given
+        let GivenDocuments {
+            auth_user,
+            state,
+            passport,
+            ..
+        } = given_documents().await;
+
+        // When
+        let blank =
+            ask_assistant(auth_user.clone(), State(state.clone()), question(" ", true)).await;
+        let (status, body) = read_body(
+            ask_assistant(
+                auth_user,
+                State(state),
+                question("When does my passport expire?", true),
+            )
+            .await,
+        )
+        .await;
+
+        // Then
+        assert_eq!(blank.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(status, StatusCode::OK);
+        let answer_at = body.find("event: answer\n").expect(&body);
+        let citations_at = body.find("event: citations\n").expect(&body);
+        assert!(answer_at < citations_at, "{body}");
+        assert!(body.contains("12 March 2031"), "{body}");
+        assert!(body.contains(&passport.id.to_string()), "{body}");
+    }
+}
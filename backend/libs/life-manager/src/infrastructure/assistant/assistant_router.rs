@@ -0,0 +1,9 @@
+use axum::{Router, routing::post};
+
+use crate::infrastructure::{
+    app_state::LifeManagerState, assistant::assistant_handler::ask_assistant,
+};
+
+pub fn assistant_router() -> Router<LifeManagerState> {
+    Router::new().route("/ask", post(ask_assistant))
+}
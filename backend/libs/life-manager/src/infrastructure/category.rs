@@ -0,0 +1,6 @@
+pub mod category_collection;
+pub mod category_dto;
+pub mod category_entity;
+pub mod category_handler;
+pub mod category_orm_collection;
+pub mod category_router;
@@ -0,0 +1,66 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::{
+    application::category_repository::{CategoryNameTaken, CategoryRepository},
+    domain::category::Category,
+};
+
+/// In-memory [`CategoryRepository`] used by tests.
+#[derive(Default)]
+pub struct CategoryCollection {
+    pub categories: Mutex<Vec<Category>>,
+}
+
+impl CategoryCollection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CategoryRepository for CategoryCollection {
+    async fn get_categories(
+        &self,
+        user_id: &Uuid,
+    ) -> Result<Vec<Category>, Box<dyn Error + Send + Sync>> {
+        let mut categories: Vec<Category> = self
+            .categories
+            .lock()
+            .await
+            .iter()
+            .filter(|category| category.user_id == *user_id)
+            .cloned()
+            .collect();
+        categories.sort_by_key(|category| category.name.to_lowercase());
+        Ok(categories)
+    }
+
+    async fn save_category(&self, category: &Category) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut categories = self.categories.lock().await;
+        if categories.iter().any(|other| {
+            other.user_id == category.user_id
+                && other.id != category.id
+                && other.is_named(&category.name)
+        }) {
+            return Err(Box::new(CategoryNameTaken(category.name.clone())));
+        }
+        categories.retain(|stored| stored.id != category.id);
+        categories.push(category.clone());
+        Ok(())
+    }
+
+    async fn delete_category(
+        &self,
+        category: &Category,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.categories
+            .lock()
+            .await
+            .retain(|stored| stored.id != category.id);
+        Ok(())
+    }
+}
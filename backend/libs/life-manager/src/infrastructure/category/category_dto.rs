@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::category::{Category, CategoryRule};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CategoryDto {
+    pub id: Uuid,
+    pub name: String,
+    pub description: String,
+    pub rules: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+impl CategoryDto {
+    pub fn from_category(category: &Category) -> Self {
+        Self {
+            id: category.id,
+            name: category.name.clone(),
+            description: category.description.clone(),
+            rules: category
+                .rules
+                .iter()
+                .map(CategoryRule::as_str)
+                .map(str::to_string)
+                .collect(),
+            tags: category.tags.clone(),
+        }
+    }
+}
+
+/// A category to create, or the new values of one.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct CategoryCommand {
+    pub name: String,
+    /// What belongs in the category; the model classifies documents by it.
+    #[serde(default)]
+    pub description: String,
+    /// Keywords, or regular expressions between slashes such as `/policy no\.? \d+/`, used to
+    /// classify documents when the model is not available.
+    #[serde(default)]
+    pub rules: Vec<String>,
+    /// Tags suggested for documents of the category.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
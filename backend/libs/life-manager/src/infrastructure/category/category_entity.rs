@@ -0,0 +1,48 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::domain::category::{Category, CategoryRule};
+
+#[derive(Insertable, Queryable, Selectable, AsChangeset, Debug, Clone)]
+#[diesel(table_name = crate::schema::categories)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct CategoryEntity {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub description: String,
+    /// JSON array of rules as read by [`CategoryRule::parse`].
+    pub rules: String,
+    /// JSON array of tag names.
+    pub tags: String,
+}
+
+impl CategoryEntity {
+    pub fn from_category(category: &Category) -> Self {
+        Self {
+            id: category.id.to_string(),
+            user_id: category.user_id.to_string(),
+            name: category.name.clone(),
+            description: category.description.clone(),
+            rules: serde_json::to_string(&category.rules).unwrap_or_else(|_| "[]".to_string()),
+            tags: serde_json::to_string(&category.tags).unwrap_or_else(|_| "[]".to_string()),
+        }
+    }
+
+    /// Maps a row to the domain model. Rules that no longer parse are dropped. Returns [`None`]
+    /// if the row is malformed.
+    pub fn into_category(self) -> Option<Category> {
+        let rules: Vec<String> = serde_json::from_str(&self.rules).ok()?;
+        Some(Category {
+            id: Uuid::parse_str(&self.id).ok()?,
+            user_id: Uuid::parse_str(&self.user_id).ok()?,
+            name: self.name,
+            description: self.description,
+            rules: rules
+                .iter()
+                .filter_map(|rule| CategoryRule::parse(rule).ok())
+                .collect(),
+            tags: serde_json::from_str(&self.tags).ok()?,
+        })
+    }
+}
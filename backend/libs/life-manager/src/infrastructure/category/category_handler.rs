@@ -0,0 +1,255 @@
+use auth::AuthUser;
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use axum::{Json, http::StatusCode};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::application::category_repository::{CategoryNameTaken, CategoryRepository};
+use crate::domain::category::Category;
+use crate::infrastructure::category::category_dto::{CategoryCommand, CategoryDto};
+use crate::infrastructure::document::document_state::DocumentState;
+
+/// Lists the user's categories by name.
+pub async fn get_categories(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+) -> impl IntoResponse {
+    tracing::info!("Fetching categories for user: {}", user_id);
+    match document_use_cases.categories.get_categories(&user_id).await {
+        Ok(categories) => {
+            let categories: Vec<CategoryDto> =
+                categories.iter().map(CategoryDto::from_category).collect();
+            (StatusCode::OK, Json(json!(categories)))
+        }
+        Err(e) => {
+            tracing::error!("Error fetching categories for user {}: {}", user_id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({})))
+        }
+    }
+}
+
+/// Creates a category. An invalid name or rule gives **400**; a name the user already has,
+/// ignoring case, gives **409**.
+pub async fn create_category(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Json(command): Json<CategoryCommand>,
+) -> impl IntoResponse {
+    tracing::info!("Creating category {} for user: {}", command.name, user_id);
+    match category_from_command(user_id, &command) {
+        Ok(category) => {
+            save_category(
+                &*document_use_cases.categories,
+                category,
+                StatusCode::CREATED,
+            )
+            .await
+        }
+        Err(response) => response,
+    }
+}
+
+/// Replaces the name, description, rules and tags of one of the user's categories. Documents
+/// already filed under it keep their classification.
+pub async fn update_category(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+    Json(command): Json<CategoryCommand>,
+) -> impl IntoResponse {
+    tracing::info!("Updating category {} for user: {}", id, user_id);
+    match document_use_cases.find_category(user_id, id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return (StatusCode::NOT_FOUND, Json(json!({}))),
+        Err(e) => {
+            tracing::error!("Error fetching category {}: {}", id, e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({})));
+        }
+    }
+    match category_from_command(user_id, &command) {
+        Ok(category) => {
+            let category = Category { id, ..category };
+            save_category(&*document_use_cases.categories, category, StatusCode::OK).await
+        }
+        Err(response) => response,
+    }
+}
+
+/// Deletes one of the user's categories. Documents filed under it lose their classification
+/// but keep the tags it added.
+pub async fn delete_category(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!("Deleting category {} for user: {}", id, user_id);
+    let category = match document_use_cases.find_category(user_id, id).await {
+        Ok(Some(category)) => category,
+        Ok(None) => return StatusCode::NOT_FOUND,
+        Err(e) => {
+            tracing::error!("Error fetching category {}: {}", id, e);
+            return StatusCode::INTERNAL_SERVER_ERROR;
+        }
+    };
+    match document_use_cases
+        .categories
+        .delete_category(&category)
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT,
+        Err(e) => {
+            tracing::error!("Error deleting category {}: {}", id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+fn category_from_command(
+    user_id: Uuid,
+    command: &CategoryCommand,
+) -> Result<Category, (StatusCode, Json<Value>)> {
+    Category::new(
+        user_id,
+        &command.name,
+        &command.description,
+        &command.rules,
+        &command.tags,
+    )
+    .map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": e.to_string()})),
+        )
+    })
+}
+
+/// Saves the category unless the user has another one with the same name.
+async fn save_category(
+    categories: &dyn CategoryRepository,
+    category: Category,
+    status: StatusCode,
+) -> (StatusCode, Json<Value>) {
+    match categories.save_category(&category).await {
+        Ok(()) => (status, Json(json!(CategoryDto::from_category(&category)))),
+        Err(e) if e.is::<CategoryNameTaken>() => {
+            (StatusCode::CONFLICT, Json(json!({"error": e.to_string()})))
+        }
+        Err(e) => {
+            tracing::error!("Error saving category {}: {}", category.id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({})))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::application::document_use_cases::DocumentUseCases;
+    use crate::infrastructure::test_support::read_json;
+
+    fn given_user() -> AuthUser {
+        AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        }
+    }
+
+    fn given_state() -> DocumentState {
+        DocumentState(Arc::new(DocumentUseCases::for_tests()))
+    }
+
+    fn command(name: &str, rules: &[&str]) -> Json<CategoryCommand> {
+        Json(CategoryCommand {
+            name: name.to_string(),
+            description: "Power, water and gas bills".to_string(),
+            rules: rules.iter().map(|r| r.to_string()).collect(),
+            tags: vec!["Bills".to_string()],
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_update_and_delete_a_category() {
+        // Given
+        let user = given_user();
+        let state = given_state();
+
+        // When
+        let (created_status, created) = read_json::<CategoryDto>(
+            create_category(
+                user.clone(),
+                State(state.clone()),
+                command("Utility bill", &["kwh", "/meter (reading|number)/"]),
+            )
+            .await,
+        )
+        .await;
+        let (duplicate_status, _) = read_json::<Value>(
+            create_category(
+                user.clone(),
+                State(state.clone()),
+                command("utility BILL", &[]),
+            )
+            .await,
+        )
+        .await;
+        let (invalid_status, _) = read_json::<Value>(
+            create_category(
+                user.clone(),
+                State(state.clone()),
+                command("Tax", &["/(unclosed/"]),
+            )
+            .await,
+        )
+        .await;
+        let (updated_status, updated) = read_json::<CategoryDto>(
+            update_category(
+                user.clone(),
+                State(state.clone()),
+                Path(created.id),
+                command("Utilities", &["kwh"]),
+            )
+            .await,
+        )
+        .await;
+        let (_, listed) =
+            read_json::<Vec<CategoryDto>>(get_categories(user.clone(), State(state.clone())).await)
+                .await;
+        let deleted_by_other =
+            delete_category(given_user(), State(state.clone()), Path(created.id))
+                .await
+                .into_response();
+        let deleted = delete_category(user.clone(), State(state.clone()), Path(created.id))
+            .await
+            .into_response();
+        let (_, remaining) =
+            read_json::<Vec<CategoryDto>>(get_categories(user, State(state)).await).await;
+
+        // Then
+        assert_eq!(created_status, StatusCode::CREATED);
+        assert_eq!(created.tags, ["bills"]);
+        assert_eq!(duplicate_status, StatusCode::CONFLICT);
+        assert_eq!(invalid_status, StatusCode::BAD_REQUEST);
+        assert_eq!(updated_status, StatusCode::OK);
+        assert_eq!(updated.id, created.id);
+        assert_eq!(listed, [updated]);
+        assert_eq!(listed[0].rules, ["kwh"]);
+        assert_eq!(deleted_by_other.status(), StatusCode::NOT_FOUND);
+        assert_eq!(deleted.status(), StatusCode::NO_CONTENT);
+        assert!(remaining.is_empty());
+    }
+}
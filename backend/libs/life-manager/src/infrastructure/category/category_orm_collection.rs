@@ -0,0 +1,101 @@
+use std::error::Error;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use deadpool_diesel::sqlite::Pool;
+use diesel::{
+    Connection, ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper,
+    result::{DatabaseErrorKind, Error as DieselError},
+};
+use uuid::Uuid;
+
+use crate::{
+    application::category_repository::{CategoryNameTaken, CategoryRepository},
+    domain::category::Category,
+    infrastructure::category::category_entity::CategoryEntity,
+    schema::{categories, document_classifications},
+};
+
+#[derive(Clone)]
+pub struct CategoryOrmCollection {
+    pub pool: Arc<Pool>,
+}
+
+impl CategoryOrmCollection {
+    pub fn new(pool: Arc<Pool>) -> Self {
+        CategoryOrmCollection { pool }
+    }
+}
+
+#[async_trait]
+impl CategoryRepository for CategoryOrmCollection {
+    async fn get_categories(
+        &self,
+        user_id: &Uuid,
+    ) -> Result<Vec<Category>, Box<dyn Error + Send + Sync>> {
+        let conn = self.pool.get().await?;
+        let user_id_str = user_id.to_string();
+
+        let entities = conn
+            .interact(move |conn| {
+                categories::table
+                    .filter(categories::user_id.eq(user_id_str))
+                    .order_by(categories::name.asc())
+                    .select(CategoryEntity::as_select())
+                    .load(conn)
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        Ok(entities
+            .into_iter()
+            .filter_map(CategoryEntity::into_category)
+            .collect())
+    }
+
+    async fn save_category(&self, category: &Category) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let conn = self.pool.get().await?;
+        let entity = CategoryEntity::from_category(category);
+
+        let saved = conn
+            .interact(move |conn| {
+                diesel::insert_into(categories::table)
+                    .values(&entity)
+                    .on_conflict(categories::id)
+                    .do_update()
+                    .set(&entity)
+                    .execute(conn)
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+        // UNIQUE (user_id, name) is case-insensitive, as the name is COLLATE NOCASE.
+        if let Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) = saved {
+            return Err(Box::new(CategoryNameTaken(category.name.clone())));
+        }
+        saved?;
+        tracing::info!("Category saved with ID: {}", category.id);
+        Ok(())
+    }
+
+    async fn delete_category(
+        &self,
+        category: &Category,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let conn = self.pool.get().await?;
+        let id = category.id.to_string();
+
+        conn.interact(move |conn| {
+            conn.transaction(|conn| {
+                diesel::delete(
+                    document_classifications::table
+                        .filter(document_classifications::category_id.eq(&id)),
+                )
+                .execute(conn)?;
+                diesel::delete(categories::table.filter(categories::id.eq(&id))).execute(conn)
+            })
+        })
+        .await
+        .map_err(|e| e.to_string())??;
+        tracing::info!("Category deleted with ID: {}", category.id);
+        Ok(())
+    }
+}
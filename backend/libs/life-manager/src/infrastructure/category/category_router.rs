@@ -0,0 +1,17 @@
+use axum::{
+    Router,
+    routing::{get, put},
+};
+
+use crate::infrastructure::{
+    app_state::LifeManagerState,
+    category::category_handler::{
+        create_category, delete_category, get_categories, update_category,
+    },
+};
+
+pub fn category_router() -> Router<LifeManagerState> {
+    Router::new()
+        .route("/", get(get_categories).post(create_category))
+        .route("/{id}", put(update_category).delete(delete_category))
+}
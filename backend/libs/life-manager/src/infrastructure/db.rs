@@ -1,11 +1,16 @@
 use std::{env, fs, path::Path};
 
-use deadpool_diesel::sqlite::{Manager, Pool, Runtime};
+use deadpool_diesel::sqlite::{Hook, HookError, Manager, Pool, Runtime};
+use diesel::connection::SimpleConnection;
 use diesel_migrations::{EmbeddedMigrations, MigrationHarness, embed_migrations};
 use dotenvy::dotenv;
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations/");
 
+/// How long a connection waits for another connection's write lock before giving up with
+/// `database is locked`. Ingestion workers write while requests are being served.
+const BUSY_TIMEOUT_MS: u32 = 5_000;
+
 pub fn create_connection_pool() -> Pool {
     dotenv().ok();
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
@@ -18,6 +23,13 @@ pub fn create_connection_pool_from_url(database_url: &str) -> Pool {
     let mgr = Manager::new(database_url.to_string(), Runtime::Tokio1);
     Pool::builder(mgr)
         .max_size(16)
+        .post_create(Hook::sync_fn(|conn, _| {
+            let mut conn = conn
+                .lock()
+                .map_err(|_| HookError::message("SQLite connection mutex is poisoned"))?;
+            conn.batch_execute(&format!("PRAGMA busy_timeout = {};", BUSY_TIMEOUT_MS))
+                .map_err(|e| HookError::message(e.to_string()))
+        }))
         .build()
         .expect("Failed to create pool.")
 }
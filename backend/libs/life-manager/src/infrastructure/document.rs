@@ -1,3 +1,4 @@
+pub mod classification_handler;
 pub mod document_collection;
 pub mod document_dto;
 pub mod document_entity;
@@ -5,3 +6,9 @@ pub mod document_handler;
 pub mod document_orm_collection;
 pub mod document_router;
 pub mod document_state;
+pub mod search_handler;
+pub mod similar_handler;
+pub mod tag_handler;
+pub mod tag_router;
+pub mod trash_handler;
+pub mod words_handler;
@@ -0,0 +1,260 @@
+use auth::AuthUser;
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use axum::{Json, http::StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::application::document_policy::DocumentAction;
+use crate::domain::document_classification::{ClassificationStatus, DocumentClassification};
+use crate::infrastructure::document::document_dto::DocumentDto;
+use crate::infrastructure::document::document_state::DocumentState;
+
+#[derive(Deserialize, Serialize)]
+pub struct ChooseCategoryCommand {
+    pub category_id: Uuid,
+    /// Tags added to the document; the category's own tags if left out.
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+}
+
+/// Accepts the category suggested for one of the user's documents and adds its suggested tags
+/// to the document. A document without a suggestion gives **409**; one the user already
+/// classified is returned as it is.
+pub async fn accept_classification(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!("Accepting the category of document {}", id);
+    let Some(document) = document_use_cases
+        .find_document(user_id, id, DocumentAction::Write)
+        .await
+    else {
+        return (StatusCode::NOT_FOUND, Json(json!({})));
+    };
+    let Some(classification) = document.classification.clone() else {
+        return (
+            StatusCode::CONFLICT,
+            Json(json!({"error": "The document has no suggested category"})),
+        );
+    };
+    if classification.status != ClassificationStatus::Suggested {
+        return (
+            StatusCode::OK,
+            Json(json!(DocumentDto::from_document(&document))),
+        );
+    }
+    match document_use_cases
+        .document_repository
+        .set_classification(&document, &classification.accept())
+        .await
+    {
+        Ok(document) => (
+            StatusCode::OK,
+            Json(json!(DocumentDto::from_document(&document))),
+        ),
+        Err(e) => {
+            tracing::error!("Error accepting the category of document {}: {}", id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({})))
+        }
+    }
+}
+
+/// Files one of the user's documents under the category the user chose, replacing any
+/// suggestion, and adds the tags to the document. An unknown category gives **400**.
+pub async fn choose_classification(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+    Json(command): Json<ChooseCategoryCommand>,
+) -> impl IntoResponse {
+    tracing::info!(
+        "Filing document {} under category {}",
+        id,
+        command.category_id
+    );
+    let Some(document) = document_use_cases
+        .find_document(user_id, id, DocumentAction::Write)
+        .await
+    else {
+        return (StatusCode::NOT_FOUND, Json(json!({})));
+    };
+    let category = match document_use_cases
+        .find_category(user_id, command.category_id)
+        .await
+    {
+        Ok(Some(category)) => category,
+        Ok(None) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": format!("Category {} not found", command.category_id)})),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Error fetching category {}: {}", command.category_id, e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({})));
+        }
+    };
+    let tags = command.tags.unwrap_or_else(|| category.tags.clone());
+    let classification = DocumentClassification::choose(&category, &tags);
+    match document_use_cases
+        .document_repository
+        .set_classification(&document, &classification)
+        .await
+    {
+        Ok(document) => (
+            StatusCode::OK,
+            Json(json!(DocumentDto::from_document(&document))),
+        ),
+        Err(e) => {
+            tracing::error!("Error filing document {}: {}", id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({})))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::application::category_repository::CategoryRepository;
+    use crate::application::document_repository::DocumentRepository;
+    use crate::application::document_use_cases::DocumentUseCases;
+    use crate::domain::category::Category;
+    use crate::domain::document::Document;
+    use crate::infrastructure::category::category_collection::CategoryCollection;
+    use crate::infrastructure::document::document_collection::DocumentCollection;
+    use crate::infrastructure::test_support::read_json;
+
+    struct GivenClassifiedDocument {
+        auth_user: AuthUser,
+        state: DocumentState,
+        document_id: Uuid,
+        suggested: Category,
+        other: Category,
+    }
+
+    async fn given_classified_document() -> GivenClassifiedDocument {
+        let auth_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let tags = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        let suggested = Category::new(
+            auth_user.user_id,
+            "Utility bill",
+            "",
+            &[],
+            &tags(&["bills"]),
+        )
+        .unwrap();
+        let other = Category::new(auth_user.user_id, "Tax", "", &[], &tags(&["tax"])).unwrap();
+        let categories = CategoryCollection::new();
+        categories.save_category(&suggested).await.unwrap();
+        categories.save_category(&other).await.unwrap();
+        let repo = DocumentCollection::new();
+        let mut document = Document::new("Bill", "Power bill", auth_user.user_id);
+        document.classification = Some(DocumentClassification::suggest(
+            &suggested,
+            &tags(&["power"]),
+            0.8,
+        ));
+        let document = repo.save_document(document).await.unwrap();
+        let state = DocumentState(Arc::new(DocumentUseCases {
+            document_repository: Arc::new(repo),
+            categories: Arc::new(categories),
+            ..DocumentUseCases::for_tests()
+        }));
+        GivenClassifiedDocument {
+            auth_user,
+            state,
+            document_id: document.id,
+            suggested,
+            other,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_accept_adds_the_suggested_tags() {
+        // Given
+        let GivenClassifiedDocument {
+            auth_user,
+            state,
+            document_id,
+            suggested,
+            ..
+        } = given_classified_document().await;
+
+        // When
+        let (status, accepted) = read_json::<DocumentDto>(
+            accept_classification(auth_user, State(state), Path(document_id)).await,
+        )
+        .await;
+
+        // Then
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(accepted.tags, ["bills", "power"]);
+        let classification = accepted.classification.unwrap();
+        assert_eq!(classification.category_id, suggested.id);
+        assert_eq!(classification.status, ClassificationStatus::Accepted);
+        assert_eq!(classification.confidence, 0.8);
+    }
+
+    #[tokio::test]
+    async fn test_choose_overrides_the_suggestion() {
+        // Given
+        let GivenClassifiedDocument {
+            auth_user,
+            state,
+            document_id,
+            other,
+            ..
+        } = given_classified_document().await;
+
+        // When
+        let (unknown_status, _) = read_json::<serde_json::Value>(
+            choose_classification(
+                auth_user.clone(),
+                State(state.clone()),
+                Path(document_id),
+                Json(ChooseCategoryCommand {
+                    category_id: Uuid::new_v4(),
+                    tags: None,
+                }),
+            )
+            .await,
+        )
+        .await;
+        let (status, chosen) = read_json::<DocumentDto>(
+            choose_classification(
+                auth_user,
+                State(state),
+                Path(document_id),
+                Json(ChooseCategoryCommand {
+                    category_id: other.id,
+                    tags: None,
+                }),
+            )
+            .await,
+        )
+        .await;
+
+        // Then
+        assert_eq!(unknown_status, StatusCode::BAD_REQUEST);
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(chosen.tags, ["tax"]);
+        let classification = chosen.classification.unwrap();
+        assert_eq!(classification.category, "Tax");
+        assert_eq!(classification.status, ClassificationStatus::Overridden);
+        assert_eq!(classification.confidence, 1.0);
+    }
+}
@@ -1,8 +1,23 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
-use crate::{application::document_repository::DocumentRepository, domain::document::Document};
+use crate::{
+    application::document_repository::DocumentRepository,
+    domain::{
+        document::Document,
+        document_chunk::{DocumentChunk, SemanticHit, cosine_similarity},
+        document_classification::{ClassificationStatus, DocumentClassification},
+        document_listing::DocumentListing,
+        document_search::{HIGHLIGHT_START, SearchHit, SearchQuery},
+        document_similarity::{SimilarDocument, hamming_distance},
+        ocr_word::OcrWord,
+        tag::{TagCount, normalize_tag, normalize_tags},
+    },
+};
 
 pub struct DocumentCollection {
     pub documents: Mutex<Vec<Document>>,
@@ -10,11 +25,24 @@ pub struct DocumentCollection {
 
 #[async_trait]
 impl DocumentRepository for DocumentCollection {
-    async fn get_document(&self, id: Uuid) -> Option<Document> {
+    async fn get_document(&self, user_id: &Uuid, id: Uuid) -> Option<Document> {
         tracing::info!("Retrieving document with ID: {}", id);
         let documents = self.documents.lock().await;
         tracing::info!("Total documents in collection: {}", documents.len());
-        documents.iter().find(|doc| doc.id == id).cloned()
+        documents
+            .iter()
+            .find(|doc| doc.id == id && doc.user_id == *user_id)
+            .cloned()
+    }
+
+    async fn find_document_by_sha256(&self, user_id: &Uuid, sha256: &str) -> Option<Document> {
+        let documents = self.documents.lock().await;
+        documents
+            .iter()
+            .filter(|doc| doc.user_id == *user_id)
+            .filter(|doc| doc.blob.as_ref().is_some_and(|blob| blob.sha256 == sha256))
+            .min_by_key(|doc| (doc.created_at, doc.id))
+            .cloned()
     }
 
     async fn get_documents(&self, user_id: &Uuid, limit: &u32) -> Vec<Document> {
@@ -27,41 +55,298 @@ impl DocumentRepository for DocumentCollection {
 
         documents
             .into_iter()
-            .filter(|doc| doc.user_id == *user_id)
+            .filter(|doc| doc.user_id == *user_id && !doc.is_trashed())
             .take(*limit as usize)
             .collect()
     }
 
-    async fn get_documents_title_cursor(
-        &self,
-        user_id: &Uuid,
-        limit: &u32,
-        title: &str,
-    ) -> Vec<Document> {
+    async fn list_documents(&self, user_id: &Uuid, listing: &DocumentListing) -> Vec<Document> {
         let mut documents: Vec<Document> = {
             let guard = self.documents.lock().await;
-            guard.clone()
+            guard
+                .iter()
+                .filter(|doc| doc.user_id == *user_id && !doc.is_trashed())
+                .filter(|doc| listing.tags.matches(&doc.tags))
+                .filter(|doc| listing.dates.contains(doc))
+                .filter(|doc| listing.after.as_ref().is_none_or(|c| c.precedes(doc)))
+                .cloned()
+                .collect()
         };
 
-        documents.sort_by_key(|d| (d.title.clone(), d.id));
-
+        documents.sort_by(|a, b| listing.sort.compare(a, b));
+        documents.truncate(listing.limit as usize);
         documents
-            .into_iter()
-            .filter(|doc| doc.user_id == *user_id)
-            .filter(|doc| *doc.title > *title)
-            .take(*limit as usize)
-            .collect()
     }
 
     async fn save_document(
         &self,
-        document: Document,
+        mut document: Document,
     ) -> Result<Document, Box<dyn std::error::Error>> {
         tracing::info!("Saving document with ID: {}", document.id);
+        document.tags = normalize_tags(&document.tags);
         let mut documents = self.documents.lock().await;
+        let sha256 = document.blob.as_ref().map(|blob| &blob.sha256);
+        let duplicate = documents.iter().any(|doc| {
+            doc.user_id == document.user_id && doc.blob.as_ref().map(|b| &b.sha256) == sha256
+        });
+        if let (Some(sha256), true) = (sha256, duplicate) {
+            return Err(format!("Document with SHA-256 {} already exists", sha256).into());
+        }
         documents.push(document.clone());
         Ok(document)
     }
+
+    async fn update_document(
+        &self,
+        mut document: Document,
+    ) -> Result<Document, Box<dyn std::error::Error>> {
+        tracing::info!("Updating document with ID: {}", document.id);
+        document.tags = normalize_tags(&document.tags);
+        self.update(document.id, |stored| {
+            stored.title = document.title;
+            stored.content = document.content;
+            stored.tags = document.tags;
+            stored.document_date = document.document_date;
+            stored.updated_at = Utc::now();
+        })
+        .await
+    }
+
+    async fn trash_document(
+        &self,
+        document: &Document,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<Document, Box<dyn std::error::Error>> {
+        self.update(document.id, |stored| stored.deleted_at = Some(deleted_at))
+            .await
+    }
+
+    async fn restore_document(
+        &self,
+        document: &Document,
+    ) -> Result<Document, Box<dyn std::error::Error>> {
+        self.update(document.id, |stored| stored.deleted_at = None)
+            .await
+    }
+
+    async fn get_trash(&self, user_id: &Uuid, limit: &u32) -> Vec<Document> {
+        let mut trash: Vec<Document> = {
+            let documents = self.documents.lock().await;
+            documents
+                .iter()
+                .filter(|doc| doc.user_id == *user_id && doc.is_trashed())
+                .cloned()
+                .collect()
+        };
+        trash.sort_by_key(|doc| std::cmp::Reverse(doc.deleted_at));
+        trash.truncate(*limit as usize);
+        trash
+    }
+
+    async fn purge_trash(
+        &self,
+        deleted_before: DateTime<Utc>,
+    ) -> Result<Vec<Document>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut documents = self.documents.lock().await;
+        Ok(documents
+            .extract_if(.., |doc| doc.deleted_at.is_some_and(|d| d < deleted_before))
+            .collect())
+    }
+
+    async fn references_blob(
+        &self,
+        sha256: &str,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.documents.lock().await.iter().any(|doc| {
+            doc.blob.as_ref().is_some_and(|blob| blob.sha256 == sha256)
+                || doc.thumbnail_sha256.as_deref() == Some(sha256)
+        }))
+    }
+
+    async fn add_tags(
+        &self,
+        document: &Document,
+        tags: &[String],
+    ) -> Result<Document, Box<dyn std::error::Error>> {
+        self.update(document.id, |stored| {
+            stored.tags.extend_from_slice(tags);
+            stored.tags = normalize_tags(&stored.tags);
+            stored.updated_at = Utc::now();
+        })
+        .await
+    }
+
+    async fn set_classification(
+        &self,
+        document: &Document,
+        classification: &DocumentClassification,
+    ) -> Result<Document, Box<dyn std::error::Error>> {
+        self.update(document.id, |stored| {
+            if classification.status != ClassificationStatus::Suggested {
+                stored.tags.extend_from_slice(&classification.tags);
+                stored.tags = normalize_tags(&stored.tags);
+            }
+            stored.classification = Some(classification.clone());
+            stored.updated_at = Utc::now();
+        })
+        .await
+    }
+
+    async fn remove_tag(
+        &self,
+        document: &Document,
+        tag: &str,
+    ) -> Result<Document, Box<dyn std::error::Error>> {
+        let tag = normalize_tag(tag);
+        self.update(document.id, |stored| {
+            stored.tags.retain(|t| Some(t) != tag.as_ref());
+            stored.updated_at = Utc::now();
+        })
+        .await
+    }
+
+    async fn get_tags(&self, user_id: &Uuid) -> Vec<TagCount> {
+        let documents = self.documents.lock().await;
+        let mut counts: BTreeMap<String, i64> = BTreeMap::new();
+        for tag in documents
+            .iter()
+            .filter(|doc| doc.user_id == *user_id && !doc.is_trashed())
+            .flat_map(|doc| doc.tags.iter())
+        {
+            *counts.entry(tag.clone()).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .map(|(name, document_count)| TagCount {
+                name,
+                document_count,
+            })
+            .collect()
+    }
+
+    async fn find_similar_documents(
+        &self,
+        document: &Document,
+        max_distance: u32,
+        limit: &u32,
+    ) -> Vec<SimilarDocument> {
+        let Some(fingerprint) = document.text_simhash else {
+            return vec![];
+        };
+        let mut similar: Vec<SimilarDocument> = {
+            let documents = self.documents.lock().await;
+            documents
+                .iter()
+                .filter(|doc| doc.user_id == document.user_id && doc.id != document.id)
+                .filter(|doc| !doc.is_trashed())
+                .filter_map(|doc| {
+                    let distance = hamming_distance(fingerprint, doc.text_simhash?);
+                    (distance <= max_distance).then(|| SimilarDocument {
+                        document: doc.clone(),
+                        distance,
+                    })
+                })
+                .collect()
+        };
+        similar.sort_by_key(|similar| similar.distance);
+        similar.truncate(*limit as usize);
+        similar
+    }
+
+    async fn get_words(&self, document: &Document) -> Vec<OcrWord> {
+        let documents = self.documents.lock().await;
+        documents
+            .iter()
+            .find(|doc| doc.id == document.id)
+            .and_then(|doc| doc.extracted_text.as_ref())
+            .map(|extracted| extracted.words.clone())
+            .unwrap_or_default()
+    }
+
+    async fn search(&self, user_id: &Uuid, query: &SearchQuery, limit: &u32) -> Vec<SearchHit> {
+        let documents = self.documents.lock().await;
+        let mut hits: Vec<SearchHit> = documents
+            .iter()
+            .filter(|doc| doc.user_id == *user_id && !doc.is_trashed())
+            .filter_map(|doc| {
+                let extracted = doc.extracted_text.as_ref().map_or("", |e| e.text.as_str());
+                let score = query.score(&doc.title, &format!("{}\n{}", doc.content, extracted))?;
+                let snippet = [doc.content.as_str(), extracted]
+                    .into_iter()
+                    .map(|text| query.highlight(text))
+                    .find(|text| text.contains(HIGHLIGHT_START))
+                    .unwrap_or_else(|| doc.content.clone());
+                Some(SearchHit {
+                    document: doc.clone(),
+                    snippet,
+                    score,
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(*limit as usize);
+        hits
+    }
+
+    async fn save_chunks(
+        &self,
+        document: &Document,
+        chunks: &[DocumentChunk],
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut documents = self.documents.lock().await;
+        let document = documents
+            .iter_mut()
+            .find(|doc| doc.id == document.id)
+            .ok_or_else(|| format!("Document {} not found", document.id))?;
+        document.chunks = chunks.to_vec();
+        Ok(())
+    }
+
+    async fn documents_to_embed(
+        &self,
+        model: &str,
+        limit: &u32,
+    ) -> Result<Vec<Document>, Box<dyn std::error::Error + Send + Sync>> {
+        let documents = self.documents.lock().await;
+        Ok(documents
+            .iter()
+            .filter(|doc| !doc.is_trashed())
+            .filter(|doc| !doc.chunks.iter().any(|chunk| chunk.model == model))
+            .take(*limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn semantic_search(
+        &self,
+        user_id: &Uuid,
+        model: &str,
+        query: &[f32],
+        limit: &u32,
+    ) -> Vec<SemanticHit> {
+        let documents = self.documents.lock().await;
+        let mut hits: Vec<SemanticHit> = documents
+            .iter()
+            .filter(|doc| doc.user_id == *user_id && !doc.is_trashed())
+            .filter_map(|doc| {
+                let (chunk, score) = doc
+                    .chunks
+                    .iter()
+                    .filter(|chunk| chunk.model == model && !chunk.embedding.is_empty())
+                    .map(|chunk| (chunk, cosine_similarity(query, &chunk.embedding)))
+                    .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+                Some(SemanticHit {
+                    document: doc.clone(),
+                    snippet: chunk.text.clone(),
+                    page: chunk.page,
+                    score,
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(*limit as usize);
+        hits
+    }
 }
 
 impl Default for DocumentCollection {
@@ -76,11 +361,32 @@ impl DocumentCollection {
             documents: Mutex::new(Vec::new()),
         }
     }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        update: impl FnOnce(&mut Document),
+    ) -> Result<Document, Box<dyn std::error::Error>> {
+        let mut documents = self.documents.lock().await;
+        let document = documents
+            .iter_mut()
+            .find(|doc| doc.id == id)
+            .ok_or_else(|| format!("Document {} not found", id))?;
+        update(document);
+        Ok(document.clone())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::document::Document;
+    use crate::domain::{
+        document::Document,
+        document_listing::{
+            DateRange, DocumentCursor, DocumentSort, DocumentSortField, SortDirection,
+        },
+        tag::{TagFilter, TagMatch},
+    };
+    use chrono::{NaiveDate, TimeDelta};
 
     use super::*;
 
@@ -105,16 +411,241 @@ mod tests {
     #[tokio::test]
     pub async fn test_get_document() {
         let collection: DocumentCollection = DocumentCollection::new();
-        let doc = Document::new("Test document", "This is a test content.", Uuid::new_v4());
+        let user_id = Uuid::new_v4();
+        let doc = Document::new("Test document", "This is a test content.", user_id);
         let doc_id = doc.id;
         collection
             .save_document(doc.clone())
             .await
             .expect("Failed to save document");
 
-        let retrieved_doc = collection.get_document(doc_id).await.unwrap();
+        let retrieved_doc = collection.get_document(&user_id, doc_id).await.unwrap();
         assert_eq!(retrieved_doc.id, doc.id);
         assert_eq!(retrieved_doc.title, doc.title);
         assert_eq!(retrieved_doc.content, doc.content);
+        assert!(
+            collection
+                .get_document(&Uuid::new_v4(), doc_id)
+                .await
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_tags_are_counted_and_filtered() {
+        // Given
+        let collection = DocumentCollection::new();
+        let user_id = Uuid::new_v4();
+        let bill = collection
+            .save_document(Document::new("Bill", "Power bill", user_id))
+            .await
+            .unwrap();
+        let receipt = collection
+            .save_document(Document::new("Receipt", "Groceries", user_id))
+            .await
+            .unwrap();
+        collection
+            .add_tags(&bill, &["Bills".to_string(), "2025".to_string()])
+            .await
+            .unwrap();
+        collection
+            .add_tags(&receipt, &["2025".to_string()])
+            .await
+            .unwrap();
+
+        // When
+        let tags = collection.get_tags(&user_id).await;
+        let both = collection
+            .list_documents(
+                &user_id,
+                &DocumentListing {
+                    tags: TagFilter::new(&["bills".to_string(), "2025".to_string()], TagMatch::All),
+                    limit: 10,
+                    ..Default::default()
+                },
+            )
+            .await;
+        let either = collection
+            .list_documents(
+                &user_id,
+                &DocumentListing {
+                    tags: TagFilter::new(&["bills".to_string(), "2025".to_string()], TagMatch::Any),
+                    limit: 10,
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        // Then
+        assert_eq!(
+            tags,
+            vec![
+                TagCount {
+                    name: "2025".to_string(),
+                    document_count: 2
+                },
+                TagCount {
+                    name: "bills".to_string(),
+                    document_count: 1
+                },
+            ]
+        );
+        assert_eq!(both.len(), 1);
+        assert_eq!(both[0].id, bill.id);
+        assert_eq!(either.len(), 2);
+    }
+
+    #[tokio::test]
+    pub async fn test_list_documents_pages_through_equal_titles() {
+        // Given
+        let collection = DocumentCollection::new();
+        let user_id = Uuid::new_v4();
+        for title in ["Bill", "Bill", "Bill", "Receipt"] {
+            collection
+                .save_document(Document::new(title, "", user_id))
+                .await
+                .unwrap();
+        }
+        let sort = DocumentSort {
+            direction: SortDirection::Desc,
+            ..Default::default()
+        };
+
+        // When
+        let mut seen = vec![];
+        let mut after = None;
+        loop {
+            let page = collection
+                .list_documents(
+                    &user_id,
+                    &DocumentListing {
+                        sort,
+                        after: after.clone(),
+                        limit: 2,
+                        ..Default::default()
+                    },
+                )
+                .await;
+            let Some(last) = page.last() else { break };
+            after = Some(DocumentCursor::after(last, &sort));
+            seen.extend(page);
+        }
+
+        // Then
+        let titles: Vec<&str> = seen.iter().map(|d| d.title.as_str()).collect();
+        assert_eq!(titles, vec!["Receipt", "Bill", "Bill", "Bill"]);
+        let mut ids: Vec<Uuid> = seen.iter().map(|d| d.id).collect();
+        ids.dedup();
+        assert_eq!(ids.len(), 4);
+    }
+
+    #[tokio::test]
+    pub async fn test_list_documents_by_date() {
+        // Given
+        let collection = DocumentCollection::new();
+        let user_id = Uuid::new_v4();
+        let mut bill = Document::new("Bill", "", user_id);
+        bill.document_date = NaiveDate::from_ymd_opt(2025, 3, 14);
+        bill.created_at -= TimeDelta::days(1);
+        let bill = collection.save_document(bill).await.unwrap();
+        let receipt = collection
+            .save_document(Document::new("Receipt", "", user_id))
+            .await
+            .unwrap();
+
+        // When
+        let march = collection
+            .list_documents(
+                &user_id,
+                &DocumentListing {
+                    dates: DateRange::new(
+                        NaiveDate::from_ymd_opt(2025, 3, 1),
+                        NaiveDate::from_ymd_opt(2025, 3, 31),
+                    ),
+                    limit: 10,
+                    ..Default::default()
+                },
+            )
+            .await;
+        let newest_first = collection
+            .list_documents(
+                &user_id,
+                &DocumentListing {
+                    sort: DocumentSort {
+                        field: DocumentSortField::CreatedAt,
+                        direction: SortDirection::Desc,
+                    },
+                    limit: 10,
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        // Then
+        assert_eq!(march.len(), 1);
+        assert_eq!(march[0].id, bill.id);
+        let ids: Vec<Uuid> = newest_first.iter().map(|d| d.id).collect();
+        assert_eq!(ids, vec![receipt.id, bill.id]);
+    }
+
+    #[tokio::test]
+    pub async fn test_remove_tag() {
+        let collection = DocumentCollection::new();
+        let doc = collection
+            .save_document(Document::new("Bill", "Power bill", Uuid::new_v4()))
+            .await
+            .unwrap();
+        collection
+            .add_tags(&doc, &["bills".to_string(), "power".to_string()])
+            .await
+            .unwrap();
+
+        let updated = collection.remove_tag(&doc, "BILLS").await.unwrap();
+
+        assert_eq!(updated.tags, vec!["power".to_string()]);
+    }
+
+    #[tokio::test]
+    pub async fn test_trash_restore_and_purge() {
+        // Given
+        let collection = DocumentCollection::new();
+        let user_id = Uuid::new_v4();
+        let kept = collection
+            .save_document(Document::new("Kept", "Stays in the trash", user_id))
+            .await
+            .unwrap();
+        let purged = collection
+            .save_document(Document::new("Purged", "Deleted long ago", user_id))
+            .await
+            .unwrap();
+        let now = Utc::now();
+        collection.trash_document(&kept, now).await.unwrap();
+        collection
+            .trash_document(&purged, now - chrono::Duration::days(40))
+            .await
+            .unwrap();
+
+        // When
+        let listed = collection.get_documents(&user_id, &10).await;
+        let trash = collection.get_trash(&user_id, &10).await;
+        let removed = collection
+            .purge_trash(now - chrono::Duration::days(30))
+            .await
+            .unwrap();
+        let restored = collection.restore_document(&kept).await.unwrap();
+
+        // Then
+        assert!(listed.is_empty());
+        assert_eq!(
+            trash.iter().map(|d| d.id).collect::<Vec<_>>(),
+            vec![kept.id, purged.id]
+        );
+        assert_eq!(
+            removed.iter().map(|d| d.id).collect::<Vec<_>>(),
+            vec![purged.id]
+        );
+        assert!(collection.get_document(&user_id, purged.id).await.is_none());
+        assert!(!restored.is_trashed());
+        assert_eq!(collection.get_documents(&user_id, &10).await.len(), 1);
     }
 }
@@ -1,8 +1,12 @@
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::Deserialize;
 use serde::Serialize;
 use uuid::Uuid;
 
-use crate::domain::document::Document;
+use crate::domain::{
+    document::Document, document_classification::DocumentClassification,
+    document_fields::DocumentFields, document_listing::DocumentPage,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct DocumentDto {
@@ -10,6 +14,21 @@ pub struct DocumentDto {
     pub title: String,
     pub content: String,
     pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub document_date: Option<NaiveDate>,
+    /// Whether `/documents/{id}/thumbnail` has a preview of the upload.
+    #[serde(default)]
+    pub has_thumbnail: bool,
+    /// Whether OCR read the document with a low confidence, so its text should be checked.
+    #[serde(default)]
+    pub needs_review: bool,
+    /// Typed fields read from the text, such as the vendor and amount of a bill.
+    #[serde(default)]
+    pub fields: Option<DocumentFields>,
+    /// Category the document is filed under and whether the user has accepted or chosen it.
+    #[serde(default)]
+    pub classification: Option<DocumentClassification>,
 }
 
 impl DocumentDto {
@@ -19,6 +38,31 @@ impl DocumentDto {
             title: document.title.clone(),
             content: document.content.clone(),
             tags: document.tags.clone(),
+            created_at: document.created_at,
+            updated_at: document.updated_at,
+            document_date: document.document_date,
+            has_thumbnail: document.thumbnail_sha256.is_some(),
+            needs_review: document.needs_review(),
+            fields: document.fields.clone(),
+            classification: document.classification.clone(),
+        }
+    }
+}
+
+/**
+ * A page of documents. `next_cursor` is [`None`] on the last page.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DocumentPageDto {
+    pub items: Vec<DocumentDto>,
+    pub next_cursor: Option<String>,
+}
+
+impl DocumentPageDto {
+    pub fn from_page(page: &DocumentPage) -> Self {
+        Self {
+            items: page.items.iter().map(DocumentDto::from_document).collect(),
+            next_cursor: page.next_cursor.clone(),
         }
     }
 }
@@ -39,5 +83,12 @@ mod tests {
         assert_eq!(dto.title, "Test Document");
         assert_eq!(dto.content, "This is a test content.");
         assert!(dto.tags.is_empty());
+        assert_eq!(dto.created_at, document.created_at);
+        assert_eq!(dto.updated_at, document.updated_at);
+        assert!(dto.document_date.is_none());
+        assert!(!dto.has_thumbnail);
+        assert!(!dto.needs_review);
+        assert!(dto.fields.is_none());
+        assert!(dto.classification.is_none());
     }
 }
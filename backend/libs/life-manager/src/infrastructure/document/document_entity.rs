@@ -1,7 +1,19 @@
+use chrono::{NaiveDate, NaiveDateTime};
 use diesel::prelude::*;
 use serde::Serialize;
+use uuid::Uuid;
 
-#[derive(Serialize, Queryable, Selectable, Debug, Clone)]
+use crate::domain::{
+    blob_store::DocumentBlob,
+    document::Document,
+    document_chunk::DocumentChunk,
+    document_classification::{ClassificationStatus, DocumentClassification},
+    document_fields::{DocumentFields, DocumentType},
+    document_text_reader::ExtractedText,
+    ocr_word::OcrWord,
+};
+
+#[derive(Serialize, Queryable, QueryableByName, Selectable, Debug, Clone)]
 #[diesel(table_name = crate::schema::documents)]
 #[diesel(check_for_backend(diesel::sqlite::Sqlite))]
 pub struct DocumentEntity {
@@ -9,6 +21,69 @@ pub struct DocumentEntity {
     pub title: String,
     pub content: String,
     pub user_id: String,
+    pub blob_sha256: Option<String>,
+    pub file_name: Option<String>,
+    pub mime_type: Option<String>,
+    pub size_bytes: Option<i64>,
+    pub extracted_text: Option<String>,
+    pub text_reader: Option<String>,
+    pub page_count: Option<i32>,
+    pub ocr_used: Option<bool>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub document_date: Option<NaiveDate>,
+    pub text_simhash: Option<i64>,
+    pub thumbnail_sha256: Option<String>,
+    /// JSON array of [`crate::domain::document_text_reader::PageText`].
+    pub text_pages: Option<String>,
+    /// OCR languages joined with `+`, e.g. `eng+deu`.
+    pub text_languages: Option<String>,
+    pub ocr_confidence: Option<f32>,
+}
+
+impl DocumentEntity {
+    /// Maps a row to the domain model. Returns [`None`] if a stored UUID is malformed.
+    pub fn into_document(self) -> Option<Document> {
+        let doc_id = Uuid::parse_str(&self.id).ok()?;
+        let user_id = Uuid::parse_str(&self.user_id).ok()?;
+        let mut document = Document::with_id(doc_id, &self.title, &self.content, user_id);
+        document.blob = match (self.blob_sha256, self.mime_type) {
+            (Some(sha256), Some(mime_type)) => Some(DocumentBlob {
+                sha256,
+                file_name: self.file_name.unwrap_or_default(),
+                mime_type,
+                size_bytes: self.size_bytes.unwrap_or_default() as u64,
+            }),
+            _ => None,
+        };
+        document.extracted_text = match (self.extracted_text, self.text_reader) {
+            (Some(text), Some(reader)) => Some(ExtractedText {
+                text,
+                reader,
+                page_count: self.page_count.map(|p| p as u32),
+                ocr_used: self.ocr_used.unwrap_or_default(),
+                pages: self
+                    .text_pages
+                    .and_then(|pages| serde_json::from_str(&pages).ok())
+                    .unwrap_or_default(),
+                languages: self
+                    .text_languages
+                    .map(|languages| languages.split('+').map(str::to_string).collect())
+                    .unwrap_or_default(),
+                words: vec![],
+                confidence: self.ocr_confidence,
+            }),
+            _ => None,
+        };
+        document.deleted_at = self.deleted_at.map(|d| d.and_utc());
+        document.created_at = self.created_at.and_utc();
+        document.updated_at = self.updated_at.and_utc();
+        document.document_date = self.document_date;
+        document.text_simhash = self.text_simhash.map(|h| h as u64);
+        document.thumbnail_sha256 = self.thumbnail_sha256;
+        Some(document)
+    }
 }
 
 #[derive(Insertable, Debug, Clone)]
@@ -18,4 +93,271 @@ pub struct NewDocumentEntity {
     pub title: String,
     pub content: String,
     pub user_id: String,
+    pub blob_sha256: Option<String>,
+    pub file_name: Option<String>,
+    pub mime_type: Option<String>,
+    pub size_bytes: Option<i64>,
+    pub extracted_text: Option<String>,
+    pub text_reader: Option<String>,
+    pub page_count: Option<i32>,
+    pub ocr_used: Option<bool>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub document_date: Option<NaiveDate>,
+    /// Same as `blob_sha256`, but unique per user.
+    pub content_sha256: Option<String>,
+    pub text_simhash: Option<i64>,
+    pub thumbnail_sha256: Option<String>,
+    /// JSON array of [`crate::domain::document_text_reader::PageText`].
+    pub text_pages: Option<String>,
+    /// OCR languages joined with `+`, e.g. `eng+deu`.
+    pub text_languages: Option<String>,
+    pub ocr_confidence: Option<f32>,
+}
+
+impl NewDocumentEntity {
+    pub fn from_document(document: &Document) -> Self {
+        let blob = document.blob.as_ref();
+        let extracted = document.extracted_text.as_ref();
+        Self {
+            id: document.id.to_string(),
+            title: document.title.clone(),
+            content: document.content.clone(),
+            user_id: document.user_id.to_string(),
+            blob_sha256: blob.map(|b| b.sha256.clone()),
+            file_name: blob.map(|b| b.file_name.clone()),
+            mime_type: blob.map(|b| b.mime_type.clone()),
+            size_bytes: blob.map(|b| b.size_bytes as i64),
+            extracted_text: extracted.map(|e| e.text.clone()),
+            text_reader: extracted.map(|e| e.reader.clone()),
+            page_count: extracted.and_then(|e| e.page_count.map(|p| p as i32)),
+            ocr_used: extracted.map(|e| e.ocr_used),
+            deleted_at: document.deleted_at.map(|d| d.naive_utc()),
+            created_at: document.created_at.naive_utc(),
+            updated_at: document.updated_at.naive_utc(),
+            document_date: document.document_date,
+            content_sha256: blob.map(|b| b.sha256.clone()),
+            text_simhash: document.text_simhash.map(|h| h as i64),
+            thumbnail_sha256: document.thumbnail_sha256.clone(),
+            text_pages: extracted
+                .filter(|e| !e.pages.is_empty())
+                .and_then(|e| serde_json::to_string(&e.pages).ok()),
+            text_languages: extracted
+                .filter(|e| !e.languages.is_empty())
+                .map(|e| e.languages.join("+")),
+            ocr_confidence: extracted.and_then(|e| e.confidence),
+        }
+    }
+}
+
+#[derive(Insertable, Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::document_fields)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DocumentFieldsEntity {
+    pub document_id: String,
+    pub document_type: String,
+    pub vendor: Option<String>,
+    pub total_amount: Option<f64>,
+    pub currency: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    pub account_number: Option<String>,
+    pub invoice_number: Option<String>,
+}
+
+impl DocumentFieldsEntity {
+    pub fn from_document(document: &Document) -> Option<Self> {
+        let fields = document.fields.as_ref()?;
+        Some(Self {
+            document_id: document.id.to_string(),
+            document_type: fields.document_type.as_str().to_string(),
+            vendor: fields.vendor.clone(),
+            total_amount: fields.total_amount,
+            currency: fields.currency.clone(),
+            due_date: fields.due_date,
+            account_number: fields.account_number.clone(),
+            invoice_number: fields.invoice_number.clone(),
+        })
+    }
+
+    /// Maps a row to the domain model. Returns [`None`] if the document type is unknown.
+    pub fn into_fields(self) -> Option<DocumentFields> {
+        Some(DocumentFields {
+            document_type: DocumentType::parse(&self.document_type)?,
+            vendor: self.vendor,
+            total_amount: self.total_amount,
+            currency: self.currency,
+            due_date: self.due_date,
+            account_number: self.account_number,
+            invoice_number: self.invoice_number,
+        })
+    }
+}
+
+#[derive(Insertable, Queryable, Selectable, AsChangeset, Debug, Clone)]
+#[diesel(table_name = crate::schema::document_classifications)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DocumentClassificationEntity {
+    pub document_id: String,
+    pub category_id: String,
+    /// JSON array of tag names.
+    pub tags: String,
+    pub confidence: f32,
+    pub status: String,
+}
+
+impl DocumentClassificationEntity {
+    pub fn from_classification(
+        document_id: &Uuid,
+        classification: &DocumentClassification,
+    ) -> Self {
+        Self {
+            document_id: document_id.to_string(),
+            category_id: classification.category_id.to_string(),
+            tags: serde_json::to_string(&classification.tags).unwrap_or_else(|_| "[]".to_string()),
+            confidence: classification.confidence,
+            status: classification.status.as_str().to_string(),
+        }
+    }
+
+    pub fn from_document(document: &Document) -> Option<Self> {
+        let classification = document.classification.as_ref()?;
+        Some(Self::from_classification(&document.id, classification))
+    }
+
+    /// Maps a row and the name of its category to the domain model. Returns [`None`] if the
+    /// row is malformed.
+    pub fn into_classification(self, category: String) -> Option<DocumentClassification> {
+        Some(DocumentClassification {
+            category_id: Uuid::parse_str(&self.category_id).ok()?,
+            category,
+            tags: serde_json::from_str(&self.tags).ok()?,
+            confidence: self.confidence,
+            status: ClassificationStatus::parse(&self.status)?,
+        })
+    }
+}
+
+/// A word OCR read in a document, at `position` in reading order.
+#[derive(Insertable, Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::document_words)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DocumentWordEntity {
+    pub document_id: String,
+    pub position: i32,
+    pub page: i32,
+    pub text: String,
+    pub box_left: i32,
+    pub box_top: i32,
+    pub box_width: i32,
+    pub box_height: i32,
+    pub confidence: f32,
+}
+
+impl DocumentWordEntity {
+    /// Rows for the words of a freshly read document.
+    pub fn from_document(document: &Document) -> Vec<Self> {
+        let words = document
+            .extracted_text
+            .as_ref()
+            .map(|e| e.words.as_slice())
+            .unwrap_or_default();
+        words
+            .iter()
+            .zip(0..)
+            .map(|(word, position)| Self {
+                document_id: document.id.to_string(),
+                position,
+                page: word.page as i32,
+                text: word.text.clone(),
+                box_left: word.left as i32,
+                box_top: word.top as i32,
+                box_width: word.width as i32,
+                box_height: word.height as i32,
+                confidence: word.confidence,
+            })
+            .collect()
+    }
+
+    pub fn into_word(self) -> OcrWord {
+        OcrWord {
+            page: self.page as u32,
+            text: self.text,
+            left: self.box_left as u32,
+            top: self.box_top as u32,
+            width: self.box_width as u32,
+            height: self.box_height as u32,
+            confidence: self.confidence,
+        }
+    }
+}
+
+/// A passage of a document with its embedding, at `position` in the document.
+#[derive(Insertable, Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::document_chunks)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DocumentChunkEntity {
+    pub document_id: String,
+    pub position: i32,
+    pub page: Option<i32>,
+    pub text: String,
+    pub model: String,
+    /// Little-endian `f32` values of the vector.
+    pub embedding: Vec<u8>,
+}
+
+impl DocumentChunkEntity {
+    pub fn from_chunks(document_id: &Uuid, chunks: &[DocumentChunk]) -> Vec<Self> {
+        chunks
+            .iter()
+            .zip(0..)
+            .map(|(chunk, position)| Self {
+                document_id: document_id.to_string(),
+                position,
+                page: chunk.page.map(|page| page as i32),
+                text: chunk.text.clone(),
+                model: chunk.model.clone(),
+                embedding: chunk
+                    .embedding
+                    .iter()
+                    .flat_map(|value| value.to_le_bytes())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Rows for the chunks of a freshly ingested document.
+    pub fn from_document(document: &Document) -> Vec<Self> {
+        Self::from_chunks(&document.id, &document.chunks)
+    }
+
+    pub fn into_chunk(self) -> DocumentChunk {
+        DocumentChunk {
+            page: self.page.map(|page| page as u32),
+            text: self.text,
+            model: self.model,
+            embedding: self
+                .embedding
+                .chunks_exact(4)
+                .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Insertable, Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::tags)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TagEntity {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+}
+
+#[derive(Insertable, Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::document_tags)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DocumentTagEntity {
+    pub document_id: String,
+    pub tag_id: String,
 }
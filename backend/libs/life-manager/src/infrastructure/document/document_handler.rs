@@ -1,145 +1,605 @@
-use crate::application::get_documents_query::{GetDocumentsQuery, GetDocumentsTitleCursorQuery};
+use crate::application::document_policy::DocumentAction;
+use crate::application::document_use_cases::{DocumentUseCases, UploadOutcome};
+use crate::application::get_documents_query::ListDocumentsQuery;
 use crate::domain::document::Document;
+use crate::domain::document_listing::{
+    DateRange, DocumentCursor, DocumentSort, DocumentSortField, SortDirection,
+};
+use crate::domain::image_preprocessing::PreprocessingChain;
+use crate::domain::tag::{TagFilter, TagMatch};
+use crate::domain::thumbnail::THUMBNAIL_MIME_TYPE;
 use crate::domain::uploaded_document_input::UploadedDocumentInput;
 use crate::infrastructure::document::document_state::DocumentState;
+use crate::infrastructure::ingestion_job::ingestion_job_dto::{
+    BulkUploadDto, IngestionJobDto, UploadResultDto,
+};
+use crate::infrastructure::streamed_upload::{UploadBudget, UploadError, UploadLimits};
+use crate::infrastructure::zip_upload::{ZipBudget, ZipLimits, expand_zip};
 use auth::AuthUser;
-use axum::extract::{Multipart, Path, Query, State};
-use axum::response::IntoResponse;
+use axum::extract::{Multipart, Path, State};
+use axum::http::{HeaderMap, header};
+use axum::response::{IntoResponse, Response};
 use axum::{Json, http::StatusCode};
+use axum_extra::extract::Query;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 
-use super::document_dto::DocumentDto;
+use super::document_dto::{DocumentDto, DocumentPageDto};
 
 const PAGE_LIMIT: u32 = 100;
+/// Thumbnails are addressed by content, so browsers may keep them for a day without asking.
+const THUMBNAIL_CACHE_CONTROL: &str = "private, max-age=86400";
 
 #[derive(Deserialize, Serialize)]
 pub struct CreateDocumentCommand {
     pub title: String,
     pub content: String,
+    /// Tesseract language codes the uploaded files are read in, e.g. `["eng", "deu"]`. Left
+    /// out, the user's default languages are used, or detected if there are none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub languages: Option<Vec<String>>,
 }
 
-#[derive(Deserialize, Debug)]
-pub struct GetDocumentsQueryParams {
+/// Partial update of a document. Fields that are left out keep their current value; `tags`
+/// replaces the document's tags.
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct UpdateDocumentCommand {
     pub title: Option<String>,
+    pub content: Option<String>,
+    pub tags: Option<Vec<String>>,
+    /// Date printed on the document, `YYYY-MM-DD`.
+    pub document_date: Option<NaiveDate>,
+}
+
+/// What to do with an uploaded file the user already has.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OnDuplicate {
+    /// Answer **409** with the existing document or job.
+    #[default]
+    Reject,
+    /// Answer with the existing document or job as if the file had just been uploaded.
+    Link,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct CreateDocumentQueryParams {
+    #[serde(default)]
+    pub on_duplicate: OnDuplicate,
+    /// Steps images go through before OCR: `default`, `none` or a comma separated list such as
+    /// `grayscale,binarize`. Left out, the reader's configured chain is used.
+    pub preprocessing: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct GetDocumentsQueryParams {
+    /// `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+    /// Page size, at most [`PAGE_LIMIT`].
+    pub limit: Option<u32>,
+    /// Field to order by. Defaults to `title`.
+    pub sort: Option<DocumentSortField>,
+    /// `asc` (default) or `desc`.
+    pub order: Option<SortDirection>,
+    /// Repeatable, e.g. `?tag=bills&tag=2025`.
+    #[serde(default)]
+    pub tag: Vec<String>,
+    /// How several `tag` values are combined. Defaults to `all`.
+    pub tag_mode: Option<TagMatch>,
+    /// Earliest document date, inclusive, `YYYY-MM-DD`.
+    pub from: Option<NaiveDate>,
+    /// Latest document date, inclusive, `YYYY-MM-DD`.
+    pub to: Option<NaiveDate>,
 }
 
-/// Creates a new document by processing multipart form data.
-/// +---------+     +-----------+     +--------+     +--------+
-/// |         |     |           |     |        |     |        |
-/// | Handler |---->| Tesseract |---->| Ollama |---->| SQLite |
-/// |         |     |           |     |        |     |        |
-/// +---------+     +-----------+     +--------+     +--------+
+/// Creates a new document by processing multipart form data. An uploaded file is stored in the
+/// blob store and queued for ingestion: the response is **202** with the job, which can be polled
+/// at `/jobs/{id}` while the workers read and summarize the file. Without a file the document is
+/// saved right away (**201**).
+///
+/// Several `file` parts, or a ZIP archive (told by its content, not its name), are a bulk upload:
+/// every file is queued as its own job and the response lists the outcome per file. It is
+/// **202** if any file was accepted, **409** if all of them were duplicates, **415** if none had a
+/// supported type and **400** otherwise, including for an archive without any files.
+///
+/// A file is only stored and read once per user. Uploading it again gives **409** with the ID
+/// of the existing document, or of the job still ingesting it; with `?on_duplicate=link` the
+/// existing document (**200**) or job (**202**) is returned instead.
+///
+/// `?preprocessing=` picks the steps images go through before OCR and `languages` in the `json`
+/// part the languages they are read in; an unknown step, or a language OCR cannot read in,
+/// gives **400**.
+///
+/// Files are streamed to disk as they arrive. A file or request over the [`UploadLimits`] is
+/// answered with **413** and the limit it went over.
+/// +---------+     +-----------+     +-----------------+
+/// |         |     |           |     |                 |
+/// | Handler |---->| BlobStore |---->| ingestion_jobs  |----> 202 + job
+/// |         |     |           |     |                 |
+/// +---------+     +-----------+     +-----------------+
 pub async fn create_document(
     AuthUser {
         user_id,
         tenant: _tenant,
     }: AuthUser,
     State(DocumentState(document_use_cases)): State<DocumentState>,
-    mut multipart: Multipart,
+    State(upload_limits): State<UploadLimits>,
+    Query(params): Query<CreateDocumentQueryParams>,
+    multipart: Multipart,
 ) -> impl IntoResponse {
     tracing::info!("Received multipart form data");
-    let mut json_data: Option<CreateDocumentCommand> = None;
-    let mut file_data = Vec::new();
-    let mut file_name = String::new();
+    let preprocessing = match params
+        .preprocessing
+        .as_deref()
+        .map(PreprocessingChain::parse)
+    {
+        None => None,
+        Some(Ok(chain)) => Some(chain),
+        Some(Err(e)) => {
+            tracing::warn!("Rejected upload: {}", e);
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": e.to_string()})),
+            );
+        }
+    };
+    let (json_data, files) = match read_upload_form(multipart, upload_limits, user_id).await {
+        Ok(form) => form,
+        Err(e) => {
+            tracing::warn!("Rejected upload: {}", e);
+            return e.response();
+        }
+    };
+
+    let Some(payload) = json_data else {
+        tracing::warn!("No valid JSON data found in the multipart form");
+        return (StatusCode::NOT_FOUND, Json(json!({})));
+    };
+    let languages = match payload
+        .languages
+        .clone()
+        .map(|codes| document_use_cases.choose_ocr_languages(codes))
+        .transpose()
+    {
+        Ok(languages) => languages,
+        Err(e) => {
+            tracing::warn!("Rejected upload: {}", e);
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": e.to_string()})),
+            );
+        }
+    };
+    let files: Vec<UploadedDocumentInput> = files
+        .into_iter()
+        .map(|file| {
+            file.with_preprocessing(preprocessing.clone())
+                .with_languages(languages.clone())
+        })
+        .collect();
 
-    while let Some(field) = multipart.next_field().await.unwrap_or(None) {
+    if files.len() == 1 && !files[0].is_zip() {
+        if let Err(e) = files[0].check_content_type() {
+            tracing::warn!("Rejected upload {}: {}", files[0].file_name, e);
+            return (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                Json(json!({"error": e.to_string()})),
+            );
+        }
+        return match document_use_cases.queue_upload(&files[0]).await {
+            Ok(outcome) => upload_response(outcome, params.on_duplicate),
+            Err(e) => {
+                tracing::error!("Error queuing upload: {}", e);
+                return_500()
+            }
+        };
+    }
+    if !files.is_empty() {
+        return queue_bulk_upload(&document_use_cases, files, user_id, params.on_duplicate).await;
+    }
+
+    let mut document = Document::new(&payload.title, &payload.content, user_id);
+    document.print_details();
+    document.chunks = document_use_cases
+        .embed_document(&document)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("No embeddings for document {}: {}", document.id, e);
+            vec![]
+        });
+
+    let repo = document_use_cases.document_repository.clone();
+    match repo.save_document(document).await {
+        Err(e) => {
+            tracing::error!("Error saving document: {}", e);
+            return_500()
+        }
+        Ok(saved_doc) => {
+            tracing::info!("Document saved: {:?}", saved_doc.title);
+            (
+                StatusCode::CREATED,
+                Json(json!(DocumentDto::from_document(&saved_doc))),
+            )
+        }
+    }
+}
+
+/// The `json` part and the non-empty `file` parts of an upload form, each file streamed to a
+/// temp file.
+async fn read_upload_form(
+    mut multipart: Multipart,
+    limits: UploadLimits,
+    user_id: Uuid,
+) -> Result<(Option<CreateDocumentCommand>, Vec<UploadedDocumentInput>), UploadError> {
+    let mut budget = UploadBudget::new(limits);
+    let mut json_data = None;
+    let mut files = Vec::new();
+    while let Some(mut field) = multipart.next_field().await? {
         match field.name() {
             Some("json") => {
-                let text = field.text().await.unwrap();
+                let text = budget.read_text(&mut field).await?;
                 json_data = serde_json::from_str(&text).ok();
             }
             Some("file") => {
                 tracing::info!("Processing file field");
-                if let Some(name) = field.file_name() {
-                    file_name = name.to_string();
+                let file_name = field.file_name().unwrap_or_default().to_string();
+                let file = budget.stream_to_file(&mut field, &file_name).await?;
+                tracing::info!("Received file: {} ({} bytes)", file_name, file.size_bytes());
+                if file.size_bytes() > 0 {
+                    files.push(UploadedDocumentInput::new(file_name, file, user_id)?);
                 }
-                file_data = field.bytes().await.unwrap().to_vec();
-                tracing::info!("Received file: {}", file_name);
             }
             _ => {}
         }
     }
+    Ok((json_data, files))
+}
 
-    if let Some(_payload) = json_data {
-        let document_opt = match !file_data.is_empty() {
-            true => {
-                let reader = document_use_cases.reader.clone();
-                let summarizer = document_use_cases.summarizer.clone();
-                let uploaded_document_input =
-                    UploadedDocumentInput::new(file_name, file_data, user_id);
-                Document::from_file(&uploaded_document_input, reader, summarizer).await
+/// Response to a single uploaded file.
+fn upload_response(
+    outcome: UploadOutcome,
+    on_duplicate: OnDuplicate,
+) -> (StatusCode, Json<serde_json::Value>) {
+    if let Some(error) = duplicate_error(&outcome, on_duplicate) {
+        let body = match &outcome {
+            UploadOutcome::Duplicate(document) => {
+                json!({"error": error, "document_id": document.id})
             }
-            false => Some(Document::new(&_payload.title, &_payload.content, user_id)),
-        };
-
-        let document = match document_opt {
-            Some(doc) => doc,
-            None => {
-                tracing::error!("Failed to create document from file data");
-                return return_500();
+            UploadOutcome::Queued(job) | UploadOutcome::AlreadyQueued(job) => {
+                json!({"error": error, "job_id": job.id})
             }
         };
+        return (StatusCode::CONFLICT, Json(body));
+    }
+    match outcome {
+        UploadOutcome::Queued(job) | UploadOutcome::AlreadyQueued(job) => {
+            tracing::info!("Upload handled by ingestion job {}", job.id);
+            (
+                StatusCode::ACCEPTED,
+                Json(json!(IngestionJobDto::from_job(&job))),
+            )
+        }
+        UploadOutcome::Duplicate(document) => {
+            tracing::info!("Upload linked to existing document {}", document.id);
+            (
+                StatusCode::OK,
+                Json(json!(DocumentDto::from_document(&document))),
+            )
+        }
+    }
+}
 
-        document.print_details();
+/// Why the file of the outcome was not accepted, or [`None`] if it was queued or linked.
+fn duplicate_error(outcome: &UploadOutcome, on_duplicate: OnDuplicate) -> Option<&'static str> {
+    match (outcome, on_duplicate) {
+        (UploadOutcome::Queued(_), _) | (_, OnDuplicate::Link) => None,
+        (UploadOutcome::Duplicate(document), _) if document.is_trashed() => {
+            Some("This file was already uploaded and is in the trash")
+        }
+        (UploadOutcome::Duplicate(_), _) => Some("This file was already uploaded"),
+        (UploadOutcome::AlreadyQueued(_), _) => Some("This file is already being processed"),
+    }
+}
 
-        let repo = document_use_cases.document_repository.clone();
-        let saved_doc_res = repo.save_document(document).await;
-        match saved_doc_res {
+/// Queues every uploaded file, expanding ZIP archives into their entries. The results follow
+/// the order of the files, with the entries of an archive in its place.
+async fn queue_bulk_upload(
+    document_use_cases: &DocumentUseCases,
+    files: Vec<UploadedDocumentInput>,
+    user_id: Uuid,
+    on_duplicate: OnDuplicate,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let mut budget = ZipBudget::new(ZipLimits::default());
+    let mut uploads = Vec::new();
+    for file in files {
+        if !file.is_zip() {
+            uploads.push(Ok(file));
+            continue;
+        }
+        let archive = file.file.clone();
+        let preprocessing = file.preprocessing.clone();
+        let languages = file.languages.clone();
+        let expanded = tokio::task::spawn_blocking(move || {
+            let mut budget = budget;
+            expand_zip(&archive, &mut budget).map(|entries| (entries, budget))
+        })
+        .await
+        .unwrap_or_else(|e| Err(e.into()))
+        .and_then(|(entries, left)| {
+            budget = left;
+            entries
+                .into_iter()
+                .map(|entry| {
+                    UploadedDocumentInput::new(entry.file_name, entry.file, user_id).map(|input| {
+                        input
+                            .with_preprocessing(preprocessing.clone())
+                            .with_languages(languages.clone())
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(Into::into)
+        });
+        match expanded {
+            Ok(entries) => uploads.extend(entries.into_iter().map(Ok)),
             Err(e) => {
-                tracing::error!("Error saving document: {}", e);
-                return_500()
+                tracing::warn!("Rejected archive {}: {}", file.file_name, e);
+                uploads.push(Err(UploadResultDto::rejected(
+                    file.file_name,
+                    e.to_string(),
+                )));
             }
-            Ok(saved_doc) => {
-                tracing::info!("Document saved: {:?}", saved_doc.title);
-                (
-                    StatusCode::CREATED,
-                    Json(json!(DocumentDto::from_document(&saved_doc))),
-                )
+        }
+    }
+
+    let mut results = Vec::new();
+    let mut unsupported = 0;
+    for upload in uploads {
+        let upload = match upload {
+            Ok(upload) => upload,
+            Err(rejected) => {
+                results.push(rejected);
+                continue;
             }
+        };
+        if let Err(e) = upload.check_content_type() {
+            tracing::warn!("Rejected upload {}: {}", upload.file_name, e);
+            results.push(UploadResultDto::rejected(upload.file_name, e.to_string()));
+            unsupported += 1;
+            continue;
         }
-    } else {
-        tracing::warn!("No valid JSON data found in the multipart form");
-        (StatusCode::NOT_FOUND, Json(json!({})))
+        match document_use_cases.queue_upload(&upload).await {
+            Ok(outcome) => {
+                let error = duplicate_error(&outcome, on_duplicate).map(str::to_string);
+                results.push(UploadResultDto::from_outcome(
+                    upload.file_name,
+                    &outcome,
+                    error,
+                ));
+            }
+            Err(e) => {
+                tracing::error!("Error queuing upload {}: {}", upload.file_name, e);
+                results.push(UploadResultDto::rejected(
+                    upload.file_name,
+                    "Could not store the file".to_string(),
+                ));
+            }
+        }
+    }
+
+    if results.is_empty() {
+        tracing::warn!("Rejected upload: the archives contain no files");
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": "The archive contains no files"})),
+        );
     }
+    let status = if results.iter().any(|result| result.error.is_none()) {
+        StatusCode::ACCEPTED
+    } else if results.iter().all(UploadResultDto::is_duplicate) {
+        StatusCode::CONFLICT
+    } else if unsupported == results.len() {
+        StatusCode::UNSUPPORTED_MEDIA_TYPE
+    } else {
+        StatusCode::BAD_REQUEST
+    };
+    (status, Json(json!(BulkUploadDto { results })))
 }
 
 pub async fn get_document(
     AuthUser {
-        user_id: _,
+        user_id,
         tenant: _tenant,
     }: AuthUser,
     State(DocumentState(document_use_cases)): State<DocumentState>,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
     tracing::info!("Fetching document with ID: {}", id);
-    let repo = document_use_cases.document_repository.clone();
-    match repo.get_document(id).await {
-        Some(document) => (StatusCode::OK, Json(json!(document.clone()))),
+    match document_use_cases
+        .find_document(user_id, id, DocumentAction::Read)
+        .await
+    {
+        Some(document) => (StatusCode::OK, Json(json!(document))),
         None => (StatusCode::NOT_FOUND, Json(json!({}))),
     }
 }
 
-/**
-* NOTE: This is a testing function that doesn't guarantee order and is not suited for pagination.
-*
-*/
-pub async fn get_documents(
+pub async fn update_document(
     AuthUser {
         user_id,
         tenant: _tenant,
     }: AuthUser,
     State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+    Json(command): Json<UpdateDocumentCommand>,
 ) -> impl IntoResponse {
-    tracing::info!("Fetching documents for user: {}", user_id.to_string());
-    let repo = document_use_cases.document_repository.clone();
-    let query = GetDocumentsQuery::new(repo, user_id, PAGE_LIMIT);
-    let documents = query.execute().await;
-    (StatusCode::OK, Json(json!(documents)))
+    tracing::info!("Updating document with ID: {}", id);
+    let Some(mut document) = document_use_cases
+        .find_document(user_id, id, DocumentAction::Write)
+        .await
+    else {
+        return (StatusCode::NOT_FOUND, Json(json!({})));
+    };
+    let UpdateDocumentCommand {
+        title,
+        content,
+        tags,
+        document_date,
+    } = command;
+    let text_changed = title.is_some() || content.is_some();
+    if let Some(title) = title {
+        document.title = title;
+    }
+    if let Some(content) = content {
+        document.set_content(content);
+    }
+    if let Some(tags) = tags {
+        document.tags = tags;
+    }
+    if let Some(document_date) = document_date {
+        document.document_date = Some(document_date);
+    }
+    let document = match document_use_cases
+        .document_repository
+        .update_document(document)
+        .await
+    {
+        Ok(document) => document,
+        Err(e) => {
+            tracing::error!("Error updating document {}: {}", id, e);
+            return return_500();
+        }
+    };
+    // Searches and the assistant would keep matching the old text otherwise.
+    if text_changed && let Err(e) = document_use_cases.reembed_document(&document).await {
+        tracing::error!("Error saving chunks of document {}: {}", id, e);
+    }
+    (
+        StatusCode::OK,
+        Json(json!(DocumentDto::from_document(&document))),
+    )
+}
+
+/// Downloads the original file a document was created from.
+pub async fn get_document_file(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    tracing::info!("Fetching file for document with ID: {}", id);
+    let blob = document_use_cases
+        .find_document(user_id, id, DocumentAction::Read)
+        .await
+        .and_then(|document| document.blob);
+    let Some(blob) = blob else {
+        return (StatusCode::NOT_FOUND, Json(json!({}))).into_response();
+    };
+
+    match document_use_cases.blob_store.get(&blob.sha256).await {
+        Ok(Some(data)) => (
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, blob.mime_type),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!(
+                        "attachment; filename=\"{}\"",
+                        blob.file_name.replace('"', "")
+                    ),
+                ),
+            ],
+            data,
+        )
+            .into_response(),
+        Ok(None) => {
+            tracing::error!("Blob {} for document {} is missing", blob.sha256, id);
+            (StatusCode::NOT_FOUND, Json(json!({}))).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Error reading blob {}: {}", blob.sha256, e);
+            return_500().into_response()
+        }
+    }
 }
 
-pub async fn get_documents_by_title(
+/// Returns a small WebP preview of a document's upload. Thumbnails never change, so the
+/// content address doubles as the `ETag`.
+pub async fn get_document_thumbnail(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Response {
+    tracing::info!("Fetching thumbnail for document with ID: {}", id);
+    let thumbnail_sha256 = document_use_cases
+        .find_document(user_id, id, DocumentAction::Read)
+        .await
+        .and_then(|document| document.thumbnail_sha256);
+    let Some(sha256) = thumbnail_sha256 else {
+        return (StatusCode::NOT_FOUND, Json(json!({}))).into_response();
+    };
+    let etag = format!("\"{}\"", sha256);
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (header::CACHE_CONTROL, THUMBNAIL_CACHE_CONTROL.to_string()),
+    ];
+    let if_none_match = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok());
+    if if_none_match.is_some_and(|tags| tags.split(',').any(|tag| tag.trim() == etag)) {
+        return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+    }
+
+    match document_use_cases.blob_store.get(&sha256).await {
+        Ok(Some(data)) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, THUMBNAIL_MIME_TYPE.to_string())],
+            cache_headers,
+            data,
+        )
+            .into_response(),
+        Ok(None) => {
+            tracing::error!("Thumbnail {} for document {} is missing", sha256, id);
+            (StatusCode::NOT_FOUND, Json(json!({}))).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Error reading thumbnail {}: {}", sha256, e);
+            return_500().into_response()
+        }
+    }
+}
+
+/// Returns the raw text read from a document's upload along with how it was extracted.
+pub async fn get_document_text(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!("Fetching extracted text for document with ID: {}", id);
+    match document_use_cases
+        .find_document(user_id, id, DocumentAction::Read)
+        .await
+        .and_then(|document| document.extracted_text)
+    {
+        Some(extracted_text) => (StatusCode::OK, Json(json!(extracted_text))),
+        None => (StatusCode::NOT_FOUND, Json(json!({}))),
+    }
+}
+
+/// Lists the user's documents one page at a time. Pass the returned `next_cursor` back as
+/// `cursor` with the same `sort` and `order` to get the following page; it is `null` on the last
+/// page.
+pub async fn list_documents(
     AuthUser {
         user_id,
         tenant: _tenant,
@@ -147,16 +607,48 @@ pub async fn get_documents_by_title(
     State(DocumentState(document_use_cases)): State<DocumentState>,
     Query(params): Query<GetDocumentsQueryParams>,
 ) -> impl IntoResponse {
-    let title = params.title.unwrap_or_else(|| "".to_string());
+    let sort = DocumentSort {
+        field: params.sort.unwrap_or_default(),
+        direction: params.order.unwrap_or_default(),
+    };
+    let after = match params
+        .cursor
+        .as_deref()
+        .map(|c| DocumentCursor::decode(c, &sort))
+    {
+        None => None,
+        Some(Ok(cursor)) => Some(cursor),
+        Some(Err(e)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": e.to_string() })),
+            );
+        }
+    };
+    let dates = DateRange::new(params.from, params.to);
+    if dates.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "`from` must not be after `to`" })),
+        );
+    }
+    let limit = params.limit.unwrap_or(PAGE_LIMIT).clamp(1, PAGE_LIMIT);
+    let tags = TagFilter::new(&params.tag, params.tag_mode.unwrap_or_default());
     tracing::info!(
-        "Fetching documents for user: {} with title cursor: {}",
+        "Fetching documents for user: {} sorted by {:?} after {:?} with tags: {:?} within {:?}",
         user_id.to_string(),
-        title
+        sort,
+        after,
+        tags,
+        dates
     );
     let repo = document_use_cases.document_repository.clone();
-    let query = GetDocumentsTitleCursorQuery::new(repo, user_id, title, PAGE_LIMIT);
-    let documents = query.execute().await;
-    (StatusCode::OK, Json(json!(documents)))
+    let query = ListDocumentsQuery::new(repo, user_id, sort, after, tags, dates, limit);
+    let page = query.execute().await;
+    (
+        StatusCode::OK,
+        Json(json!(DocumentPageDto::from_page(&page))),
+    )
 }
 
 fn return_500() -> (StatusCode, Json<serde_json::Value>) {
@@ -182,8 +674,13 @@ mod tests {
 
     use crate::application::document_repository::DocumentRepository;
     use crate::application::document_use_cases::DocumentUseCases;
+    use crate::application::ingest_document_command::IngestDocumentCommand;
+    use crate::application::retry_policy::RetryPolicy;
     use crate::domain::document_summarizer::{DocumentSummarizer, DocumentSummaryResult};
-    use crate::domain::document_text_reader::DocumentTextReader;
+    use crate::domain::document_text_reader::{DocumentTextReader, ExtractedText};
+    use crate::domain::ingestion_job::{IngestionJob, IngestionJobStatus};
+    use crate::domain::ocr_language::OcrLanguages;
+    use crate::domain::user_settings::UserSettings;
     use crate::infrastructure::document::document_collection::DocumentCollection;
 
     use super::*;
@@ -192,7 +689,6 @@ mod tests {
     use axum::extract::FromRequest;
     use axum::http::{Request, StatusCode};
     use serde::de::DeserializeOwned;
-    use serde_json::from_slice;
     use uuid::Uuid;
 
     struct MockDocumentTextReader;
@@ -202,8 +698,17 @@ mod tests {
         async fn read_image(
             &self,
             _uploaded_document_input: &UploadedDocumentInput,
-        ) -> Result<String, Box<dyn Error>> {
-            Ok(String::from("This is test content."))
+        ) -> Result<ExtractedText, Box<dyn Error>> {
+            Ok(ExtractedText {
+                text: String::from("This is test content."),
+                reader: String::from("mock"),
+                page_count: Some(1),
+                ocr_used: false,
+                pages: vec![],
+                languages: vec![],
+                words: vec![],
+                confidence: None,
+            })
         }
     }
 
@@ -237,12 +742,13 @@ mod tests {
         let payload = CreateDocumentCommand {
             title: String::from("Test Document"),
             content: String::from("This is test content."),
+            languages: None,
         };
 
         let document_use_cases = Arc::new(DocumentUseCases {
-            document_repository: Arc::new(DocumentCollection::new()),
             reader: Arc::new(MockDocumentTextReader {}),
             summarizer: Arc::new(MockDocumentSummarizer {}),
+            ..DocumentUseCases::for_tests()
         });
 
         // Serialize the JSON payload
@@ -276,26 +782,639 @@ mod tests {
         let response = create_document(
             auth_user,
             State(DocumentState(document_use_cases.clone())),
+            State(UploadLimits::default()),
+            Query(CreateDocumentQueryParams::default()),
             multipart,
         )
         .await
         .into_response();
 
-        let (parts, body) = response.into_parts();
-        let status_code = parts.status;
+        let ProcessedResponse {
+            status_code,
+            response_payload: queued,
+        } = process_response::<IngestionJobDto>(response).await;
+
         // Assert
-        assert_eq!(status_code, StatusCode::CREATED);
+        assert_eq!(status_code, StatusCode::ACCEPTED);
+        assert_eq!(queued.status, IngestionJobStatus::Queued);
+        assert_eq!(queued.file_name, "test.txt");
 
-        let bytes = to_bytes(body, usize::MAX)
+        let job = run_next_ingestion_job(&document_use_cases).await;
+        assert_eq!(job.id, queued.id);
+        assert_eq!(job.status, IngestionJobStatus::Succeeded);
+        let document = document_use_cases
+            .document_repository
+            .get_document(&job.user_id, job.document_id.unwrap())
+            .await
+            .expect("Ingested document should be saved");
+        assert_eq!(document.title, "Test Document");
+        assert_eq!(document.content, "This is test content.");
+    }
+
+    /// An upload form with the `json` part and a `file` part per `(file_name, content)`.
+    async fn multipart_form(json: &str, files: &[(&str, &str)]) -> Multipart {
+        let mut body = format!(
+            "--boundary\r\n\
+            Content-Disposition: form-data; name=\"json\"\r\n\
+            Content-Type: application/json\r\n\r\n\
+            {}\r\n",
+            json
+        );
+        for (file_name, content) in files {
+            body.push_str(&format!(
+                "--boundary\r\n\
+                Content-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\n\
+                Content-Type: application/octet-stream\r\n\r\n\
+                {}\r\n",
+                file_name, content
+            ));
+        }
+        body.push_str("--boundary--");
+        let request = Request::builder()
+            .header("content-type", "multipart/form-data; boundary=boundary")
+            .body(Body::from(body))
+            .unwrap();
+        Multipart::from_request(request, &()).await.unwrap()
+    }
+
+    /// Posts the upload form to [`create_document`] within the default upload limits.
+    async fn upload(
+        document_use_cases: &Arc<DocumentUseCases>,
+        auth_user: &AuthUser,
+        params: CreateDocumentQueryParams,
+        json: &str,
+        files: &[(&str, &str)],
+    ) -> Response {
+        create_document(
+            auth_user.clone(),
+            State(DocumentState(document_use_cases.clone())),
+            State(UploadLimits::default()),
+            Query(params),
+            multipart_form(json, files).await,
+        )
+        .await
+        .into_response()
+    }
+
+    const NO_METADATA: &str = r#"{"title": "", "content": ""}"#;
+
+    async fn upload_receipt(
+        document_use_cases: &Arc<DocumentUseCases>,
+        auth_user: &AuthUser,
+        on_duplicate: OnDuplicate,
+    ) -> Response {
+        let params = CreateDocumentQueryParams {
+            on_duplicate,
+            ..CreateDocumentQueryParams::default()
+        };
+        let files = [("receipt.txt", "Total: 12.50")];
+        upload(document_use_cases, auth_user, params, NO_METADATA, &files).await
+    }
+
+    #[tokio::test]
+    async fn test_create_document_short_circuits_duplicate_uploads() {
+        // Given
+        let document_use_cases = Arc::new(DocumentUseCases {
+            reader: Arc::new(MockDocumentTextReader {}),
+            summarizer: Arc::new(MockDocumentSummarizer {}),
+            ..DocumentUseCases::for_tests()
+        });
+        let auth_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let first = upload_receipt(&document_use_cases, &auth_user, OnDuplicate::Reject).await;
+        let queued = process_response::<IngestionJobDto>(first).await;
+        assert_eq!(queued.status_code, StatusCode::ACCEPTED);
+
+        // When
+        let while_queued =
+            upload_receipt(&document_use_cases, &auth_user, OnDuplicate::Reject).await;
+        let job = run_next_ingestion_job(&document_use_cases).await;
+        let rejected = upload_receipt(&document_use_cases, &auth_user, OnDuplicate::Reject).await;
+        let linked = upload_receipt(&document_use_cases, &auth_user, OnDuplicate::Link).await;
+
+        // Then
+        let while_queued = process_response::<serde_json::Value>(while_queued).await;
+        assert_eq!(while_queued.status_code, StatusCode::CONFLICT);
+        assert_eq!(
+            while_queued.response_payload["job_id"],
+            json!(queued.response_payload.id)
+        );
+        let rejected = process_response::<serde_json::Value>(rejected).await;
+        assert_eq!(rejected.status_code, StatusCode::CONFLICT);
+        assert_eq!(
+            rejected.response_payload["document_id"],
+            json!(job.document_id.unwrap())
+        );
+        let linked = process_response::<DocumentDto>(linked).await;
+        assert_eq!(linked.status_code, StatusCode::OK);
+        assert_eq!(linked.response_payload.id, job.document_id.unwrap());
+        let jobs = document_use_cases
+            .ingestion_queue
+            .jobs
+            .list_jobs(&auth_user.user_id, None, &10)
+            .await;
+        assert_eq!(jobs.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_document_rejects_a_file_whose_content_does_not_match_its_name() {
+        // Given
+        let document_use_cases = Arc::new(DocumentUseCases {
+            reader: Arc::new(MockDocumentTextReader {}),
+            summarizer: Arc::new(MockDocumentSummarizer {}),
+            ..DocumentUseCases::for_tests()
+        });
+        let auth_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+
+        // When
+        let response = upload(
+            &document_use_cases,
+            &auth_user,
+            CreateDocumentQueryParams::default(),
+            NO_METADATA,
+            &[("invoice.pdf", "Total: 12.50")],
+        )
+        .await;
+
+        // Then
+        let ProcessedResponse {
+            status_code,
+            response_payload,
+        } = process_response::<serde_json::Value>(response).await;
+        assert_eq!(status_code, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            response_payload["error"],
+            "The file is named .pdf but its content is text/plain"
+        );
+        let jobs = document_use_cases
+            .ingestion_queue
+            .jobs
+            .list_jobs(&auth_user.user_id, None, &10)
+            .await;
+        assert!(jobs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_document_rejects_files_over_the_upload_limit() {
+        // Given
+        let document_use_cases = Arc::new(DocumentUseCases {
+            reader: Arc::new(MockDocumentTextReader {}),
+            summarizer: Arc::new(MockDocumentSummarizer {}),
+            ..DocumentUseCases::for_tests()
+        });
+        let multipart = multipart_form(
+            NO_METADATA,
+            &[("statement.txt", "Opening balance: 1,024.00")],
+        )
+        .await;
+        let auth_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let upload_limits = UploadLimits {
+            max_file_bytes: 16,
+            ..UploadLimits::default()
+        };
+
+        // When
+        let response = create_document(
+            auth_user.clone(),
+            State(DocumentState(document_use_cases.clone())),
+            State(upload_limits),
+            Query(CreateDocumentQueryParams::default()),
+            multipart,
+        )
+        .await
+        .into_response();
+
+        // Then
+        let ProcessedResponse {
+            status_code,
+            response_payload,
+        } = process_response::<serde_json::Value>(response).await;
+        assert_eq!(status_code, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            response_payload,
+            json!({
+                "error": "statement.txt is larger than 16 bytes",
+                "limit": "file",
+                "limit_bytes": 16,
+                "file_name": "statement.txt",
+            })
+        );
+        let jobs = document_use_cases
+            .ingestion_queue
+            .jobs
+            .list_jobs(&auth_user.user_id, None, &10)
+            .await;
+        assert!(jobs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_document_queues_the_chosen_preprocessing() {
+        // Given
+        let document_use_cases = Arc::new(DocumentUseCases {
+            reader: Arc::new(MockDocumentTextReader {}),
+            summarizer: Arc::new(MockDocumentSummarizer {}),
+            ..DocumentUseCases::for_tests()
+        });
+        let auth_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let upload_with = |preprocessing: &str| {
+            let params = CreateDocumentQueryParams {
+                preprocessing: Some(preprocessing.to_string()),
+                ..CreateDocumentQueryParams::default()
+            };
+            upload(
+                &document_use_cases,
+                &auth_user,
+                params,
+                NO_METADATA,
+                &[("receipt.txt", "Total: 12.50")],
+            )
+        };
+
+        // When
+        let rejected =
+            process_response::<serde_json::Value>(upload_with("grayscale,sharpen").await).await;
+        let queued =
+            process_response::<IngestionJobDto>(upload_with("grayscale,binarize").await).await;
+
+        // Then
+        assert_eq!(rejected.status_code, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            rejected.response_payload,
+            json!({
+                "error": "Unknown preprocessing step 'sharpen'; use none, default or some of \
+                    orient, grayscale, upscale, normalize_contrast, deskew, binarize"
+            })
+        );
+        assert_eq!(queued.status_code, StatusCode::ACCEPTED);
+        assert_eq!(
+            queued.response_payload.preprocessing.as_deref(),
+            Some("grayscale,binarize")
+        );
+        let job = run_next_ingestion_job(&document_use_cases).await;
+        assert_eq!(
+            job.preprocessing,
+            Some(PreprocessingChain::parse("grayscale,binarize").unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_document_queues_the_chosen_or_default_languages() {
+        // Given
+        let document_use_cases = Arc::new(DocumentUseCases {
+            reader: Arc::new(MockDocumentTextReader {}),
+            summarizer: Arc::new(MockDocumentSummarizer {}),
+            ..DocumentUseCases::for_tests()
+        });
+        let auth_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        document_use_cases
+            .user_settings
+            .save_settings(
+                &auth_user.user_id,
+                &UserSettings {
+                    ocr_languages: Some(OcrLanguages::parse("deu").unwrap()),
+                },
+            )
+            .await
+            .unwrap();
+        let (use_cases, user) = (&document_use_cases, &auth_user);
+        let upload_letter = |json: &'static str, content: &'static str| async move {
+            let params = CreateDocumentQueryParams::default();
+            upload(use_cases, user, params, json, &[("letter.txt", content)]).await
+        };
+
+        // When
+        let rejected = upload_letter(
+            r#"{"title": "", "content": "", "languages": ["german"]}"#,
+            "Sehr geehrte Damen und Herren",
+        )
+        .await;
+        let chosen = process_response::<IngestionJobDto>(
+            upload_letter(
+                r#"{"title": "", "content": "", "languages": ["spa", "eng"]}"#,
+                "Estimados señores",
+            )
+            .await,
+        )
+        .await;
+        let defaulted = process_response::<IngestionJobDto>(
+            upload_letter(NO_METADATA, "Sehr geehrte Damen und Herren").await,
+        )
+        .await;
+
+        // Then
+        assert_eq!(rejected.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(chosen.status_code, StatusCode::ACCEPTED);
+        assert_eq!(
+            chosen.response_payload.languages,
+            Some(vec!["spa".to_string(), "eng".to_string()])
+        );
+        assert_eq!(defaulted.status_code, StatusCode::ACCEPTED);
+        assert_eq!(
+            defaulted.response_payload.languages,
+            Some(vec!["deu".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_document_queues_every_file_of_a_bulk_upload() {
+        // Given
+        let document_use_cases = Arc::new(DocumentUseCases {
+            reader: Arc::new(MockDocumentTextReader {}),
+            summarizer: Arc::new(MockDocumentSummarizer {}),
+            ..DocumentUseCases::for_tests()
+        });
+        let auth_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+
+        // When
+        let response = upload(
+            &document_use_cases,
+            &auth_user,
+            CreateDocumentQueryParams::default(),
+            r#"{"title": "Receipts", "content": ""}"#,
+            &[
+                ("first.txt", "Coffee 3.50"),
+                ("second.txt", "Bread 2.10"),
+                ("scans.zip", "not a zip"),
+            ],
+        )
+        .await;
+
+        // Then
+        let ProcessedResponse {
+            status_code,
+            response_payload: bulk,
+        } = process_response::<BulkUploadDto>(response).await;
+        assert_eq!(status_code, StatusCode::ACCEPTED);
+        let outcomes: Vec<(&str, bool)> = bulk
+            .results
+            .iter()
+            .map(|result| (result.file_name.as_str(), result.job.is_some()))
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                ("first.txt", true),
+                ("second.txt", true),
+                ("scans.zip", false)
+            ]
+        );
+        assert!(bulk.results[2].error.is_some());
+        let first = run_next_ingestion_job(&document_use_cases).await;
+        let second = run_next_ingestion_job(&document_use_cases).await;
+        assert_eq!(first.status, IngestionJobStatus::Succeeded);
+        assert_eq!(second.status, IngestionJobStatus::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_get_document_file_returns_original_upload() {
+        // Given
+        let document_use_cases = Arc::new(DocumentUseCases {
+            reader: Arc::new(MockDocumentTextReader {}),
+            summarizer: Arc::new(MockDocumentSummarizer {}),
+            ..DocumentUseCases::for_tests()
+        });
+        let auth_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        upload_receipt(&document_use_cases, &auth_user, OnDuplicate::Reject).await;
+        let job = run_next_ingestion_job(&document_use_cases).await;
+
+        // When
+        let response = get_document_file(
+            auth_user,
+            State(DocumentState(document_use_cases.clone())),
+            Path(job.document_id.unwrap()),
+        )
+        .await;
+
+        // Then
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/plain"
+        );
+        let bytes = to_bytes(response.into_body(), usize::MAX)
             .await
             .expect("Failed to read body");
+        assert_eq!(&bytes[..], b"Total: 12.50");
+    }
 
-        // Deserialize the bytes into a DocumentDto object
-        let response_document: DocumentDto =
-            from_slice(&bytes).expect("Failed to deserialize body");
-        assert_eq!(response_document.title, "Test Document");
-        assert_eq!(response_document.content, "This is test content.");
-        assert!(!response_document.id.is_nil());
+    #[tokio::test]
+    async fn test_get_document_file_without_upload_not_found() {
+        let GivenUserAndDocuments {
+            auth_user,
+            document_use_cases,
+            document1_id,
+            ..
+        } = given_user_and_documents().await;
+
+        let response = get_document_file(
+            auth_user,
+            State(DocumentState(document_use_cases.clone())),
+            Path(document1_id),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_document_thumbnail_is_cached_by_etag() {
+        // Given
+        let GivenUserAndDocuments {
+            auth_user,
+            document_use_cases,
+            ..
+        } = given_user_and_documents().await;
+        let thumbnail_sha256 = document_use_cases.blob_store.put(b"webp").await.unwrap();
+        let mut document = Document::new("Scan", "Summary", auth_user.user_id);
+        document.thumbnail_sha256 = Some(thumbnail_sha256.clone());
+        let document = document_use_cases
+            .document_repository
+            .save_document(document)
+            .await
+            .unwrap();
+
+        // When
+        let response = get_document_thumbnail(
+            auth_user.clone(),
+            State(DocumentState(document_use_cases.clone())),
+            Path(document.id),
+            HeaderMap::new(),
+        )
+        .await;
+        let etag = response.headers().get(header::ETAG).unwrap().clone();
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag.clone());
+        let revalidated = get_document_thumbnail(
+            auth_user,
+            State(DocumentState(document_use_cases.clone())),
+            Path(document.id),
+            headers,
+        )
+        .await;
+
+        // Then
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(etag, format!("\"{}\"", thumbnail_sha256).as_str());
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "image/webp"
+        );
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read body");
+        assert_eq!(&bytes[..], b"webp");
+        assert_eq!(revalidated.status(), StatusCode::NOT_MODIFIED);
+    }
+
+    #[tokio::test]
+    async fn test_get_document_thumbnail_without_thumbnail_not_found() {
+        let GivenUserAndDocuments {
+            auth_user,
+            document_use_cases,
+            document1_id,
+            ..
+        } = given_user_and_documents().await;
+
+        let response = get_document_thumbnail(
+            auth_user,
+            State(DocumentState(document_use_cases.clone())),
+            Path(document1_id),
+            HeaderMap::new(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_document_text_returns_extracted_text() {
+        // Given
+        let GivenUserAndDocuments {
+            auth_user,
+            document_use_cases,
+            ..
+        } = given_user_and_documents().await;
+        let mut document = Document::new("Scan", "Summary", auth_user.user_id);
+        document.extracted_text = Some(ExtractedText {
+            text: "Full scanned text".to_string(),
+            reader: "tesseract".to_string(),
+            page_count: Some(1),
+            ocr_used: true,
+            pages: vec![],
+            languages: vec![],
+            words: vec![],
+            confidence: None,
+        });
+        let document = document_use_cases
+            .document_repository
+            .save_document(document)
+            .await
+            .expect("Failed to save document");
+
+        // When
+        let ProcessedResponse {
+            status_code,
+            response_payload,
+        } = process_response::<ExtractedText>(
+            get_document_text(
+                auth_user,
+                State(DocumentState(document_use_cases.clone())),
+                Path(document.id),
+            )
+            .await,
+        )
+        .await;
+
+        // Then
+        assert_eq!(status_code, StatusCode::OK);
+        let serialized = serde_json::to_string(&document).unwrap();
+        assert!(!serialized.contains("Full scanned text"));
+        assert_eq!(Some(response_payload), document.extracted_text);
+    }
+
+    #[tokio::test]
+    async fn test_get_document_text_without_extraction_not_found() {
+        let GivenUserAndDocuments {
+            auth_user,
+            document_use_cases,
+            document1_id,
+            ..
+        } = given_user_and_documents().await;
+
+        let response = get_document_text(
+            auth_user,
+            State(DocumentState(document_use_cases.clone())),
+            Path(document1_id),
+        )
+        .await
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_update_document_changes_only_given_fields() {
+        // Given
+        let GivenUserAndDocuments {
+            auth_user,
+            document_use_cases,
+            document1_id,
+            ..
+        } = given_user_and_documents().await;
+
+        // When
+        let ProcessedResponse {
+            status_code,
+            response_payload,
+        } = process_response::<DocumentDto>(
+            update_document(
+                auth_user.clone(),
+                State(DocumentState(document_use_cases.clone())),
+                Path(document1_id),
+                Json(UpdateDocumentCommand {
+                    title: Some("Renamed".to_string()),
+                    tags: Some(vec!["Bills".to_string()]),
+                    document_date: NaiveDate::from_ymd_opt(2025, 3, 14),
+                    ..Default::default()
+                }),
+            )
+            .await,
+        )
+        .await;
+
+        // Then
+        assert_eq!(status_code, StatusCode::OK);
+        assert_eq!(response_payload.title, "Renamed");
+        assert_eq!(response_payload.tags, vec!["bills".to_string()]);
+        let stored = document_use_cases
+            .document_repository
+            .get_document(&auth_user.user_id, document1_id)
+            .await
+            .unwrap();
+        assert_eq!(stored.title, "Renamed");
+        assert_eq!(stored.content, response_payload.content);
+        assert_eq!(stored.document_date, NaiveDate::from_ymd_opt(2025, 3, 14));
+        assert!(stored.updated_at > stored.created_at);
     }
 
     #[tokio::test]
@@ -324,6 +1443,29 @@ mod tests {
         assert_eq!(response_document.content, "This is test content.");
     }
 
+    #[tokio::test]
+    async fn test_get_other_users_document_not_found() {
+        let GivenUserAndDocuments {
+            document_use_cases,
+            document1_id,
+            ..
+        } = given_user_and_documents().await;
+        let other_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+
+        let response = get_document(
+            other_user,
+            State(DocumentState(document_use_cases.clone())),
+            Path(document1_id),
+        )
+        .await
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_get_document_not_found() {
         let GivenUserAndDocuments {
@@ -346,84 +1488,152 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_get_documents_by_title_empty() {
+    async fn test_list_documents_first_page() {
         let GivenUserAndDocuments {
             auth_user,
             document_use_cases,
             ..
         } = given_user_and_documents().await;
 
-        let response = get_documents_by_title(
+        let response = list_documents(
             auth_user,
             State(DocumentState(document_use_cases.clone())),
-            Query(GetDocumentsQueryParams { title: None }),
+            Query(GetDocumentsQueryParams::default()),
         )
         .await;
         let ProcessedResponse {
             status_code,
-            response_payload: response_documents,
-        } = process_response::<Vec<Document>>(response).await;
+            response_payload: page,
+        } = process_response::<DocumentPageDto>(response).await;
 
         // Assert
         assert_eq!(status_code, StatusCode::OK);
-        assert_eq!(response_documents.len(), 2); // NOTE: Check given_user_and_documents function for length
+        assert_eq!(page.items.len(), 2); // NOTE: Check given_user_and_documents function for length
+        assert_eq!(page.items[0].title, "Second Document");
+        assert!(page.next_cursor.is_none());
     }
 
-    /// This tests that documents are returned in order, and we should skip the ones with a name
-    /// before the one we search on.
+    /// This tests that following `next_cursor` continues after the last document of the previous
+    /// page, and that the last page has no cursor.
     #[tokio::test]
-    async fn test_get_documents_by_title_named() {
+    async fn test_list_documents_follows_cursor() {
         let GivenUserAndDocuments {
             auth_user,
             document_use_cases,
             ..
         } = given_user_and_documents().await;
 
-        let response = get_documents_by_title(
+        let response = list_documents(
+            auth_user.clone(),
+            State(DocumentState(document_use_cases.clone())),
+            Query(GetDocumentsQueryParams {
+                limit: Some(1),
+                ..Default::default()
+            }),
+        )
+        .await;
+        let ProcessedResponse {
+            response_payload: first_page,
+            ..
+        } = process_response::<DocumentPageDto>(response).await;
+        assert_eq!(first_page.items.len(), 1);
+        assert_eq!(first_page.items[0].title, "Second Document");
+
+        let response = list_documents(
             auth_user,
             State(DocumentState(document_use_cases.clone())),
             Query(GetDocumentsQueryParams {
-                title: Some("Second Document".to_string()),
+                limit: Some(1),
+                cursor: first_page.next_cursor,
+                ..Default::default()
             }),
         )
         .await;
         let ProcessedResponse {
             status_code,
-            response_payload: response_documents,
-        } = process_response::<Vec<Document>>(response).await;
+            response_payload: second_page,
+        } = process_response::<DocumentPageDto>(response).await;
 
         // Assert
         assert_eq!(status_code, StatusCode::OK);
-        assert_eq!(response_documents.len(), 1); // NOTE: Check given_user_and_documents function for length
+        assert_eq!(second_page.items.len(), 1);
+        // NOTE: Check given_user_and_documents function for name of last document.
+        assert_eq!(second_page.items[0].title, "Test Document");
+        assert!(second_page.next_cursor.is_none());
     }
 
-    /// This tests that documents are returned in order, and we should not return any when we search
-    /// with the last one.
     #[tokio::test]
-    async fn test_get_documents_by_title_last() {
+    async fn test_list_documents_descending() {
         let GivenUserAndDocuments {
             auth_user,
             document_use_cases,
             ..
         } = given_user_and_documents().await;
 
-        let response = get_documents_by_title(
+        let response = list_documents(
             auth_user,
             State(DocumentState(document_use_cases.clone())),
             Query(GetDocumentsQueryParams {
-                title: Some("Test Document".to_string()),
-            }), // NOTE: Check given_user_and_documents function for
-                // name of last document.
+                order: Some(SortDirection::Desc),
+                ..Default::default()
+            }),
         )
         .await;
         let ProcessedResponse {
             status_code,
-            response_payload: response_documents,
-        } = process_response::<Vec<Document>>(response).await;
+            response_payload: page,
+        } = process_response::<DocumentPageDto>(response).await;
 
         // Assert
         assert_eq!(status_code, StatusCode::OK);
-        assert_eq!(response_documents.len(), 0);
+        assert_eq!(page.items[0].title, "Test Document");
+    }
+
+    #[tokio::test]
+    async fn test_list_documents_invalid_cursor() {
+        let GivenUserAndDocuments {
+            auth_user,
+            document_use_cases,
+            ..
+        } = given_user_and_documents().await;
+
+        let response = list_documents(
+            auth_user,
+            State(DocumentState(document_use_cases.clone())),
+            Query(GetDocumentsQueryParams {
+                cursor: Some("not-a-cursor".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await;
+        let response = response.into_response();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_list_documents_inverted_date_range() {
+        let GivenUserAndDocuments {
+            auth_user,
+            document_use_cases,
+            ..
+        } = given_user_and_documents().await;
+
+        let response = list_documents(
+            auth_user,
+            State(DocumentState(document_use_cases.clone())),
+            Query(GetDocumentsQueryParams {
+                from: NaiveDate::from_ymd_opt(2025, 4, 1),
+                to: NaiveDate::from_ymd_opt(2025, 3, 1),
+                ..Default::default()
+            }),
+        )
+        .await;
+        let response = response.into_response();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
     async fn given_user_and_documents() -> GivenUserAndDocuments {
@@ -451,6 +1661,7 @@ mod tests {
             document_repository: Arc::new(repo),
             reader: Arc::new(MockDocumentTextReader {}),
             summarizer: Arc::new(MockDocumentSummarizer {}),
+            ..DocumentUseCases::for_tests()
         });
 
         GivenUserAndDocuments {
@@ -461,6 +1672,20 @@ mod tests {
         }
     }
 
+    /// Runs the oldest queued ingestion job the way a worker would.
+    async fn run_next_ingestion_job(document_use_cases: &Arc<DocumentUseCases>) -> IngestionJob {
+        let job = document_use_cases
+            .ingestion_queue
+            .jobs
+            .claim_next_job()
+            .await
+            .unwrap()
+            .expect("Expected a queued ingestion job");
+        IngestDocumentCommand::new(document_use_cases.clone(), RetryPolicy::default())
+            .execute(job)
+            .await
+    }
+
     async fn process_response<T>(response: impl IntoResponse) -> ProcessedResponse<T>
     where
         T: DeserializeOwned,
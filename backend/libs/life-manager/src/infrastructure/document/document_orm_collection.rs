@@ -1,17 +1,84 @@
+use std::collections::HashMap;
 use std::error::Error;
 use std::sync::Arc;
 
 use crate::application::document_repository::DocumentRepository;
-use crate::schema::documents;
+use crate::domain::document_chunk::{DocumentChunk, SemanticHit, cosine_similarity};
+use crate::domain::document_classification::{ClassificationStatus, DocumentClassification};
+use crate::domain::document_listing::{
+    DocumentListing, DocumentSortField, SortDirection, parse_timestamp_key,
+};
+use crate::domain::document_search::{
+    HIGHLIGHT_END, HIGHLIGHT_START, SearchHit, SearchQuery, SearchTerm,
+};
+use crate::domain::document_similarity::{SimilarDocument, hamming_distance};
+use crate::domain::ocr_word::OcrWord;
+use crate::domain::tag::{TagCount, TagMatch, normalize_tag, normalize_tags};
+use crate::schema::{
+    categories, document_chunks, document_classifications, document_fields, document_tags,
+    document_words, documents, tags,
+};
 use crate::{
     domain::document::Document,
-    infrastructure::document::document_entity::{DocumentEntity, NewDocumentEntity},
+    infrastructure::document::document_entity::{
+        DocumentChunkEntity, DocumentClassificationEntity, DocumentEntity, DocumentFieldsEntity,
+        DocumentTagEntity, DocumentWordEntity, NewDocumentEntity, TagEntity,
+    },
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use deadpool_diesel::sqlite::Pool;
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
+use diesel::dsl::count;
+use diesel::sql_types::{BigInt, Date, Double, Nullable, Text, Timestamp};
+use diesel::sqlite::SqliteConnection;
+use diesel::{
+    BoolExpressionMethods, Connection, ExpressionMethods, QueryDsl, QueryResult, QueryableByName,
+    RunQueryDsl, SelectableHelper,
+};
 use uuid::Uuid;
 
+/// Words inserted per statement, well under SQLite's limit on bound parameters.
+const WORD_INSERT_BATCH: usize = 500;
+
+/// Chunks inserted per statement.
+const CHUNK_INSERT_BATCH: usize = 500;
+
+/// Restricts a boxed documents query to the rows after `(key, id)` in the given direction and
+/// orders it by the sort column, then by ID.
+macro_rules! keyset {
+    ($query:expr, $column:expr, $after:expr, $direction:expr) => {{
+        let mut query = $query;
+        if let Some((key, id)) = $after {
+            query = match $direction {
+                SortDirection::Asc => query.filter(
+                    $column
+                        .gt(key.clone())
+                        .or($column.eq(key).and(documents::id.gt(id))),
+                ),
+                SortDirection::Desc => query.filter(
+                    $column
+                        .lt(key.clone())
+                        .or($column.eq(key).and(documents::id.lt(id))),
+                ),
+            };
+        }
+        match $direction {
+            SortDirection::Asc => query.order_by(($column.asc(), documents::id.asc())),
+            SortDirection::Desc => query.order_by(($column.desc(), documents::id.desc())),
+        }
+    }};
+}
+
+diesel::define_sql_function! {
+    fn coalesce(x: Nullable<Date>, y: Date) -> Date;
+}
+
+diesel::define_sql_function! {
+    /// SQLite's `date()`, the calendar day of a timestamp.
+    #[sql_name = "date"]
+    fn day_of(x: Timestamp) -> Date;
+}
+
 #[derive(Clone)]
 pub struct DocumentOrmCollection {
     pub pool: Arc<Pool>,
@@ -21,11 +88,40 @@ impl DocumentOrmCollection {
     pub fn new(pool: Arc<Pool>) -> Self {
         DocumentOrmCollection { pool }
     }
+
+    async fn set_deleted_at(
+        &self,
+        document: &Document,
+        deleted_at: Option<DateTime<Utc>>,
+    ) -> Result<Document, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+        let user_id = document.user_id.to_string();
+        let document_id = document.id.to_string();
+        let deleted_at = deleted_at.map(|d| d.naive_utc());
+
+        let documents = conn
+            .interact(move |conn| {
+                diesel::update(
+                    documents::table
+                        .filter(documents::id.eq(&document_id))
+                        .filter(documents::user_id.eq(&user_id)),
+                )
+                .set(documents::deleted_at.eq(deleted_at))
+                .execute(conn)?;
+                load_document(conn, document_id)
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        documents
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("Document {} not found", document.id).into())
+    }
 }
 
 #[async_trait]
 impl DocumentRepository for DocumentOrmCollection {
-    async fn get_document(&self, id: Uuid) -> Option<Document> {
+    async fn get_document(&self, user_id: &Uuid, id: Uuid) -> Option<Document> {
         tracing::info!("Retrieving document with ID: {}", id);
         let conn = self
             .pool
@@ -34,27 +130,21 @@ impl DocumentRepository for DocumentOrmCollection {
             .expect("Failed to get DB connection from pool");
 
         let id_str = id.to_string();
+        let user_id_str = user_id.to_string();
         let result = conn
             .interact(move |conn| {
-                documents::table
+                let entity = documents::table
                     .filter(documents::id.eq(id_str))
+                    .filter(documents::user_id.eq(user_id_str))
                     .select(DocumentEntity::as_select())
-                    .get_result(conn)
+                    .get_result(conn)?;
+                with_relations(conn, vec![entity])
             })
             .await;
 
         match result {
             Ok(r) => match r {
-                Ok(entity) => {
-                    let doc_id = Uuid::parse_str(&entity.id).ok()?;
-                    let user_id = Uuid::parse_str(&entity.user_id).ok()?;
-                    Some(Document::with_id(
-                        doc_id,
-                        &entity.title,
-                        &entity.content,
-                        user_id,
-                    ))
-                }
+                Ok(documents) => documents.into_iter().next(),
                 Err(_) => None,
             },
             Err(e) => {
@@ -64,6 +154,43 @@ impl DocumentRepository for DocumentOrmCollection {
         }
     }
 
+    async fn find_document_by_sha256(&self, user_id: &Uuid, sha256: &str) -> Option<Document> {
+        let conn = match self.pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::error!("Error getting DB connection: {}", e);
+                return None;
+            }
+        };
+
+        let user_id_str = user_id.to_string();
+        let sha256 = sha256.to_string();
+        let result = conn
+            .interact(move |conn| {
+                // Only the oldest copy of a file has its hash here, which is unique per user
+                let entities = documents::table
+                    .filter(documents::user_id.eq(user_id_str))
+                    .filter(documents::content_sha256.eq(sha256))
+                    .limit(1)
+                    .select(DocumentEntity::as_select())
+                    .load(conn)?;
+                with_relations(conn, entities)
+            })
+            .await;
+
+        match result {
+            Ok(Ok(documents)) => documents.into_iter().next(),
+            Ok(Err(e)) => {
+                tracing::error!("Error finding document by SHA-256: {}", e);
+                None
+            }
+            Err(e) => {
+                tracing::error!("Error finding document by SHA-256: {}", e);
+                None
+            }
+        }
+    }
+
     async fn get_documents(&self, user_id: &Uuid, limit: &u32) -> Vec<Document> {
         let conn = match self.pool.get().await {
             Ok(conn) => conn,
@@ -78,26 +205,21 @@ impl DocumentRepository for DocumentOrmCollection {
 
         let result = conn
             .interact(move |conn| {
-                documents::table
+                let entities = documents::table
                     .filter(documents::user_id.eq(user_id_str))
+                    .filter(documents::deleted_at.is_null())
                     .limit(limit)
                     .select(DocumentEntity::as_select())
-                    .get_results(conn)
+                    .get_results(conn)?;
+                with_relations(conn, entities)
             })
             .await;
 
         match result {
-            Ok(r) => match r {
-                Ok(entities) => entities
-                    .into_iter()
-                    .filter_map(|e| {
-                        let doc_id = Uuid::parse_str(&e.id).ok()?;
-                        let user_id = Uuid::parse_str(&e.user_id).ok()?;
-                        Some(Document::with_id(doc_id, &e.title, &e.content, user_id))
-                    })
-                    .collect(),
-                Err(_) => vec![],
-            },
+            Ok(r) => r.unwrap_or_else(|e| {
+                tracing::error!("Error querying documents: {}", e);
+                vec![]
+            }),
             Err(e) => {
                 tracing::error!("Error retrieving documents: {}", e);
                 vec![]
@@ -105,12 +227,7 @@ impl DocumentRepository for DocumentOrmCollection {
         }
     }
 
-    async fn get_documents_title_cursor(
-        &self,
-        user_id: &Uuid,
-        limit: &u32,
-        title: &str,
-    ) -> Vec<Document> {
+    async fn list_documents(&self, user_id: &Uuid, listing: &DocumentListing) -> Vec<Document> {
         let conn = match self.pool.get().await {
             Ok(conn) => conn,
             Err(e) => {
@@ -120,33 +237,82 @@ impl DocumentRepository for DocumentOrmCollection {
         };
 
         let user_id_str = user_id.to_string();
-        let limit = *limit as i64;
-        let title = title.to_owned();
+        let limit = listing.limit as i64;
+        let sort = listing.sort;
+        let after = listing
+            .after
+            .as_ref()
+            .map(|cursor| (cursor.sort_key.clone(), cursor.id.to_string()));
+        let tags = listing.tags.clone();
+        let dates = listing.dates;
 
         let result = conn
             .interact(move |conn| {
-                documents::table
-                    .filter(documents::user_id.eq(user_id_str))
-                    .filter(documents::title.gt(title))
-                    .order_by(documents::title.asc())
+                let mut query = documents::table
+                    .filter(documents::user_id.eq(user_id_str.clone()))
+                    .filter(documents::deleted_at.is_null())
+                    .into_boxed();
+                if !tags.is_empty() {
+                    let tagged_with = |names: Vec<String>| {
+                        document_tags::table
+                            .inner_join(tags::table)
+                            .filter(tags::user_id.eq(user_id_str.clone()))
+                            .filter(tags::name.eq_any(names))
+                            .select(document_tags::document_id)
+                    };
+                    match tags.mode {
+                        TagMatch::Any => {
+                            query = query.filter(documents::id.eq_any(tagged_with(tags.tags)));
+                        }
+                        TagMatch::All => {
+                            for tag in tags.tags {
+                                query = query.filter(documents::id.eq_any(tagged_with(vec![tag])));
+                            }
+                        }
+                    }
+                }
+                // Same as Document::date
+                let document_date =
+                    || coalesce(documents::document_date, day_of(documents::created_at));
+                if let Some(from) = dates.from {
+                    query = query.filter(document_date().ge(from));
+                }
+                if let Some(to) = dates.to {
+                    query = query.filter(document_date().le(to));
+                }
+                let after_timestamp = after
+                    .clone()
+                    .and_then(|(key, id)| Some((parse_timestamp_key(&key)?.naive_utc(), id)));
+                query = match sort.field {
+                    DocumentSortField::Title => {
+                        keyset!(query, documents::title, after, sort.direction)
+                    }
+                    DocumentSortField::CreatedAt => keyset!(
+                        query,
+                        documents::created_at,
+                        after_timestamp,
+                        sort.direction
+                    ),
+                    DocumentSortField::UpdatedAt => keyset!(
+                        query,
+                        documents::updated_at,
+                        after_timestamp,
+                        sort.direction
+                    ),
+                };
+                let entities = query
                     .limit(limit)
                     .select(DocumentEntity::as_select())
-                    .get_results(conn)
+                    .get_results(conn)?;
+                with_relations(conn, entities)
             })
             .await;
 
         match result {
-            Ok(r) => match r {
-                Ok(entities) => entities
-                    .into_iter()
-                    .filter_map(|e| {
-                        let doc_id = Uuid::parse_str(&e.id).ok()?;
-                        let user_id = Uuid::parse_str(&e.user_id).ok()?;
-                        Some(Document::with_id(doc_id, &e.title, &e.content, user_id))
-                    })
-                    .collect(),
-                Err(_) => vec![],
-            },
+            Ok(r) => r.unwrap_or_else(|e| {
+                tracing::error!("Error querying documents: {}", e);
+                vec![]
+            }),
             Err(e) => {
                 tracing::error!("Error retrieving documents: {}", e);
                 vec![]
@@ -156,34 +322,55 @@ impl DocumentRepository for DocumentOrmCollection {
 
     async fn save_document(&self, document: Document) -> Result<Document, Box<dyn Error>> {
         let conn = self.pool.get().await?;
-        let new_document = NewDocumentEntity {
-            id: document.id.to_string(),
-            title: document.title.clone(),
-            content: document.content.clone(),
-            user_id: document.user_id.to_string(),
-        };
+        let new_document = NewDocumentEntity::from_document(&document);
+        let words = DocumentWordEntity::from_document(&document);
+        let chunks = DocumentChunkEntity::from_document(&document);
+        let fields = DocumentFieldsEntity::from_document(&document);
+        let classification = DocumentClassificationEntity::from_document(&document);
+        let tag_names = document.tags.clone();
 
         let result = conn
             .interact(move |conn| {
-                diesel::insert_into(documents::table)
-                    .values(&new_document)
-                    .returning(DocumentEntity::as_returning())
-                    .get_result::<DocumentEntity>(conn)
+                conn.transaction(|conn| {
+                    let saved_doc = diesel::insert_into(documents::table)
+                        .values(&new_document)
+                        .returning(DocumentEntity::as_returning())
+                        .get_result::<DocumentEntity>(conn)?;
+                    if let Some(fields) = &fields {
+                        diesel::insert_into(document_fields::table)
+                            .values(fields)
+                            .execute(conn)?;
+                    }
+                    if let Some(classification) = &classification {
+                        diesel::insert_into(document_classifications::table)
+                            .values(classification)
+                            .execute(conn)?;
+                    }
+                    for chunk in words.chunks(WORD_INSERT_BATCH) {
+                        diesel::insert_into(document_words::table)
+                            .values(chunk)
+                            .execute(conn)?;
+                    }
+                    for batch in chunks.chunks(CHUNK_INSERT_BATCH) {
+                        diesel::insert_into(document_chunks::table)
+                            .values(batch)
+                            .execute(conn)?;
+                    }
+                    attach_tags(conn, &saved_doc.user_id, &saved_doc.id, &tag_names)?;
+                    with_relations(conn, vec![saved_doc])
+                })
             })
             .await;
 
         match result {
             Ok(success) => match success {
-                Ok(saved_doc) => {
+                Ok(saved_docs) => {
+                    let saved_doc = saved_docs
+                        .into_iter()
+                        .next()
+                        .ok_or("Saved document has a malformed ID")?;
                     tracing::info!("Document saved with ID: {}", saved_doc.id);
-                    let doc_id = Uuid::parse_str(&saved_doc.id)?;
-                    let user_id = Uuid::parse_str(&saved_doc.user_id)?;
-                    Ok(Document::with_id(
-                        doc_id,
-                        &saved_doc.title,
-                        &saved_doc.content,
-                        user_id,
-                    ))
+                    Ok(saved_doc)
                 }
                 Err(e) => {
                     tracing::error!("Error saving document: {}", e);
@@ -196,4 +383,784 @@ impl DocumentRepository for DocumentOrmCollection {
             }
         }
     }
+
+    async fn update_document(&self, document: Document) -> Result<Document, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+        let user_id = document.user_id.to_string();
+        let document_id = document.id.to_string();
+
+        let documents = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    diesel::update(
+                        documents::table
+                            .filter(documents::id.eq(&document_id))
+                            .filter(documents::user_id.eq(&user_id)),
+                    )
+                    .set((
+                        documents::title.eq(&document.title),
+                        documents::content.eq(&document.content),
+                        documents::document_date.eq(document.document_date),
+                        documents::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .execute(conn)?;
+                    diesel::delete(
+                        document_tags::table.filter(document_tags::document_id.eq(&document_id)),
+                    )
+                    .execute(conn)?;
+                    attach_tags(conn, &user_id, &document_id, &document.tags)?;
+                    delete_unused_tags(conn, &user_id)?;
+                    load_document(conn, document_id)
+                })
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        documents
+            .into_iter()
+            .next()
+            .ok_or_else(|| "Updated document not found".into())
+    }
+
+    async fn trash_document(
+        &self,
+        document: &Document,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<Document, Box<dyn Error>> {
+        self.set_deleted_at(document, Some(deleted_at)).await
+    }
+
+    async fn restore_document(&self, document: &Document) -> Result<Document, Box<dyn Error>> {
+        self.set_deleted_at(document, None).await
+    }
+
+    async fn get_trash(&self, user_id: &Uuid, limit: &u32) -> Vec<Document> {
+        let conn = match self.pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::error!("Could not get db connection for get_trash: {}", e);
+                return vec![];
+            }
+        };
+
+        let user_id_str = user_id.to_string();
+        let limit = *limit as i64;
+
+        let result = conn
+            .interact(move |conn| {
+                let entities = documents::table
+                    .filter(documents::user_id.eq(user_id_str))
+                    .filter(documents::deleted_at.is_not_null())
+                    .order_by(documents::deleted_at.desc())
+                    .limit(limit)
+                    .select(DocumentEntity::as_select())
+                    .get_results(conn)?;
+                with_relations(conn, entities)
+            })
+            .await;
+
+        match result {
+            Ok(r) => r.unwrap_or_else(|e| {
+                tracing::error!("Error querying trash: {}", e);
+                vec![]
+            }),
+            Err(e) => {
+                tracing::error!("Error retrieving trash: {}", e);
+                vec![]
+            }
+        }
+    }
+
+    async fn purge_trash(
+        &self,
+        deleted_before: DateTime<Utc>,
+    ) -> Result<Vec<Document>, Box<dyn Error + Send + Sync>> {
+        let conn = self.pool.get().await?;
+        let cutoff = deleted_before.naive_utc();
+
+        let purged = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    let entities = documents::table
+                        .filter(documents::deleted_at.lt(cutoff))
+                        .select(DocumentEntity::as_select())
+                        .load(conn)?;
+                    let purged = with_relations(conn, entities)?;
+                    let expired = documents::table
+                        .filter(documents::deleted_at.lt(cutoff))
+                        .select(documents::id);
+                    diesel::delete(
+                        document_tags::table.filter(document_tags::document_id.eq_any(expired)),
+                    )
+                    .execute(conn)?;
+                    diesel::delete(
+                        document_words::table.filter(document_words::document_id.eq_any(expired)),
+                    )
+                    .execute(conn)?;
+                    diesel::delete(
+                        document_fields::table.filter(document_fields::document_id.eq_any(expired)),
+                    )
+                    .execute(conn)?;
+                    diesel::delete(
+                        document_classifications::table
+                            .filter(document_classifications::document_id.eq_any(expired)),
+                    )
+                    .execute(conn)?;
+                    diesel::delete(
+                        document_chunks::table.filter(document_chunks::document_id.eq_any(expired)),
+                    )
+                    .execute(conn)?;
+                    diesel::delete(documents::table.filter(documents::deleted_at.lt(cutoff)))
+                        .execute(conn)?;
+                    let used = document_tags::table.select(document_tags::tag_id);
+                    diesel::delete(tags::table.filter(diesel::dsl::not(tags::id.eq_any(used))))
+                        .execute(conn)?;
+                    Ok::<_, diesel::result::Error>(purged)
+                })
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        Ok(purged)
+    }
+
+    async fn references_blob(&self, sha256: &str) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        let conn = self.pool.get().await?;
+        let sha256 = sha256.to_string();
+
+        let referenced = conn
+            .interact(move |conn| {
+                diesel::select(diesel::dsl::exists(
+                    documents::table.filter(
+                        documents::blob_sha256
+                            .eq(&sha256)
+                            .or(documents::thumbnail_sha256.eq(&sha256)),
+                    ),
+                ))
+                .get_result::<bool>(conn)
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        Ok(referenced)
+    }
+
+    async fn add_tags(
+        &self,
+        document: &Document,
+        tags: &[String],
+    ) -> Result<Document, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+        let user_id = document.user_id.to_string();
+        let document_id = document.id.to_string();
+        let tag_names = tags.to_vec();
+
+        let documents = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    attach_tags(conn, &user_id, &document_id, &tag_names)?;
+                    touch(conn, &document_id)?;
+                    load_document(conn, document_id)
+                })
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        documents
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("Document {} not found", document.id).into())
+    }
+
+    async fn remove_tag(&self, document: &Document, tag: &str) -> Result<Document, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+        let user_id = document.user_id.to_string();
+        let document_id = document.id.to_string();
+        let tag = normalize_tag(tag).unwrap_or_default();
+
+        let documents = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    let tag_ids = tags::table
+                        .filter(tags::user_id.eq(&user_id))
+                        .filter(tags::name.eq(&tag))
+                        .select(tags::id);
+                    diesel::delete(
+                        document_tags::table.filter(
+                            document_tags::document_id
+                                .eq(&document_id)
+                                .and(document_tags::tag_id.eq_any(tag_ids)),
+                        ),
+                    )
+                    .execute(conn)?;
+                    delete_unused_tags(conn, &user_id)?;
+                    touch(conn, &document_id)?;
+                    load_document(conn, document_id)
+                })
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        documents
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("Document {} not found", document.id).into())
+    }
+
+    async fn set_classification(
+        &self,
+        document: &Document,
+        classification: &DocumentClassification,
+    ) -> Result<Document, Box<dyn Error>> {
+        let conn = self.pool.get().await?;
+        let user_id = document.user_id.to_string();
+        let document_id = document.id.to_string();
+        let entity =
+            DocumentClassificationEntity::from_classification(&document.id, classification);
+        let tag_names = match classification.status {
+            ClassificationStatus::Suggested => vec![],
+            _ => classification.tags.clone(),
+        };
+
+        let documents = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    diesel::insert_into(document_classifications::table)
+                        .values(&entity)
+                        .on_conflict(document_classifications::document_id)
+                        .do_update()
+                        .set(&entity)
+                        .execute(conn)?;
+                    attach_tags(conn, &user_id, &document_id, &tag_names)?;
+                    touch(conn, &document_id)?;
+                    load_document(conn, document_id)
+                })
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        documents
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("Document {} not found", document.id).into())
+    }
+
+    async fn get_tags(&self, user_id: &Uuid) -> Vec<TagCount> {
+        let conn = match self.pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::error!("Could not get db connection for get_tags: {}", e);
+                return vec![];
+            }
+        };
+
+        let user_id_str = user_id.to_string();
+        let result = conn
+            .interact(move |conn| {
+                tags::table
+                    .inner_join(document_tags::table.inner_join(documents::table))
+                    .filter(tags::user_id.eq(user_id_str))
+                    .filter(documents::deleted_at.is_null())
+                    .group_by(tags::name)
+                    .select((tags::name, count(document_tags::document_id)))
+                    .order_by(tags::name.asc())
+                    .load::<(String, i64)>(conn)
+            })
+            .await;
+
+        match result {
+            Ok(Ok(rows)) => rows
+                .into_iter()
+                .map(|(name, document_count)| TagCount {
+                    name,
+                    document_count,
+                })
+                .collect(),
+            Ok(Err(e)) => {
+                tracing::error!("Error retrieving tags: {}", e);
+                vec![]
+            }
+            Err(e) => {
+                tracing::error!("Error retrieving tags: {}", e);
+                vec![]
+            }
+        }
+    }
+
+    async fn find_similar_documents(
+        &self,
+        document: &Document,
+        max_distance: u32,
+        limit: &u32,
+    ) -> Vec<SimilarDocument> {
+        let Some(fingerprint) = document.text_simhash else {
+            return vec![];
+        };
+        let conn = match self.pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::error!(
+                    "Could not get db connection for find_similar_documents: {}",
+                    e
+                );
+                return vec![];
+            }
+        };
+
+        let user_id_str = document.user_id.to_string();
+        let document_id = document.id.to_string();
+        let limit = *limit as usize;
+        let result = conn
+            .interact(move |conn| {
+                // SQLite cannot count bits, so the distances are computed here. A user's
+                // fingerprints are small enough to load at once.
+                let fingerprints: Vec<(String, Option<i64>)> = documents::table
+                    .filter(documents::user_id.eq(user_id_str))
+                    .filter(documents::deleted_at.is_null())
+                    .filter(documents::text_simhash.is_not_null())
+                    .filter(documents::id.ne(document_id))
+                    .select((documents::id, documents::text_simhash))
+                    .load(conn)?;
+                let mut closest: Vec<(String, u32)> = fingerprints
+                    .into_iter()
+                    .filter_map(|(id, simhash)| {
+                        let distance = hamming_distance(fingerprint, simhash? as u64);
+                        (distance <= max_distance).then_some((id, distance))
+                    })
+                    .collect();
+                closest.sort_by_key(|(_, distance)| *distance);
+                closest.truncate(limit);
+
+                let ids: Vec<String> = closest.iter().map(|(id, _)| id.clone()).collect();
+                let entities = documents::table
+                    .filter(documents::id.eq_any(ids))
+                    .select(DocumentEntity::as_select())
+                    .load(conn)?;
+                let mut documents: HashMap<String, Document> = with_relations(conn, entities)?
+                    .into_iter()
+                    .map(|document| (document.id.to_string(), document))
+                    .collect();
+                Ok::<_, diesel::result::Error>(
+                    closest
+                        .into_iter()
+                        .filter_map(|(id, distance)| {
+                            Some(SimilarDocument {
+                                document: documents.remove(&id)?,
+                                distance,
+                            })
+                        })
+                        .collect(),
+                )
+            })
+            .await;
+
+        match result {
+            Ok(r) => r.unwrap_or_else(|e| {
+                tracing::error!("Error finding similar documents: {}", e);
+                vec![]
+            }),
+            Err(e) => {
+                tracing::error!("Error finding similar documents: {}", e);
+                vec![]
+            }
+        }
+    }
+
+    async fn get_words(&self, document: &Document) -> Vec<OcrWord> {
+        let conn = match self.pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::error!("Could not get db connection for get_words: {}", e);
+                return vec![];
+            }
+        };
+
+        let document_id = document.id.to_string();
+        let result = conn
+            .interact(move |conn| {
+                document_words::table
+                    .filter(document_words::document_id.eq(document_id))
+                    .order_by(document_words::position.asc())
+                    .select(DocumentWordEntity::as_select())
+                    .load::<DocumentWordEntity>(conn)
+            })
+            .await;
+
+        match result {
+            Ok(Ok(rows)) => rows
+                .into_iter()
+                .map(DocumentWordEntity::into_word)
+                .collect(),
+            Ok(Err(e)) => {
+                tracing::error!("Error retrieving words of document {}: {}", document.id, e);
+                vec![]
+            }
+            Err(e) => {
+                tracing::error!("Error retrieving words of document {}: {}", document.id, e);
+                vec![]
+            }
+        }
+    }
+
+    async fn search(&self, user_id: &Uuid, query: &SearchQuery, limit: &u32) -> Vec<SearchHit> {
+        if query.is_empty() {
+            return vec![];
+        }
+        let conn = match self.pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::error!("Could not get db connection for search: {}", e);
+                return vec![];
+            }
+        };
+
+        let user_id_str = user_id.to_string();
+        let fts_query = fts5_match(query);
+        let limit = *limit as i64;
+        tracing::info!("Searching documents with FTS query: {}", fts_query);
+
+        let result = conn
+            .interact(move |conn| {
+                // bm25() is lower for better matches; titles weigh ten times more than the summary
+                // and the extracted text.
+                let rows = diesel::sql_query(
+                    "SELECT documents.*, \
+                         snippet(documents_fts, -1, ?, ?, '…', 16) AS snippet, \
+                         bm25(documents_fts, 0.0, 10.0, 1.0, 1.0) AS rank \
+                     FROM documents_fts \
+                     JOIN documents ON documents.id = documents_fts.document_id \
+                     WHERE documents_fts MATCH ? AND documents.user_id = ? \
+                       AND documents.deleted_at IS NULL \
+                     ORDER BY rank \
+                     LIMIT ?",
+                )
+                .bind::<Text, _>(HIGHLIGHT_START)
+                .bind::<Text, _>(HIGHLIGHT_END)
+                .bind::<Text, _>(fts_query)
+                .bind::<Text, _>(user_id_str)
+                .bind::<BigInt, _>(limit)
+                .load::<SearchRow>(conn)?;
+
+                let ranked: Vec<(String, String, f64)> = rows
+                    .iter()
+                    .map(|row| (row.document.id.clone(), row.snippet.clone(), row.rank))
+                    .collect();
+                let mut documents: HashMap<String, Document> =
+                    with_relations(conn, rows.into_iter().map(|row| row.document).collect())?
+                        .into_iter()
+                        .map(|document| (document.id.to_string(), document))
+                        .collect();
+                Ok::<_, diesel::result::Error>(
+                    ranked
+                        .into_iter()
+                        .filter_map(|(id, snippet, rank)| {
+                            Some(SearchHit {
+                                document: documents.remove(&id)?,
+                                snippet,
+                                score: -rank,
+                            })
+                        })
+                        .collect(),
+                )
+            })
+            .await;
+
+        match result {
+            Ok(r) => r.unwrap_or_else(|e| {
+                tracing::error!("Error searching documents: {}", e);
+                vec![]
+            }),
+            Err(e) => {
+                tracing::error!("Error searching documents: {}", e);
+                vec![]
+            }
+        }
+    }
+
+    async fn save_chunks(
+        &self,
+        document: &Document,
+        chunks: &[DocumentChunk],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let conn = self.pool.get().await?;
+        let document_id = document.id.to_string();
+        let rows = DocumentChunkEntity::from_chunks(&document.id, chunks);
+
+        conn.interact(move |conn| {
+            conn.transaction(|conn| {
+                diesel::delete(
+                    document_chunks::table.filter(document_chunks::document_id.eq(&document_id)),
+                )
+                .execute(conn)?;
+                for batch in rows.chunks(CHUNK_INSERT_BATCH) {
+                    diesel::insert_into(document_chunks::table)
+                        .values(batch)
+                        .execute(conn)?;
+                }
+                Ok::<_, diesel::result::Error>(())
+            })
+        })
+        .await
+        .map_err(|e| e.to_string())??;
+        Ok(())
+    }
+
+    async fn documents_to_embed(
+        &self,
+        model: &str,
+        limit: &u32,
+    ) -> Result<Vec<Document>, Box<dyn Error + Send + Sync>> {
+        let conn = self.pool.get().await?;
+        let model = model.to_string();
+        let limit = *limit as i64;
+
+        let documents = conn
+            .interact(move |conn| {
+                let embedded = document_chunks::table
+                    .filter(document_chunks::model.eq(model))
+                    .select(document_chunks::document_id);
+                let entities = documents::table
+                    .filter(documents::deleted_at.is_null())
+                    .filter(diesel::dsl::not(documents::id.eq_any(embedded)))
+                    .order_by((documents::created_at.asc(), documents::id.asc()))
+                    .limit(limit)
+                    .select(DocumentEntity::as_select())
+                    .get_results(conn)?;
+                with_relations(conn, entities)
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        Ok(documents)
+    }
+
+    async fn semantic_search(
+        &self,
+        user_id: &Uuid,
+        model: &str,
+        query: &[f32],
+        limit: &u32,
+    ) -> Vec<SemanticHit> {
+        let conn = match self.pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::error!("Could not get db connection for semantic_search: {}", e);
+                return vec![];
+            }
+        };
+
+        let user_id_str = user_id.to_string();
+        let model = model.to_string();
+        let query = query.to_vec();
+        let limit = *limit as usize;
+        let result = conn
+            .interact(move |conn| {
+                // SQLite has no vector index, so the similarities are computed here. A user's
+                // chunks are few enough to compare them all.
+                let chunks: Vec<DocumentChunkEntity> = document_chunks::table
+                    .inner_join(documents::table)
+                    .filter(documents::user_id.eq(user_id_str))
+                    .filter(documents::deleted_at.is_null())
+                    .filter(document_chunks::model.eq(model))
+                    .select(DocumentChunkEntity::as_select())
+                    .load(conn)?;
+                let mut best: HashMap<String, (DocumentChunk, f32)> = HashMap::new();
+                for entity in chunks {
+                    let document_id = entity.document_id.clone();
+                    let chunk = entity.into_chunk();
+                    if chunk.embedding.is_empty() {
+                        // Marks a document without text.
+                        continue;
+                    }
+                    let score = cosine_similarity(&query, &chunk.embedding);
+                    if best
+                        .get(&document_id)
+                        .is_none_or(|(_, best_score)| score > *best_score)
+                    {
+                        best.insert(document_id, (chunk, score));
+                    }
+                }
+                let mut closest: Vec<(String, DocumentChunk, f32)> = best
+                    .into_iter()
+                    .map(|(id, (chunk, score))| (id, chunk, score))
+                    .collect();
+                closest.sort_by(|(a_id, _, a), (b_id, _, b)| b.total_cmp(a).then(a_id.cmp(b_id)));
+                closest.truncate(limit);
+
+                let ids: Vec<String> = closest.iter().map(|(id, _, _)| id.clone()).collect();
+                let entities = documents::table
+                    .filter(documents::id.eq_any(ids))
+                    .select(DocumentEntity::as_select())
+                    .load(conn)?;
+                let mut documents: HashMap<String, Document> = with_relations(conn, entities)?
+                    .into_iter()
+                    .map(|document| (document.id.to_string(), document))
+                    .collect();
+                Ok::<_, diesel::result::Error>(
+                    closest
+                        .into_iter()
+                        .filter_map(|(id, chunk, score)| {
+                            Some(SemanticHit {
+                                document: documents.remove(&id)?,
+                                snippet: chunk.text,
+                                page: chunk.page,
+                                score,
+                            })
+                        })
+                        .collect(),
+                )
+            })
+            .await;
+
+        match result {
+            Ok(r) => r.unwrap_or_else(|e| {
+                tracing::error!("Error searching documents by meaning: {}", e);
+                vec![]
+            }),
+            Err(e) => {
+                tracing::error!("Error searching documents by meaning: {}", e);
+                vec![]
+            }
+        }
+    }
+}
+
+#[derive(QueryableByName)]
+struct SearchRow {
+    #[diesel(embed)]
+    document: DocumentEntity,
+    #[diesel(sql_type = Text)]
+    snippet: String,
+    #[diesel(sql_type = Double)]
+    rank: f64,
+}
+
+/// Renders the parsed query in FTS5 syntax. Every word is quoted so it is never read as an
+/// operator; terms are implicitly combined with AND.
+fn fts5_match(query: &SearchQuery) -> String {
+    query
+        .terms
+        .iter()
+        .map(|term| match term {
+            SearchTerm::Word(word) => format!("\"{}\"", word),
+            SearchTerm::Prefix(prefix) => format!("\"{}\"*", prefix),
+            SearchTerm::Phrase(words) => format!("\"{}\"", words.join(" ")),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Marks the document as changed now.
+fn touch(conn: &mut SqliteConnection, id: &str) -> QueryResult<usize> {
+    diesel::update(documents::table.filter(documents::id.eq(id)))
+        .set(documents::updated_at.eq(Utc::now().naive_utc()))
+        .execute(conn)
+}
+
+fn load_document(conn: &mut SqliteConnection, id: String) -> QueryResult<Vec<Document>> {
+    let entities = documents::table
+        .filter(documents::id.eq(id))
+        .select(DocumentEntity::as_select())
+        .get_results(conn)?;
+    with_relations(conn, entities)
+}
+
+/// Maps rows to domain documents, loading the tags, the fields and the classification of all of
+/// them in a query each.
+fn with_relations(
+    conn: &mut SqliteConnection,
+    entities: Vec<DocumentEntity>,
+) -> QueryResult<Vec<Document>> {
+    let ids: Vec<String> = entities.iter().map(|e| e.id.clone()).collect();
+    let mut fields_by_document: HashMap<String, DocumentFieldsEntity> = document_fields::table
+        .filter(document_fields::document_id.eq_any(&ids))
+        .select(DocumentFieldsEntity::as_select())
+        .load(conn)?
+        .into_iter()
+        .map(|fields| (fields.document_id.clone(), fields))
+        .collect();
+    let mut classifications_by_document: HashMap<String, (DocumentClassificationEntity, String)> =
+        document_classifications::table
+            .inner_join(categories::table)
+            .filter(document_classifications::document_id.eq_any(&ids))
+            .select((DocumentClassificationEntity::as_select(), categories::name))
+            .load::<(DocumentClassificationEntity, String)>(conn)?
+            .into_iter()
+            .map(|(classification, category)| {
+                (
+                    classification.document_id.clone(),
+                    (classification, category),
+                )
+            })
+            .collect();
+    let rows = document_tags::table
+        .inner_join(tags::table)
+        .filter(document_tags::document_id.eq_any(ids))
+        .select((document_tags::document_id, tags::name))
+        .order_by(tags::name.asc())
+        .load::<(String, String)>(conn)?;
+    let mut tags_by_document: HashMap<String, Vec<String>> = HashMap::new();
+    for (document_id, name) in rows {
+        tags_by_document.entry(document_id).or_default().push(name);
+    }
+
+    Ok(entities
+        .into_iter()
+        .filter_map(|entity| {
+            let tags = tags_by_document.remove(&entity.id).unwrap_or_default();
+            let fields = fields_by_document.remove(&entity.id);
+            let classification = classifications_by_document.remove(&entity.id);
+            let mut document = entity.into_document()?;
+            document.tags = tags;
+            document.fields = fields.and_then(DocumentFieldsEntity::into_fields);
+            document.classification = classification.and_then(|(classification, category)| {
+                classification.into_classification(category)
+            });
+            Some(document)
+        })
+        .collect())
+}
+
+/// Creates any missing tags for the user and links them to the document.
+fn attach_tags(
+    conn: &mut SqliteConnection,
+    user_id: &str,
+    document_id: &str,
+    names: &[String],
+) -> QueryResult<()> {
+    let names = normalize_tags(names);
+    if names.is_empty() {
+        return Ok(());
+    }
+    let new_tags: Vec<TagEntity> = names
+        .iter()
+        .map(|name| TagEntity {
+            id: Uuid::new_v4().to_string(),
+            user_id: user_id.to_string(),
+            name: name.clone(),
+        })
+        .collect();
+    diesel::insert_or_ignore_into(tags::table)
+        .values(&new_tags)
+        .execute(conn)?;
+
+    let tag_ids: Vec<String> = tags::table
+        .filter(tags::user_id.eq(user_id))
+        .filter(tags::name.eq_any(&names))
+        .select(tags::id)
+        .load(conn)?;
+    let links: Vec<DocumentTagEntity> = tag_ids
+        .into_iter()
+        .map(|tag_id| DocumentTagEntity {
+            document_id: document_id.to_string(),
+            tag_id,
+        })
+        .collect();
+    diesel::insert_or_ignore_into(document_tags::table)
+        .values(&links)
+        .execute(conn)?;
+    Ok(())
+}
+
+fn delete_unused_tags(conn: &mut SqliteConnection, user_id: &str) -> QueryResult<usize> {
+    let used = document_tags::table.select(document_tags::tag_id);
+    diesel::delete(
+        tags::table
+            .filter(tags::user_id.eq(user_id))
+            .filter(diesel::dsl::not(tags::id.eq_any(used))),
+    )
+    .execute(conn)
 }
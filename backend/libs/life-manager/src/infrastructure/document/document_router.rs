@@ -1,16 +1,50 @@
 use axum::{
     Router,
-    routing::{get, post},
+    extract::DefaultBodyLimit,
+    routing::{delete, get, post, put},
 };
 
 use crate::infrastructure::{
     app_state::LifeManagerState,
-    document::document_handler::{create_document, get_document, get_documents_by_title},
+    document::{
+        classification_handler::{accept_classification, choose_classification},
+        document_handler::{
+            create_document, get_document, get_document_file, get_document_text,
+            get_document_thumbnail, list_documents, update_document,
+        },
+        search_handler::{search_documents, semantic_search_documents},
+        similar_handler::get_similar_documents,
+        tag_handler::{add_document_tags, remove_document_tag},
+        trash_handler::{delete_document, get_trash, restore_document},
+        words_handler::get_document_words,
+    },
 };
 
 pub fn document_router() -> Router<LifeManagerState> {
     Router::new()
-        .route("/", post(create_document))
-        .route("/{id}", get(get_document))
-        .route("/", get(get_documents_by_title))
+        // Uploads are streamed to disk and held to `UploadLimits` instead.
+        .route(
+            "/",
+            post(create_document).layer(DefaultBodyLimit::disable()),
+        )
+        .route("/search", get(search_documents))
+        .route("/semantic-search", get(semantic_search_documents))
+        .route("/trash", get(get_trash))
+        .route(
+            "/{id}",
+            get(get_document)
+                .patch(update_document)
+                .delete(delete_document),
+        )
+        .route("/{id}/restore", post(restore_document))
+        .route("/{id}/file", get(get_document_file))
+        .route("/{id}/thumbnail", get(get_document_thumbnail))
+        .route("/{id}/text", get(get_document_text))
+        .route("/{id}/words", get(get_document_words))
+        .route("/{id}/similar", get(get_similar_documents))
+        .route("/{id}/tags", post(add_document_tags))
+        .route("/{id}/tags/{tag}", delete(remove_document_tag))
+        .route("/{id}/classification", put(choose_classification))
+        .route("/{id}/classification/accept", post(accept_classification))
+        .route("/", get(list_documents))
 }
@@ -0,0 +1,251 @@
+use auth::AuthUser;
+use axum::extract::{Query, State};
+use axum::response::IntoResponse;
+use axum::{Json, http::StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::domain::document_chunk::SemanticHit;
+use crate::domain::document_search::{SearchHit, SearchQuery};
+use crate::infrastructure::document::document_dto::DocumentDto;
+use crate::infrastructure::document::document_state::DocumentState;
+
+const SEARCH_LIMIT: u32 = 20;
+/// Documents returned by a semantic search unless the request asks for fewer.
+const SEMANTIC_SEARCH_LIMIT: u32 = 10;
+
+#[derive(Deserialize, Debug, Default)]
+pub struct SearchDocumentsQueryParams {
+    pub q: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct SemanticSearchQueryParams {
+    pub q: Option<String>,
+    /// Number of documents to return, at most [`SEARCH_LIMIT`].
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SearchHitDto {
+    pub document: DocumentDto,
+    pub snippet: String,
+    pub score: f64,
+}
+
+impl SearchHitDto {
+    pub fn from_hit(hit: &SearchHit) -> Self {
+        Self {
+            document: DocumentDto::from_document(&hit.document),
+            snippet: hit.snippet.clone(),
+            score: hit.score,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SemanticHitDto {
+    pub document: DocumentDto,
+    /// The passage of the document closest to the query.
+    pub snippet: String,
+    pub page: Option<u32>,
+    pub score: f32,
+}
+
+impl SemanticHitDto {
+    pub fn from_hit(hit: &SemanticHit) -> Self {
+        Self {
+            document: DocumentDto::from_document(&hit.document),
+            snippet: hit.snippet.clone(),
+            page: hit.page,
+            score: hit.score,
+        }
+    }
+}
+
+/// Full-text search over the user's documents, best matches first. Supports `"quoted phrases"`
+/// and `prefix*` terms; all terms must match.
+pub async fn search_documents(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Query(params): Query<SearchDocumentsQueryParams>,
+) -> impl IntoResponse {
+    let query = SearchQuery::parse(params.q.as_deref().unwrap_or_default());
+    if query.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "query parameter q must contain at least one word" })),
+        );
+    }
+    tracing::info!("Searching documents for user: {} with {:?}", user_id, query);
+    let hits = document_use_cases
+        .document_repository
+        .search(&user_id, &query, &SEARCH_LIMIT)
+        .await;
+    let hits: Vec<SearchHitDto> = hits.iter().map(SearchHitDto::from_hit).collect();
+    (StatusCode::OK, Json(json!(hits)))
+}
+
+/// Search by meaning over the user's documents: the query is embedded and the documents with
+/// the most similar chunks come first, so "car insurance" also finds an "auto policy". Gives
+/// **503** when the query cannot be embedded.
+pub async fn semantic_search_documents(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Query(params): Query<SemanticSearchQueryParams>,
+) -> impl IntoResponse {
+    let query = params.q.as_deref().unwrap_or_default().trim();
+    if query.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "query parameter q must not be empty" })),
+        );
+    }
+    let limit = params
+        .limit
+        .unwrap_or(SEMANTIC_SEARCH_LIMIT)
+        .clamp(1, SEARCH_LIMIT);
+    tracing::info!("Searching documents by meaning for user: {}", user_id);
+    match document_use_cases
+        .semantic_search(user_id, query, limit)
+        .await
+    {
+        Ok(hits) => {
+            let hits: Vec<SemanticHitDto> = hits.iter().map(SemanticHitDto::from_hit).collect();
+            (StatusCode::OK, Json(json!(hits)))
+        }
+        Err(e) => {
+            tracing::error!("Error embedding search query for user {}: {}", user_id, e);
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(json!({ "error": "The embedding model is unavailable" })),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::body::to_bytes;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::application::document_repository::DocumentRepository;
+    use crate::application::document_use_cases::DocumentUseCases;
+    use crate::application::embed_documents_command::EmbedDocumentsCommand;
+    use crate::domain::document::Document;
+    use crate::infrastructure::document::document_collection::DocumentCollection;
+
+    struct GivenSearchableDocuments {
+        auth_user: AuthUser,
+        state: DocumentState,
+    }
+
+    async fn given_searchable_documents() -> GivenSearchableDocuments {
+        let auth_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let repo = DocumentCollection::new();
+        for (title, content) in [
+            ("Power bill", "Electricity due on the 5th"),
+            ("Car insurance", "Renewal of the power steering cover"),
+        ] {
+            repo.save_document(Document::new(title, content, auth_user.user_id))
+                .await
+                .expect("Failed to save document to seed test");
+        }
+        let state = DocumentState(Arc::new(DocumentUseCases {
+            document_repository: Arc::new(repo),
+            ..DocumentUseCases::for_tests()
+        }));
+        GivenSearchableDocuments { auth_user, state }
+    }
+
+    #[tokio::test]
+    async fn test_search_ranks_title_matches_first() {
+        // Given
+        let GivenSearchableDocuments { auth_user, state } = given_searchable_documents().await;
+
+        // When
+        let response = search_documents(
+            auth_user,
+            State(state),
+            Query(SearchDocumentsQueryParams {
+                q: Some("pow*".to_string()),
+            }),
+        )
+        .await
+        .into_response();
+
+        // Then
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read body");
+        let hits: Vec<SearchHitDto> =
+            serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+        let titles: Vec<&str> = hits.iter().map(|h| h.document.title.as_str()).collect();
+        assert_eq!(titles, vec!["Power bill", "Car insurance"]);
+        assert!(hits[1].snippet.contains("<mark>power</mark>"));
+    }
+
+    #[tokio::test]
+    async fn test_search_without_words_is_bad_request() {
+        let GivenSearchableDocuments { auth_user, state } = given_searchable_documents().await;
+
+        let response = search_documents(
+            auth_user,
+            State(state),
+            Query(SearchDocumentsQueryParams {
+                q: Some(" * ".to_string()),
+            }),
+        )
+        .await
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_semantic_search_ranks_the_closest_document_first() {
+        // Given
+        let GivenSearchableDocuments { auth_user, state } = given_searchable_documents().await;
+        EmbedDocumentsCommand::new(state.0.clone())
+            .execute()
+            .await
+            .expect("Failed to embed documents");
+
+        // When
+        let response = semantic_search_documents(
+            auth_user,
+            State(state),
+            Query(SemanticSearchQueryParams {
+                q: Some("insurance renewal for my car".to_string()),
+                limit: Some(1),
+            }),
+        )
+        .await
+        .into_response();
+
+        // Then
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read body");
+        let hits: Vec<SemanticHitDto> =
+            serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].document.title, "Car insurance");
+        assert!(hits[0].snippet.starts_with("Car insurance"));
+        assert!(hits[0].score > 0.0);
+    }
+}
@@ -0,0 +1,143 @@
+use auth::AuthUser;
+use axum::extract::{Path, Query, State};
+use axum::response::IntoResponse;
+use axum::{Json, http::StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::application::document_policy::DocumentAction;
+use crate::domain::document_similarity::{DEFAULT_MAX_DISTANCE, SimilarDocument};
+use crate::infrastructure::document::document_dto::DocumentDto;
+use crate::infrastructure::document::document_state::DocumentState;
+
+const SIMILAR_LIMIT: u32 = 20;
+/// Unrelated texts are about 32 bits apart, so a larger distance matches everything.
+const MAX_DISTANCE_LIMIT: u32 = 32;
+
+#[derive(Deserialize, Debug, Default)]
+pub struct SimilarDocumentsQueryParams {
+    /// Bits the fingerprints may differ in, at most [`MAX_DISTANCE_LIMIT`]. Defaults to
+    /// [`DEFAULT_MAX_DISTANCE`].
+    pub max_distance: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SimilarDocumentDto {
+    pub document: DocumentDto,
+    pub distance: u32,
+}
+
+impl SimilarDocumentDto {
+    pub fn from_similar(similar: &SimilarDocument) -> Self {
+        Self {
+            document: DocumentDto::from_document(&similar.document),
+            distance: similar.distance,
+        }
+    }
+}
+
+/// The user's documents whose extracted text reads almost the same as this document's, closest
+/// first. Documents without extracted text have no similar documents.
+pub async fn get_similar_documents(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<SimilarDocumentsQueryParams>,
+) -> impl IntoResponse {
+    let Some(document) = document_use_cases
+        .find_document(user_id, id, DocumentAction::Read)
+        .await
+    else {
+        return (StatusCode::NOT_FOUND, Json(json!({})));
+    };
+    let max_distance = params
+        .max_distance
+        .unwrap_or(DEFAULT_MAX_DISTANCE)
+        .min(MAX_DISTANCE_LIMIT);
+    tracing::info!(
+        "Finding documents within {} bits of document {}",
+        max_distance,
+        id
+    );
+    let similar = document_use_cases
+        .document_repository
+        .find_similar_documents(&document, max_distance, &SIMILAR_LIMIT)
+        .await;
+    let similar: Vec<SimilarDocumentDto> = similar
+        .iter()
+        .map(SimilarDocumentDto::from_similar)
+        .collect();
+    (StatusCode::OK, Json(json!(similar)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::body::to_bytes;
+
+    use super::*;
+    use crate::application::document_repository::DocumentRepository;
+    use crate::application::document_use_cases::DocumentUseCases;
+    use crate::domain::document::Document;
+    use crate::domain::document_similarity::simhash;
+    use crate::infrastructure::document::document_collection::DocumentCollection;
+
+    const SCAN: &str = "Rent agreement for the flat at 12 Harbour Street between Ms Lee and Mr \
+        Novak. The monthly rent is 950 and is due on the first day of every month. The deposit \
+        is two months of rent. Either party may end the agreement with three months notice.";
+    const GYM: &str = "Welcome to the gym. Your card opens the doors from six in the morning until \
+        ten at night. Towels are free and lockers can be rented at the front desk.";
+
+    #[tokio::test]
+    async fn test_similar_documents_finds_rescans_only() {
+        // Given
+        let auth_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let repo = DocumentCollection::new();
+        let mut saved = Vec::new();
+        for (title, text) in [
+            ("Rent agreement", SCAN.to_string()),
+            (
+                "Rent agreement (rescan)",
+                SCAN.replace("deposit", "dep0sit"),
+            ),
+            ("Gym membership", GYM.to_string()),
+        ] {
+            let mut document = Document::new(title, "", auth_user.user_id);
+            document.text_simhash = simhash(&text);
+            saved.push(repo.save_document(document).await.unwrap());
+        }
+        let state = DocumentState(Arc::new(DocumentUseCases {
+            document_repository: Arc::new(repo),
+            ..DocumentUseCases::for_tests()
+        }));
+
+        // When
+        let response = get_similar_documents(
+            auth_user,
+            State(state),
+            Path(saved[0].id),
+            Query(SimilarDocumentsQueryParams::default()),
+        )
+        .await
+        .into_response();
+
+        // Then
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read body");
+        let similar: Vec<SimilarDocumentDto> =
+            serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+        let titles: Vec<&str> = similar.iter().map(|s| s.document.title.as_str()).collect();
+        assert_eq!(titles, ["Rent agreement (rescan)"]);
+        assert!(similar[0].distance <= DEFAULT_MAX_DISTANCE);
+    }
+}
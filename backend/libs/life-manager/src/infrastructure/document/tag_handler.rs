@@ -0,0 +1,201 @@
+use auth::AuthUser;
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use axum::{Json, http::StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::application::document_policy::DocumentAction;
+use crate::infrastructure::document::document_dto::DocumentDto;
+use crate::infrastructure::document::document_state::DocumentState;
+
+#[derive(Deserialize, Serialize)]
+pub struct AddTagsCommand {
+    pub tags: Vec<String>,
+}
+
+/// Adds tags to one of the user's documents. Tags that do not exist yet are created.
+pub async fn add_document_tags(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+    Json(command): Json<AddTagsCommand>,
+) -> impl IntoResponse {
+    tracing::info!("Adding tags {:?} to document {}", command.tags, id);
+    let repo = document_use_cases.document_repository.clone();
+    let Some(document) = document_use_cases
+        .find_document(user_id, id, DocumentAction::Write)
+        .await
+    else {
+        return (StatusCode::NOT_FOUND, Json(json!({})));
+    };
+    match repo.add_tags(&document, &command.tags).await {
+        Ok(document) => (
+            StatusCode::OK,
+            Json(json!(DocumentDto::from_document(&document))),
+        ),
+        Err(e) => {
+            tracing::error!("Error adding tags to document {}: {}", id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({})))
+        }
+    }
+}
+
+/// Removes a single tag from one of the user's documents.
+pub async fn remove_document_tag(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path((id, tag)): Path<(Uuid, String)>,
+) -> impl IntoResponse {
+    tracing::info!("Removing tag {} from document {}", tag, id);
+    let repo = document_use_cases.document_repository.clone();
+    let Some(document) = document_use_cases
+        .find_document(user_id, id, DocumentAction::Write)
+        .await
+    else {
+        return (StatusCode::NOT_FOUND, Json(json!({})));
+    };
+    match repo.remove_tag(&document, &tag).await {
+        Ok(document) => (
+            StatusCode::OK,
+            Json(json!(DocumentDto::from_document(&document))),
+        ),
+        Err(e) => {
+            tracing::error!("Error removing tag from document {}: {}", id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({})))
+        }
+    }
+}
+
+/// Lists the user's tags with the number of documents carrying each one.
+pub async fn get_tags(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+) -> impl IntoResponse {
+    tracing::info!("Fetching tags for user: {}", user_id);
+    let tags = document_use_cases
+        .document_repository
+        .get_tags(&user_id)
+        .await;
+    (StatusCode::OK, Json(json!(tags)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::application::document_repository::DocumentRepository;
+    use crate::application::document_use_cases::DocumentUseCases;
+    use crate::domain::document::Document;
+    use crate::domain::tag::TagCount;
+    use crate::infrastructure::document::document_collection::DocumentCollection;
+    use crate::infrastructure::test_support::read_json;
+
+    struct GivenTaggableDocument {
+        auth_user: AuthUser,
+        state: DocumentState,
+        document_id: Uuid,
+    }
+
+    async fn given_taggable_document() -> GivenTaggableDocument {
+        let auth_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let repo = DocumentCollection::new();
+        let document = repo
+            .save_document(Document::new("Bill", "Power bill", auth_user.user_id))
+            .await
+            .expect("Failed to save document to seed test");
+        let state = DocumentState(Arc::new(DocumentUseCases {
+            document_repository: Arc::new(repo),
+            ..DocumentUseCases::for_tests()
+        }));
+        GivenTaggableDocument {
+            auth_user,
+            state,
+            document_id: document.id,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_and_remove_tags() {
+        // Given
+        let GivenTaggableDocument {
+            auth_user,
+            state,
+            document_id,
+        } = given_taggable_document().await;
+
+        // When
+        let (status, tagged) = read_json::<DocumentDto>(
+            add_document_tags(
+                auth_user.clone(),
+                State(state.clone()),
+                Path(document_id),
+                Json(AddTagsCommand {
+                    tags: vec!["Bills".to_string(), "power".to_string()],
+                }),
+            )
+            .await,
+        )
+        .await;
+        let (_, untagged) = read_json::<DocumentDto>(
+            remove_document_tag(
+                auth_user.clone(),
+                State(state.clone()),
+                Path((document_id, "power".to_string())),
+            )
+            .await,
+        )
+        .await;
+
+        // Then
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(tagged.tags, vec!["bills".to_string(), "power".to_string()]);
+        assert_eq!(untagged.tags, vec!["bills".to_string()]);
+        let (_, tags) = read_json::<Vec<TagCount>>(get_tags(auth_user, State(state)).await).await;
+        assert_eq!(
+            tags,
+            vec![TagCount {
+                name: "bills".to_string(),
+                document_count: 1
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_tags_to_other_users_document_not_found() {
+        let GivenTaggableDocument {
+            state, document_id, ..
+        } = given_taggable_document().await;
+        let other_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+
+        let response = add_document_tags(
+            other_user,
+            State(state),
+            Path(document_id),
+            Json(AddTagsCommand {
+                tags: vec!["mine".to_string()],
+            }),
+        )
+        .await
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
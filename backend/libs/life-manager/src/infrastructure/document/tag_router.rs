@@ -0,0 +1,7 @@
+use axum::{Router, routing::get};
+
+use crate::infrastructure::{app_state::LifeManagerState, document::tag_handler::get_tags};
+
+pub fn tag_router() -> Router<LifeManagerState> {
+    Router::new().route("/", get(get_tags))
+}
@@ -0,0 +1,190 @@
+use auth::AuthUser;
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use axum::{Json, http::StatusCode};
+use chrono::Utc;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::application::document_policy::DocumentAction;
+use crate::infrastructure::document::document_dto::DocumentDto;
+use crate::infrastructure::document::document_state::DocumentState;
+
+const TRASH_LIMIT: u32 = 100;
+
+/// Moves one of the user's documents to the trash. It is purged once the retention period has
+/// passed unless it is restored first.
+pub async fn delete_document(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!("Moving document {} to the trash", id);
+    let Some(document) = document_use_cases
+        .find_document(user_id, id, DocumentAction::Delete)
+        .await
+    else {
+        return StatusCode::NOT_FOUND;
+    };
+    match document_use_cases
+        .document_repository
+        .trash_document(&document, Utc::now())
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT,
+        Err(e) => {
+            tracing::error!("Error moving document {} to the trash: {}", id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+/// Takes one of the user's documents out of the trash.
+pub async fn restore_document(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!("Restoring document {} from the trash", id);
+    let Some(document) = document_use_cases
+        .find_trashed_document(user_id, id, DocumentAction::Write)
+        .await
+    else {
+        return (StatusCode::NOT_FOUND, Json(json!({})));
+    };
+    match document_use_cases
+        .document_repository
+        .restore_document(&document)
+        .await
+    {
+        Ok(document) => (
+            StatusCode::OK,
+            Json(json!(DocumentDto::from_document(&document))),
+        ),
+        Err(e) => {
+            tracing::error!("Error restoring document {}: {}", id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({})))
+        }
+    }
+}
+
+/// Lists the user's documents in the trash, most recently deleted first.
+pub async fn get_trash(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+) -> impl IntoResponse {
+    tracing::info!("Fetching trash for user: {}", user_id);
+    let documents = document_use_cases
+        .document_repository
+        .get_trash(&user_id, &TRASH_LIMIT)
+        .await;
+    (StatusCode::OK, Json(json!(documents)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::body::to_bytes;
+
+    use super::*;
+    use crate::application::document_repository::DocumentRepository;
+    use crate::application::document_use_cases::DocumentUseCases;
+    use crate::domain::document::Document;
+    use crate::infrastructure::document::document_collection::DocumentCollection;
+
+    struct GivenDocument {
+        auth_user: AuthUser,
+        state: DocumentState,
+        document_id: Uuid,
+    }
+
+    async fn given_document() -> GivenDocument {
+        let auth_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let repo = DocumentCollection::new();
+        let document = repo
+            .save_document(Document::new("Bill", "Power bill", auth_user.user_id))
+            .await
+            .expect("Failed to save document to seed test");
+        let state = DocumentState(Arc::new(DocumentUseCases {
+            document_repository: Arc::new(repo),
+            ..DocumentUseCases::for_tests()
+        }));
+        GivenDocument {
+            auth_user,
+            state,
+            document_id: document.id,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_restore_document() {
+        // Given
+        let GivenDocument {
+            auth_user,
+            state,
+            document_id,
+        } = given_document().await;
+
+        // When
+        let deleted = delete_document(auth_user.clone(), State(state.clone()), Path(document_id))
+            .await
+            .into_response();
+        let trash = get_trash(auth_user.clone(), State(state.clone()))
+            .await
+            .into_response();
+        let deleted_again =
+            delete_document(auth_user.clone(), State(state.clone()), Path(document_id))
+                .await
+                .into_response();
+        let restored = restore_document(auth_user.clone(), State(state.clone()), Path(document_id))
+            .await
+            .into_response();
+
+        // Then
+        assert_eq!(deleted.status(), StatusCode::NO_CONTENT);
+        let bytes = to_bytes(trash.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read body");
+        let trash: Vec<Document> =
+            serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+        assert_eq!(trash.len(), 1);
+        assert!(trash[0].is_trashed());
+        assert_eq!(deleted_again.status(), StatusCode::NOT_FOUND);
+        assert_eq!(restored.status(), StatusCode::OK);
+        let DocumentState(document_use_cases) = state;
+        assert!(
+            document_use_cases
+                .find_document(auth_user.user_id, document_id, DocumentAction::Read)
+                .await
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_document_not_in_trash_not_found() {
+        let GivenDocument {
+            auth_user,
+            state,
+            document_id,
+        } = given_document().await;
+
+        let response = restore_document(auth_user, State(state), Path(document_id))
+            .await
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
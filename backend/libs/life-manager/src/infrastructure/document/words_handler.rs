@@ -0,0 +1,168 @@
+use auth::AuthUser;
+use axum::extract::{Path, Query, State};
+use axum::response::IntoResponse;
+use axum::{Json, http::StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::application::document_policy::DocumentAction;
+use crate::domain::document_text_reader::PageText;
+use crate::domain::ocr_word::{OcrWord, query_terms};
+use crate::infrastructure::document::document_state::DocumentState;
+
+#[derive(Deserialize, Debug, Default)]
+pub struct DocumentWordsQueryParams {
+    /// Only return the words containing one of the query's terms, e.g. those of a search.
+    pub q: Option<String>,
+}
+
+/// Size of the page image word boxes are relative to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PageSizeDto {
+    pub page: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl PageSizeDto {
+    fn from_page(page: &PageText) -> Option<Self> {
+        Some(Self {
+            page: page.page,
+            width: page.width?,
+            height: page.height?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DocumentWordsDto {
+    pub pages: Vec<PageSizeDto>,
+    pub words: Vec<OcrWord>,
+    /// Mean confidence of all the words OCR read, from 0 to 100.
+    pub confidence: Option<f32>,
+    pub needs_review: bool,
+}
+
+/// The words OCR read in a document with their bounding boxes, for highlighting them on the
+/// scan. Documents whose text was not read with OCR have no words.
+pub async fn get_document_words(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<DocumentWordsQueryParams>,
+) -> impl IntoResponse {
+    let Some(document) = document_use_cases
+        .find_document(user_id, id, DocumentAction::Read)
+        .await
+    else {
+        return (StatusCode::NOT_FOUND, Json(json!({})));
+    };
+    tracing::info!("Fetching OCR words of document {}", id);
+    let mut words = document_use_cases
+        .document_repository
+        .get_words(&document)
+        .await;
+    if let Some(q) = params.q {
+        let terms = query_terms(&q);
+        words.retain(|word| word.matches_any(&terms));
+    }
+    let extracted = document.extracted_text.as_ref();
+    let words = DocumentWordsDto {
+        pages: extracted
+            .map(|e| e.pages.iter().filter_map(PageSizeDto::from_page).collect())
+            .unwrap_or_default(),
+        words,
+        confidence: extracted.and_then(|e| e.confidence),
+        needs_review: document.needs_review(),
+    };
+    (StatusCode::OK, Json(json!(words)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::body::to_bytes;
+
+    use super::*;
+    use crate::application::document_repository::DocumentRepository;
+    use crate::application::document_use_cases::DocumentUseCases;
+    use crate::domain::document::Document;
+    use crate::domain::document_text_reader::ExtractedText;
+    use crate::infrastructure::document::document_collection::DocumentCollection;
+
+    fn word(text: &str, left: u32, confidence: f32) -> OcrWord {
+        OcrWord {
+            page: 1,
+            text: text.to_string(),
+            left,
+            top: 40,
+            width: 80,
+            height: 20,
+            confidence,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_document_words_finds_the_boxes_of_a_search_hit() {
+        // Given
+        let auth_user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let repo = DocumentCollection::new();
+        let page = PageText {
+            width: Some(1240),
+            height: Some(1754),
+            ..PageText::read(1, "Invoice total 12.50")
+        };
+        let mut document = Document::new("Invoice", "", auth_user.user_id);
+        document.extracted_text = Some(
+            ExtractedText::from_pages(vec![page], "tesseract", true).with_words(vec![
+                word("Invoice", 10, 70.0),
+                word("total:", 100, 50.0),
+                word("12.50", 200, 30.0),
+            ]),
+        );
+        let saved = repo.save_document(document).await.unwrap();
+        let state = DocumentState(Arc::new(DocumentUseCases {
+            document_repository: Arc::new(repo),
+            ..DocumentUseCases::for_tests()
+        }));
+
+        // When
+        let response = get_document_words(
+            auth_user,
+            State(state),
+            Path(saved.id),
+            Query(DocumentWordsQueryParams {
+                q: Some("Total".to_string()),
+            }),
+        )
+        .await
+        .into_response();
+
+        // Then
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read body");
+        let words: DocumentWordsDto =
+            serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+        assert_eq!(
+            words.pages,
+            [PageSizeDto {
+                page: 1,
+                width: 1240,
+                height: 1754,
+            }]
+        );
+        assert_eq!(words.words, [word("total:", 100, 50.0)]);
+        assert_eq!(words.confidence, Some(50.0));
+        assert!(words.needs_review);
+    }
+}
@@ -1,24 +1,47 @@
 //! Shared PDF text extraction and OCR-needed detection for [`DocumentTextReader`] implementations.
 
-use std::{collections::HashSet, error::Error, io::Write};
+use std::{
+    error::Error,
+    io::{BufReader, Cursor, Read, Seek},
+};
 
-use once_cell::sync::Lazy;
-use tempfile::NamedTempFile;
+use image::{DynamicImage, ImageBuffer, Luma, LumaA, Rgb, Rgba, codecs::png::PngEncoder};
+use tiff::{
+    ColorType,
+    decoder::{Decoder, DecodingResult},
+};
 
-use crate::domain::uploaded_document_input::UploadedDocumentInput;
+use crate::domain::{
+    content_type,
+    document_text_reader::{ExtractedText, PageText},
+    uploaded_document_input::UploadedDocumentInput,
+    uploaded_file::UploadedFile,
+};
 
-static OCR_EXTENSIONS: Lazy<HashSet<&'static str>> =
-    Lazy::new(|| HashSet::from(["png", "jpg", "jpeg", "tiff", "bmp", "gif"]));
+/// Reader name recorded for text embedded in a PDF.
+pub const PDF_TEXT_READER: &str = "pdf-extract";
+
+/// Reader name recorded for plain text uploads, read as they are.
+pub const PLAIN_TEXT_READER: &str = "plain-text";
+
+/// Runs one of the functions below, which parse or decode the whole file, on the blocking
+/// thread pool instead of the async runtime. Its error, or a panic, comes back as a message.
+pub async fn spawn_read<T: Send + 'static>(
+    uploaded_document_input: &UploadedDocumentInput,
+    read: fn(&UploadedDocumentInput) -> Result<T, Box<dyn Error>>,
+) -> Result<T, String> {
+    let uploaded_document_input = uploaded_document_input.clone();
+    tokio::task::spawn_blocking(move || read(&uploaded_document_input).map_err(|e| e.to_string()))
+        .await
+        .map_err(|e| e.to_string())?
+}
 
 /// Attempts to extract embedded text from a PDF without OCR. Returns [`None`] if no text is found.
 pub fn get_text_from_pdf(
     uploaded_document_input: &UploadedDocumentInput,
-) -> Result<Option<String>, Box<dyn Error>> {
-    let mut tmp = NamedTempFile::new()?;
-    tmp.write_all(&uploaded_document_input.file_data)?;
-    tmp.flush()?;
-
-    let text = pdf_extract::extract_text(tmp.path())?;
+) -> Result<Option<ExtractedText>, Box<dyn Error>> {
+    let pages = pdf_extract::extract_text_by_pages(uploaded_document_input.file.path())?;
+    let text = pages.concat();
 
     if text.trim().is_empty() {
         tracing::info!(
@@ -32,11 +55,174 @@ pub fn get_text_from_pdf(
             uploaded_document_input.file_name,
             text
         );
-        Ok(Some(text))
+        Ok(Some(ExtractedText {
+            text,
+            reader: PDF_TEXT_READER.to_string(),
+            page_count: Some(pages.len() as u32),
+            ocr_used: false,
+            pages: pages
+                .iter()
+                .zip(1..)
+                .map(|(text, page)| PageText::read(page, text.as_str()))
+                .collect(),
+            languages: vec![],
+            words: vec![],
+            confidence: None,
+        }))
     }
 }
 
-/// Whether the upload likely needs remote OCR based on its file extension.
+/// Whether the upload is plain text, which is read as it is.
+pub fn is_plain_text(uploaded_document_input: &UploadedDocumentInput) -> bool {
+    uploaded_document_input.detected_mime_type == Some(content_type::TEXT)
+}
+
+/// Reads a plain text upload as UTF-8, replacing invalid sequences.
+pub fn get_text_from_plain_text(
+    uploaded_document_input: &UploadedDocumentInput,
+) -> Result<ExtractedText, Box<dyn Error>> {
+    let data = uploaded_document_input.file.read()?;
+    let text = String::from_utf8_lossy(&data);
+    Ok(ExtractedText::from_pages(
+        vec![PageText::read(1, text)],
+        PLAIN_TEXT_READER,
+        false,
+    ))
+}
+
+/// Whether the upload is an image, whose text can only be read with OCR.
 pub fn needs_ocr(uploaded_document_input: &UploadedDocumentInput) -> bool {
-    OCR_EXTENSIONS.contains(uploaded_document_input.extension.as_str())
+    uploaded_document_input.is_image()
+}
+
+/// Number of pages in a PDF.
+pub fn pdf_page_count(
+    uploaded_document_input: &UploadedDocumentInput,
+) -> Result<u32, Box<dyn Error>> {
+    let pdf = lopdf::Document::load(uploaded_document_input.file.path())?;
+    Ok(pdf.get_pages().len() as u32)
+}
+
+/// Whether the upload is a TIFF, which may hold several pages as frames.
+pub fn is_tiff(uploaded_document_input: &UploadedDocumentInput) -> bool {
+    uploaded_document_input.detected_mime_type == Some(content_type::TIFF)
+}
+
+/// A TIFF frame encoded as a PNG, or why it could not be decoded.
+pub type FramePng = Result<Vec<u8>, String>;
+
+/// Decodes each frame of a TIFF and encodes it as a PNG. A frame that cannot be decoded gives an
+/// error in its place; only a file that is not a TIFF at all fails as a whole.
+pub fn tiff_frames_as_png(file: &UploadedFile) -> Result<Vec<FramePng>, Box<dyn Error>> {
+    let mut decoder = Decoder::new(BufReader::new(file.open()?))?;
+    let mut frames = vec![frame_as_png(&mut decoder)];
+    while decoder.more_images() {
+        decoder.next_image()?;
+        frames.push(frame_as_png(&mut decoder));
+    }
+    Ok(frames)
+}
+
+fn frame_as_png<R: Read + Seek>(decoder: &mut Decoder<R>) -> FramePng {
+    let (width, height) = decoder.dimensions().map_err(|e| e.to_string())?;
+    let color_type = decoder.colortype().map_err(|e| e.to_string())?;
+    let pixels = decoder.read_image().map_err(|e| e.to_string())?;
+    let image = match (color_type, pixels) {
+        (ColorType::Gray(8), DecodingResult::U8(p)) => {
+            ImageBuffer::<Luma<u8>, _>::from_raw(width, height, p).map(DynamicImage::ImageLuma8)
+        }
+        (ColorType::Gray(16), DecodingResult::U16(p)) => {
+            ImageBuffer::<Luma<u16>, _>::from_raw(width, height, p).map(DynamicImage::ImageLuma16)
+        }
+        (ColorType::GrayA(8), DecodingResult::U8(p)) => {
+            ImageBuffer::<LumaA<u8>, _>::from_raw(width, height, p).map(DynamicImage::ImageLumaA8)
+        }
+        (ColorType::RGB(8), DecodingResult::U8(p)) => {
+            ImageBuffer::<Rgb<u8>, _>::from_raw(width, height, p).map(DynamicImage::ImageRgb8)
+        }
+        (ColorType::RGB(16), DecodingResult::U16(p)) => {
+            ImageBuffer::<Rgb<u16>, _>::from_raw(width, height, p).map(DynamicImage::ImageRgb16)
+        }
+        (ColorType::RGBA(8), DecodingResult::U8(p)) => {
+            ImageBuffer::<Rgba<u8>, _>::from_raw(width, height, p).map(DynamicImage::ImageRgba8)
+        }
+        (ColorType::RGBA(16), DecodingResult::U16(p)) => {
+            ImageBuffer::<Rgba<u16>, _>::from_raw(width, height, p).map(DynamicImage::ImageRgba16)
+        }
+        (color_type, _) => return Err(format!("Unsupported TIFF color type {:?}", color_type)),
+    }
+    .ok_or("TIFF frame is smaller than its dimensions")?;
+    let mut png = Cursor::new(Vec::new());
+    image
+        .write_with_encoder(PngEncoder::new(&mut png))
+        .map_err(|e| e.to_string())?;
+    Ok(png.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{GenericImageView, RgbImage};
+    use tiff::encoder::{TiffEncoder, colortype};
+
+    use super::*;
+
+    #[test]
+    fn test_tiff_frames_as_png_splits_every_frame() {
+        let mut tiff = Cursor::new(Vec::new());
+        let mut encoder = TiffEncoder::new(&mut tiff).unwrap();
+        for width in [40, 60] {
+            let frame = RgbImage::new(width, 30);
+            encoder
+                .write_image::<colortype::RGB8>(width, 30, frame.as_raw())
+                .unwrap();
+        }
+
+        let file = UploadedFile::from_bytes(tiff.get_ref()).unwrap();
+
+        let frames = tiff_frames_as_png(&file).unwrap();
+
+        let sizes: Vec<(u32, u32)> = frames
+            .into_iter()
+            .map(|frame| {
+                image::load_from_memory(&frame.unwrap())
+                    .unwrap()
+                    .dimensions()
+            })
+            .collect();
+        assert_eq!(sizes, [(40, 30), (60, 30)]);
+    }
+
+    #[test]
+    fn test_get_text_from_plain_text_reads_the_file() {
+        let input = UploadedDocumentInput::from_bytes(
+            "notes.txt".to_string(),
+            "Caf\u{e9} 3.50\n".as_bytes(),
+            uuid::Uuid::new_v4(),
+        )
+        .unwrap();
+
+        let extracted = get_text_from_plain_text(&input).unwrap();
+
+        assert!(is_plain_text(&input));
+        assert_eq!(extracted.text, "Caf\u{e9} 3.50");
+        assert_eq!(extracted.reader, PLAIN_TEXT_READER);
+        assert_eq!(extracted.page_count, Some(1));
+        assert!(!extracted.ocr_used);
+    }
+
+    #[test]
+    fn test_pdf_page_count() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../tests/resources/hello_world.pdf"
+        );
+        let input = UploadedDocumentInput::new(
+            "hello_world.pdf".to_string(),
+            UploadedFile::existing(path).unwrap(),
+            uuid::Uuid::new_v4(),
+        )
+        .unwrap();
+
+        assert_eq!(pdf_page_count(&input).unwrap(), 1);
+    }
 }
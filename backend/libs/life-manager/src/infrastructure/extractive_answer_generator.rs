@@ -0,0 +1,38 @@
+use std::{collections::HashSet, error::Error};
+
+use async_trait::async_trait;
+
+use crate::domain::{
+    answer_generator::{AnswerGenerator, AnswerStream},
+    assistant_answer::{ContextPassage, words},
+};
+
+/**
+ * Answer generator used when `ASSISTANT` is `extractive`, e.g. in tests: answers with the
+ * sentence of the closest passage that shares most words with the question, citing it, without
+ * a model.
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExtractiveAnswerGenerator;
+
+impl ExtractiveAnswerGenerator {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl AnswerGenerator for ExtractiveAnswerGenerator {
+    async fn answer(
+        &self,
+        question: &str,
+        passages: &[ContextPassage],
+    ) -> Result<AnswerStream, Box<dyn Error + Send + Sync>> {
+        let question_words: HashSet<String> = words(question).collect();
+        let answer = match passages.first() {
+            Some(passage) => format!("{} [1]", passage.quote(&question_words)),
+            None => "I do not know.".to_string(),
+        };
+        Ok(Box::pin(tokio_stream::once(Ok(answer))))
+    }
+}
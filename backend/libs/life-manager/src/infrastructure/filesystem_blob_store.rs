@@ -0,0 +1,205 @@
+use std::{
+    error::Error,
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use tempfile::NamedTempFile;
+
+use crate::domain::{
+    blob_store::{BlobStore, content_address, is_content_address},
+    uploaded_file::UploadedFile,
+};
+
+/**
+ * Stores blobs on the local filesystem under `<root>/<first two hex chars>/<sha256>`.
+ *
+ * Writes go to a temp file in the target directory first and are then renamed into place, so a
+ * crash mid-write never leaves a truncated blob behind.
+ */
+#[derive(Clone, Debug)]
+pub struct FilesystemBlobStore {
+    root: PathBuf,
+}
+
+impl FilesystemBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn blob_path(&self, sha256: &str) -> PathBuf {
+        self.root.join(&sha256[..2]).join(sha256)
+    }
+}
+
+fn write_blob(path: &Path, data: &[u8]) -> io::Result<()> {
+    write_blob_with(path, |tmp| tmp.write_all(data))
+}
+
+/// Writes the blob through `write` into a temp file next to `path` and renames it into place,
+/// unless the blob is already stored.
+fn write_blob_with(
+    path: &Path,
+    write: impl FnOnce(&mut NamedTempFile) -> io::Result<()>,
+) -> io::Result<()> {
+    if path.exists() {
+        return Ok(());
+    }
+    let dir = path.parent().expect("blob path always has a parent");
+    std::fs::create_dir_all(dir)?;
+    let mut tmp = NamedTempFile::new_in(dir)?;
+    write(&mut tmp)?;
+    tmp.flush()?;
+    tmp.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+#[async_trait]
+impl BlobStore for FilesystemBlobStore {
+    async fn put(&self, data: &[u8]) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let sha256 = content_address(data);
+        let path = self.blob_path(&sha256);
+        let data = data.to_vec();
+        tokio::task::spawn_blocking(move || write_blob(&path, &data)).await??;
+        tracing::info!("Stored blob {} under {}", sha256, self.root.display());
+        Ok(sha256)
+    }
+
+    async fn get(&self, sha256: &str) -> Result<Option<Vec<u8>>, Box<dyn Error + Send + Sync>> {
+        if !is_content_address(sha256) {
+            return Err(format!("Invalid blob address: {}", sha256).into());
+        }
+        match tokio::fs::read(self.blob_path(sha256)).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(Box::new(e)),
+        }
+    }
+
+    async fn put_file(&self, file: &UploadedFile) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let file = file.clone();
+        let store = self.clone();
+        let sha256 = tokio::task::spawn_blocking(move || -> io::Result<String> {
+            let sha256 = file.content_address()?;
+            let path = store.blob_path(&sha256);
+            write_blob_with(&path, |tmp| {
+                io::copy(&mut file.open()?, tmp)?;
+                Ok(())
+            })?;
+            Ok(sha256)
+        })
+        .await??;
+        tracing::info!("Stored blob {} under {}", sha256, self.root.display());
+        Ok(sha256)
+    }
+
+    async fn get_file(
+        &self,
+        sha256: &str,
+    ) -> Result<Option<UploadedFile>, Box<dyn Error + Send + Sync>> {
+        if !is_content_address(sha256) {
+            return Err(format!("Invalid blob address: {}", sha256).into());
+        }
+        match UploadedFile::existing(self.blob_path(sha256)) {
+            Ok(file) => Ok(Some(file)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(Box::new(e)),
+        }
+    }
+
+    async fn delete(&self, sha256: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if !is_content_address(sha256) {
+            return Err(format!("Invalid blob address: {}", sha256).into());
+        }
+        match tokio::fs::remove_file(self.blob_path(sha256)).await {
+            Ok(()) => {
+                tracing::info!("Deleted blob {} under {}", sha256, self.root.display());
+                Ok(())
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(Box::new(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_put_and_get() {
+        let dir = TempDir::new().unwrap();
+        let store = FilesystemBlobStore::new(dir.path());
+
+        let sha256 = store.put(b"scanned receipt").await.expect("put");
+
+        assert_eq!(sha256, content_address(b"scanned receipt"));
+        assert!(dir.path().join(&sha256[..2]).join(&sha256).exists());
+        let data = store.get(&sha256).await.expect("get");
+        assert_eq!(data.as_deref(), Some(&b"scanned receipt"[..]));
+    }
+
+    #[tokio::test]
+    async fn test_put_same_bytes_twice_is_idempotent() {
+        let dir = TempDir::new().unwrap();
+        let store = FilesystemBlobStore::new(dir.path());
+
+        let first = store.put(b"same").await.expect("first put");
+        let second = store.put(b"same").await.expect("second put");
+
+        assert_eq!(first, second);
+        let entries = std::fs::read_dir(dir.path().join(&first[..2])).unwrap();
+        assert_eq!(entries.count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_put_file_and_get_file() {
+        let dir = TempDir::new().unwrap();
+        let store = FilesystemBlobStore::new(dir.path());
+        let upload = UploadedFile::from_bytes(b"scanned receipt").unwrap();
+
+        let sha256 = store.put_file(&upload).await.expect("put_file");
+
+        assert_eq!(sha256, content_address(b"scanned receipt"));
+        drop(upload);
+        let file = store.get_file(&sha256).await.expect("get_file").unwrap();
+        assert_eq!(file.read().unwrap(), b"scanned receipt");
+        drop(file);
+        assert!(store.get(&sha256).await.expect("get").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_missing_blob() {
+        let dir = TempDir::new().unwrap();
+        let store = FilesystemBlobStore::new(dir.path());
+
+        let data = store.get(&content_address(b"missing")).await.expect("get");
+
+        assert!(data.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_blob() {
+        let dir = TempDir::new().unwrap();
+        let store = FilesystemBlobStore::new(dir.path());
+        let sha256 = store.put(b"scanned receipt").await.expect("put");
+
+        store.delete(&sha256).await.expect("delete");
+        store.delete(&sha256).await.expect("delete again");
+
+        assert!(store.get(&sha256).await.expect("get").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_rejects_non_hash_keys() {
+        let dir = TempDir::new().unwrap();
+        let store = FilesystemBlobStore::new(dir.path());
+
+        let result = store.get("../../etc/passwd").await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+
+use crate::domain::{document_similarity::feature_hash, embedding_provider::EmbeddingProvider};
+
+/// Length of the vectors.
+const DIMENSIONS: usize = 256;
+
+/**
+ * Embedding provider used when `EMBEDDING_PROVIDER` is `hashing`, e.g. in tests: hashes the
+ * words of the text into a fixed number of dimensions, so texts that share words end up close.
+ * It needs no model but knows nothing of synonyms; "auto policy" is only close to "car
+ * insurance" with a real embedding model.
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HashingEmbeddingProvider;
+
+impl HashingEmbeddingProvider {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn embed_text(text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0; DIMENSIONS];
+        for word in text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+        {
+            let hash = feature_hash(&word.to_lowercase());
+            let sign = if hash & 1 == 0 { 1.0 } else { -1.0 };
+            vector[(hash >> 1) as usize % DIMENSIONS] += sign;
+        }
+        vector
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for HashingEmbeddingProvider {
+    fn model(&self) -> &str {
+        "hashing-256"
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, Box<dyn Error + Send + Sync>> {
+        Ok(texts.iter().map(|text| Self::embed_text(text)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::document_chunk::cosine_similarity;
+
+    #[tokio::test]
+    async fn test_texts_sharing_words_are_closer() {
+        let provider = HashingEmbeddingProvider::new();
+
+        let embeddings = provider
+            .embed(&[
+                "Car insurance renewal".to_string(),
+                "Your car insurance policy".to_string(),
+                "Chocolate cake recipe".to_string(),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(embeddings[0].len(), DIMENSIONS);
+        assert!(
+            cosine_similarity(&embeddings[0], &embeddings[1])
+                > cosine_similarity(&embeddings[0], &embeddings[2])
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::{collections::HashMap, error::Error};
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use crate::domain::{
+    blob_store::{BlobStore, content_address},
+    uploaded_file::UploadedFile,
+};
+
+/// In-memory [`BlobStore`] used by tests and setups without a writable data directory.
+#[derive(Default)]
+pub struct InMemoryBlobStore {
+    pub blobs: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl InMemoryBlobStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl BlobStore for InMemoryBlobStore {
+    async fn put(&self, data: &[u8]) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let sha256 = content_address(data);
+        self.blobs
+            .lock()
+            .await
+            .entry(sha256.clone())
+            .or_insert_with(|| data.to_vec());
+        Ok(sha256)
+    }
+
+    async fn get(&self, sha256: &str) -> Result<Option<Vec<u8>>, Box<dyn Error + Send + Sync>> {
+        Ok(self.blobs.lock().await.get(sha256).cloned())
+    }
+
+    async fn put_file(&self, file: &UploadedFile) -> Result<String, Box<dyn Error + Send + Sync>> {
+        self.put(&tokio::fs::read(file.path()).await?).await
+    }
+
+    async fn get_file(
+        &self,
+        sha256: &str,
+    ) -> Result<Option<UploadedFile>, Box<dyn Error + Send + Sync>> {
+        match self.get(sha256).await? {
+            Some(data) => Ok(Some(UploadedFile::from_bytes(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn delete(&self, sha256: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.blobs.lock().await.remove(sha256);
+        Ok(())
+    }
+}
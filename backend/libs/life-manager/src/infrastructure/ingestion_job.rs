@@ -0,0 +1,8 @@
+pub mod ingestion_event_dto;
+pub mod ingestion_event_handler;
+pub mod ingestion_job_collection;
+pub mod ingestion_job_dto;
+pub mod ingestion_job_entity;
+pub mod ingestion_job_handler;
+pub mod ingestion_job_orm_collection;
+pub mod ingestion_job_router;
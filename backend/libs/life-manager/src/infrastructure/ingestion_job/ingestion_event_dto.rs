@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::ingestion_event::{IngestionEvent, IngestionStage};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IngestionEventDto {
+    pub job_id: Uuid,
+    pub file_name: String,
+    pub stage: IngestionStage,
+    /// The created document, once it is saved.
+    pub document_id: Option<Uuid>,
+    /// Likely duplicates of the saved document.
+    pub similar_document_ids: Vec<Uuid>,
+    pub error: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+impl IngestionEventDto {
+    pub fn from_event(event: &IngestionEvent) -> Self {
+        Self {
+            job_id: event.job_id,
+            file_name: event.file_name.clone(),
+            stage: event.stage,
+            document_id: event.document_id,
+            similar_document_ids: event.similar_document_ids.clone(),
+            error: event.error.clone(),
+            at: event.at,
+        }
+    }
+}
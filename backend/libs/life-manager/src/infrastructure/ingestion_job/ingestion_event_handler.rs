@@ -0,0 +1,110 @@
+use std::convert::Infallible;
+
+use auth::AuthUser;
+use axum::extract::State;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::infrastructure::document::document_state::DocumentState;
+use crate::infrastructure::ingestion_job::ingestion_event_dto::IngestionEventDto;
+
+/// Server-sent events with the progress of the user's uploads while the stream is open. Events
+/// are named after their stage (`received`, `text_extracted`, `summarizing`, `saved`, `failed`)
+/// and carry an [`IngestionEventDto`].
+pub async fn stream_ingestion_events(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    tracing::info!("Streaming ingestion events for user: {}", user_id);
+    let events = BroadcastStream::new(document_use_cases.ingestion_queue.events.subscribe())
+        .filter_map(move |event| match event {
+            Ok(event) if event.user_id == user_id => {
+                match Event::default()
+                    .event(event.stage.as_str())
+                    .json_data(IngestionEventDto::from_event(&event))
+                {
+                    Ok(sse_event) => Some(Ok(sse_event)),
+                    Err(e) => {
+                        tracing::error!("Error serializing ingestion event: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(_) => None,
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                tracing::warn!(
+                    "Ingestion event stream of user {} skipped {} events",
+                    user_id,
+                    skipped
+                );
+                None
+            }
+        });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::response::IntoResponse;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::application::document_use_cases::DocumentUseCases;
+    use crate::domain::blob_store::DocumentBlob;
+    use crate::domain::ingestion_event::{IngestionEvent, IngestionStage};
+    use crate::domain::ingestion_job::IngestionJob;
+    use crate::infrastructure::ollama_document_summarizer_adapter::OllamaDocumentSummarizerAdapter;
+
+    fn job_of(user_id: Uuid) -> IngestionJob {
+        IngestionJob::new(
+            user_id,
+            DocumentBlob {
+                sha256: "abc".to_string(),
+                file_name: "bill.pdf".to_string(),
+                mime_type: "application/pdf".to_string(),
+                size_bytes: 3,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_stream_only_carries_the_users_own_events() {
+        // Given
+        let user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let document_use_cases = Arc::new(DocumentUseCases {
+            summarizer: Arc::new(OllamaDocumentSummarizerAdapter::new(None)),
+            ..DocumentUseCases::for_tests()
+        });
+        let response = stream_ingestion_events(
+            user.clone(),
+            State(DocumentState(document_use_cases.clone())),
+        )
+        .await
+        .into_response();
+        let mut body = response.into_body().into_data_stream();
+        let events = &document_use_cases.ingestion_queue.events;
+        let others_job = job_of(Uuid::new_v4());
+        let own_job = job_of(user.user_id);
+
+        // When
+        events.publish(IngestionEvent::new(&others_job, IngestionStage::Received));
+        events.publish(IngestionEvent::new(&own_job, IngestionStage::TextExtracted));
+
+        // Then
+        let frame = body.next().await.unwrap().unwrap();
+        let frame = String::from_utf8(frame.to_vec()).unwrap();
+        assert!(frame.starts_with("event: text_extracted\n"), "{frame}");
+        assert!(frame.contains(&own_job.id.to_string()), "{frame}");
+        assert!(!frame.contains(&others_job.id.to_string()), "{frame}");
+    }
+}
@@ -0,0 +1,127 @@
+use std::{cmp::Reverse, error::Error};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::{
+    application::ingestion_job_repository::IngestionJobRepository,
+    domain::ingestion_job::{IngestionJob, IngestionJobStatus},
+};
+
+/// In-memory [`IngestionJobRepository`] used by tests.
+#[derive(Default)]
+pub struct IngestionJobCollection {
+    pub jobs: Mutex<Vec<IngestionJob>>,
+}
+
+impl IngestionJobCollection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl IngestionJobRepository for IngestionJobCollection {
+    async fn save_job(
+        &self,
+        job: IngestionJob,
+    ) -> Result<IngestionJob, Box<dyn Error + Send + Sync>> {
+        self.jobs.lock().await.push(job.clone());
+        Ok(job)
+    }
+
+    async fn get_job(&self, user_id: &Uuid, id: Uuid) -> Option<IngestionJob> {
+        self.jobs
+            .lock()
+            .await
+            .iter()
+            .find(|job| job.id == id && job.user_id == *user_id)
+            .cloned()
+    }
+
+    async fn find_pending_job(&self, user_id: &Uuid, sha256: &str) -> Option<IngestionJob> {
+        self.jobs
+            .lock()
+            .await
+            .iter()
+            .filter(|job| job.user_id == *user_id && job.blob.sha256 == sha256)
+            .filter(|job| {
+                matches!(
+                    job.status,
+                    IngestionJobStatus::Queued | IngestionJobStatus::Running
+                )
+            })
+            .min_by_key(|job| job.created_at)
+            .cloned()
+    }
+
+    async fn list_jobs(
+        &self,
+        user_id: &Uuid,
+        status: Option<IngestionJobStatus>,
+        limit: &u32,
+    ) -> Vec<IngestionJob> {
+        let mut jobs: Vec<IngestionJob> = self
+            .jobs
+            .lock()
+            .await
+            .iter()
+            .filter(|job| job.user_id == *user_id)
+            .filter(|job| status.is_none_or(|status| job.status == status))
+            .cloned()
+            .collect();
+        jobs.sort_by_key(|job| Reverse(job.updated_at));
+        jobs.truncate(*limit as usize);
+        jobs
+    }
+
+    async fn claim_next_job(&self) -> Result<Option<IngestionJob>, Box<dyn Error + Send + Sync>> {
+        let now = Utc::now();
+        let mut jobs = self.jobs.lock().await;
+        let next = jobs
+            .iter_mut()
+            .filter(|job| job.status == IngestionJobStatus::Queued && job.next_attempt_at <= now)
+            .min_by_key(|job| job.created_at);
+        Ok(next.map(|job| {
+            job.status = IngestionJobStatus::Running;
+            job.attempts += 1;
+            job.updated_at = now;
+            job.clone()
+        }))
+    }
+
+    async fn update_job(&self, job: &IngestionJob) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut jobs = self.jobs.lock().await;
+        let stored = jobs
+            .iter_mut()
+            .find(|stored| stored.id == job.id)
+            .ok_or_else(|| format!("Ingestion job {} not found", job.id))?;
+        *stored = job.clone();
+        Ok(())
+    }
+
+    async fn requeue_running_jobs(&self) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let mut jobs = self.jobs.lock().await;
+        let mut requeued = 0;
+        for job in jobs
+            .iter_mut()
+            .filter(|job| job.status == IngestionJobStatus::Running)
+        {
+            job.status = IngestionJobStatus::Queued;
+            job.updated_at = Utc::now();
+            requeued += 1;
+        }
+        Ok(requeued)
+    }
+
+    async fn references_blob(&self, sha256: &str) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        Ok(self
+            .jobs
+            .lock()
+            .await
+            .iter()
+            .any(|job| job.blob.sha256 == sha256 && job.status != IngestionJobStatus::Succeeded))
+    }
+}
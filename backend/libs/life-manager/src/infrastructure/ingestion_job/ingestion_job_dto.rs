@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    application::document_use_cases::UploadOutcome,
+    domain::ingestion_job::{IngestionJob, IngestionJobStatus},
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IngestionJobDto {
+    pub id: Uuid,
+    pub status: IngestionJobStatus,
+    pub file_name: String,
+    /// The created document, once the job has succeeded.
+    pub document_id: Option<Uuid>,
+    /// Existing documents that read almost the same as the created one, likely duplicates the
+    /// app can offer to merge.
+    pub similar_document_ids: Vec<Uuid>,
+    /// Why the last attempt failed.
+    pub error: Option<String>,
+    /// Preprocessing steps chosen for the upload, e.g. `grayscale,binarize`.
+    #[serde(default)]
+    pub preprocessing: Option<String>,
+    /// OCR languages chosen for the upload, e.g. `["eng", "deu"]`. Left out, they are detected.
+    #[serde(default)]
+    pub languages: Option<Vec<String>>,
+    pub attempts: u32,
+    /// When a queued job will be run again after a retryable failure.
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl IngestionJobDto {
+    pub fn from_job(job: &IngestionJob) -> Self {
+        Self {
+            id: job.id,
+            status: job.status,
+            file_name: job.blob.file_name.clone(),
+            document_id: job.document_id,
+            similar_document_ids: job.similar_document_ids.clone(),
+            error: job.error.clone(),
+            preprocessing: job.preprocessing.as_ref().map(|chain| chain.to_spec()),
+            languages: job
+                .languages
+                .as_ref()
+                .map(|languages| languages.codes().to_vec()),
+            attempts: job.attempts,
+            next_attempt_at: job.next_attempt_at,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+        }
+    }
+}
+
+/// Outcome of one file of a bulk upload: the job or existing document that handles it, and why
+/// the file was not accepted if it was not.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UploadResultDto {
+    pub file_name: String,
+    pub job: Option<IngestionJobDto>,
+    /// The existing document of a duplicate file.
+    pub document_id: Option<Uuid>,
+    pub error: Option<String>,
+}
+
+impl UploadResultDto {
+    pub fn from_outcome(file_name: String, outcome: &UploadOutcome, error: Option<String>) -> Self {
+        let (job, document_id) = match outcome {
+            UploadOutcome::Queued(job) | UploadOutcome::AlreadyQueued(job) => {
+                (Some(IngestionJobDto::from_job(job)), None)
+            }
+            UploadOutcome::Duplicate(document) => (None, Some(document.id)),
+        };
+        Self {
+            file_name,
+            job,
+            document_id,
+            error,
+        }
+    }
+
+    pub fn rejected(file_name: String, error: String) -> Self {
+        Self {
+            file_name,
+            job: None,
+            document_id: None,
+            error: Some(error),
+        }
+    }
+
+    /// Whether the file was rejected because the user already has it.
+    pub fn is_duplicate(&self) -> bool {
+        self.error.is_some() && (self.job.is_some() || self.document_id.is_some())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BulkUploadDto {
+    pub results: Vec<UploadResultDto>,
+}
@@ -0,0 +1,93 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::domain::{
+    blob_store::DocumentBlob,
+    image_preprocessing::PreprocessingChain,
+    ingestion_job::{IngestionJob, IngestionJobStatus},
+    ocr_language::OcrLanguages,
+};
+
+#[derive(Insertable, Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::ingestion_jobs)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct IngestionJobEntity {
+    pub id: String,
+    pub user_id: String,
+    pub status: String,
+    pub blob_sha256: String,
+    pub file_name: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub document_id: Option<String>,
+    pub error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub attempts: i32,
+    pub next_attempt_at: NaiveDateTime,
+    /// JSON array of document IDs.
+    pub similar_document_ids: String,
+    /// Step names as read by [`PreprocessingChain::parse`].
+    pub preprocessing: Option<String>,
+    /// Languages as read by [`OcrLanguages::parse`], e.g. `eng+deu`.
+    pub ocr_languages: Option<String>,
+}
+
+impl IngestionJobEntity {
+    pub fn from_job(job: &IngestionJob) -> Self {
+        Self {
+            id: job.id.to_string(),
+            user_id: job.user_id.to_string(),
+            status: job.status.as_str().to_string(),
+            blob_sha256: job.blob.sha256.clone(),
+            file_name: job.blob.file_name.clone(),
+            mime_type: job.blob.mime_type.clone(),
+            size_bytes: job.blob.size_bytes as i64,
+            document_id: job.document_id.map(|id| id.to_string()),
+            error: job.error.clone(),
+            created_at: job.created_at.naive_utc(),
+            updated_at: job.updated_at.naive_utc(),
+            attempts: job.attempts as i32,
+            next_attempt_at: job.next_attempt_at.naive_utc(),
+            similar_document_ids: serde_json::to_string(&job.similar_document_ids)
+                .unwrap_or_else(|_| "[]".to_string()),
+            preprocessing: job.preprocessing.as_ref().map(PreprocessingChain::to_spec),
+            ocr_languages: job.languages.as_ref().map(OcrLanguages::to_spec),
+        }
+    }
+
+    /// Maps a row to the domain model. Returns [`None`] if a stored UUID, status, ID list,
+    /// preprocessing chain or language list is malformed.
+    pub fn into_job(self) -> Option<IngestionJob> {
+        Some(IngestionJob {
+            id: Uuid::parse_str(&self.id).ok()?,
+            user_id: Uuid::parse_str(&self.user_id).ok()?,
+            status: IngestionJobStatus::parse(&self.status)?,
+            blob: DocumentBlob {
+                sha256: self.blob_sha256,
+                file_name: self.file_name,
+                mime_type: self.mime_type,
+                size_bytes: self.size_bytes as u64,
+            },
+            document_id: match self.document_id {
+                Some(id) => Some(Uuid::parse_str(&id).ok()?),
+                None => None,
+            },
+            error: self.error,
+            similar_document_ids: serde_json::from_str(&self.similar_document_ids).ok()?,
+            preprocessing: match self.preprocessing {
+                Some(spec) => Some(PreprocessingChain::parse(&spec).ok()?),
+                None => None,
+            },
+            languages: match self.ocr_languages {
+                Some(spec) => Some(OcrLanguages::parse(&spec).ok()?),
+                None => None,
+            },
+            attempts: self.attempts as u32,
+            next_attempt_at: self.next_attempt_at.and_utc(),
+            created_at: self.created_at.and_utc(),
+            updated_at: self.updated_at.and_utc(),
+        })
+    }
+}
@@ -0,0 +1,239 @@
+use auth::AuthUser;
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use axum::{Json, http::StatusCode};
+use axum_extra::extract::Query;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::domain::ingestion_job::IngestionJobStatus;
+use crate::infrastructure::document::document_state::DocumentState;
+use crate::infrastructure::ingestion_job::ingestion_job_dto::IngestionJobDto;
+
+/// Maximum number of jobs in a listing.
+const JOB_LIST_LIMIT: u32 = 100;
+
+#[derive(Deserialize, Debug, Default)]
+pub struct ListIngestionJobsQueryParams {
+    /// Only jobs with this status, e.g. `failed`.
+    pub status: Option<IngestionJobStatus>,
+}
+
+/// Lists the user's ingestion jobs, most recently updated first. `?status=failed` lists the
+/// uploads that could not be turned into documents.
+pub async fn list_ingestion_jobs(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Query(params): Query<ListIngestionJobsQueryParams>,
+) -> impl IntoResponse {
+    tracing::info!("Listing ingestion jobs for user: {}", user_id);
+    let jobs: Vec<IngestionJobDto> = document_use_cases
+        .ingestion_queue
+        .jobs
+        .list_jobs(&user_id, params.status, &JOB_LIST_LIMIT)
+        .await
+        .iter()
+        .map(IngestionJobDto::from_job)
+        .collect();
+    (StatusCode::OK, Json(json!(jobs)))
+}
+
+/// Status of one of the user's ingestion jobs. Poll it after an upload until the status is
+/// `succeeded`, which carries the `document_id`, or `failed`, which carries the `error`.
+pub async fn get_ingestion_job(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!("Fetching ingestion job with ID: {}", id);
+    match document_use_cases
+        .ingestion_queue
+        .jobs
+        .get_job(&user_id, id)
+        .await
+    {
+        Some(job) => (StatusCode::OK, Json(json!(IngestionJobDto::from_job(&job)))),
+        None => (StatusCode::NOT_FOUND, Json(json!({}))),
+    }
+}
+
+/// Queues a failed job again with a fresh set of attempts. Jobs that have not failed answer
+/// **409**.
+pub async fn retry_ingestion_job(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!("Retrying ingestion job with ID: {}", id);
+    let queue = &document_use_cases.ingestion_queue;
+    let Some(job) = queue.jobs.get_job(&user_id, id).await else {
+        return (StatusCode::NOT_FOUND, Json(json!({})));
+    };
+    if job.status != IngestionJobStatus::Failed {
+        let error = format!(
+            "Job is {}, only failed jobs can be retried",
+            job.status.as_str()
+        );
+        return (StatusCode::CONFLICT, Json(json!({ "error": error })));
+    }
+    match queue.retry(job).await {
+        Ok(job) => (
+            StatusCode::ACCEPTED,
+            Json(json!(IngestionJobDto::from_job(&job))),
+        ),
+        Err(e) => {
+            tracing::error!("Error retrying ingestion job {}: {}", id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({})))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::body::to_bytes;
+
+    use super::*;
+    use crate::application::document_use_cases::DocumentUseCases;
+    use crate::application::ingestion_queue::IngestionQueue;
+    use crate::domain::blob_store::DocumentBlob;
+    use crate::domain::ingestion_job::{IngestionJob, IngestionJobStatus};
+    use crate::infrastructure::ingestion_job::ingestion_job_collection::IngestionJobCollection;
+    use crate::infrastructure::ollama_document_summarizer_adapter::OllamaDocumentSummarizerAdapter;
+
+    #[tokio::test]
+    async fn test_get_ingestion_job_is_private_to_its_owner() {
+        // Given
+        let owner = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let intruder = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let queue = IngestionQueue::new(Arc::new(IngestionJobCollection::new()));
+        let job = queue
+            .enqueue(IngestionJob::new(
+                owner.user_id,
+                DocumentBlob {
+                    sha256: "abc".to_string(),
+                    file_name: "bill.pdf".to_string(),
+                    mime_type: "application/pdf".to_string(),
+                    size_bytes: 3,
+                },
+            ))
+            .await
+            .unwrap();
+        let document_use_cases = Arc::new(DocumentUseCases {
+            summarizer: Arc::new(OllamaDocumentSummarizerAdapter::new(None)),
+            ingestion_queue: queue,
+            ..DocumentUseCases::for_tests()
+        });
+
+        // When
+        let owned = get_ingestion_job(
+            owner,
+            State(DocumentState(document_use_cases.clone())),
+            Path(job.id),
+        )
+        .await
+        .into_response();
+        let other = get_ingestion_job(
+            intruder,
+            State(DocumentState(document_use_cases.clone())),
+            Path(job.id),
+        )
+        .await
+        .into_response();
+
+        // Then
+        assert_eq!(owned.status(), StatusCode::OK);
+        let bytes = to_bytes(owned.into_body(), usize::MAX).await.unwrap();
+        let dto: IngestionJobDto = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(dto.id, job.id);
+        assert_eq!(dto.status, IngestionJobStatus::Queued);
+        assert_eq!(dto.file_name, "bill.pdf");
+        assert_eq!(other.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_failed_jobs_are_listed_and_can_be_retried() {
+        // Given
+        let user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let queue = IngestionQueue::new(Arc::new(IngestionJobCollection::new()));
+        let blob = DocumentBlob {
+            sha256: "abc".to_string(),
+            file_name: "bill.pdf".to_string(),
+            mime_type: "application/pdf".to_string(),
+            size_bytes: 3,
+        };
+        let queued = queue
+            .enqueue(IngestionJob::new(user.user_id, blob.clone()))
+            .await
+            .unwrap();
+        let mut failed = IngestionJob::new(user.user_id, blob);
+        failed.attempts = 5;
+        failed.fail("Ollama timed out".to_string());
+        let failed = queue.enqueue(failed).await.unwrap();
+        let document_use_cases = Arc::new(DocumentUseCases {
+            summarizer: Arc::new(OllamaDocumentSummarizerAdapter::new(None)),
+            ingestion_queue: queue,
+            ..DocumentUseCases::for_tests()
+        });
+
+        // When
+        let listed = list_ingestion_jobs(
+            user.clone(),
+            State(DocumentState(document_use_cases.clone())),
+            Query(ListIngestionJobsQueryParams {
+                status: Some(IngestionJobStatus::Failed),
+            }),
+        )
+        .await
+        .into_response();
+        let retried_queued = retry_ingestion_job(
+            user.clone(),
+            State(DocumentState(document_use_cases.clone())),
+            Path(queued.id),
+        )
+        .await
+        .into_response();
+        let retried_failed = retry_ingestion_job(
+            user,
+            State(DocumentState(document_use_cases.clone())),
+            Path(failed.id),
+        )
+        .await
+        .into_response();
+
+        // Then
+        let bytes = to_bytes(listed.into_body(), usize::MAX).await.unwrap();
+        let listed: Vec<IngestionJobDto> = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, failed.id);
+        assert_eq!(listed[0].error.as_deref(), Some("Ollama timed out"));
+        assert_eq!(retried_queued.status(), StatusCode::CONFLICT);
+        assert_eq!(retried_failed.status(), StatusCode::ACCEPTED);
+        let bytes = to_bytes(retried_failed.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let retried: IngestionJobDto = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(retried.status, IngestionJobStatus::Queued);
+        assert_eq!(retried.attempts, 0);
+    }
+}
@@ -0,0 +1,280 @@
+use std::error::Error;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use deadpool_diesel::sqlite::Pool;
+use diesel::{
+    Connection, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SelectableHelper,
+};
+use uuid::Uuid;
+
+use crate::{
+    application::ingestion_job_repository::IngestionJobRepository,
+    domain::ingestion_job::{IngestionJob, IngestionJobStatus},
+    infrastructure::ingestion_job::ingestion_job_entity::IngestionJobEntity,
+    schema::ingestion_jobs,
+};
+
+#[derive(Clone)]
+pub struct IngestionJobOrmCollection {
+    pub pool: Arc<Pool>,
+}
+
+impl IngestionJobOrmCollection {
+    pub fn new(pool: Arc<Pool>) -> Self {
+        IngestionJobOrmCollection { pool }
+    }
+}
+
+#[async_trait]
+impl IngestionJobRepository for IngestionJobOrmCollection {
+    async fn save_job(
+        &self,
+        job: IngestionJob,
+    ) -> Result<IngestionJob, Box<dyn Error + Send + Sync>> {
+        let conn = self.pool.get().await?;
+        let entity = IngestionJobEntity::from_job(&job);
+
+        let saved = conn
+            .interact(move |conn| {
+                diesel::insert_into(ingestion_jobs::table)
+                    .values(&entity)
+                    .returning(IngestionJobEntity::as_returning())
+                    .get_result::<IngestionJobEntity>(conn)
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        tracing::info!("Ingestion job saved with ID: {}", saved.id);
+        saved
+            .into_job()
+            .ok_or_else(|| "Saved ingestion job is malformed".into())
+    }
+
+    async fn get_job(&self, user_id: &Uuid, id: Uuid) -> Option<IngestionJob> {
+        let conn = match self.pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::error!("Could not get db connection for get_job: {}", e);
+                return None;
+            }
+        };
+
+        let id_str = id.to_string();
+        let user_id_str = user_id.to_string();
+        let result = conn
+            .interact(move |conn| {
+                ingestion_jobs::table
+                    .filter(ingestion_jobs::id.eq(id_str))
+                    .filter(ingestion_jobs::user_id.eq(user_id_str))
+                    .select(IngestionJobEntity::as_select())
+                    .get_result(conn)
+                    .optional()
+            })
+            .await;
+
+        match result {
+            Ok(Ok(entity)) => entity.and_then(IngestionJobEntity::into_job),
+            Ok(Err(e)) => {
+                tracing::error!("Error retrieving ingestion job: {}", e);
+                None
+            }
+            Err(e) => {
+                tracing::error!("Error retrieving ingestion job: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn find_pending_job(&self, user_id: &Uuid, sha256: &str) -> Option<IngestionJob> {
+        let conn = match self.pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::error!("Could not get db connection for find_pending_job: {}", e);
+                return None;
+            }
+        };
+
+        let user_id_str = user_id.to_string();
+        let sha256 = sha256.to_string();
+        let result = conn
+            .interact(move |conn| {
+                ingestion_jobs::table
+                    .filter(ingestion_jobs::user_id.eq(user_id_str))
+                    .filter(ingestion_jobs::blob_sha256.eq(sha256))
+                    .filter(ingestion_jobs::status.eq_any([
+                        IngestionJobStatus::Queued.as_str(),
+                        IngestionJobStatus::Running.as_str(),
+                    ]))
+                    .order(ingestion_jobs::created_at.asc())
+                    .select(IngestionJobEntity::as_select())
+                    .first(conn)
+                    .optional()
+            })
+            .await;
+
+        match result {
+            Ok(Ok(entity)) => entity.and_then(IngestionJobEntity::into_job),
+            Ok(Err(e)) => {
+                tracing::error!("Error finding pending ingestion job: {}", e);
+                None
+            }
+            Err(e) => {
+                tracing::error!("Error finding pending ingestion job: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn list_jobs(
+        &self,
+        user_id: &Uuid,
+        status: Option<IngestionJobStatus>,
+        limit: &u32,
+    ) -> Vec<IngestionJob> {
+        let conn = match self.pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::error!("Could not get db connection for list_jobs: {}", e);
+                return vec![];
+            }
+        };
+
+        let user_id_str = user_id.to_string();
+        let limit = *limit as i64;
+        let result = conn
+            .interact(move |conn| {
+                let mut query = ingestion_jobs::table
+                    .filter(ingestion_jobs::user_id.eq(user_id_str))
+                    .into_boxed();
+                if let Some(status) = status {
+                    query = query.filter(ingestion_jobs::status.eq(status.as_str()));
+                }
+                query
+                    .order_by(ingestion_jobs::updated_at.desc())
+                    .limit(limit)
+                    .select(IngestionJobEntity::as_select())
+                    .load(conn)
+            })
+            .await;
+
+        match result {
+            Ok(Ok(entities)) => entities
+                .into_iter()
+                .filter_map(IngestionJobEntity::into_job)
+                .collect(),
+            Ok(Err(e)) => {
+                tracing::error!("Error listing ingestion jobs: {}", e);
+                vec![]
+            }
+            Err(e) => {
+                tracing::error!("Error listing ingestion jobs: {}", e);
+                vec![]
+            }
+        }
+    }
+
+    async fn claim_next_job(&self) -> Result<Option<IngestionJob>, Box<dyn Error + Send + Sync>> {
+        let conn = self.pool.get().await?;
+        let queued = IngestionJobStatus::Queued.as_str();
+        let running = IngestionJobStatus::Running.as_str();
+        let now = Utc::now().naive_utc();
+
+        let claimed = conn
+            .interact(move |conn| {
+                conn.transaction(|conn| {
+                    let Some(id) = ingestion_jobs::table
+                        .filter(ingestion_jobs::status.eq(queued))
+                        .filter(ingestion_jobs::next_attempt_at.le(now))
+                        .order_by(ingestion_jobs::created_at.asc())
+                        .select(ingestion_jobs::id)
+                        .first::<String>(conn)
+                        .optional()?
+                    else {
+                        return Ok(None);
+                    };
+                    // The status check makes the claim fail if another worker got there first.
+                    diesel::update(
+                        ingestion_jobs::table
+                            .filter(ingestion_jobs::id.eq(id))
+                            .filter(ingestion_jobs::status.eq(queued)),
+                    )
+                    .set((
+                        ingestion_jobs::status.eq(running),
+                        ingestion_jobs::attempts.eq(ingestion_jobs::attempts + 1),
+                        ingestion_jobs::updated_at.eq(now),
+                    ))
+                    .returning(IngestionJobEntity::as_returning())
+                    .get_result::<IngestionJobEntity>(conn)
+                    .optional()
+                })
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        Ok(claimed.and_then(IngestionJobEntity::into_job))
+    }
+
+    async fn update_job(&self, job: &IngestionJob) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let conn = self.pool.get().await?;
+        let entity = IngestionJobEntity::from_job(job);
+
+        let updated = conn
+            .interact(move |conn| {
+                diesel::update(ingestion_jobs::table.filter(ingestion_jobs::id.eq(&entity.id)))
+                    .set((
+                        ingestion_jobs::status.eq(&entity.status),
+                        ingestion_jobs::document_id.eq(&entity.document_id),
+                        ingestion_jobs::similar_document_ids.eq(&entity.similar_document_ids),
+                        ingestion_jobs::error.eq(&entity.error),
+                        ingestion_jobs::attempts.eq(entity.attempts),
+                        ingestion_jobs::next_attempt_at.eq(entity.next_attempt_at),
+                        ingestion_jobs::updated_at.eq(entity.updated_at),
+                    ))
+                    .execute(conn)
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        if updated == 0 {
+            return Err(format!("Ingestion job {} not found", job.id).into());
+        }
+        Ok(())
+    }
+
+    async fn requeue_running_jobs(&self) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let conn = self.pool.get().await?;
+        let queued = IngestionJobStatus::Queued.as_str();
+        let running = IngestionJobStatus::Running.as_str();
+
+        let requeued = conn
+            .interact(move |conn| {
+                diesel::update(ingestion_jobs::table.filter(ingestion_jobs::status.eq(running)))
+                    .set((
+                        ingestion_jobs::status.eq(queued),
+                        ingestion_jobs::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .execute(conn)
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        Ok(requeued)
+    }
+
+    async fn references_blob(&self, sha256: &str) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        let conn = self.pool.get().await?;
+        let sha256 = sha256.to_string();
+        let succeeded = IngestionJobStatus::Succeeded.as_str();
+
+        let referenced = conn
+            .interact(move |conn| {
+                diesel::select(diesel::dsl::exists(
+                    ingestion_jobs::table
+                        .filter(ingestion_jobs::blob_sha256.eq(sha256))
+                        .filter(ingestion_jobs::status.ne(succeeded)),
+                ))
+                .get_result::<bool>(conn)
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        Ok(referenced)
+    }
+}
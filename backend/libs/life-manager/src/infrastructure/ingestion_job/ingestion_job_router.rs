@@ -0,0 +1,20 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::infrastructure::{
+    app_state::LifeManagerState,
+    ingestion_job::{
+        ingestion_event_handler::stream_ingestion_events,
+        ingestion_job_handler::{get_ingestion_job, list_ingestion_jobs, retry_ingestion_job},
+    },
+};
+
+pub fn ingestion_job_router() -> Router<LifeManagerState> {
+    Router::new()
+        .route("/", get(list_ingestion_jobs))
+        .route("/events", get(stream_ingestion_events))
+        .route("/{id}", get(get_ingestion_job))
+        .route("/{id}/retry", post(retry_ingestion_job))
+}
@@ -4,9 +4,13 @@ use async_trait::async_trait;
 
 use crate::{
     domain::{
-        document_text_reader::DocumentTextReader, uploaded_document_input::UploadedDocumentInput,
+        document_text_reader::{DocumentTextReader, ExtractedText},
+        ingestion_failure::IngestionFailure,
+        uploaded_document_input::UploadedDocumentInput,
+    },
+    infrastructure::document_text_extraction::{
+        get_text_from_pdf, get_text_from_plain_text, is_plain_text, needs_ocr, spawn_read,
     },
-    infrastructure::document_text_extraction::{get_text_from_pdf, needs_ocr},
 };
 
 /// Reader used when `TESSERACT_ENABLED` is false: extracts embedded PDF text locally; returns an
@@ -25,10 +29,13 @@ impl DocumentTextReader for NoOpDocumentTextReader {
     async fn read_image(
         &self,
         uploaded_document_input: &UploadedDocumentInput,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<ExtractedText, Box<dyn Error>> {
         if uploaded_document_input.is_pdf() {
             tracing::info!("File '{}' is a PDF.", uploaded_document_input.file_name);
-            match get_text_from_pdf(uploaded_document_input)? {
+            let text = spawn_read(uploaded_document_input, get_text_from_pdf)
+                .await
+                .map_err(|e| IngestionFailure::permanent(format!("Could not read PDF: {}", e)))?;
+            match text {
                 Some(text) => {
                     tracing::info!("Extracted text from PDF without OCR.");
                     return Ok(text);
@@ -42,26 +49,36 @@ impl DocumentTextReader for NoOpDocumentTextReader {
             }
         }
 
+        if is_plain_text(uploaded_document_input) {
+            return spawn_read(uploaded_document_input, get_text_from_plain_text)
+                .await
+                .map_err(|e| {
+                    IngestionFailure::permanent(format!("Could not read text: {}", e)).into()
+                });
+        }
+
         if !needs_ocr(uploaded_document_input) {
             tracing::info!(
                 "File '{}' does not need OCR.",
                 uploaded_document_input.file_name
             );
-            return Ok(String::new());
+            return Ok(ExtractedText::empty());
         }
 
-        Err("Tesseract OCR is disabled (set TESSERACT_ENABLED=true and run Docker with --profile tesseract).".into())
+        Err(Box::new(IngestionFailure::permanent(
+            "Tesseract OCR is disabled (set TESSERACT_ENABLED=true and run Docker with --profile tesseract).",
+        )))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{fs::File, io::Read, path::PathBuf};
+    use std::path::PathBuf;
 
     use uuid::Uuid;
 
     use super::*;
-    use crate::domain::document_text_reader::DocumentTextReader;
+    use crate::domain::{document_text_reader::DocumentTextReader, uploaded_file::UploadedFile};
 
     fn test_resources_path(file_name: &str) -> PathBuf {
         PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -72,23 +89,42 @@ mod tests {
     #[tokio::test]
     async fn pdf_with_text_succeeds() {
         let path = test_resources_path("hello_world.pdf");
-        let mut file = File::open(&path).expect("open pdf");
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer).unwrap();
-        let input =
-            UploadedDocumentInput::new("hello_world.pdf".to_string(), buffer, Uuid::new_v4());
+        let file = UploadedFile::existing(path).expect("open pdf");
+        let input = UploadedDocumentInput::new("hello_world.pdf".to_string(), file, Uuid::new_v4())
+            .unwrap();
         let reader = NoOpDocumentTextReader::new();
-        let text = reader.read_image(&input).await.expect("pdf text");
+        let extracted = reader.read_image(&input).await.expect("pdf text");
+        let text = &extracted.text;
+        assert_eq!(extracted.page_count, Some(1));
+        assert!(!extracted.ocr_used);
         assert!(
             text.to_lowercase().contains("hello"),
             "expected hello in extracted text: {text:?}"
         );
     }
 
+    #[tokio::test]
+    async fn plain_text_is_read_as_it_is() {
+        let input = UploadedDocumentInput::from_bytes(
+            "receipt.txt".to_string(),
+            b"Coffee 3.50",
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        let reader = NoOpDocumentTextReader::new();
+        let extracted = reader.read_image(&input).await.expect("text");
+        assert_eq!(extracted.text, "Coffee 3.50");
+        assert!(!extracted.ocr_used);
+    }
+
     #[tokio::test]
     async fn png_requires_ocr_errors() {
-        let input =
-            UploadedDocumentInput::new("scan.png".to_string(), vec![0_u8, 1, 2], Uuid::new_v4());
+        let input = UploadedDocumentInput::from_bytes(
+            "scan.png".to_string(),
+            b"\x89PNG\r\n\x1a\n",
+            Uuid::new_v4(),
+        )
+        .unwrap();
         let reader = NoOpDocumentTextReader::new();
         let err = reader
             .read_image(&input)
@@ -99,4 +135,21 @@ mod tests {
             "{err}"
         );
     }
+
+    #[tokio::test]
+    async fn corrupt_pdf_is_a_permanent_failure() {
+        let input = UploadedDocumentInput::from_bytes(
+            "broken.pdf".to_string(),
+            b"%PDF-1.4 truncated",
+            Uuid::new_v4(),
+        )
+        .unwrap();
+        let reader = NoOpDocumentTextReader::new();
+        let err = reader
+            .read_image(&input)
+            .await
+            .expect_err("pdf should not be readable");
+        let failure = err.downcast_ref::<IngestionFailure>().unwrap();
+        assert!(!failure.retryable, "{err}");
+    }
 }
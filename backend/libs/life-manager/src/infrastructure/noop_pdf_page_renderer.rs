@@ -0,0 +1,27 @@
+use std::{error::Error, path::Path};
+
+use async_trait::async_trait;
+
+use crate::domain::pdf_page_renderer::PdfPageRenderer;
+
+/// Renderer used when `PDF_PREVIEWS_ENABLED` is false: PDFs get no preview.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoOpPdfPageRenderer;
+
+impl NoOpPdfPageRenderer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl PdfPageRenderer for NoOpPdfPageRenderer {
+    async fn render_page(
+        &self,
+        _pdf: &Path,
+        _page: u32,
+        _max_size: u32,
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        Err("PDF rendering is disabled".into())
+    }
+}
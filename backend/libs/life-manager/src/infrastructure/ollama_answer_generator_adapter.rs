@@ -0,0 +1,110 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+use ollama_rs::{Ollama, generation::completion::request::GenerationRequest};
+use reqwest::Url;
+use tokio_stream::StreamExt;
+
+use crate::domain::{
+    answer_generator::{AnswerGenerator, AnswerStream},
+    assistant_answer::ContextPassage,
+};
+
+const MODEL_NAME: &str = "llama2";
+
+/**
+* An adapter that uses the Ollama client to answer questions from numbered passages of the
+* user's documents. The answer is streamed as the model writes it.
+*/
+#[derive(Clone)]
+pub struct OllamaAnswerGeneratorAdapter {
+    ollama_client: Ollama,
+}
+
+impl OllamaAnswerGeneratorAdapter {
+    pub fn new(url: Option<Url>) -> Self {
+        OllamaAnswerGeneratorAdapter {
+            ollama_client: match url {
+                Some(url) => Ollama::from_url(url),
+                None => Ollama::default(),
+            },
+        }
+    }
+}
+
+impl Default for OllamaAnswerGeneratorAdapter {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[async_trait]
+impl AnswerGenerator for OllamaAnswerGeneratorAdapter {
+    async fn answer(
+        &self,
+        question: &str,
+        passages: &[ContextPassage],
+    ) -> Result<AnswerStream, Box<dyn Error + Send + Sync>> {
+        let sources: Vec<String> = passages
+            .iter()
+            .zip(1..)
+            .map(|(passage, source)| format!("[{}] {}:\n{}", source, passage.title, passage.text))
+            .collect();
+        let prompt = format!(
+            "Answer the question using only the numbered passages from the user's documents below. After each statement, cite the passages it is based on by their number in brackets, e.g. [1]. If the passages do not contain the answer, say that you do not know. Answer briefly.\n\nPassages:\n{}\n\nQuestion: {}",
+            sources.join("\n\n"),
+            question
+        );
+
+        let request = GenerationRequest::new(MODEL_NAME.to_string(), prompt);
+        let stream = self.ollama_client.generate_stream(request).await?;
+        Ok(Box::pin(stream.map(|chunk| {
+            chunk
+                .map(|responses| {
+                    responses
+                        .into_iter()
+                        .map(|response| response.response)
+                        .collect::<String>()
+                })
+                .map_err(|e| Box::new(e) as Box<dyn Error + Send + Sync>)
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_test::traced_test;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::domain::assistant_answer::AssistantAnswer;
+
+    // NOTE: This test requires an Ollama server running locally with the llama2 model available.
+    // It is good for quickly testing prompts but not suitable for unit tests.
+    #[tokio::test]
+    #[ignore]
+    #[traced_test]
+    async fn test_answer() {
+        let generator = OllamaAnswerGeneratorAdapter::new(None);
+        let passages = [ContextPassage {
+            document_id: Uuid::new_v4(),
+            title: "Passport".to_string(),
+            page: Some(1),
+            text: "Passport no. X123. Date of expiry: 12 March 2031.".to_string(),
+            source_text: "Passport no. X123.\nDate of expiry: 12 March 2031.".to_string(),
+        }];
+
+        let mut stream = generator
+            .answer("When does my passport expire?", &passages)
+            .await
+            .unwrap();
+        let mut answer = String::new();
+        while let Some(piece) = stream.next().await {
+            answer.push_str(&piece.unwrap());
+        }
+
+        let answer = AssistantAnswer::new(&answer, &passages);
+        tracing::info!("Answer: {:?}", answer);
+        assert!(answer.answer.contains("2031"));
+    }
+}
@@ -0,0 +1,148 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+use ollama_rs::{
+    Ollama,
+    generation::{
+        completion::request::GenerationRequest,
+        parameters::{FormatType, JsonStructure},
+    },
+};
+use reqwest::Url;
+use serde_json::{Value, json};
+
+use crate::domain::{
+    category::Category, document_classification::DocumentClassification,
+    document_classifier::DocumentClassifier, ingestion_failure::IngestionFailure,
+};
+
+const MODEL_NAME: &str = "llama2";
+/// Answer of the model for documents that fit none of the categories.
+const NO_CATEGORY: &str = "none";
+/// Most tags kept from an answer, besides those of the category.
+const MAX_SUGGESTED_TAGS: usize = 5;
+
+/**
+* An adapter that uses the Ollama client to pick the category of a document from the names and
+* descriptions of the user's categories. The model answers with JSON naming the category, how
+* sure it is and a few tags.
+*/
+#[derive(Clone)]
+pub struct OllamaDocumentClassifierAdapter {
+    ollama_client: Ollama,
+}
+
+impl OllamaDocumentClassifierAdapter {
+    pub fn new(url: Option<Url>) -> Self {
+        OllamaDocumentClassifierAdapter {
+            ollama_client: match url {
+                Some(url) => Ollama::from_url(url),
+                None => Ollama::default(),
+            },
+        }
+    }
+}
+
+impl Default for OllamaDocumentClassifierAdapter {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[async_trait]
+impl DocumentClassifier for OllamaDocumentClassifierAdapter {
+    async fn classify(
+        &self,
+        text: &str,
+        categories: &[Category],
+    ) -> Result<Option<DocumentClassification>, Box<dyn Error>> {
+        let listed: Vec<String> = categories
+            .iter()
+            .map(|category| format!("- {}: {}", category.name, category.description))
+            .collect();
+        let mut names: Vec<&str> = categories.iter().map(|c| c.name.as_str()).collect();
+        names.push(NO_CATEGORY);
+        let prompt = format!(
+            "Which of these categories does the following document belong to? Answer \"{}\" if none fits. Also rate how sure you are from 0 to 1 and suggest up to {} short tags for it.\n\nCategories:\n{}\n\nDocument:\n{}",
+            NO_CATEGORY,
+            MAX_SUGGESTED_TAGS,
+            listed.join("\n"),
+            text
+        );
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "category": { "type": "string", "enum": names },
+                "confidence": { "type": "number", "minimum": 0, "maximum": 1 },
+                "tags": { "type": "array", "items": { "type": "string" } },
+            },
+            "required": ["category", "confidence", "tags"],
+        });
+        let format =
+            FormatType::StructuredJson(Box::new(JsonStructure::new_for_schema(schema.try_into()?)));
+        let request = GenerationRequest::new(MODEL_NAME.to_string(), prompt).format(format);
+        let response = self.ollama_client.generate(request).await?;
+        let answer: Value = serde_json::from_str(&response.response).map_err(|e| {
+            Box::new(IngestionFailure::retryable(format!(
+                "Ollama answered with invalid JSON: {}",
+                e
+            ))) as Box<dyn Error>
+        })?;
+
+        let name = answer["category"].as_str().unwrap_or(NO_CATEGORY);
+        let Some(category) = categories.iter().find(|category| category.is_named(name)) else {
+            return Ok(None);
+        };
+        let tags: Vec<String> = answer["tags"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .take(MAX_SUGGESTED_TAGS)
+            .map(str::to_string)
+            .collect();
+        let confidence = answer["confidence"].as_f64().unwrap_or_default() as f32;
+        Ok(Some(DocumentClassification::suggest(
+            category, &tags, confidence,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_test::traced_test;
+    use uuid::Uuid;
+
+    // NOTE: This test requires an Ollama server running locally with the llama2 model available.
+    // It is good for quickly testing prompts but not suitable for unit tests.
+    #[tokio::test]
+    #[ignore]
+    #[traced_test]
+    async fn test_classify() {
+        let classifier = OllamaDocumentClassifierAdapter::new(None);
+        let user_id = Uuid::new_v4();
+        let categories = [
+            Category::new(
+                user_id,
+                "Utility bill",
+                "Power, water and gas bills",
+                &[],
+                &[],
+            )
+            .unwrap(),
+            Category::new(
+                user_id,
+                "Medical",
+                "Doctor visits and prescriptions",
+                &[],
+                &[],
+            )
+            .unwrap(),
+        ];
+        let text = "City Power Ltd\nElectricity used this month: 312 kWh\nAmount due: EUR 84.20";
+        let classification = classifier.classify(text, &categories).await.unwrap();
+        tracing::info!("Classification: {:?}", classification);
+        assert_eq!(classification.unwrap().category, "Utility bill");
+    }
+}
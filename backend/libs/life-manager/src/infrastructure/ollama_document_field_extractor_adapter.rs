@@ -0,0 +1,116 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+use ollama_rs::{
+    Ollama,
+    generation::{
+        completion::request::GenerationRequest,
+        parameters::{FormatType, JsonStructure},
+    },
+};
+use reqwest::Url;
+use serde_json::{Value, json};
+
+use crate::domain::{
+    document_field_extractor::DocumentFieldExtractor,
+    document_fields::{DocumentFields, DocumentType, InvalidDocumentFields},
+    ingestion_failure::IngestionFailure,
+};
+
+const MODEL_NAME: &str = "llama2";
+
+/**
+* An adapter that uses the Ollama client to read document fields. The model first tells the
+* document type, then answers with JSON matching [`DocumentType::json_schema`], which is
+* validated before use.
+*/
+#[derive(Clone)]
+pub struct OllamaDocumentFieldExtractorAdapter {
+    ollama_client: Ollama,
+}
+
+impl OllamaDocumentFieldExtractorAdapter {
+    pub fn new(url: Option<Url>) -> Self {
+        OllamaDocumentFieldExtractorAdapter {
+            ollama_client: match url {
+                Some(url) => Ollama::from_url(url),
+                None => Ollama::default(),
+            },
+        }
+    }
+
+    /// Asks the model about the text and returns its answer, constrained to `schema`.
+    async fn ask_json(&self, prompt: String, schema: Value) -> Result<Value, Box<dyn Error>> {
+        let format =
+            FormatType::StructuredJson(Box::new(JsonStructure::new_for_schema(schema.try_into()?)));
+        let request = GenerationRequest::new(MODEL_NAME.to_string(), prompt).format(format);
+        let response = self.ollama_client.generate(request).await?;
+        serde_json::from_str(&response.response).map_err(|e| {
+            // The model does not always follow the format; asking again usually works.
+            Box::new(IngestionFailure::retryable(format!(
+                "Ollama answered with invalid JSON: {}",
+                e
+            ))) as Box<dyn Error>
+        })
+    }
+
+    async fn document_type(&self, text: &str) -> Result<DocumentType, Box<dyn Error>> {
+        let names: Vec<&str> = DocumentType::ALL.iter().map(DocumentType::as_str).collect();
+        let prompt = format!(
+            "Is the following document a bill (an invoice, utility bill or statement asking for a payment), a receipt (proof of a payment already made) or other? Answer with its document_type:\n\n{}",
+            text
+        );
+        let schema = json!({
+            "type": "object",
+            "properties": { "document_type": { "type": "string", "enum": names } },
+            "required": ["document_type"],
+        });
+        let answer = self.ask_json(prompt, schema).await?;
+        let name = answer["document_type"].as_str().unwrap_or_default();
+        Ok(DocumentType::parse(name)
+            .ok_or_else(|| InvalidDocumentFields::UnknownDocumentType(name.to_string()))?)
+    }
+}
+
+impl Default for OllamaDocumentFieldExtractorAdapter {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[async_trait]
+impl DocumentFieldExtractor for OllamaDocumentFieldExtractorAdapter {
+    async fn extract_fields(&self, text: &str) -> Result<DocumentFields, Box<dyn Error>> {
+        let document_type = self.document_type(text).await?;
+        if document_type.fields().is_empty() {
+            return Ok(DocumentFields::new(document_type));
+        }
+        let prompt = format!(
+            "Read the fields of the following {} and answer with JSON. Use null for fields that are not in the text. Write dates as YYYY-MM-DD, amounts as plain numbers without currency symbols and currencies as ISO 4217 codes:\n\n{}",
+            document_type.as_str(),
+            text
+        );
+        let answer = self.ask_json(prompt, document_type.json_schema()).await?;
+        Ok(DocumentFields::from_answer(document_type, &answer)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_test::traced_test;
+
+    // NOTE: This test requires an Ollama server running locally with the llama2 model available.
+    // It is good for quickly testing prompts but not suitable for unit tests.
+    #[tokio::test]
+    #[ignore]
+    #[traced_test]
+    async fn test_extract_fields() {
+        let extractor = OllamaDocumentFieldExtractorAdapter::new(None);
+        let text = "City Power Ltd\nInvoice number: INV-2026-0042\nCustomer account: 88213\nAmount due: EUR 84.20\nPlease pay by 1 November 2026.";
+        let fields = extractor.extract_fields(text).await.unwrap();
+        tracing::info!("Fields: {:?}", fields);
+        assert_eq!(fields.document_type, DocumentType::Bill);
+        assert_eq!(fields.total_amount, Some(84.2));
+    }
+}
@@ -4,7 +4,10 @@ use async_trait::async_trait;
 use ollama_rs::{Ollama, generation::completion::request::GenerationRequest};
 use reqwest::Url;
 
-use crate::domain::document_summarizer::{DocumentSummarizer, DocumentSummaryResult};
+use crate::domain::{
+    document_summarizer::{DocumentSummarizer, DocumentSummaryResult},
+    ingestion_failure::IngestionFailure,
+};
 
 const MODEL_NAME: &str = "llama2";
 const SUMMARY_CHAR_MAX_LENGTH: usize = 200;
@@ -51,6 +54,12 @@ impl DocumentSummarizer for OllamaDocumentSummarizerAdapter {
             .map(|s| s.trim().to_string())
             .filter(|s| !s.is_empty())
             .collect::<Vec<String>>();
+        if result.len() < 2 {
+            // The model does not always follow the prompt; asking again usually works.
+            return Err(Box::new(IngestionFailure::retryable(
+                "Ollama answered without a summary and a title",
+            )));
+        }
 
         Ok(DocumentSummaryResult {
             title: result.swap_remove(1),
@@ -0,0 +1,106 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+use ollama_rs::{
+    Ollama,
+    generation::embeddings::request::{EmbeddingsInput, GenerateEmbeddingsRequest},
+};
+use reqwest::Url;
+
+use crate::domain::embedding_provider::EmbeddingProvider;
+
+const MODEL_NAME: &str = "nomic-embed-text";
+/// Texts embedded per request, so a long document does not make one huge request.
+const EMBED_BATCH: usize = 16;
+
+/**
+* An adapter that uses the Ollama client to embed text with a local embedding model. The model
+* has to be pulled into Ollama first, e.g. `ollama pull nomic-embed-text`.
+*/
+#[derive(Clone)]
+pub struct OllamaEmbeddingProviderAdapter {
+    ollama_client: Ollama,
+    model: String,
+}
+
+impl OllamaEmbeddingProviderAdapter {
+    pub fn new(url: Option<Url>) -> Self {
+        OllamaEmbeddingProviderAdapter {
+            ollama_client: match url {
+                Some(url) => Ollama::from_url(url),
+                None => Ollama::default(),
+            },
+            model: MODEL_NAME.to_string(),
+        }
+    }
+
+    pub fn with_model(self, model: &str) -> Self {
+        Self {
+            model: model.to_string(),
+            ..self
+        }
+    }
+}
+
+impl Default for OllamaEmbeddingProviderAdapter {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OllamaEmbeddingProviderAdapter {
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, Box<dyn Error + Send + Sync>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(EMBED_BATCH) {
+            let request = GenerateEmbeddingsRequest::new(
+                self.model.clone(),
+                EmbeddingsInput::Multiple(batch.to_vec()),
+            );
+            let response = self.ollama_client.generate_embeddings(request).await?;
+            if response.embeddings.len() != batch.len() {
+                return Err(format!(
+                    "Expected {} embeddings from {} but got {}",
+                    batch.len(),
+                    self.model,
+                    response.embeddings.len()
+                )
+                .into());
+            }
+            embeddings.extend(response.embeddings);
+        }
+        Ok(embeddings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::document_chunk::cosine_similarity;
+
+    // NOTE: This test requires an Ollama server running locally with the nomic-embed-text model
+    // available. It is good for comparing models but not suitable for unit tests.
+    #[tokio::test]
+    #[ignore]
+    async fn test_embed_related_texts_are_closer() {
+        let provider = OllamaEmbeddingProviderAdapter::default();
+
+        let embeddings = provider
+            .embed(&[
+                "car insurance".to_string(),
+                "auto policy".to_string(),
+                "chocolate cake recipe".to_string(),
+            ])
+            .await
+            .unwrap();
+
+        assert!(
+            cosine_similarity(&embeddings[0], &embeddings[1])
+                > cosine_similarity(&embeddings[0], &embeddings[2])
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::{error::Error, path::Path, time::Duration};
+
+use async_trait::async_trait;
+use tempfile::TempDir;
+use tokio::process::Command;
+
+use crate::domain::pdf_page_renderer::PdfPageRenderer;
+
+/// A page that takes longer than this to render is given up on.
+const RENDER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Renders PDF pages with `pdftoppm` from poppler-utils, in a separate process so a malformed
+/// PDF cannot take the server down.
+#[derive(Clone, Debug)]
+pub struct PdftoppmRenderer {
+    /// Path of the `pdftoppm` binary.
+    program: String,
+}
+
+impl PdftoppmRenderer {
+    pub fn new(program: String) -> Self {
+        Self { program }
+    }
+}
+
+impl Default for PdftoppmRenderer {
+    fn default() -> Self {
+        Self::new("pdftoppm".to_string())
+    }
+}
+
+#[async_trait]
+impl PdfPageRenderer for PdftoppmRenderer {
+    async fn render_page(
+        &self,
+        pdf: &Path,
+        page: u32,
+        max_size: u32,
+    ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        let out_dir = TempDir::new()?;
+        let out_prefix = out_dir.path().join("page");
+        let page = page.to_string();
+
+        let output = Command::new(&self.program)
+            .args(["-png", "-singlefile", "-f", &page, "-l", &page])
+            .args(["-scale-to", &max_size.to_string()])
+            .arg(pdf)
+            .arg(&out_prefix)
+            .kill_on_drop(true)
+            .output();
+        let output = tokio::time::timeout(RENDER_TIMEOUT, output)
+            .await
+            .map_err(|_| format!("{} timed out after {:?}", self.program, RENDER_TIMEOUT))??;
+        if !output.status.success() {
+            return Err(format!(
+                "{} failed ({}): {}",
+                self.program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .into());
+        }
+        Ok(tokio::fs::read(out_prefix.with_extension("png")).await?)
+    }
+}
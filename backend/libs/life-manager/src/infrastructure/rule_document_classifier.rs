@@ -0,0 +1,34 @@
+use std::error::Error;
+
+use async_trait::async_trait;
+
+use crate::domain::{
+    category::Category,
+    document_classification::{DocumentClassification, classify_by_rules},
+    document_classifier::DocumentClassifier,
+};
+
+/**
+ * Classifier used when `CLASSIFIER` is `rules`, e.g. in tests: files documents by the keyword
+ * and regex rules of the categories alone, without a model. The Ollama classifier falls back to
+ * the same rules.
+ */
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RuleDocumentClassifier;
+
+impl RuleDocumentClassifier {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl DocumentClassifier for RuleDocumentClassifier {
+    async fn classify(
+        &self,
+        text: &str,
+        categories: &[Category],
+    ) -> Result<Option<DocumentClassification>, Box<dyn Error>> {
+        Ok(classify_by_rules(text, categories))
+    }
+}
@@ -0,0 +1,211 @@
+//! Streams the parts of multipart uploads to temp files, so large files never sit in memory.
+
+use std::{error::Error, fmt, io};
+
+use axum::{
+    Json,
+    extract::{FromRef, multipart::Field, multipart::MultipartError},
+    http::StatusCode,
+};
+use serde::Serialize;
+use serde_json::json;
+use tempfile::NamedTempFile;
+use tokio::io::AsyncWriteExt;
+
+use crate::{domain::uploaded_file::UploadedFile, infrastructure::app_state::LifeManagerState};
+
+/// Largest `json` part of an upload form. It only holds a title and some text.
+const MAX_TEXT_BYTES: u64 = 64 * 1024;
+
+/**
+ * How much a single upload request may carry. Sizes are counted on the bytes received, not on
+ * any length the client declares.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UploadLimits {
+    pub max_file_bytes: u64,
+    /// All parts of the request together, e.g. every file of a bulk upload.
+    pub max_request_bytes: u64,
+}
+
+impl Default for UploadLimits {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: 100 * 1024 * 1024,
+            max_request_bytes: 512 * 1024 * 1024,
+        }
+    }
+}
+
+impl FromRef<LifeManagerState> for UploadLimits {
+    fn from_ref(state: &LifeManagerState) -> Self {
+        state.upload_limits
+    }
+}
+
+/// Which of the [`UploadLimits`] an upload went over.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadLimit {
+    File,
+    Request,
+    /// The `json` part, see [`MAX_TEXT_BYTES`].
+    Text,
+}
+
+#[derive(Debug)]
+pub enum UploadError {
+    TooLarge {
+        limit: UploadLimit,
+        limit_bytes: u64,
+        /// The file being received when the limit was reached, if any.
+        file_name: Option<String>,
+    },
+    Multipart(MultipartError),
+    Io(io::Error),
+}
+
+impl UploadError {
+    /// Answer to the request: **413** with the limit that was reached, **400** for a malformed
+    /// form and **500** if the file could not be written.
+    pub fn response(&self) -> (StatusCode, Json<serde_json::Value>) {
+        match self {
+            UploadError::TooLarge {
+                limit,
+                limit_bytes,
+                file_name,
+            } => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(json!({
+                    "error": self.to_string(),
+                    "limit": limit,
+                    "limit_bytes": limit_bytes,
+                    "file_name": file_name,
+                })),
+            ),
+            UploadError::Multipart(_) => (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": self.to_string()})),
+            ),
+            UploadError::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({}))),
+        }
+    }
+}
+
+impl fmt::Display for UploadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UploadError::TooLarge {
+                limit: UploadLimit::File,
+                limit_bytes,
+                file_name,
+            } => write!(
+                f,
+                "{} is larger than {} bytes",
+                file_name.as_deref().unwrap_or("The file"),
+                limit_bytes
+            ),
+            UploadError::TooLarge {
+                limit: UploadLimit::Request,
+                limit_bytes,
+                ..
+            } => write!(f, "The upload is larger than {} bytes", limit_bytes),
+            UploadError::TooLarge {
+                limit: UploadLimit::Text,
+                limit_bytes,
+                ..
+            } => write!(f, "The json part is larger than {} bytes", limit_bytes),
+            UploadError::Multipart(e) => write!(f, "Invalid multipart form: {}", e),
+            UploadError::Io(e) => write!(f, "Could not store the upload: {}", e),
+        }
+    }
+}
+
+impl Error for UploadError {}
+
+impl From<MultipartError> for UploadError {
+    fn from(error: MultipartError) -> Self {
+        UploadError::Multipart(error)
+    }
+}
+
+impl From<io::Error> for UploadError {
+    fn from(error: io::Error) -> Self {
+        UploadError::Io(error)
+    }
+}
+
+/**
+ * Counts the bytes of one request against its [`UploadLimits`] while its parts are received.
+ */
+#[derive(Debug)]
+pub struct UploadBudget {
+    limits: UploadLimits,
+    received_bytes: u64,
+}
+
+impl UploadBudget {
+    pub fn new(limits: UploadLimits) -> Self {
+        Self {
+            limits,
+            received_bytes: 0,
+        }
+    }
+
+    /// Writes the part to a temp file chunk by chunk. Stops at the first chunk that goes over a
+    /// limit; the partial file is deleted.
+    pub async fn stream_to_file(
+        &mut self,
+        field: &mut Field<'_>,
+        file_name: &str,
+    ) -> Result<UploadedFile, UploadError> {
+        let (file, path) = NamedTempFile::new()?.into_parts();
+        let mut file = tokio::fs::File::from_std(file);
+        let mut file_bytes = 0;
+        while let Some(chunk) = field.chunk().await? {
+            file_bytes += chunk.len() as u64;
+            self.receive(chunk.len(), Some(file_name))?;
+            if file_bytes > self.limits.max_file_bytes {
+                return Err(UploadError::TooLarge {
+                    limit: UploadLimit::File,
+                    limit_bytes: self.limits.max_file_bytes,
+                    file_name: Some(file_name.to_string()),
+                });
+            }
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+        drop(file);
+        Ok(UploadedFile::temporary(path)?)
+    }
+
+    /// Reads a small text part, such as the `json` of the upload form.
+    pub async fn read_text(&mut self, field: &mut Field<'_>) -> Result<String, UploadError> {
+        let mut text = Vec::new();
+        while let Some(chunk) = field.chunk().await? {
+            self.receive(chunk.len(), None)?;
+            if (text.len() + chunk.len()) as u64 > MAX_TEXT_BYTES {
+                return Err(UploadError::TooLarge {
+                    limit: UploadLimit::Text,
+                    limit_bytes: MAX_TEXT_BYTES,
+                    file_name: None,
+                });
+            }
+            text.extend_from_slice(&chunk);
+        }
+        Ok(String::from_utf8_lossy(&text).into_owned())
+    }
+
+    /// Counts a chunk of the part named `file_name` against the request limit.
+    fn receive(&mut self, bytes: usize, file_name: Option<&str>) -> Result<(), UploadError> {
+        self.received_bytes += bytes as u64;
+        if self.received_bytes > self.limits.max_request_bytes {
+            return Err(UploadError::TooLarge {
+                limit: UploadLimit::Request,
+                limit_bytes: self.limits.max_request_bytes,
+                file_name: file_name.map(str::to_string),
+            });
+        }
+        Ok(())
+    }
+}
@@ -3,19 +3,39 @@ use std::{error::Error, sync::Arc};
 use async_trait::async_trait;
 use reqwest::multipart::{Form, Part};
 use serde_json::json;
+use tokio::{sync::Semaphore, task::JoinSet};
 
 use crate::{
     domain::{
-        document_text_reader::DocumentTextReader, uploaded_document_input::UploadedDocumentInput,
+        content_type::sniff_mime_type,
+        document_text_reader::{DocumentTextReader, ExtractedText, PageText},
+        image_preprocessing::PreprocessingChain,
+        ingestion_failure::IngestionFailure,
+        ocr_language::{OcrLanguages, detected_script},
+        pdf_page_renderer::PdfPageRenderer,
+        uploaded_document_input::UploadedDocumentInput,
+        uploaded_file::UploadedFile,
     },
     infrastructure::{
-        document_text_extraction::{get_text_from_pdf, needs_ocr},
+        document_text_extraction::{
+            get_text_from_pdf, get_text_from_plain_text, is_plain_text, is_tiff, needs_ocr,
+            pdf_page_count, spawn_read, tiff_frames_as_png,
+        },
         http_client::{HttpClient, HttpResponse},
+        tesseract_tsv::parse_tsv,
     },
 };
 
 use serde::{Deserialize, Serialize};
 
+/// Reader name recorded for text produced by the Tesseract service.
+pub const TESSERACT_READER: &str = "tesseract";
+
+/// Pages sent to Tesseract at the same time.
+const DEFAULT_CONCURRENCY: usize = 2;
+/// Longest side of rendered PDF pages: an A4 page at 300 DPI, which Tesseract reads well.
+const OCR_PAGE_SIZE: u32 = 3508;
+
 #[derive(Debug, Deserialize, Serialize)]
 struct TesseractResponse {
     data: TesseractData,
@@ -27,66 +47,256 @@ struct TesseractData {
     stderr: String,
 }
 
+/// Where the image of one page comes from.
+#[derive(Clone)]
+enum PageImage {
+    /// A page of a scanned PDF, rendered just before it is read.
+    Pdf {
+        pdf: UploadedFile,
+        page: u32,
+    },
+    Image(Vec<u8>),
+    /// A page already rendered and preprocessed, ready for OCR.
+    Prepared(Vec<u8>),
+    /// A page that could not be turned into an image.
+    Unreadable(String),
+}
+
+/**
+ * Reads text with the Tesseract service. Scanned PDFs and multi-frame TIFFs are read page by
+ * page, a few pages at a time, and a page that cannot be read does not lose the others, unless it
+ * may be read on another attempt. Every page image goes through a [`PreprocessingChain`] first:
+ * the upload's own, or the adapter's default.
+ *
+ * Pages are read in the upload's languages. Without any, Tesseract's script detection runs on
+ * the first page and those of the adapter's languages written in that script are used.
+ *
+ * Tesseract prints TSV, from which the text is rebuilt and the words are kept with their
+ * bounding boxes and confidences. Output that is not TSV is taken as plain text.
+ */
 #[derive(Clone)]
 pub struct TesseractAdapter {
     url: String,
     http_client: Arc<dyn HttpClient>,
+    pdf_renderer: Arc<dyn PdfPageRenderer>,
+    concurrency: usize,
+    preprocessing: PreprocessingChain,
+    /// Languages documents may be in, narrowed down by script detection.
+    languages: OcrLanguages,
 }
 
 impl TesseractAdapter {
-    pub fn new(url: String, http_client: Arc<dyn HttpClient + Send + Sync>) -> Self {
+    pub fn new(
+        url: String,
+        http_client: Arc<dyn HttpClient + Send + Sync>,
+        pdf_renderer: Arc<dyn PdfPageRenderer>,
+    ) -> Self {
         Self {
             url: format!("{}/tesseract", url),
             http_client,
+            pdf_renderer,
+            concurrency: DEFAULT_CONCURRENCY,
+            preprocessing: PreprocessingChain::default(),
+            languages: OcrLanguages::default(),
         }
     }
-}
 
-#[async_trait]
-impl DocumentTextReader for TesseractAdapter {
-    async fn read_image(
+    /// Sets the languages uploads that do not choose their own may be in. The Tesseract service
+    /// needs the traineddata of each of them, and of `osd` for the script detection.
+    pub fn with_languages(mut self, languages: OcrLanguages) -> Self {
+        self.languages = languages;
+        self
+    }
+
+    /// Sets the preprocessing of uploads that do not choose their own.
+    pub fn with_preprocessing(mut self, preprocessing: PreprocessingChain) -> Self {
+        self.preprocessing = preprocessing;
+        self
+    }
+
+    /// Sets how many pages are read at the same time, at least one.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Reads the pages with at most `concurrency` requests in flight. Pages that cannot be read
+    /// are kept with their error, unless none could be read. A page that may be read on another
+    /// attempt fails the whole read as retryable, so that the job is retried instead of saving
+    /// the document with a hole.
+    async fn read_pages(
         &self,
-        uploaded_document_input: &UploadedDocumentInput,
-    ) -> Result<String, Box<dyn Error>> {
-        // If it's a PDF, try to extract text without OCR first
-        if uploaded_document_input.is_pdf() {
-            tracing::info!("File '{}' is a PDF.", uploaded_document_input.file_name);
-            match get_text_from_pdf(uploaded_document_input)? {
-                Some(text) => {
-                    tracing::info!("Extracted text from PDF without OCR.");
-                    return Ok(text);
-                }
-                None => {
-                    tracing::info!(
-                        "No text extracted from PDF, proceeding with OCR for file '{}'.",
-                        uploaded_document_input.file_name
+        file_name: &str,
+        images: Vec<PageImage>,
+        preprocessing: &PreprocessingChain,
+        languages: &OcrLanguages,
+    ) -> Result<ExtractedText, IngestionFailure> {
+        let permits = Arc::new(Semaphore::new(self.concurrency));
+        let mut tasks = JoinSet::new();
+        for (image, page) in images.into_iter().zip(1..) {
+            let adapter = self.clone();
+            let permits = permits.clone();
+            let file_name = file_name.to_string();
+            let preprocessing = preprocessing.clone();
+            let options = read_options(languages);
+            tasks.spawn(async move {
+                let _permit = permits
+                    .acquire_owned()
+                    .await
+                    .expect("The semaphore is never closed");
+                let text = adapter
+                    .read_page(&file_name, image, preprocessing, options)
+                    .await;
+                (page, text)
+            });
+        }
+        let mut results = tasks.join_all().await;
+        results.sort_by_key(|(page, _)| *page);
+
+        let mut failures = Vec::new();
+        let mut words = Vec::new();
+        let pages = results
+            .into_iter()
+            .map(|(page, result)| match result {
+                Ok(output) => match parse_tsv(&output, page) {
+                    Some(layout) => {
+                        words.extend(layout.words);
+                        PageText {
+                            width: layout.width,
+                            height: layout.height,
+                            ..PageText::read(page, layout.text)
+                        }
+                    }
+                    None => PageText::read(page, output),
+                },
+                Err(failure) => {
+                    tracing::warn!(
+                        "Could not read page {} of '{}': {}",
+                        page,
+                        file_name,
+                        failure
                     );
+                    let page_text = PageText::failed(page, failure.message.as_str());
+                    failures.push(failure);
+                    page_text
                 }
-            }
+            })
+            .collect::<Vec<_>>();
+        if let Some(index) = failures.iter().position(|failure| failure.retryable) {
+            return Err(failures.swap_remove(index));
         }
-
-        if !needs_ocr(uploaded_document_input) {
-            tracing::info!(
-                "File '{}' does not need OCR.",
-                uploaded_document_input.file_name
-            );
-            return Ok(String::new());
+        if !pages.is_empty() && failures.len() == pages.len() {
+            return Err(failures.swap_remove(0));
         }
+        let mut extracted =
+            ExtractedText::from_pages(pages, TESSERACT_READER, true).with_words(words);
+        extracted.languages = languages.codes().to_vec();
+        Ok(extracted)
+    }
 
-        let bytes = &uploaded_document_input.file_data;
-        let options = json!({
-            "languages": ["eng"]
-        })
-        .to_string();
+    /// Languages for an upload that did not choose any: those of the adapter's languages that
+    /// are written in the script detected on the first page. All of them if the script cannot
+    /// be told. The first page is left prepared, so that it is not rendered and preprocessed
+    /// again to be read.
+    async fn detect_languages(
+        &self,
+        file_name: &str,
+        images: &mut [PageImage],
+        preprocessing: &PreprocessingChain,
+    ) -> OcrLanguages {
+        let Some(first_page) = images.first_mut() else {
+            return self.languages.clone();
+        };
+        let osd = match self
+            .prepare_page(file_name, first_page.clone(), preprocessing.clone())
+            .await
+        {
+            Ok(image) => {
+                *first_page = PageImage::Prepared(image.clone());
+                let options = json!({"languages": ["osd"], "pageSegmentationMethod": 0});
+                self.ocr_image(file_name, image, options).await
+            }
+            Err(failure) => Err(failure),
+        };
+        let languages = match &osd {
+            Ok(osd) => match detected_script(osd) {
+                Some(script) => {
+                    tracing::info!("Detected {} script in '{}'", script, file_name);
+                    self.languages.for_script(script)
+                }
+                None => None,
+            },
+            Err(failure) => {
+                tracing::warn!(
+                    "Could not detect the script of '{}': {}",
+                    file_name,
+                    failure
+                );
+                None
+            }
+        };
+        languages.unwrap_or_else(|| self.languages.clone())
+    }
+
+    async fn read_page(
+        &self,
+        file_name: &str,
+        image: PageImage,
+        preprocessing: PreprocessingChain,
+        options: serde_json::Value,
+    ) -> Result<String, IngestionFailure> {
+        let image = self.prepare_page(file_name, image, preprocessing).await?;
+        self.ocr_image(file_name, image, options).await
+    }
+
+    /// The image of the page as it is sent to OCR.
+    async fn prepare_page(
+        &self,
+        file_name: &str,
+        image: PageImage,
+        preprocessing: PreprocessingChain,
+    ) -> Result<Vec<u8>, IngestionFailure> {
+        let image = match image {
+            PageImage::Pdf { pdf, page } => self
+                .pdf_renderer
+                .render_page(pdf.path(), page, OCR_PAGE_SIZE)
+                .await
+                .map_err(|e| {
+                    IngestionFailure::permanent(format!("Could not render page: {}", e))
+                })?,
+            PageImage::Image(image) => image,
+            PageImage::Prepared(image) => return Ok(image),
+            PageImage::Unreadable(error) => return Err(IngestionFailure::permanent(error)),
+        };
+        Ok(preprocess(file_name, image, preprocessing).await)
+    }
+
+    /// Sends one image to the Tesseract service with the `options` it takes, e.g. the
+    /// languages, and returns what it printed.
+    async fn ocr_image(
+        &self,
+        file_name: &str,
+        image: Vec<u8>,
+        options: serde_json::Value,
+    ) -> Result<String, IngestionFailure> {
+        let mime_type = sniff_mime_type(&image).unwrap_or("application/octet-stream");
+        let options = options.to_string();
+        let invalid_form = |e: reqwest::Error| IngestionFailure::permanent(e.to_string());
 
         // Build multipart form
         let form = Form::new()
-            .part("options", Part::text(options).mime_str("application/json")?)
+            .part(
+                "options",
+                Part::text(options)
+                    .mime_str("application/json")
+                    .map_err(invalid_form)?,
+            )
             .part(
                 "file",
-                Part::bytes(bytes.to_vec())
-                    .file_name(uploaded_document_input.file_name.clone())
-                    .mime_str("image/jpeg")?, // TODO: clean this up
+                Part::bytes(image)
+                    .file_name(file_name.to_string())
+                    .mime_str(mime_type)
+                    .map_err(invalid_form)?,
             );
 
         tracing::info!("Sending request to Tesseract service at: {}", self.url);
@@ -96,42 +306,181 @@ impl DocumentTextReader for TesseractAdapter {
             Ok(resp) => resp,
             Err(e) => {
                 tracing::error!("HTTP request to Tesseract service failed: {}", e);
-                return Err(e);
+                return Err(IngestionFailure::retryable(format!(
+                    "Tesseract service is unreachable: {}",
+                    e
+                )));
             }
         };
 
         let status = response.status;
         tracing::info!("Tesseract response status: {}", status);
+        if status >= 500 {
+            return Err(IngestionFailure::retryable(format!(
+                "Tesseract service answered {}",
+                status
+            )));
+        }
+        if status >= 400 {
+            return Err(IngestionFailure::permanent(format!(
+                "Tesseract service rejected the file with {}",
+                status
+            )));
+        }
         let body: TesseractResponse = match serde_json::from_slice(&response.body) {
             Ok(body) => body,
             Err(e) => {
                 tracing::error!("Failed to parse Tesseract response JSON: {}", e);
-                return Err(Box::new(e));
+                return Err(IngestionFailure::retryable(format!(
+                    "Tesseract service answered with invalid JSON: {}",
+                    e
+                )));
             }
         };
         tracing::info!("Tesseract stdout received: {}", body.data.stdout);
+        Ok(body.data.stdout)
+    }
+}
+
+/// Options of the Tesseract service for reading text in the languages. It prints TSV, which has
+/// the words with their boxes and confidences as well as the text.
+fn read_options(languages: &OcrLanguages) -> serde_json::Value {
+    json!({
+        "languages": languages.codes(),
+        "configParams": {
+            "tessedit_create_tsv": "1",
+            "tessedit_create_txt": "0",
+        },
+    })
+}
+
+/// Runs the chain on a page image off the async runtime. An image the chain cannot decode is
+/// sent to OCR as it is.
+async fn preprocess(file_name: &str, image: Vec<u8>, preprocessing: PreprocessingChain) -> Vec<u8> {
+    if preprocessing.is_empty() {
+        return image;
+    }
+    let image = Arc::new(image);
+    let original = image.clone();
+    match tokio::task::spawn_blocking(move || preprocessing.apply_to_file(&original)).await {
+        Ok(Ok(processed)) => processed,
+        Ok(Err(e)) => {
+            tracing::warn!("Could not preprocess a page of '{}': {}", file_name, e);
+            Arc::unwrap_or_clone(image)
+        }
+        Err(e) => {
+            tracing::warn!("Preprocessing a page of '{}' panicked: {}", file_name, e);
+            Arc::unwrap_or_clone(image)
+        }
+    }
+}
+
+#[async_trait]
+impl DocumentTextReader for TesseractAdapter {
+    async fn read_image(
+        &self,
+        uploaded_document_input: &UploadedDocumentInput,
+    ) -> Result<ExtractedText, Box<dyn Error>> {
+        let file_name = &uploaded_document_input.file_name;
+        // If it's a PDF, try to extract text without OCR first
+        let mut images = if uploaded_document_input.is_pdf() {
+            tracing::info!("File '{}' is a PDF.", file_name);
+            let text = spawn_read(uploaded_document_input, get_text_from_pdf)
+                .await
+                .map_err(|e| IngestionFailure::permanent(format!("Could not read PDF: {}", e)))?;
+            if let Some(text) = text {
+                tracing::info!("Extracted text from PDF without OCR.");
+                return Ok(text);
+            }
+            let page_count = spawn_read(uploaded_document_input, pdf_page_count)
+                .await
+                .map_err(|e| IngestionFailure::permanent(format!("Could not read PDF: {}", e)))?;
+            tracing::info!(
+                "No text extracted from PDF, proceeding with OCR of {} pages for file '{}'.",
+                page_count,
+                file_name
+            );
+            (1..=page_count)
+                .map(|page| PageImage::Pdf {
+                    pdf: uploaded_document_input.file.clone(),
+                    page,
+                })
+                .collect()
+        } else if is_plain_text(uploaded_document_input) {
+            return spawn_read(uploaded_document_input, get_text_from_plain_text)
+                .await
+                .map_err(|e| {
+                    IngestionFailure::permanent(format!("Could not read text: {}", e)).into()
+                });
+        } else if !needs_ocr(uploaded_document_input) {
+            tracing::info!("File '{}' does not need OCR.", file_name);
+            return Ok(ExtractedText::empty());
+        } else if is_tiff(uploaded_document_input) {
+            spawn_read(uploaded_document_input, |input| {
+                tiff_frames_as_png(&input.file)
+            })
+            .await
+            .map_err(|e| IngestionFailure::permanent(format!("Could not read TIFF: {}", e)))?
+            .into_iter()
+            .map(|frame| frame.map_or_else(PageImage::Unreadable, PageImage::Image))
+            .collect()
+        } else {
+            let image = uploaded_document_input
+                .file
+                .read()
+                .map_err(|e| IngestionFailure::permanent(format!("Could not read image: {}", e)))?;
+            vec![PageImage::Image(image)]
+        };
 
-        Ok(body.data.stdout.trim().to_string())
+        let preprocessing = uploaded_document_input
+            .preprocessing
+            .as_ref()
+            .unwrap_or(&self.preprocessing);
+        let languages = match &uploaded_document_input.languages {
+            Some(languages) => languages.clone(),
+            None => {
+                self.detect_languages(file_name, &mut images, preprocessing)
+                    .await
+            }
+        };
+        Ok(self
+            .read_pages(file_name, images, preprocessing, &languages)
+            .await?)
     }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use std::sync::Arc;
+    use std::{
+        error::Error,
+        io::Cursor,
+        path::Path,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+    };
 
     use async_trait::async_trait;
+    use image::RgbImage;
     use serde_json::to_vec;
+    use tiff::encoder::{TiffEncoder, colortype};
     use uuid::Uuid;
 
     use crate::{
         domain::{
-            document_text_reader::DocumentTextReader,
-            uploaded_document_input::UploadedDocumentInput,
+            document_text_reader::DocumentTextReader, image_preprocessing::PreprocessingChain,
+            ingestion_failure::IngestionFailure, ocr_language::OcrLanguages,
+            pdf_page_renderer::PdfPageRenderer, uploaded_document_input::UploadedDocumentInput,
+            uploaded_file::UploadedFile,
         },
         infrastructure::{
             http_client::{HttpClient, HttpResponse},
-            tesseract_adapter::{TesseractData, TesseractResponse},
+            noop_pdf_page_renderer::NoOpPdfPageRenderer,
+            tesseract_adapter::{
+                PageImage, TesseractAdapter, TesseractData, TesseractResponse, preprocess,
+            },
         },
     };
 
@@ -168,27 +517,458 @@ mod tests {
         }
     }
 
+    /// Answers every request with the output of Tesseract's script detection and counts them.
+    struct ScriptDetectingHttpClient {
+        script: &'static str,
+        requests: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl HttpClient for ScriptDetectingHttpClient {
+        async fn post_multipart(
+            &self,
+            _url: &str,
+            _form: reqwest::multipart::Form,
+        ) -> Result<HttpResponse, Box<dyn std::error::Error + Send + Sync>> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let response = TesseractResponse {
+                data: TesseractData {
+                    stdout: format!(
+                        "Rotate: 0\nScript: {}\nScript confidence: 2.10",
+                        self.script
+                    ),
+                    stderr: "".to_string(),
+                },
+            };
+            Ok(HttpResponse {
+                body: to_vec(&response)?,
+                status: 200,
+            })
+        }
+    }
+
+    /// Answers every request with the same TSV output.
+    struct TsvHttpClient {
+        tsv: &'static str,
+    }
+
+    #[async_trait]
+    impl HttpClient for TsvHttpClient {
+        async fn post_multipart(
+            &self,
+            _url: &str,
+            _form: reqwest::multipart::Form,
+        ) -> Result<HttpResponse, Box<dyn std::error::Error + Send + Sync>> {
+            let response = TesseractResponse {
+                data: TesseractData {
+                    stdout: self.tsv.to_string(),
+                    stderr: "".to_string(),
+                },
+            };
+            Ok(HttpResponse {
+                body: to_vec(&response)?,
+                status: 200,
+            })
+        }
+    }
+
+    struct FailingHttpClient {
+        status: u16,
+    }
+
+    #[async_trait]
+    impl HttpClient for FailingHttpClient {
+        async fn post_multipart(
+            &self,
+            _url: &str,
+            _form: reqwest::multipart::Form,
+        ) -> Result<HttpResponse, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(HttpResponse {
+                body: Vec::new(),
+                status: self.status,
+            })
+        }
+    }
+
+    /// Answers `failing_request` with 503 and the others like [`MockHttpClient`].
+    struct FlakyHttpClient {
+        failing_request: usize,
+        requests: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl HttpClient for FlakyHttpClient {
+        async fn post_multipart(
+            &self,
+            url: &str,
+            form: reqwest::multipart::Form,
+        ) -> Result<HttpResponse, Box<dyn std::error::Error + Send + Sync>> {
+            if self.requests.fetch_add(1, Ordering::SeqCst) + 1 == self.failing_request {
+                return FailingHttpClient { status: 503 }
+                    .post_multipart(url, form)
+                    .await;
+            }
+            MockHttpClient::new().post_multipart(url, form).await
+        }
+    }
+
+    /// Renders every page as a tiny PNG, except `failing_page`, and counts the renders.
+    struct MockPdfPageRenderer {
+        failing_page: u32,
+        renders: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl PdfPageRenderer for MockPdfPageRenderer {
+        async fn render_page(
+            &self,
+            _pdf: &Path,
+            page: u32,
+            _max_size: u32,
+        ) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+            self.renders.fetch_add(1, Ordering::SeqCst);
+            if page == self.failing_page {
+                return Err("pdftoppm crashed".into());
+            }
+            let mut png = Cursor::new(Vec::new());
+            RgbImage::new(2, 2)
+                .write_to(&mut png, image::ImageFormat::Png)
+                .unwrap();
+            Ok(png.into_inner())
+        }
+    }
+
     #[tokio::test]
     pub async fn test_read_image() {
         let file_name = "hello_world.png";
-        let buffer = vec![0_u8, 1, 2];
+        let buffer = b"\x89PNG\r\n\x1a\n";
         let adapter = super::TesseractAdapter::new(
             "http://localhost:8884".to_string(),
             Arc::new(MockHttpClient::new()),
+            Arc::new(NoOpPdfPageRenderer::new()),
         );
         let uploaded_document_input =
-            UploadedDocumentInput::new(file_name.to_string(), buffer, Uuid::new_v4());
+            UploadedDocumentInput::from_bytes(file_name.to_string(), buffer, Uuid::new_v4())
+                .unwrap();
         let result = adapter.read_image(&uploaded_document_input).await;
         let text = match result {
-            Ok(text) => {
-                tracing::info!("OCR Result: {}", text);
-                text
+            Ok(extracted) => {
+                tracing::info!("OCR Result: {}", extracted.text);
+                extracted
             }
             Err(e) => {
                 panic!("OCR failed with error: {}", e);
             }
         };
-        let txt = text.as_str();
+        let txt = text.text.as_str();
         assert_eq!(txt.to_lowercase(), "Hello World".to_lowercase());
+        assert_eq!(text.reader, super::TESSERACT_READER);
+        assert!(text.ocr_used);
+    }
+
+    #[tokio::test]
+    pub async fn test_read_image_classifies_error_answers() {
+        let input = UploadedDocumentInput::from_bytes(
+            "receipt.png".to_string(),
+            b"\x89PNG\r\n\x1a\n",
+            Uuid::new_v4(),
+        )
+        .unwrap();
+
+        let mut retryable = Vec::new();
+        for status in [503, 422] {
+            let adapter = super::TesseractAdapter::new(
+                "http://localhost:8884".to_string(),
+                Arc::new(FailingHttpClient { status }),
+                Arc::new(NoOpPdfPageRenderer::new()),
+            );
+            let error = adapter.read_image(&input).await.unwrap_err();
+            let failure = error.downcast_ref::<IngestionFailure>().unwrap();
+            retryable.push(failure.retryable);
+        }
+
+        assert_eq!(retryable, [true, false]);
+    }
+
+    #[tokio::test]
+    pub async fn test_read_image_reads_each_tiff_frame() {
+        let mut tiff = Cursor::new(Vec::new());
+        let mut encoder = TiffEncoder::new(&mut tiff).unwrap();
+        for _ in 0..2 {
+            encoder
+                .write_image::<colortype::RGB8>(4, 4, RgbImage::new(4, 4).as_raw())
+                .unwrap();
+        }
+        let adapter = TesseractAdapter::new(
+            "http://localhost:8884".to_string(),
+            Arc::new(MockHttpClient::new()),
+            Arc::new(NoOpPdfPageRenderer::new()),
+        );
+        let input = UploadedDocumentInput::from_bytes(
+            "statement.tiff".to_string(),
+            tiff.get_ref(),
+            Uuid::new_v4(),
+        )
+        .unwrap();
+
+        let extracted = adapter.read_image(&input).await.unwrap();
+
+        assert_eq!(
+            extracted.text,
+            "--- Page 1 ---\nHello World\n\n--- Page 2 ---\nHello World"
+        );
+        assert_eq!(extracted.page_count, Some(2));
+    }
+
+    #[tokio::test]
+    pub async fn test_read_pages_keeps_the_pages_around_a_failed_one() {
+        let adapter = TesseractAdapter::new(
+            "http://localhost:8884".to_string(),
+            Arc::new(MockHttpClient::new()),
+            Arc::new(MockPdfPageRenderer {
+                failing_page: 2,
+                renders: AtomicUsize::new(0),
+            }),
+        )
+        .with_concurrency(2);
+        let pdf = UploadedFile::from_bytes(b"%PDF-1.4").unwrap();
+        let images = (1..=3)
+            .map(|page| PageImage::Pdf {
+                pdf: pdf.clone(),
+                page,
+            })
+            .collect();
+
+        let extracted = adapter
+            .read_pages(
+                "statement.pdf",
+                images,
+                &PreprocessingChain::default(),
+                &OcrLanguages::default(),
+            )
+            .await
+            .unwrap();
+
+        let texts: Vec<&str> = extracted.pages.iter().map(|p| p.text.as_str()).collect();
+        assert_eq!(texts, ["Hello World", "", "Hello World"]);
+        assert_eq!(
+            extracted.pages[1].error.as_deref(),
+            Some("Could not render page: pdftoppm crashed")
+        );
+        assert!(extracted.ocr_used);
+    }
+
+    #[tokio::test]
+    pub async fn test_read_pages_fails_when_no_page_can_be_read() {
+        let adapter = TesseractAdapter::new(
+            "http://localhost:8884".to_string(),
+            Arc::new(FailingHttpClient { status: 400 }),
+            Arc::new(NoOpPdfPageRenderer::new()),
+        );
+        let images = vec![
+            PageImage::Unreadable("Unsupported TIFF color type".to_string()),
+            PageImage::Image(vec![0_u8, 1, 2]),
+        ];
+
+        let failure = adapter
+            .read_pages(
+                "scan.tiff",
+                images,
+                &PreprocessingChain::default(),
+                &OcrLanguages::default(),
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(failure.message, "Unsupported TIFF color type");
+        assert!(!failure.retryable);
+    }
+
+    #[tokio::test]
+    pub async fn test_read_pages_is_retried_when_a_page_may_be_read_later() {
+        let adapter = TesseractAdapter::new(
+            "http://localhost:8884".to_string(),
+            Arc::new(FlakyHttpClient {
+                failing_request: 2,
+                requests: AtomicUsize::new(0),
+            }),
+            Arc::new(NoOpPdfPageRenderer::new()),
+        )
+        .with_concurrency(1);
+        let images = (0..3).map(|_| PageImage::Image(vec![0_u8, 1, 2])).collect();
+
+        let failure = adapter
+            .read_pages(
+                "scan.tiff",
+                images,
+                &PreprocessingChain::none(),
+                &OcrLanguages::default(),
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(failure.message, "Tesseract service answered 503");
+        assert!(failure.retryable);
+    }
+
+    #[tokio::test]
+    pub async fn test_read_image_detects_the_languages_from_the_script() {
+        let client = Arc::new(ScriptDetectingHttpClient {
+            script: "Cyrillic",
+            requests: AtomicUsize::new(0),
+        });
+        let adapter = TesseractAdapter::new(
+            "http://localhost:8884".to_string(),
+            client.clone(),
+            Arc::new(NoOpPdfPageRenderer::new()),
+        )
+        .with_languages(OcrLanguages::parse("eng,deu,rus,ukr").unwrap());
+        let input = UploadedDocumentInput::from_bytes(
+            "lease.png".to_string(),
+            b"\x89PNG\r\n\x1a\n",
+            Uuid::new_v4(),
+        )
+        .unwrap();
+
+        let extracted = adapter.read_image(&input).await.unwrap();
+
+        assert_eq!(extracted.languages, ["rus", "ukr"]);
+        assert_eq!(client.requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    pub async fn test_detect_languages_prepares_the_first_page_only_once() {
+        let renderer = Arc::new(MockPdfPageRenderer {
+            failing_page: 0,
+            renders: AtomicUsize::new(0),
+        });
+        let adapter = TesseractAdapter::new(
+            "http://localhost:8884".to_string(),
+            Arc::new(ScriptDetectingHttpClient {
+                script: "Latin",
+                requests: AtomicUsize::new(0),
+            }),
+            renderer.clone(),
+        )
+        .with_languages(OcrLanguages::parse("eng,rus").unwrap());
+        let pdf = UploadedFile::from_bytes(b"%PDF-1.4").unwrap();
+        let mut images: Vec<PageImage> = (1..=2)
+            .map(|page| PageImage::Pdf {
+                pdf: pdf.clone(),
+                page,
+            })
+            .collect();
+        let preprocessing = PreprocessingChain::default();
+
+        let languages = adapter
+            .detect_languages("scan.pdf", &mut images, &preprocessing)
+            .await;
+        let extracted = adapter
+            .read_pages("scan.pdf", images, &preprocessing, &languages)
+            .await
+            .unwrap();
+
+        assert_eq!(extracted.languages, ["eng"]);
+        assert_eq!(extracted.pages.len(), 2);
+        assert_eq!(renderer.renders.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    pub async fn test_read_image_uses_the_chosen_languages_without_detection() {
+        let client = Arc::new(ScriptDetectingHttpClient {
+            script: "Latin",
+            requests: AtomicUsize::new(0),
+        });
+        let adapter = TesseractAdapter::new(
+            "http://localhost:8884".to_string(),
+            client.clone(),
+            Arc::new(NoOpPdfPageRenderer::new()),
+        );
+        let input = UploadedDocumentInput::from_bytes(
+            "factura.png".to_string(),
+            b"\x89PNG\r\n\x1a\n",
+            Uuid::new_v4(),
+        )
+        .unwrap()
+        .with_languages(Some(OcrLanguages::parse("spa").unwrap()));
+
+        let extracted = adapter.read_image(&input).await.unwrap();
+
+        assert_eq!(extracted.languages, ["spa"]);
+        assert_eq!(client.requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    pub async fn test_read_image_keeps_the_words_of_tsv_output() {
+        let adapter = TesseractAdapter::new(
+            "http://localhost:8884".to_string(),
+            Arc::new(TsvHttpClient {
+                tsv: "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\t\
+                      width\theight\tconf\ttext\n\
+                      1\t1\t0\t0\t0\t0\t0\t0\t800\t600\t-1\t\n\
+                      5\t1\t1\t1\t1\t1\t40\t50\t120\t30\t62.5\tHello\n\
+                      5\t1\t1\t1\t1\t2\t180\t50\t130\t30\t41.5\tWorld\n",
+            }),
+            Arc::new(NoOpPdfPageRenderer::new()),
+        );
+        let input = UploadedDocumentInput::from_bytes(
+            "note.png".to_string(),
+            b"\x89PNG\r\n\x1a\n",
+            Uuid::new_v4(),
+        )
+        .unwrap()
+        .with_languages(Some(OcrLanguages::parse("eng").unwrap()));
+
+        let extracted = adapter.read_image(&input).await.unwrap();
+
+        assert_eq!(extracted.text, "Hello World");
+        assert_eq!(
+            (extracted.pages[0].width, extracted.pages[0].height),
+            (Some(800), Some(600))
+        );
+        let boxes: Vec<(&str, u32, u32)> = extracted
+            .words
+            .iter()
+            .map(|word| (word.text.as_str(), word.page, word.left))
+            .collect();
+        assert_eq!(boxes, [("Hello", 1, 40), ("World", 1, 180)]);
+        assert_eq!(extracted.confidence, Some(52.0));
+        assert!(extracted.needs_review());
+    }
+
+    #[tokio::test]
+    pub async fn test_preprocess_prepares_the_ocr_input() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../tests/resources/hello_world.png"
+        );
+        let original = std::fs::read(path).unwrap();
+
+        let processed = preprocess(
+            "hello_world.png",
+            original.clone(),
+            PreprocessingChain::default(),
+        )
+        .await;
+        let untouched = preprocess(
+            "hello_world.png",
+            original.clone(),
+            PreprocessingChain::none(),
+        )
+        .await;
+        let undecodable = preprocess(
+            "scan.png",
+            b"\x89PNG\r\n\x1a\n".to_vec(),
+            PreprocessingChain::default(),
+        )
+        .await;
+
+        let before = image::load_from_memory(&original).unwrap();
+        let after = image::load_from_memory(&processed).unwrap();
+        assert_eq!((before.width(), before.height()), (400, 200));
+        assert_eq!((after.width(), after.height()), (1600, 800));
+        assert!(after.as_luma8().is_some());
+        assert_eq!(untouched, original);
+        assert_eq!(undecodable, b"\x89PNG\r\n\x1a\n");
     }
 }
@@ -0,0 +1,162 @@
+use crate::domain::ocr_word::OcrWord;
+
+/// Columns of the header line Tesseract's TSV output starts with.
+const TSV_HEADER: &str =
+    "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext";
+
+/// Row level of the whole page, which carries the size of the page image.
+const PAGE_LEVEL: u32 = 1;
+/// Row level of a single word.
+const WORD_LEVEL: u32 = 5;
+
+/**
+ * A page read from Tesseract's TSV output: its text, the size of the image and the words on it.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageLayout {
+    pub text: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub words: Vec<OcrWord>,
+}
+
+/// One row of the TSV output.
+struct TsvRow<'a> {
+    level: u32,
+    /// Block, paragraph and line the row is in.
+    line: (u32, u32, u32),
+    left: u32,
+    top: u32,
+    width: u32,
+    height: u32,
+    confidence: f32,
+    text: &'a str,
+}
+
+impl<'a> TsvRow<'a> {
+    fn parse(row: &'a str) -> Option<Self> {
+        let columns: Vec<&str> = row.splitn(12, '\t').collect();
+        let [
+            level,
+            _,
+            block,
+            paragraph,
+            line,
+            _,
+            left,
+            top,
+            width,
+            height,
+            conf,
+            text,
+        ] = columns.as_slice()
+        else {
+            return None;
+        };
+        Some(Self {
+            level: level.parse().ok()?,
+            line: (
+                block.parse().ok()?,
+                paragraph.parse().ok()?,
+                line.parse().ok()?,
+            ),
+            left: left.parse().ok()?,
+            top: top.parse().ok()?,
+            width: width.parse().ok()?,
+            height: height.parse().ok()?,
+            confidence: conf.parse().ok()?,
+            text: text.trim(),
+        })
+    }
+}
+
+/// Parses the TSV Tesseract printed for one page. The text is rebuilt from the words, a line
+/// per line and a blank line between paragraphs. [`None`] if `tsv` is not TSV output, e.g.
+/// plain text.
+pub fn parse_tsv(tsv: &str, page: u32) -> Option<PageLayout> {
+    let mut rows = tsv.lines();
+    if rows.next()?.trim_end() != TSV_HEADER {
+        return None;
+    }
+    let mut layout = PageLayout {
+        text: String::new(),
+        width: None,
+        height: None,
+        words: vec![],
+    };
+    let mut previous_line = None;
+    for row in rows.filter_map(TsvRow::parse) {
+        if row.level == PAGE_LEVEL {
+            layout.width = Some(row.width);
+            layout.height = Some(row.height);
+        }
+        if row.level != WORD_LEVEL || row.text.is_empty() {
+            continue;
+        }
+        match previous_line {
+            None => {}
+            Some((block, paragraph, _)) if (block, paragraph) != (row.line.0, row.line.1) => {
+                layout.text.push_str("\n\n")
+            }
+            Some(line) if line != row.line => layout.text.push('\n'),
+            Some(_) => layout.text.push(' '),
+        }
+        previous_line = Some(row.line);
+        layout.text.push_str(row.text);
+        layout.words.push(OcrWord {
+            page,
+            text: row.text.to_string(),
+            left: row.left,
+            top: row.top,
+            width: row.width,
+            height: row.height,
+            confidence: row.confidence.max(0.0),
+        });
+    }
+    Some(layout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tsv_rebuilds_the_text_and_keeps_the_words() {
+        let tsv = format!(
+            "{}\n\
+             1\t1\t0\t0\t0\t0\t0\t0\t1240\t1754\t-1\t\n\
+             2\t1\t1\t0\t0\t0\t100\t80\t600\t90\t-1\t\n\
+             4\t1\t1\t1\t1\t0\t100\t80\t420\t30\t-1\t\n\
+             5\t1\t1\t1\t1\t1\t100\t80\t180\t30\t96.51\tInvoice\n\
+             5\t1\t1\t1\t1\t2\t300\t80\t220\t30\t91.2\tNo.42\n\
+             5\t1\t1\t1\t2\t1\t100\t120\t90\t30\t88\tDue\n\
+             5\t1\t1\t1\t2\t2\t200\t120\t40\t30\t-1\t \n\
+             5\t1\t2\t1\t1\t1\t100\t400\t160\t30\t45.5\tTotal\n",
+            TSV_HEADER
+        );
+
+        let layout = parse_tsv(&tsv, 3).unwrap();
+
+        assert_eq!(layout.text, "Invoice No.42\nDue\n\nTotal");
+        assert_eq!((layout.width, layout.height), (Some(1240), Some(1754)));
+        assert_eq!(layout.words.len(), 4);
+        assert_eq!(
+            layout.words[0],
+            OcrWord {
+                page: 3,
+                text: "Invoice".to_string(),
+                left: 100,
+                top: 80,
+                width: 180,
+                height: 30,
+                confidence: 96.51,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_tsv_rejects_plain_text() {
+        assert_eq!(parse_tsv("Hello World", 1), None);
+        assert_eq!(parse_tsv("", 1), None);
+    }
+}
@@ -0,0 +1,17 @@
+//! Helpers shared by the handler tests.
+
+use axum::{body::to_bytes, http::StatusCode, response::IntoResponse};
+use serde::de::DeserializeOwned;
+
+/// The status and JSON body of a handler's response.
+pub async fn read_json<T: DeserializeOwned>(response: impl IntoResponse) -> (StatusCode, T) {
+    let response = response.into_response();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body");
+    (
+        status,
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON"),
+    )
+}
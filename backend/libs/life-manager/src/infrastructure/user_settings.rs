@@ -0,0 +1,6 @@
+pub mod user_settings_collection;
+pub mod user_settings_dto;
+pub mod user_settings_entity;
+pub mod user_settings_handler;
+pub mod user_settings_orm_collection;
+pub mod user_settings_router;
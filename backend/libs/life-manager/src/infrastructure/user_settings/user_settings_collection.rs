@@ -0,0 +1,50 @@
+use std::{collections::HashMap, error::Error};
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::{
+    application::user_settings_repository::UserSettingsRepository,
+    domain::user_settings::UserSettings,
+};
+
+/// In-memory [`UserSettingsRepository`] used by tests.
+#[derive(Default)]
+pub struct UserSettingsCollection {
+    pub settings: Mutex<HashMap<Uuid, UserSettings>>,
+}
+
+impl UserSettingsCollection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl UserSettingsRepository for UserSettingsCollection {
+    async fn get_settings(
+        &self,
+        user_id: &Uuid,
+    ) -> Result<UserSettings, Box<dyn Error + Send + Sync>> {
+        Ok(self
+            .settings
+            .lock()
+            .await
+            .get(user_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn save_settings(
+        &self,
+        user_id: &Uuid,
+        settings: &UserSettings,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.settings
+            .lock()
+            .await
+            .insert(*user_id, settings.clone());
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::user_settings::UserSettings;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserSettingsDto {
+    /// Tesseract language codes uploads are read in, e.g. `["eng", "deu"]`. `null` lets the
+    /// languages be detected from the script of each document.
+    #[serde(default)]
+    pub ocr_languages: Option<Vec<String>>,
+}
+
+impl UserSettingsDto {
+    pub fn from_settings(settings: &UserSettings) -> Self {
+        Self {
+            ocr_languages: settings
+                .ocr_languages
+                .as_ref()
+                .map(|languages| languages.codes().to_vec()),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::domain::{ocr_language::OcrLanguages, user_settings::UserSettings};
+
+#[derive(Insertable, Queryable, Selectable, AsChangeset, Debug, Clone)]
+#[diesel(table_name = crate::schema::user_settings)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+#[diesel(treat_none_as_null = true)]
+pub struct UserSettingsEntity {
+    pub user_id: String,
+    /// Languages as read by [`OcrLanguages::parse`], e.g. `eng+deu`.
+    pub ocr_languages: Option<String>,
+}
+
+impl UserSettingsEntity {
+    pub fn from_settings(user_id: &Uuid, settings: &UserSettings) -> Self {
+        Self {
+            user_id: user_id.to_string(),
+            ocr_languages: settings.ocr_languages.as_ref().map(OcrLanguages::to_spec),
+        }
+    }
+
+    /// Maps a row to the domain model. Languages that no longer parse are dropped, so the user
+    /// falls back to detection.
+    pub fn into_settings(self) -> UserSettings {
+        UserSettings {
+            ocr_languages: self
+                .ocr_languages
+                .and_then(|spec| OcrLanguages::parse(&spec).ok()),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use auth::AuthUser;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::{Json, http::StatusCode};
+use serde_json::json;
+
+use crate::domain::user_settings::UserSettings;
+use crate::infrastructure::document::document_state::DocumentState;
+use crate::infrastructure::user_settings::user_settings_dto::UserSettingsDto;
+
+/// The user's settings. Users who never saved any get the defaults.
+pub async fn get_user_settings(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+) -> impl IntoResponse {
+    tracing::info!("Fetching settings for user: {}", user_id);
+    match document_use_cases
+        .user_settings
+        .get_settings(&user_id)
+        .await
+    {
+        Ok(settings) => (
+            StatusCode::OK,
+            Json(json!(UserSettingsDto::from_settings(&settings))),
+        ),
+        Err(e) => {
+            tracing::error!("Error fetching settings for user {}: {}", user_id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({})))
+        }
+    }
+}
+
+/// Replaces the user's settings. `ocr_languages` must be Tesseract language codes OCR can read
+/// in; any other code gives **400**.
+pub async fn update_user_settings(
+    AuthUser {
+        user_id,
+        tenant: _tenant,
+    }: AuthUser,
+    State(DocumentState(document_use_cases)): State<DocumentState>,
+    Json(command): Json<UserSettingsDto>,
+) -> impl IntoResponse {
+    tracing::info!("Updating settings for user: {}", user_id);
+    let ocr_languages = match command
+        .ocr_languages
+        .map(|codes| document_use_cases.choose_ocr_languages(codes))
+        .transpose()
+    {
+        Ok(ocr_languages) => ocr_languages,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": e.to_string()})),
+            );
+        }
+    };
+    let settings = UserSettings { ocr_languages };
+    match document_use_cases
+        .user_settings
+        .save_settings(&user_id, &settings)
+        .await
+    {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(json!(UserSettingsDto::from_settings(&settings))),
+        ),
+        Err(e) => {
+            tracing::error!("Error saving settings for user {}: {}", user_id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({})))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::body::to_bytes;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::application::document_use_cases::DocumentUseCases;
+    use crate::infrastructure::ollama_document_summarizer_adapter::OllamaDocumentSummarizerAdapter;
+
+    #[tokio::test]
+    async fn test_ocr_languages_are_saved_per_user() {
+        // Given
+        let user = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let other = AuthUser {
+            user_id: Uuid::new_v4(),
+            tenant: "test-tenant".to_string(),
+        };
+        let state = DocumentState(Arc::new(DocumentUseCases {
+            summarizer: Arc::new(OllamaDocumentSummarizerAdapter::new(None)),
+            ..DocumentUseCases::for_tests()
+        }));
+
+        // When
+        let rejected = update_user_settings(
+            user.clone(),
+            State(state.clone()),
+            Json(UserSettingsDto {
+                ocr_languages: Some(vec!["eng".to_string(), "german".to_string()]),
+            }),
+        )
+        .await
+        .into_response();
+        let not_installed = update_user_settings(
+            user.clone(),
+            State(state.clone()),
+            Json(UserSettingsDto {
+                ocr_languages: Some(vec!["xyz".to_string()]),
+            }),
+        )
+        .await
+        .into_response();
+        let updated = update_user_settings(
+            user.clone(),
+            State(state.clone()),
+            Json(UserSettingsDto {
+                ocr_languages: Some(vec!["deu".to_string(), "SPA".to_string()]),
+            }),
+        )
+        .await
+        .into_response();
+        let fetched = get_user_settings(user, State(state.clone()))
+            .await
+            .into_response();
+        let defaults = get_user_settings(other, State(state)).await.into_response();
+
+        // Then
+        assert_eq!(rejected.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(not_installed.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(updated.status(), StatusCode::OK);
+        let bytes = to_bytes(fetched.into_body(), usize::MAX).await.unwrap();
+        let fetched: UserSettingsDto = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            fetched.ocr_languages,
+            Some(vec!["deu".to_string(), "spa".to_string()])
+        );
+        let bytes = to_bytes(defaults.into_body(), usize::MAX).await.unwrap();
+        let defaults: UserSettingsDto = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(defaults, UserSettingsDto::default());
+    }
+}
@@ -0,0 +1,71 @@
+use std::error::Error;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use deadpool_diesel::sqlite::Pool;
+use diesel::{OptionalExtension, QueryDsl, RunQueryDsl, SelectableHelper};
+use uuid::Uuid;
+
+use crate::{
+    application::user_settings_repository::UserSettingsRepository,
+    domain::user_settings::UserSettings,
+    infrastructure::user_settings::user_settings_entity::UserSettingsEntity, schema::user_settings,
+};
+
+#[derive(Clone)]
+pub struct UserSettingsOrmCollection {
+    pub pool: Arc<Pool>,
+}
+
+impl UserSettingsOrmCollection {
+    pub fn new(pool: Arc<Pool>) -> Self {
+        UserSettingsOrmCollection { pool }
+    }
+}
+
+#[async_trait]
+impl UserSettingsRepository for UserSettingsOrmCollection {
+    async fn get_settings(
+        &self,
+        user_id: &Uuid,
+    ) -> Result<UserSettings, Box<dyn Error + Send + Sync>> {
+        let conn = self.pool.get().await?;
+        let user_id_str = user_id.to_string();
+
+        let entity = conn
+            .interact(move |conn| {
+                user_settings::table
+                    .find(user_id_str)
+                    .select(UserSettingsEntity::as_select())
+                    .get_result(conn)
+                    .optional()
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        Ok(entity
+            .map(UserSettingsEntity::into_settings)
+            .unwrap_or_default())
+    }
+
+    async fn save_settings(
+        &self,
+        user_id: &Uuid,
+        settings: &UserSettings,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let conn = self.pool.get().await?;
+        let entity = UserSettingsEntity::from_settings(user_id, settings);
+
+        conn.interact(move |conn| {
+            diesel::insert_into(user_settings::table)
+                .values(&entity)
+                .on_conflict(user_settings::user_id)
+                .do_update()
+                .set(&entity)
+                .execute(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())??;
+        tracing::info!("Settings saved for user: {}", user_id);
+        Ok(())
+    }
+}
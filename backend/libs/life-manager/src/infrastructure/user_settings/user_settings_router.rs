@@ -0,0 +1,10 @@
+use axum::{Router, routing::get};
+
+use crate::infrastructure::{
+    app_state::LifeManagerState,
+    user_settings::user_settings_handler::{get_user_settings, update_user_settings},
+};
+
+pub fn user_settings_router() -> Router<LifeManagerState> {
+    Router::new().route("/", get(get_user_settings).put(update_user_settings))
+}
@@ -0,0 +1,227 @@
+//! Expands uploaded ZIP archives into their files, with limits that reject zip bombs.
+
+use std::{
+    error::Error,
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+use tempfile::NamedTempFile;
+use zip::ZipArchive;
+
+use crate::domain::uploaded_file::UploadedFile;
+
+/**
+ * Limits the archives of one request must stay within to be expanded. Entries and sizes are
+ * counted over all of them, sizes on the inflated bytes, not on the sizes the archives declare.
+ * The depth applies to each entry.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZipLimits {
+    pub max_entries: usize,
+    pub max_uncompressed_bytes: u64,
+    /// Directories plus the file name, e.g. `2025/march/receipt.jpg` has depth 3.
+    pub max_path_depth: usize,
+}
+
+impl Default for ZipLimits {
+    fn default() -> Self {
+        Self {
+            max_entries: 500,
+            max_uncompressed_bytes: 256 * 1024 * 1024,
+            max_path_depth: 8,
+        }
+    }
+}
+
+/// What is left of the [`ZipLimits`] while the archives of a request are expanded, so that
+/// splitting a zip bomb over several archives does not get around them.
+#[derive(Clone, Copy, Debug)]
+pub struct ZipBudget {
+    limits: ZipLimits,
+    entries: usize,
+    uncompressed_bytes: u64,
+}
+
+impl ZipBudget {
+    pub fn new(limits: ZipLimits) -> Self {
+        Self {
+            limits,
+            entries: limits.max_entries,
+            uncompressed_bytes: limits.max_uncompressed_bytes,
+        }
+    }
+}
+
+/// A file taken out of an archive. `file_name` is its path inside the archive.
+#[derive(Debug)]
+pub struct ZipEntry {
+    pub file_name: String,
+    pub file: UploadedFile,
+}
+
+/// The files in the archive, in archive order. Directories and macOS metadata are skipped. The
+/// whole archive is rejected if it goes over what is left of the `budget` or has an entry outside
+/// of it, and then takes nothing from the budget. Entries are inflated into temp files, not into
+/// memory.
+pub fn expand_zip(
+    file: &UploadedFile,
+    budget: &mut ZipBudget,
+) -> Result<Vec<ZipEntry>, Box<dyn Error + Send + Sync>> {
+    let limits = budget.limits;
+    let mut archive = ZipArchive::new(BufReader::new(file.open()?))?;
+    if archive.len() > budget.entries {
+        return Err(format!(
+            "Archive has {} entries, but only {} of the {} allowed per request are left",
+            archive.len(),
+            budget.entries,
+            limits.max_entries
+        )
+        .into());
+    }
+
+    let mut entries = Vec::new();
+    let mut total_bytes: u64 = 0;
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(path) = entry.enclosed_name() else {
+            return Err(
+                format!("Archive entry {} points outside the archive", entry.name()).into(),
+            );
+        };
+        if path.components().count() > limits.max_path_depth {
+            return Err(format!(
+                "Archive entry {} is nested deeper than {} levels",
+                path.display(),
+                limits.max_path_depth
+            )
+            .into());
+        }
+        if is_metadata(&path) {
+            continue;
+        }
+
+        // Read one byte past the budget so an archive that lies about its sizes is caught.
+        let left = budget.uncompressed_bytes - total_bytes;
+        let mut entry_file = NamedTempFile::new()?;
+        total_bytes += io::copy(&mut entry.take(left + 1), &mut entry_file)?;
+        if total_bytes > budget.uncompressed_bytes {
+            return Err(format!(
+                "Archives expand to more than the {} bytes allowed per request",
+                limits.max_uncompressed_bytes
+            )
+            .into());
+        }
+        entries.push(ZipEntry {
+            file_name: path.to_string_lossy().into_owned(),
+            file: UploadedFile::temporary(entry_file.into_temp_path())?,
+        });
+    }
+    budget.entries -= archive.len();
+    budget.uncompressed_bytes -= total_bytes;
+    Ok(entries)
+}
+
+/// Finder metadata that macOS adds to archives it creates.
+fn is_metadata(path: &Path) -> bool {
+    path.components().any(|component| {
+        let name = component.as_os_str().to_string_lossy();
+        name == "__MACOSX" || name == ".DS_Store"
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+    use super::*;
+
+    fn zip_of(files: &[(&str, &[u8])]) -> UploadedFile {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        for (name, data) in files {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(data).unwrap();
+        }
+        UploadedFile::from_bytes(&writer.finish().unwrap().into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_expand_zip_returns_the_files() {
+        let archive = zip_of(&[
+            ("receipts/one.jpg", b"first"),
+            ("__MACOSX/receipts/._one.jpg", b"finder"),
+            ("two.pdf", b"second"),
+        ]);
+
+        let entries = expand_zip(&archive, &mut ZipBudget::new(ZipLimits::default())).unwrap();
+
+        let entries: Vec<(String, Vec<u8>)> = entries
+            .into_iter()
+            .map(|entry| (entry.file_name, entry.file.read().unwrap()))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                ("receipts/one.jpg".to_string(), b"first".to_vec()),
+                ("two.pdf".to_string(), b"second".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_zip_enforces_the_limits() {
+        let limits = ZipLimits {
+            max_entries: 2,
+            max_uncompressed_bytes: 1024,
+            max_path_depth: 2,
+        };
+        let too_many = zip_of(&[("a.jpg", b"a"), ("b.jpg", b"b"), ("c.jpg", b"c")]);
+        let too_large = zip_of(&[("bomb.txt", &[0_u8; 4096])]);
+        let too_deep = zip_of(&[("a/b/c.jpg", b"c")]);
+        let escaping = zip_of(&[("../etc/passwd", b"root")]);
+
+        for (archive, reason) in [
+            (too_many, "entries"),
+            (too_large, "bytes"),
+            (too_deep, "deeper"),
+            (escaping, "outside"),
+        ] {
+            let error = expand_zip(&archive, &mut ZipBudget::new(limits)).unwrap_err();
+            assert!(error.to_string().contains(reason), "{error}");
+        }
+    }
+
+    #[test]
+    fn test_expand_zip_shares_the_limits_between_archives() {
+        let mut budget = ZipBudget::new(ZipLimits {
+            max_entries: 3,
+            max_uncompressed_bytes: 1024,
+            max_path_depth: 8,
+        });
+        let first = zip_of(&[("a.jpg", b"a"), ("b.jpg", b"b")]);
+        let too_many = zip_of(&[("c.jpg", b"c"), ("d.jpg", b"d")]);
+        let too_large = zip_of(&[("e.txt", &[0_u8; 1023])]);
+        let last = zip_of(&[("f.jpg", b"f")]);
+
+        assert_eq!(expand_zip(&first, &mut budget).unwrap().len(), 2);
+        let error = expand_zip(&too_many, &mut budget).unwrap_err();
+        assert!(error.to_string().contains("entries"), "{error}");
+        let error = expand_zip(&too_large, &mut budget).unwrap_err();
+        assert!(error.to_string().contains("bytes"), "{error}");
+        // Rejected archives take nothing from the budget
+        assert_eq!(expand_zip(&last, &mut budget).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_expand_zip_rejects_a_file_that_is_not_an_archive() {
+        let file = UploadedFile::from_bytes(b"not a zip").unwrap();
+
+        assert!(expand_zip(&file, &mut ZipBudget::new(ZipLimits::default())).is_err());
+    }
+}
@@ -5,7 +5,11 @@ use server_host::{AppBootstrap, TenantMount};
 
 use crate::infrastructure::{
     app_state::{LifeManagerDeps, LifeManagerState, LifeManagerStateBuilder},
-    document::document_router::document_router,
+    assistant::assistant_router::assistant_router,
+    category::category_router::category_router,
+    document::{document_router::document_router, tag_router::tag_router},
+    ingestion_job::ingestion_job_router::ingestion_job_router,
+    user_settings::user_settings_router::user_settings_router,
 };
 
 pub struct LifeManagerTenant;
@@ -36,6 +40,11 @@ pub fn api_router() -> Router<LifeManagerState> {
         "/api/v1",
         Router::new()
             .nest("/auth", auth_router::<LifeManagerState>())
-            .nest("/documents", document_router()),
+            .nest("/documents", document_router())
+            .nest("/tags", tag_router())
+            .nest("/categories", category_router())
+            .nest("/assistant", assistant_router())
+            .nest("/jobs", ingestion_job_router())
+            .nest("/settings", user_settings_router()),
     )
 }
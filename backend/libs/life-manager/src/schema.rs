@@ -4,5 +4,146 @@ diesel::table! {
         title -> Text,
         content -> Text,
         user_id -> Text,
+        blob_sha256 -> Nullable<Text>,
+        file_name -> Nullable<Text>,
+        mime_type -> Nullable<Text>,
+        size_bytes -> Nullable<BigInt>,
+        extracted_text -> Nullable<Text>,
+        text_reader -> Nullable<Text>,
+        page_count -> Nullable<Integer>,
+        ocr_used -> Nullable<Bool>,
+        deleted_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        document_date -> Nullable<Date>,
+        content_sha256 -> Nullable<Text>,
+        text_simhash -> Nullable<BigInt>,
+        thumbnail_sha256 -> Nullable<Text>,
+        text_pages -> Nullable<Text>,
+        text_languages -> Nullable<Text>,
+        ocr_confidence -> Nullable<Float>,
     }
 }
+
+diesel::table! {
+    tags (id) {
+        id -> Text,
+        user_id -> Text,
+        name -> Text,
+    }
+}
+
+diesel::table! {
+    categories (id) {
+        id -> Text,
+        user_id -> Text,
+        name -> Text,
+        description -> Text,
+        rules -> Text,
+        tags -> Text,
+    }
+}
+
+diesel::table! {
+    document_classifications (document_id) {
+        document_id -> Text,
+        category_id -> Text,
+        tags -> Text,
+        confidence -> Float,
+        status -> Text,
+    }
+}
+
+diesel::table! {
+    document_chunks (document_id, position) {
+        document_id -> Text,
+        position -> Integer,
+        page -> Nullable<Integer>,
+        text -> Text,
+        model -> Text,
+        embedding -> Binary,
+    }
+}
+
+diesel::table! {
+    document_fields (document_id) {
+        document_id -> Text,
+        document_type -> Text,
+        vendor -> Nullable<Text>,
+        total_amount -> Nullable<Double>,
+        currency -> Nullable<Text>,
+        due_date -> Nullable<Date>,
+        account_number -> Nullable<Text>,
+        invoice_number -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    document_words (document_id, position) {
+        document_id -> Text,
+        position -> Integer,
+        page -> Integer,
+        text -> Text,
+        box_left -> Integer,
+        box_top -> Integer,
+        box_width -> Integer,
+        box_height -> Integer,
+        confidence -> Float,
+    }
+}
+
+diesel::table! {
+    document_tags (document_id, tag_id) {
+        document_id -> Text,
+        tag_id -> Text,
+    }
+}
+
+diesel::table! {
+    ingestion_jobs (id) {
+        id -> Text,
+        user_id -> Text,
+        status -> Text,
+        blob_sha256 -> Text,
+        file_name -> Text,
+        mime_type -> Text,
+        size_bytes -> BigInt,
+        document_id -> Nullable<Text>,
+        error -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        attempts -> Integer,
+        next_attempt_at -> Timestamp,
+        similar_document_ids -> Text,
+        preprocessing -> Nullable<Text>,
+        ocr_languages -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    user_settings (user_id) {
+        user_id -> Text,
+        ocr_languages -> Nullable<Text>,
+    }
+}
+
+diesel::joinable!(document_classifications -> categories (category_id));
+diesel::joinable!(document_classifications -> documents (document_id));
+diesel::joinable!(document_chunks -> documents (document_id));
+diesel::joinable!(document_tags -> documents (document_id));
+diesel::joinable!(document_fields -> documents (document_id));
+diesel::joinable!(document_tags -> tags (tag_id));
+diesel::joinable!(document_words -> documents (document_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    categories,
+    documents,
+    document_chunks,
+    document_classifications,
+    document_fields,
+    document_tags,
+    document_words,
+    ingestion_jobs,
+    tags,
+    user_settings
+);
@@ -12,12 +12,12 @@ use life_manager::{
 use mikeyjay_server::build_app_with_life_manager_state;
 use reqwest::{Client, ClientBuilder};
 use serde::{Deserialize, Serialize};
-use tempfile::NamedTempFile;
+use tempfile::{NamedTempFile, TempDir};
 
 use serde_json::json;
 use wiremock::{
     Mock, MockServer, ResponseTemplate,
-    matchers::{method, path},
+    matchers::{body_string_contains, method, path},
 };
 
 use crate::common::docker::{docker_compose_down, start_docker_compose_dev_profile};
@@ -35,6 +35,7 @@ pub struct LoginResponse {
     pub token: String,
 }
 
+#[allow(dead_code)]
 #[derive(Serialize, Deserialize)]
 struct TestClaims {
     sub: Uuid,
@@ -42,14 +43,17 @@ struct TestClaims {
     tenant: String,
 }
 
+#[allow(dead_code)]
 pub fn decode_token_tenant(token: &str) -> String {
     decode_token_claims(token).tenant
 }
 
+#[allow(dead_code)]
 pub fn decode_token_user_id(token: &str) -> Uuid {
     decode_token_claims(token).sub
 }
 
+#[allow(dead_code)]
 fn decode_token_claims(token: &str) -> TestClaims {
     let token = token.strip_prefix("Bearer ").unwrap_or(token);
     let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
@@ -62,6 +66,7 @@ fn decode_token_claims(token: &str) -> TestClaims {
     .claims
 }
 
+#[allow(dead_code)]
 pub fn build_bearer_token_with_tenant(user_id: Uuid, tenant: &str) -> String {
     let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
     let exp = OffsetDateTime::now_utc() + TimeDuration::hours(1);
@@ -143,10 +148,12 @@ where
     dotenv::from_filename(&test_env_path).ok();
 
     let ollama: MockServer = mock_ollama_response().await;
+    let blob_dir = TempDir::new().expect("Failed to create temp blob store dir");
 
     unsafe {
         set_var("OLLAMA_URL", ollama.uri());
         set_var("DATABASE_URL", &database_url);
+        set_var("BLOB_STORE_PATH", blob_dir.path());
     }
 
     let server = build_app_server_with_db_setup(&database_url, db_setup).await;
@@ -164,6 +171,50 @@ where
     docker_compose_down();
 }
 
+/// Like `run_test_with_test_profile`, but uploads are read by a mocked Tesseract service that
+/// detects the Latin script and reads `ocr_text` on every page.
+#[allow(dead_code)]
+pub async fn run_test_with_mock_tesseract<F, Fut>(ocr_text: &str, test: F)
+where
+    F: FnOnce(TestServer) -> Fut,
+    Fut: std::future::Future<Output = ()>,
+{
+    let tesseract = mock_tesseract_response(ocr_text).await;
+    unsafe {
+        set_var("TESSERACT_ENABLED", "true");
+        set_var("TESSERACT_URL", tesseract.uri());
+    }
+    run_test_with_test_profile(test).await;
+    unsafe {
+        set_var("TESSERACT_ENABLED", "false");
+    }
+}
+
+async fn mock_tesseract_response(ocr_text: &str) -> MockServer {
+    let server = MockServer::start().await;
+    let answer = |stdout: &str| {
+        ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "stdout": stdout, "stderr": "" }
+        }))
+    };
+
+    Mock::given(method("POST"))
+        .and(path("/tesseract"))
+        .and(body_string_contains("\"osd\""))
+        .respond_with(answer("Script: Latin\nScript confidence: 4.20"))
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/tesseract"))
+        .respond_with(answer(ocr_text))
+        .mount(&server)
+        .await;
+
+    tracing::info!("Mocked Tesseract server at {}", server.uri());
+    server
+}
+
 async fn mock_ollama_response() -> MockServer {
     let server = MockServer::start().await;
 
@@ -3,20 +3,39 @@ mod common;
 use std::fs;
 
 use axum_test::TestServer;
+use life_manager::domain::document_classification::ClassificationStatus;
+use life_manager::domain::document_fields::DocumentType;
+use life_manager::domain::ingestion_job::IngestionJobStatus;
+use life_manager::infrastructure::assistant::assistant_dto::AssistantAnswerDto;
+use life_manager::infrastructure::category::category_dto::CategoryDto;
 use life_manager::infrastructure::document::{
-    document_dto::DocumentDto, document_handler::CreateDocumentCommand,
+    document_dto::{DocumentDto, DocumentPageDto},
+    document_handler::CreateDocumentCommand,
+    words_handler::DocumentWordsDto,
 };
+use life_manager::infrastructure::ingestion_job::ingestion_job_dto::{
+    BulkUploadDto, IngestionJobDto,
+};
+use life_manager::infrastructure::user_settings::user_settings_dto::UserSettingsDto;
 use reqwest::multipart::{Form, Part};
 use serial_test::serial;
+use std::io::{Cursor, Write};
 use tracing_test::traced_test;
+use uuid::Uuid;
+use zip::{ZipWriter, write::SimpleFileOptions};
 
 use crate::common::setup::{
-    build_auth_header, run_test_with_all_containers, run_test_with_test_profile,
+    build_auth_header, build_bearer_token_with_tenant, run_test_with_all_containers,
+    run_test_with_mock_tesseract, run_test_with_test_profile,
 };
 use reqwest::ClientBuilder;
 use std::time::Duration;
 
 const DOCUMENTS_URL: &str = "/life-manager/api/v1/documents";
+const JOBS_URL: &str = "/life-manager/api/v1/jobs";
+const SETTINGS_URL: &str = "/life-manager/api/v1/settings";
+const CATEGORIES_URL: &str = "/life-manager/api/v1/categories";
+const ASSISTANT_URL: &str = "/life-manager/api/v1/assistant";
 
 #[tokio::test]
 #[serial]
@@ -30,6 +49,7 @@ async fn create_and_get_document_docker_compose() {
         let payload = CreateDocumentCommand {
             title: String::from("Integration Test Document"),
             content: String::from("This is a test content."),
+            languages: None,
         };
 
         let json_string = serde_json::to_string(&payload).unwrap();
@@ -78,7 +98,9 @@ async fn create_and_get_document_docker_compose() {
             "Response status was not successful: {}",
             res.error_for_status().unwrap_err()
         );
-        let saved_document_resp: DocumentDto = res.json().await.unwrap();
+        assert_eq!(res.status(), reqwest::StatusCode::ACCEPTED);
+        let job: IngestionJobDto = res.json().await.unwrap();
+        let saved_document_resp = wait_for_ingestion(&server, &auth_header, &job).await;
 
         // Verify the document was created in the database
         let get_request_url_result = server
@@ -120,6 +142,7 @@ async fn create_and_get_document() {
         let payload = CreateDocumentCommand {
             title: String::from("Integration Test Document"),
             content: String::from("This is a test content."),
+            languages: None,
         };
 
         let json_string = serde_json::to_string(&payload).unwrap();
@@ -167,7 +190,9 @@ async fn create_and_get_document() {
             "Response status was not successful: {}",
             res.error_for_status().unwrap_err()
         );
-        let saved_document_resp: DocumentDto = res.json().await.unwrap();
+        assert_eq!(res.status(), reqwest::StatusCode::ACCEPTED);
+        let job: IngestionJobDto = res.json().await.unwrap();
+        let saved_document_resp = wait_for_ingestion(&server, &auth_header, &job).await;
 
         // Verify the document was created in the database
         let get_request_url_result = server
@@ -210,6 +235,7 @@ async fn create_and_get_document_no_file() {
         let payload = CreateDocumentCommand {
             title: String::from("Integration Test Document"),
             content: String::from("This is a test content."),
+            languages: None,
         };
         // Make REST API call to create a document
         let json_string = serde_json::to_string(&payload).unwrap();
@@ -275,14 +301,17 @@ async fn get_all_documents() {
             CreateDocumentCommand {
                 title: String::from("First Document"),
                 content: String::from("Content of first document"),
+                languages: None,
             },
             CreateDocumentCommand {
                 title: String::from("Second Document"),
                 content: String::from("Content of second document"),
+                languages: None,
             },
             CreateDocumentCommand {
                 title: String::from("Third Document"),
                 content: String::from("Content of third document"),
+                languages: None,
             },
         ];
 
@@ -341,10 +370,11 @@ async fn get_all_documents() {
             get_response.status()
         );
 
-        let documents: Vec<DocumentDto> = get_response
+        let page: DocumentPageDto = get_response
             .json()
             .await
-            .expect("Failed to parse response as Vec<DocumentDto>");
+            .expect("Failed to parse response as DocumentPageDto");
+        let documents = page.items;
 
         // Verify we got at least the documents we created
         assert!(
@@ -367,38 +397,1626 @@ async fn get_all_documents() {
 
         tracing::info!("Successfully retrieved {} documents", documents.len());
 
-        // Test title query parameter
-        let get_all_url_result = server
-            .server_url("/life-manager/api/v1/documents?title=First")
-            .expect("Failed to get server URL");
-        let get_all_url = get_all_url_result.as_str();
-        tracing::info!("GET All Documents URL: {}", get_all_url);
+        // Page through the same documents two at a time
+        let first_page: DocumentPageDto =
+            get_json(&server, &auth_header, &format!("{}?limit=2", DOCUMENTS_URL)).await;
+        assert_eq!(first_page.items.len(), 2);
+        assert_eq!(first_page.items[0].title, "First Document");
+        assert_eq!(first_page.items[1].title, "Second Document");
+        let cursor = first_page
+            .next_cursor
+            .expect("Expected a cursor for the next page");
 
-        let get_response = reqwest::Client::new()
-            .get(get_all_url)
+        let second_page: DocumentPageDto = get_json(
+            &server,
+            &auth_header,
+            &format!("{}?limit=2&cursor={}", DOCUMENTS_URL, cursor),
+        )
+        .await;
+        assert_eq!(second_page.items.len(), 1);
+        assert_eq!(second_page.items[0].title, "Third Document");
+        assert!(second_page.next_cursor.is_none());
+
+        // Descending order starts from the other end
+        let desc_page: DocumentPageDto = get_json(
+            &server,
+            &auth_header,
+            &format!("{}?sort=title&order=desc&limit=1", DOCUMENTS_URL),
+        )
+        .await;
+        assert_eq!(desc_page.items[0].title, "Third Document");
+
+        // A cursor is only valid for the sort it was issued for
+        let url = server
+            .server_url(&format!("{}?order=desc&cursor={}", DOCUMENTS_URL, cursor))
+            .expect("Failed to get server URL");
+        let res = reqwest::Client::new()
+            .get(url.as_str())
             .header("Authorization", &auth_header)
             .send()
             .await
             .expect("Failed to send GET request");
+        assert_eq!(res.status(), reqwest::StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
 
-        tracing::info!("GET Response status: {:?}", get_response.status());
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn create_document_and_download_file() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+
+        let payload = CreateDocumentCommand {
+            title: String::from("Integration Test Document"),
+            content: String::from("This is a test content."),
+            languages: None,
+        };
+        let json_string = serde_json::to_string(&payload).unwrap();
+        let file_name = "tests/resources/hello_world.pdf";
+        let file_bytes = fs::read(file_name)
+            .unwrap_or_else(|_| panic!("Could not read bytes from file: {}", file_name));
+
+        let form = Form::new()
+            .part(
+                "json",
+                Part::text(json_string)
+                    .mime_str("application/json")
+                    .expect("Could not set mime type to json"),
+            )
+            .part(
+                "file",
+                Part::bytes(file_bytes.clone())
+                    .file_name("hello_world.pdf")
+                    .mime_str("application/pdf")
+                    .expect("Could not set mime type to pdf"),
+            );
+
+        let url_result = server
+            .server_url(DOCUMENTS_URL)
+            .expect("Failed to get server URL");
+        let res = reqwest::Client::new()
+            .post(url_result.as_str())
+            .multipart(form)
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert!(res.status().is_success());
+        assert_eq!(res.status(), reqwest::StatusCode::ACCEPTED);
+        let job: IngestionJobDto = res.json().await.unwrap();
+        let saved_document_resp = wait_for_ingestion(&server, &auth_header, &job).await;
+
+        // Download the original upload
+        let file_url_result = server
+            .server_url(&format!(
+                "{}/{}/file",
+                DOCUMENTS_URL, &saved_document_resp.id
+            ))
+            .expect("Failed to get server URL");
+        let file_response = reqwest::Client::new()
+            .get(file_url_result.as_str())
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        tracing::info!("File Response: {:?}", file_response);
+        assert!(file_response.status().is_success());
+        assert_eq!(
+            file_response
+                .headers()
+                .get("content-type")
+                .expect("Missing content type"),
+            "application/pdf"
+        );
+        let downloaded = file_response.bytes().await.expect("Failed to read file");
+        assert_eq!(downloaded.to_vec(), file_bytes);
+
+        // Read the raw extracted text
+        let extracted: serde_json::Value = get_json(
+            &server,
+            &auth_header,
+            &format!("{}/{}/text", DOCUMENTS_URL, &saved_document_resp.id),
+        )
+        .await;
         assert!(
-            get_response.status().is_success(),
-            "GET all documents failed with status: {}",
-            get_response.status()
+            extracted["text"]
+                .as_str()
+                .unwrap()
+                .to_lowercase()
+                .contains("hello")
         );
+        assert_eq!(extracted["reader"], "pdf-extract");
+        assert_eq!(extracted["page_count"], 1);
+        assert_eq!(extracted["ocr_used"], false);
 
-        let documents: Vec<DocumentDto> = get_response
-            .json()
+        // PDF previews are disabled in the test profile
+        let thumbnail_url = server
+            .server_url(&format!(
+                "{}/{}/thumbnail",
+                DOCUMENTS_URL, &saved_document_resp.id
+            ))
+            .expect("Failed to get server URL");
+        let thumbnail_response = reqwest::Client::new()
+            .get(thumbnail_url.as_str())
+            .header("Authorization", &auth_header)
+            .send()
             .await
-            .expect("Failed to parse response as Vec<DocumentDto>");
+            .expect("Failed to send request");
+        assert_eq!(thumbnail_response.status(), reqwest::StatusCode::NOT_FOUND);
+    })
+    .await;
+}
 
-        // Verify we got at least the documents we created
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn tag_and_filter_documents() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        let bill = create_document(&server, &auth_header, "Power Bill", "Electricity").await;
+        let receipt = create_document(&server, &auth_header, "Receipt", "Groceries").await;
+        create_document(&server, &auth_header, "Letter", "Hello").await;
+
+        // Tag the documents
+        let client = reqwest::Client::new();
+        for (document, tags) in [(&bill, vec!["Bills", "2025"]), (&receipt, vec!["2025"])] {
+            let url = server
+                .server_url(&format!("{}/{}/tags", DOCUMENTS_URL, document.id))
+                .expect("Failed to get server URL");
+            let res = client
+                .post(url.as_str())
+                .json(&serde_json::json!({ "tags": tags }))
+                .header("Authorization", &auth_header)
+                .send()
+                .await
+                .expect("Failed to send request");
+            assert!(res.status().is_success());
+            let tagged: DocumentDto = res.json().await.unwrap();
+            assert_eq!(tagged.tags.len(), tags.len());
+        }
+
+        // Filter with AND and OR
+        let all = get_json::<DocumentPageDto>(
+            &server,
+            &auth_header,
+            &format!("{}?tag=bills&tag=2025", DOCUMENTS_URL),
+        )
+        .await
+        .items;
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].id, bill.id);
+        assert_eq!(all[0].tags, vec!["2025".to_string(), "bills".to_string()]);
+
+        let any = get_json::<DocumentPageDto>(
+            &server,
+            &auth_header,
+            &format!("{}?tag=bills&tag=2025&tag_mode=any", DOCUMENTS_URL),
+        )
+        .await
+        .items;
+        assert_eq!(any.len(), 2);
+
+        // List tags with counts
+        let tags: Vec<serde_json::Value> =
+            get_json(&server, &auth_header, "/life-manager/api/v1/tags").await;
+        assert_eq!(
+            tags,
+            vec![
+                serde_json::json!({ "name": "2025", "document_count": 2 }),
+                serde_json::json!({ "name": "bills", "document_count": 1 }),
+            ]
+        );
+
+        // Remove a tag
+        let url = server
+            .server_url(&format!("{}/{}/tags/bills", DOCUMENTS_URL, bill.id))
+            .expect("Failed to get server URL");
+        let res = client
+            .delete(url.as_str())
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert!(res.status().is_success());
+        let untagged: DocumentDto = res.json().await.unwrap();
+        assert_eq!(untagged.tags, vec!["2025".to_string()]);
+
+        let tags: Vec<serde_json::Value> =
+            get_json(&server, &auth_header, "/life-manager/api/v1/tags").await;
+        assert_eq!(tags.len(), 1);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn search_documents() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        let bill = create_document(
+            &server,
+            &auth_header,
+            "Power Bill",
+            "Electricity usage for March, payment due on the 5th",
+        )
+        .await;
+        let insurance = create_document(
+            &server,
+            &auth_header,
+            "Car Insurance",
+            "Renewal notice: the bill covers power steering repairs",
+        )
+        .await;
+        create_document(&server, &auth_header, "Letter", "Hello from grandma").await;
+
+        // Title matches rank above content matches
+        let hits: Vec<serde_json::Value> = get_json(
+            &server,
+            &auth_header,
+            &format!("{}/search?q=bill", DOCUMENTS_URL),
+        )
+        .await;
+        let ids: Vec<String> = hits
+            .iter()
+            .map(|h| h["document"]["id"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(ids, vec![bill.id.to_string(), insurance.id.to_string()]);
         assert!(
-            documents.len() >= 2,
-            "Expected at least 3 documents, got {}",
-            documents.len()
+            hits[1]["snippet"]
+                .as_str()
+                .unwrap()
+                .contains("<mark>bill</mark>")
+        );
+
+        // Phrase query
+        let hits: Vec<serde_json::Value> = get_json(
+            &server,
+            &auth_header,
+            &format!("{}/search?q=%22power%20steering%22", DOCUMENTS_URL),
+        )
+        .await;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0]["document"]["id"], insurance.id.to_string());
+
+        // Prefix query
+        let hits: Vec<serde_json::Value> = get_json(
+            &server,
+            &auth_header,
+            &format!("{}/search?q=grand*", DOCUMENTS_URL),
+        )
+        .await;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0]["document"]["title"], "Letter");
+
+        // Missing query
+        let url = server
+            .server_url(&format!("{}/search?q=", DOCUMENTS_URL))
+            .expect("Failed to get server URL");
+        let res = reqwest::Client::new()
+            .get(url.as_str())
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(res.status(), reqwest::StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn update_trash_and_restore_document() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        let document = create_document(&server, &auth_header, "Draft", "Electricity").await;
+        let document_url = format!("{}/{}", DOCUMENTS_URL, document.id);
+        let client = reqwest::Client::new();
+
+        // Update title, content and tags
+        let url = server
+            .server_url(&document_url)
+            .expect("Failed to get server URL");
+        let res = client
+            .patch(url.as_str())
+            .json(&serde_json::json!({
+                "title": "Power Bill",
+                "content": "Electricity for March",
+                "tags": ["Bills"],
+            }))
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert!(res.status().is_success());
+        let updated: DocumentDto = res.json().await.unwrap();
+        assert_eq!(updated.title, "Power Bill");
+        assert_eq!(updated.content, "Electricity for March");
+        assert_eq!(updated.tags, vec!["bills".to_string()]);
+        let hits: Vec<serde_json::Value> = get_json(
+            &server,
+            &auth_header,
+            &format!("{}/search?q=march", DOCUMENTS_URL),
+        )
+        .await;
+        assert_eq!(hits.len(), 1);
+
+        // Move it to the trash
+        let res = client
+            .delete(url.as_str())
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(res.status(), reqwest::StatusCode::NO_CONTENT);
+        let res = client
+            .get(url.as_str())
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(res.status(), reqwest::StatusCode::NOT_FOUND);
+        let listed: DocumentPageDto = get_json(&server, &auth_header, DOCUMENTS_URL).await;
+        assert!(listed.items.is_empty());
+        let trash: Vec<serde_json::Value> =
+            get_json(&server, &auth_header, &format!("{}/trash", DOCUMENTS_URL)).await;
+        assert_eq!(trash.len(), 1);
+        assert!(trash[0]["deleted_at"].is_string());
+
+        // Restore it
+        let restore_url = server
+            .server_url(&format!("{}/restore", document_url))
+            .expect("Failed to get server URL");
+        let res = client
+            .post(restore_url.as_str())
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert!(res.status().is_success());
+        let listed: DocumentPageDto = get_json(&server, &auth_header, DOCUMENTS_URL).await;
+        assert_eq!(listed.items.len(), 1);
+        let trash: Vec<serde_json::Value> =
+            get_json(&server, &auth_header, &format!("{}/trash", DOCUMENTS_URL)).await;
+        assert!(trash.is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn documents_are_private_to_their_owner() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let owner_header = build_auth_header(&server).await;
+        let intruder_header = build_bearer_token_with_tenant(Uuid::new_v4(), "life-manager");
+        let document = create_document(&server, &owner_header, "Payslip", "Salary for March").await;
+        let document_url = format!("{}/{}", DOCUMENTS_URL, document.id);
+        let client = reqwest::Client::new();
+        let send = |method: reqwest::Method, path: String, auth_header: String| {
+            let url = server.server_url(&path).expect("Failed to get server URL");
+            let request = client
+                .request(method, url.as_str())
+                .header("Authorization", auth_header);
+            async move { request.send().await.expect("Failed to send request") }
+        };
+
+        // Every document route answers 404 to another user, as if the document did not exist
+        for (method, path) in [
+            (reqwest::Method::GET, document_url.clone()),
+            (reqwest::Method::GET, format!("{}/file", document_url)),
+            (reqwest::Method::GET, format!("{}/text", document_url)),
+            (reqwest::Method::DELETE, document_url.clone()),
+            (reqwest::Method::POST, format!("{}/restore", document_url)),
+            (
+                reqwest::Method::DELETE,
+                format!("{}/tags/payslip", document_url),
+            ),
+        ] {
+            let res = send(method.clone(), path.clone(), intruder_header.clone()).await;
+            assert_eq!(
+                res.status(),
+                reqwest::StatusCode::NOT_FOUND,
+                "{} {} should be hidden from other users",
+                method,
+                path
+            );
+        }
+        let url = server
+            .server_url(&document_url)
+            .expect("Failed to get server URL");
+        let res = client
+            .patch(url.as_str())
+            .json(&serde_json::json!({ "title": "Hijacked" }))
+            .header("Authorization", &intruder_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(res.status(), reqwest::StatusCode::NOT_FOUND);
+        let url = server
+            .server_url(&format!("{}/tags", document_url))
+            .expect("Failed to get server URL");
+        let res = client
+            .post(url.as_str())
+            .json(&serde_json::json!({ "tags": ["stolen"] }))
+            .header("Authorization", &intruder_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(res.status(), reqwest::StatusCode::NOT_FOUND);
+
+        // Listings and search only show the caller's documents
+        let listed: DocumentPageDto = get_json(&server, &intruder_header, DOCUMENTS_URL).await;
+        assert!(listed.items.is_empty());
+        let hits: Vec<serde_json::Value> = get_json(
+            &server,
+            &intruder_header,
+            &format!("{}/search?q=salary", DOCUMENTS_URL),
+        )
+        .await;
+        assert!(hits.is_empty());
+
+        // The owner still sees the untouched document
+        let owned: serde_json::Value = get_json(&server, &owner_header, &document_url).await;
+        assert_eq!(owned["title"], "Payslip");
+        assert_eq!(owned["tags"], serde_json::json!([]));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn filter_and_sort_documents_by_date() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        let bill = create_document(&server, &auth_header, "Power Bill", "Electricity").await;
+        let letter = create_document(&server, &auth_header, "Letter", "From the bank").await;
+        let receipt = create_document(&server, &auth_header, "Receipt", "Groceries").await;
+        assert!(bill.created_at <= letter.created_at && letter.created_at <= receipt.created_at);
+        assert_eq!(bill.created_at, bill.updated_at);
+        assert!(bill.document_date.is_none());
+
+        // Set the date printed on the bill
+        let url = server
+            .server_url(&format!("{}/{}", DOCUMENTS_URL, bill.id))
+            .expect("Failed to get server URL");
+        let res = reqwest::Client::new()
+            .patch(url.as_str())
+            .json(&serde_json::json!({ "document_date": "2025-03-14" }))
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert!(res.status().is_success());
+        let updated: DocumentDto = res.json().await.unwrap();
+        assert_eq!(updated.document_date.unwrap().to_string(), "2025-03-14");
+        assert!(updated.updated_at > bill.updated_at);
+        assert_eq!(updated.created_at, bill.created_at);
+
+        // Filter on the document date; undated documents fall back to the day they were added
+        let march: DocumentPageDto = get_json(
+            &server,
+            &auth_header,
+            &format!("{}?from=2025-03-01&to=2025-03-31", DOCUMENTS_URL),
+        )
+        .await;
+        assert_eq!(march.items.len(), 1);
+        assert_eq!(march.items[0].id, bill.id);
+        let today = receipt.created_at.date_naive();
+        let added_today: DocumentPageDto = get_json(
+            &server,
+            &auth_header,
+            &format!("{}?from={}", DOCUMENTS_URL, today),
+        )
+        .await;
+        assert_eq!(added_today.items.len(), 2);
+
+        // Newest first, one per page
+        let mut ids = vec![];
+        let mut path = format!("{}?sort=created_at&order=desc&limit=1", DOCUMENTS_URL);
+        loop {
+            let page: DocumentPageDto = get_json(&server, &auth_header, &path).await;
+            ids.extend(page.items.iter().map(|d| d.id));
+            let Some(cursor) = page.next_cursor else {
+                break;
+            };
+            path = format!(
+                "{}?sort=created_at&order=desc&limit=1&cursor={}",
+                DOCUMENTS_URL, cursor
+            );
+        }
+        assert_eq!(ids, vec![receipt.id, letter.id, bill.id]);
+
+        // Most recently changed first
+        let recent: DocumentPageDto = get_json(
+            &server,
+            &auth_header,
+            &format!("{}?sort=updated_at&order=desc&limit=1", DOCUMENTS_URL),
+        )
+        .await;
+        assert_eq!(recent.items[0].id, bill.id);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn failed_uploads_are_kept_and_can_be_retried() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+
+        // A PDF that cannot be parsed fails permanently, without retries
+        let job = upload_file(
+            &server,
+            &auth_header,
+            "broken.pdf",
+            "application/pdf",
+            b"%PDF-1.4 truncated".to_vec(),
+        )
+        .await;
+        let failed = wait_for_job(&server, &auth_header, &job).await;
+        assert_eq!(failed.status, IngestionJobStatus::Failed);
+        assert_eq!(failed.attempts, 1);
+        assert!(failed.error.unwrap().contains("Could not read PDF"));
+
+        let listed: Vec<IngestionJobDto> = get_json(
+            &server,
+            &auth_header,
+            &format!("{}?status=failed", JOBS_URL),
+        )
+        .await;
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, job.id);
+        let succeeded: Vec<IngestionJobDto> = get_json(
+            &server,
+            &auth_header,
+            &format!("{}?status=succeeded", JOBS_URL),
+        )
+        .await;
+        assert!(succeeded.is_empty());
+
+        // Retry it by hand
+        let retry_url = server
+            .server_url(&format!("{}/{}/retry", JOBS_URL, job.id))
+            .expect("Failed to get server URL");
+        let res = reqwest::Client::new()
+            .post(retry_url.as_str())
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(res.status(), reqwest::StatusCode::ACCEPTED);
+        let retried: IngestionJobDto = res.json().await.unwrap();
+        assert_eq!(retried.attempts, 0);
+        let failed_again = wait_for_job(&server, &auth_header, &retried).await;
+        assert_eq!(failed_again.status, IngestionJobStatus::Failed);
+        assert_eq!(failed_again.attempts, 1);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn duplicate_uploads_are_not_ingested_again() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        let receipt = b"Coffee 3.50".to_vec();
+        let job = upload_file(
+            &server,
+            &auth_header,
+            "receipt.txt",
+            "text/plain",
+            receipt.clone(),
+        )
+        .await;
+        let document = wait_for_ingestion(&server, &auth_header, &job).await;
+
+        // The same bytes under another name are rejected with the existing document
+        let res = post_file(
+            &server,
+            &auth_header,
+            DOCUMENTS_URL,
+            "copy.txt",
+            "text/plain",
+            receipt.clone(),
+        )
+        .await;
+        assert_eq!(res.status(), reqwest::StatusCode::CONFLICT);
+        let conflict: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(conflict["document_id"], serde_json::json!(document.id));
+
+        // Or linked to it
+        let res = post_file(
+            &server,
+            &auth_header,
+            &format!("{}?on_duplicate=link", DOCUMENTS_URL),
+            "copy.txt",
+            "text/plain",
+            receipt,
+        )
+        .await;
+        assert_eq!(res.status(), reqwest::StatusCode::OK);
+        let linked: DocumentDto = res.json().await.unwrap();
+        assert_eq!(linked.id, document.id);
+
+        let jobs: Vec<IngestionJobDto> = get_json(&server, &auth_header, JOBS_URL).await;
+        assert_eq!(jobs.len(), 1);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn uploads_are_typed_by_their_content() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+
+        // A PDF without an extension is still read as a PDF
+        let job = upload_file(
+            &server,
+            &auth_header,
+            "statement",
+            "application/octet-stream",
+            text_pdf("Closing balance 1200.00"),
+        )
+        .await;
+        let document = wait_for_ingestion(&server, &auth_header, &job).await;
+        let document: serde_json::Value = get_json(
+            &server,
+            &auth_header,
+            &format!("{}/{}", DOCUMENTS_URL, document.id),
+        )
+        .await;
+        assert_eq!(document["blob"]["mime_type"], "application/pdf");
+
+        // Plain text is stored with its text
+        let job = upload_file(
+            &server,
+            &auth_header,
+            "notes.txt",
+            "text/plain",
+            "Boiler serviced on 3 May.\nNext service due in May 2027.".into(),
+        )
+        .await;
+        let document = wait_for_ingestion(&server, &auth_header, &job).await;
+        let extracted: serde_json::Value = get_json(
+            &server,
+            &auth_header,
+            &format!("{}/{}/text", DOCUMENTS_URL, document.id),
+        )
+        .await;
+        assert_eq!(
+            extracted["text"],
+            "Boiler serviced on 3 May.\nNext service due in May 2027."
+        );
+        assert_eq!(extracted["reader"], "plain-text");
+        assert_eq!(extracted["ocr_used"], false);
+
+        // Text named like a PDF, and content of no supported type, are rejected
+        for (file_name, data) in [
+            ("invoice.pdf", b"Total 12.50".to_vec()),
+            ("archive.bin", vec![0_u8, 1, 2, 3]),
+        ] {
+            let res = post_file(
+                &server,
+                &auth_header,
+                DOCUMENTS_URL,
+                file_name,
+                "application/pdf",
+                data,
+            )
+            .await;
+            assert_eq!(res.status(), reqwest::StatusCode::UNSUPPORTED_MEDIA_TYPE);
+            let error: serde_json::Value = res.json().await.unwrap();
+            assert!(error["error"].is_string());
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn zip_uploads_queue_every_file_in_order() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in [
+            ("2025/coffee.txt", b"Coffee 3.50".as_slice()),
+            ("photo.raw", &[0_u8, 1, 2, 3]),
+        ] {
+            archive
+                .start_file(name, SimpleFileOptions::default())
+                .unwrap();
+            archive.write_all(data).unwrap();
+        }
+        let archive = archive.finish().unwrap().into_inner();
+        let form = Form::new()
+            .part(
+                "json",
+                Part::text(r#"{"title": "Receipts", "content": ""}"#)
+                    .mime_str("application/json")
+                    .unwrap(),
+            )
+            .part(
+                "file",
+                // Archives are told by their content, not their name
+                Part::bytes(archive)
+                    .file_name("receipts")
+                    .mime_str("application/octet-stream")
+                    .unwrap(),
+            )
+            .part(
+                "file",
+                Part::bytes(b"Bread 2.10".to_vec())
+                    .file_name("bread.txt")
+                    .mime_str("text/plain")
+                    .unwrap(),
+            );
+        let url = server
+            .server_url(DOCUMENTS_URL)
+            .expect("Failed to get server URL");
+        let res = reqwest::Client::new()
+            .post(url.as_str())
+            .multipart(form)
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(res.status(), reqwest::StatusCode::ACCEPTED);
+        let bulk: BulkUploadDto = res.json().await.unwrap();
+
+        // The entries of the archive take its place, the unsupported one with an error
+        let file_names: Vec<&str> = bulk
+            .results
+            .iter()
+            .map(|result| result.file_name.as_str())
+            .collect();
+        assert_eq!(file_names, ["2025/coffee.txt", "photo.raw", "bread.txt"]);
+        assert!(bulk.results[1].error.is_some());
+        for (result, text) in [
+            (&bulk.results[0], "Coffee 3.50"),
+            (&bulk.results[2], "Bread 2.10"),
+        ] {
+            let job = result.job.as_ref().expect("File was not queued");
+            let document = wait_for_ingestion(&server, &auth_header, job).await;
+            let extracted: serde_json::Value = get_json(
+                &server,
+                &auth_header,
+                &format!("{}/{}/text", DOCUMENTS_URL, document.id),
+            )
+            .await;
+            assert_eq!(extracted["text"], text);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn zip_uploads_without_files_are_rejected() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+        archive
+            .add_directory("2025/", SimpleFileOptions::default())
+            .unwrap();
+        let archive = archive.finish().unwrap().into_inner();
+        let form = Form::new()
+            .part(
+                "json",
+                Part::text(r#"{"title": "Receipts", "content": ""}"#)
+                    .mime_str("application/json")
+                    .unwrap(),
+            )
+            .part(
+                "file",
+                Part::bytes(archive)
+                    .file_name("receipts.zip")
+                    .mime_str("application/zip")
+                    .unwrap(),
+            );
+        let url = server
+            .server_url(DOCUMENTS_URL)
+            .expect("Failed to get server URL");
+        let res = reqwest::Client::new()
+            .post(url.as_str())
+            .multipart(form)
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+
+        assert_eq!(res.status(), reqwest::StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(body["error"], "The archive contains no files");
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn large_uploads_are_streamed_to_the_blob_store() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        // Larger than the 2 MB axum buffers by default
+        let statement = "2025-03-01 Card payment 12.50\n"
+            .repeat(100_000)
+            .into_bytes();
+
+        let job = upload_file(
+            &server,
+            &auth_header,
+            "statement.txt",
+            "text/plain",
+            statement.clone(),
+        )
+        .await;
+
+        assert_eq!(job.file_name, "statement.txt");
+        let document = wait_for_ingestion(&server, &auth_header, &job).await;
+        let file_url_result = server
+            .server_url(&format!("{}/{}/file", DOCUMENTS_URL, document.id))
+            .expect("Failed to get server URL");
+        let file_response = reqwest::Client::new()
+            .get(file_url_result.as_str())
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(file_response.status(), reqwest::StatusCode::OK);
+        let downloaded = file_response.bytes().await.expect("Failed to read file");
+        assert_eq!(downloaded.to_vec(), statement);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn uploads_are_read_in_the_default_ocr_languages_of_the_user() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        let settings_url = server
+            .server_url(SETTINGS_URL)
+            .expect("Failed to get server URL");
+
+        let res = reqwest::Client::new()
+            .put(settings_url.as_str())
+            .json(&serde_json::json!({ "ocr_languages": ["deu", "spa"] }))
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(res.status(), reqwest::StatusCode::OK);
+        let settings: UserSettingsDto = get_json(&server, &auth_header, SETTINGS_URL).await;
+        assert_eq!(
+            settings.ocr_languages,
+            Some(vec!["deu".to_string(), "spa".to_string()])
+        );
+
+        let job = upload_file(
+            &server,
+            &auth_header,
+            "kontoauszug.txt",
+            "text/plain",
+            b"Kontostand: 1.024,00 EUR".to_vec(),
+        )
+        .await;
+        assert_eq!(
+            job.languages,
+            Some(vec!["deu".to_string(), "spa".to_string()])
+        );
+        let stored: IngestionJobDto =
+            get_json(&server, &auth_header, &format!("{}/{}", JOBS_URL, job.id)).await;
+        assert_eq!(stored.languages, job.languages);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn scans_are_read_in_the_detected_or_chosen_languages() {
+    run_test_with_mock_tesseract("Rechnung 12,50 EUR", |server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        let scan = fs::read("tests/resources/hello_world.png").unwrap();
+
+        // Without languages, the script detection keeps the installed Latin ones
+        let job = upload_file(
+            &server,
+            &auth_header,
+            "rechnung.png",
+            "image/png",
+            scan.clone(),
+        )
+        .await;
+        let document = wait_for_ingestion(&server, &auth_header, &job).await;
+        let extracted: serde_json::Value = get_json(
+            &server,
+            &auth_header,
+            &format!("{}/{}/text", DOCUMENTS_URL, document.id),
+        )
+        .await;
+        assert_eq!(extracted["text"], "Rechnung 12,50 EUR");
+        assert_eq!(extracted["ocr_used"], true);
+        assert_eq!(
+            extracted["languages"],
+            serde_json::json!(["eng", "deu", "spa"])
         );
+
+        // A language that is not installed is rejected before anything is stored
+        let mut rescan = scan;
+        rescan.extend(b"rescan");
+        for (languages, status) in [
+            (vec!["xyz"], reqwest::StatusCode::BAD_REQUEST),
+            (vec!["deu"], reqwest::StatusCode::ACCEPTED),
+        ] {
+            let form = Form::new()
+                .part(
+                    "json",
+                    Part::text(
+                        serde_json::json!({"title": "", "content": "", "languages": languages})
+                            .to_string(),
+                    )
+                    .mime_str("application/json")
+                    .unwrap(),
+                )
+                .part(
+                    "file",
+                    Part::bytes(rescan.clone())
+                        .file_name("rechnung.png")
+                        .mime_str("image/png")
+                        .unwrap(),
+                );
+            let url = server
+                .server_url(DOCUMENTS_URL)
+                .expect("Failed to get server URL");
+            let res = reqwest::Client::new()
+                .post(url.as_str())
+                .multipart(form)
+                .header("Authorization", &auth_header)
+                .send()
+                .await
+                .expect("Failed to send request");
+            assert_eq!(res.status(), status);
+            if status == reqwest::StatusCode::ACCEPTED {
+                let job: IngestionJobDto = res.json().await.unwrap();
+                let document = wait_for_ingestion(&server, &auth_header, &job).await;
+                let extracted: serde_json::Value = get_json(
+                    &server,
+                    &auth_header,
+                    &format!("{}/{}/text", DOCUMENTS_URL, document.id),
+                )
+                .await;
+                assert_eq!(extracted["languages"], serde_json::json!(["deu"]));
+            }
+        }
     })
     .await;
 }
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn rescans_are_flagged_as_similar_documents() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        let scan = "Lease for the flat at 12 Harbour Street between Ms Lee and Mr Novak. The \
+            monthly rent is 950 and is due on the first day of every month. The deposit is two \
+            months of rent and is returned within thirty days after the keys are handed back. \
+            Either party may end the lease with three months notice in writing. Pets are allowed \
+            with the written consent of the landlord. The tenant keeps the garden tidy and \
+            reports any damage to the flat without delay. Heating and water are included in the \
+            rent; electricity and internet are paid by the tenant.";
+        let first = upload_file(
+            &server,
+            &auth_header,
+            "lease.pdf",
+            "application/pdf",
+            text_pdf(scan),
+        )
+        .await;
+        let original = wait_for_ingestion(&server, &auth_header, &first).await;
+
+        // A rescan with a misread word has other bytes but nearly the same text
+        let rescan = upload_file(
+            &server,
+            &auth_header,
+            "lease-rescan.pdf",
+            "application/pdf",
+            text_pdf(&scan.replace("deposit", "dep0sit")),
+        )
+        .await;
+        let rescan = wait_for_job(&server, &auth_header, &rescan).await;
+        assert_eq!(rescan.status, IngestionJobStatus::Succeeded);
+        assert_eq!(rescan.similar_document_ids, [original.id]);
+
+        let similar: Vec<serde_json::Value> = get_json(
+            &server,
+            &auth_header,
+            &format!("{}/{}/similar", DOCUMENTS_URL, original.id),
+        )
+        .await;
+        assert_eq!(similar.len(), 1);
+        assert_eq!(
+            similar[0]["document"]["id"],
+            serde_json::json!(rescan.document_id.unwrap())
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn documents_read_without_ocr_have_no_words() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        let job = upload_file(
+            &server,
+            &auth_header,
+            "receipt.pdf",
+            "application/pdf",
+            text_pdf("Coffee 3.20 Total 3.20"),
+        )
+        .await;
+        let document = wait_for_ingestion(&server, &auth_header, &job).await;
+        assert!(!document.needs_review);
+
+        let words: DocumentWordsDto = get_json(
+            &server,
+            &auth_header,
+            &format!("{}/{}/words?q=total", DOCUMENTS_URL, document.id),
+        )
+        .await;
+        assert!(words.words.is_empty());
+        assert_eq!(words.confidence, None);
+        assert!(!words.needs_review);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn bills_are_saved_with_their_fields() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        let job = upload_file(
+            &server,
+            &auth_header,
+            "power-bill.pdf",
+            "application/pdf",
+            text_pdf(
+                "City Power invoice\nInvoice number: INV-2026-0042\nAmount due: EUR 84.20\n\
+                 Due date: 2026-11-01",
+            ),
+        )
+        .await;
+        let document = wait_for_ingestion(&server, &auth_header, &job).await;
+
+        let fields = document.fields.expect("Bill has no fields");
+        assert_eq!(fields.document_type, DocumentType::Bill);
+        assert_eq!(fields.invoice_number.as_deref(), Some("INV-2026-0042"));
+        assert_eq!(fields.total_amount, Some(84.2));
+        assert_eq!(fields.currency.as_deref(), Some("EUR"));
+        assert_eq!(
+            fields.due_date.map(|date| date.to_string()).as_deref(),
+            Some("2026-11-01")
+        );
+        let page: DocumentPageDto = get_json(&server, &auth_header, DOCUMENTS_URL).await;
+        assert_eq!(page.items[0].fields.as_ref(), Some(&fields));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn uploads_are_classified_into_the_categories_of_the_user() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        let client = reqwest::Client::new();
+        let url = server
+            .server_url(CATEGORIES_URL)
+            .expect("Failed to get server URL");
+        let res = client
+            .post(url.as_str())
+            .json(&serde_json::json!({
+                "name": "Utility bill",
+                "description": "Power, water and gas bills",
+                "rules": ["kwh", "/meter (reading|number)/"],
+                "tags": ["Bills"],
+            }))
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(res.status(), 201);
+        let category: CategoryDto = res.json().await.unwrap();
+
+        // Names are unique per user whatever their case
+        let res = client
+            .post(url.as_str())
+            .json(&serde_json::json!({
+                "name": "UTILITY BILL",
+                "description": "",
+                "rules": ["kwh"],
+                "tags": [],
+            }))
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(res.status(), reqwest::StatusCode::CONFLICT);
+
+        let job = upload_file(
+            &server,
+            &auth_header,
+            "power.pdf",
+            "application/pdf",
+            text_pdf("City Power utility bill\nElectricity used: 312 kWh\nMeter reading: 4411"),
+        )
+        .await;
+        let document = wait_for_ingestion(&server, &auth_header, &job).await;
+
+        let classification = document.classification.expect("Upload was not classified");
+        assert_eq!(classification.category_id, category.id);
+        assert_eq!(classification.category, "Utility bill");
+        assert_eq!(classification.status, ClassificationStatus::Suggested);
+        assert_eq!(classification.confidence, 1.0);
+        assert!(document.tags.is_empty());
+
+        let url = server
+            .server_url(&format!(
+                "{}/{}/classification/accept",
+                DOCUMENTS_URL, document.id
+            ))
+            .expect("Failed to get server URL");
+        let res = client
+            .post(url.as_str())
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert!(res.status().is_success());
+        let accepted: DocumentDto = res.json().await.unwrap();
+        assert_eq!(accepted.tags, vec!["bills".to_string()]);
+        assert_eq!(
+            accepted.classification.map(|c| c.status),
+            Some(ClassificationStatus::Accepted)
+        );
+
+        let categories: Vec<CategoryDto> = get_json(&server, &auth_header, CATEGORIES_URL).await;
+        assert_eq!(categories, vec![category]);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn semantic_search_finds_documents_by_meaning() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        create_document(
+            &server,
+            &auth_header,
+            "Power Bill",
+            "Electricity usage for March, payment due on the 5th",
+        )
+        .await;
+        let letter = create_document(&server, &auth_header, "Letter", "Hello from grandma").await;
+        let job = upload_file(
+            &server,
+            &auth_header,
+            "policy.pdf",
+            "application/pdf",
+            text_pdf("Auto policy\nVehicle: family car\nPolicy number: 7781"),
+        )
+        .await;
+        let policy = wait_for_ingestion(&server, &auth_header, &job).await;
+
+        let hits: Vec<serde_json::Value> = get_json(
+            &server,
+            &auth_header,
+            &format!(
+                "{}/semantic-search?q=policy%20number%20of%20my%20car&limit=2",
+                DOCUMENTS_URL
+            ),
+        )
+        .await;
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0]["document"]["id"], policy.id.to_string());
+        assert!(hits[0]["snippet"].as_str().unwrap().contains("7781"));
+        assert!(hits[0]["score"].as_f64().unwrap() > hits[1]["score"].as_f64().unwrap());
+
+        // An edited document is found by its new text only
+        let url = server
+            .server_url(&format!("{}/{}", DOCUMENTS_URL, letter.id))
+            .expect("Failed to get server URL");
+        let res = reqwest::Client::new()
+            .patch(url.as_str())
+            .json(&serde_json::json!({
+                "title": "Dentist",
+                "content": "Appointment with the dentist on Friday",
+            }))
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert!(res.status().is_success());
+        let hits: Vec<serde_json::Value> = get_json(
+            &server,
+            &auth_header,
+            &format!("{}/semantic-search?q=dentist%20appointment", DOCUMENTS_URL),
+        )
+        .await;
+        assert_eq!(hits[0]["document"]["id"], letter.id.to_string());
+        assert_eq!(
+            hits[0]["snippet"],
+            "Dentist Appointment with the dentist on Friday"
+        );
+        let hits: Vec<serde_json::Value> = get_json(
+            &server,
+            &auth_header,
+            &format!("{}/semantic-search?q=hello%20from%20grandma", DOCUMENTS_URL),
+        )
+        .await;
+        assert!(
+            hits.iter()
+                .all(|hit| !hit["snippet"].as_str().unwrap().contains("grandma"))
+        );
+
+        // Missing query
+        let url = server
+            .server_url(&format!("{}/semantic-search?q=%20", DOCUMENTS_URL))
+            .expect("Failed to get server URL");
+        let res = reqwest::Client::new()
+            .get(url.as_str())
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(res.status(), reqwest::StatusCode::BAD_REQUEST);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn the_assistant_answers_from_the_users_own_documents() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let owner_header = build_auth_header(&server).await;
+        let other_header = build_bearer_token_with_tenant(Uuid::new_v4(), "life-manager");
+        let passport = create_document(
+            &server,
+            &owner_header,
+            "Passport",
+            "Passport no. X123. Your passport expires on 12 March 2031.",
+        )
+        .await;
+        let others_passport = create_document(
+            &server,
+            &other_header,
+            "Passport",
+            "Passport no. Y987. Your passport expires on 1 May 2027.",
+        )
+        .await;
+        let client = reqwest::Client::new();
+        let url = server
+            .server_url(&format!("{}/ask", ASSISTANT_URL))
+            .expect("Failed to get server URL");
+        let ask = |auth_header: String, stream: bool| {
+            let request = client
+                .post(url.as_str())
+                .json(&serde_json::json!({
+                    "question": "When does my passport expire?",
+                    "stream": stream,
+                }))
+                .header("Authorization", auth_header);
+            async move { request.send().await.expect("Failed to send request") }
+        };
+
+        // Each user is answered from their own passport only
+        for (auth_header, own, others, date) in [
+            (&owner_header, &passport, &others_passport, "12 March 2031"),
+            (&other_header, &others_passport, &passport, "1 May 2027"),
+        ] {
+            let res = ask(auth_header.clone(), false).await;
+            assert!(res.status().is_success());
+            let body = res.text().await.unwrap();
+            let answer: AssistantAnswerDto = serde_json::from_str(&body).unwrap();
+            assert!(answer.answer.contains(date), "{}", answer.answer);
+            let cited: Vec<Uuid> = answer.citations.iter().map(|c| c.document_id).collect();
+            assert_eq!(cited, [own.id]);
+            assert_eq!(
+                answer.citations[0].quote,
+                format!("Your passport expires on {}.", date)
+            );
+            assert!(!body.contains(&others.id.to_string()), "{}", body);
+            assert!(!body.contains(&others.content), "{}", body);
+        }
+
+        // Streamed, the citations come after the answer
+        let res = ask(owner_header, true).await;
+        assert!(res.status().is_success());
+        let body = res.text().await.unwrap();
+        let answer_at = body.find("event: answer").expect(&body);
+        let citations_at = body.find("event: citations").expect(&body);
+        assert!(answer_at < citations_at, "{}", body);
+        assert!(body.contains(&passport.id.to_string()), "{}", body);
+        assert!(!body.contains(&others_passport.id.to_string()), "{}", body);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+#[traced_test]
+async fn stream_ingestion_progress() {
+    run_test_with_test_profile(|server: TestServer| async move {
+        let auth_header = build_auth_header(&server).await;
+        let events_url = server
+            .server_url(&format!("{}/events", JOBS_URL))
+            .expect("Failed to get server URL");
+        let mut events = reqwest::Client::new()
+            .get(events_url.as_str())
+            .header("Authorization", &auth_header)
+            .send()
+            .await
+            .expect("Failed to open event stream");
+        assert!(events.status().is_success());
+        assert_eq!(events.headers()["content-type"], "text/event-stream");
+
+        let file_bytes = fs::read("tests/resources/hello_world.pdf").unwrap();
+        let job = upload_file(
+            &server,
+            &auth_header,
+            "hello_world.pdf",
+            "application/pdf",
+            file_bytes,
+        )
+        .await;
+
+        let mut stages = Vec::new();
+        let mut buffer = String::new();
+        while !stages.contains(&"saved".to_string()) {
+            let chunk = tokio::time::timeout(Duration::from_secs(30), events.chunk())
+                .await
+                .expect("Timed out waiting for ingestion events")
+                .expect("Failed to read event stream")
+                .expect("Event stream ended");
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+            while let Some(end) = buffer.find("\n\n") {
+                let frame: String = buffer.drain(..end + 2).collect();
+                let Some(stage) = frame.lines().find_map(|line| line.strip_prefix("event: "))
+                else {
+                    continue;
+                };
+                assert!(frame.contains(&job.id.to_string()), "{frame}");
+                stages.push(stage.to_string());
+            }
+        }
+        assert_eq!(
+            stages,
+            ["received", "text_extracted", "summarizing", "saved"]
+        );
+    })
+    .await;
+}
+
+async fn create_document(
+    server: &TestServer,
+    auth_header: &str,
+    title: &str,
+    content: &str,
+) -> DocumentDto {
+    let payload = CreateDocumentCommand {
+        title: title.to_string(),
+        content: content.to_string(),
+        languages: None,
+    };
+    let form = Form::new().part(
+        "json",
+        Part::text(serde_json::to_string(&payload).unwrap())
+            .mime_str("application/json")
+            .expect("Could not set mime type to json"),
+    );
+    let url = server
+        .server_url(DOCUMENTS_URL)
+        .expect("Failed to get server URL");
+    let res = reqwest::Client::new()
+        .post(url.as_str())
+        .multipart(form)
+        .header("Authorization", auth_header)
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert!(
+        res.status().is_success(),
+        "Failed to create document with title: {}",
+        title
+    );
+    res.json().await.expect("Failed to parse created document")
+}
+
+/// Polls the upload's ingestion job until a worker has finished it and returns the created
+/// document.
+async fn wait_for_ingestion(
+    server: &TestServer,
+    auth_header: &str,
+    job: &IngestionJobDto,
+) -> DocumentDto {
+    let job = wait_for_job(server, auth_header, job).await;
+    assert_eq!(
+        job.status,
+        IngestionJobStatus::Succeeded,
+        "Ingestion job failed: {:?}",
+        job.error
+    );
+    let document_id = job.document_id.expect("Succeeded job has no document");
+    get_json(
+        server,
+        auth_header,
+        &format!("{}/{}", DOCUMENTS_URL, document_id),
+    )
+    .await
+}
+
+/// Polls an ingestion job until it has succeeded or failed.
+async fn wait_for_job(
+    server: &TestServer,
+    auth_header: &str,
+    job: &IngestionJobDto,
+) -> IngestionJobDto {
+    let job_path = format!("{}/{}", JOBS_URL, job.id);
+    for _ in 0..60 {
+        let job: IngestionJobDto = get_json(server, auth_header, &job_path).await;
+        match job.status {
+            IngestionJobStatus::Succeeded | IngestionJobStatus::Failed => return job,
+            _ => tokio::time::sleep(Duration::from_millis(500)).await,
+        }
+    }
+    panic!("Ingestion job {} did not finish", job.id);
+}
+
+/// Uploads `file_data` as `file_name` and returns the queued ingestion job.
+async fn upload_file(
+    server: &TestServer,
+    auth_header: &str,
+    file_name: &str,
+    mime_type: &str,
+    file_data: Vec<u8>,
+) -> IngestionJobDto {
+    let res = post_file(
+        server,
+        auth_header,
+        DOCUMENTS_URL,
+        file_name,
+        mime_type,
+        file_data,
+    )
+    .await;
+    assert_eq!(res.status(), reqwest::StatusCode::ACCEPTED);
+    res.json().await.expect("Failed to parse ingestion job")
+}
+
+async fn post_file(
+    server: &TestServer,
+    auth_header: &str,
+    path: &str,
+    file_name: &str,
+    mime_type: &str,
+    file_data: Vec<u8>,
+) -> reqwest::Response {
+    let payload = CreateDocumentCommand {
+        title: file_name.to_string(),
+        content: String::new(),
+        languages: None,
+    };
+    let form = Form::new()
+        .part(
+            "json",
+            Part::text(serde_json::to_string(&payload).unwrap())
+                .mime_str("application/json")
+                .expect("Could not set mime type to json"),
+        )
+        .part(
+            "file",
+            Part::bytes(file_data)
+                .file_name(file_name.to_string())
+                .mime_str(mime_type)
+                .expect("Could not set mime type"),
+        );
+    let url = server.server_url(path).expect("Failed to get server URL");
+    reqwest::Client::new()
+        .post(url.as_str())
+        .multipart(form)
+        .header("Authorization", auth_header)
+        .send()
+        .await
+        .expect("Failed to send request")
+}
+
+/// A one page PDF with the text in Helvetica, so it is read without OCR.
+fn text_pdf(text: &str) -> Vec<u8> {
+    let lines: Vec<String> = text
+        .lines()
+        .flat_map(|line| {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            words
+                .chunks(12)
+                .map(|line| format!("({}) Tj T*", line.join(" ")))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let content = format!("BT /F1 10 Tf 14 TL 50 750 Td {} ET", lines.join(" "));
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents 4 0 R \
+         /Resources << /Font << /F1 5 0 R >> >> >>"
+            .to_string(),
+        format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            content.len(),
+            content
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+    ];
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).into_bytes());
+    }
+    let xref = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+    for offset in offsets {
+        pdf.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+    }
+    pdf.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        )
+        .into_bytes(),
+    );
+    pdf
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(
+    server: &TestServer,
+    auth_header: &str,
+    path: &str,
+) -> T {
+    let url = server.server_url(path).expect("Failed to get server URL");
+    let res = reqwest::Client::new()
+        .get(url.as_str())
+        .header("Authorization", auth_header)
+        .send()
+        .await
+        .expect("Failed to send GET request");
+    assert!(
+        res.status().is_success(),
+        "GET {} failed with status: {}",
+        path,
+        res.status()
+    );
+    res.json().await.expect("Failed to parse response")
+}
@@ -116,4 +116,82 @@ describe('DocumentCreateForm', () => {
       );
     });
   });
+
+  it('shows processing alert when the upload is queued as a job', async () => {
+    const alertSpy = vi.spyOn(Alert, 'alert');
+    mockApiFetch.mockResolvedValue(
+      new Response(
+        JSON.stringify({ id: '7d444840-9dc0-11d1-b245-5ffdce74fad2', status: 'queued', file_name: 'bill.pdf' }),
+        { status: 202 }
+      )
+    );
+    renderDocumentCreateForm();
+    fireEvent.changeText(screen.getByPlaceholderText('Document title'), 'Hello');
+    fireEvent.changeText(screen.getByPlaceholderText('Document content'), 'World');
+    fireEvent.press(screen.getByText('Create document'));
+    await waitFor(() => {
+      expect(alertSpy).toHaveBeenCalledWith('Success', 'Processing "bill.pdf" (job 7d444840-9dc0-11d1-b245-5ffdce74fad2).');
+    });
+  });
+
+  it('points at the existing document when the file was already uploaded', async () => {
+    const alertSpy = vi.spyOn(Alert, 'alert');
+    mockApiFetch.mockResolvedValue(
+      new Response(
+        JSON.stringify({ error: 'This file was already uploaded', document_id: '550e8400-e29b-41d4-a716-446655440000' }),
+        { status: 409 }
+      )
+    );
+    renderDocumentCreateForm();
+    fireEvent.changeText(screen.getByPlaceholderText('Document title'), 'Hello');
+    fireEvent.changeText(screen.getByPlaceholderText('Document content'), 'World');
+    fireEvent.press(screen.getByText('Create document'));
+    await waitFor(() => {
+      expect(alertSpy).toHaveBeenCalledWith(
+        'Already uploaded',
+        'This file was already uploaded (document 550e8400-e29b-41d4-a716-446655440000).'
+      );
+    });
+  });
+
+  it('explains why an unsupported file was rejected', async () => {
+    const alertSpy = vi.spyOn(Alert, 'alert');
+    mockApiFetch.mockResolvedValue(
+      new Response(JSON.stringify({ error: 'The file is named .pdf but its content is image/png' }), {
+        status: 415,
+      })
+    );
+    renderDocumentCreateForm();
+    fireEvent.changeText(screen.getByPlaceholderText('Document title'), 'Hello');
+    fireEvent.changeText(screen.getByPlaceholderText('Document content'), 'World');
+    fireEvent.press(screen.getByText('Create document'));
+    await waitFor(() => {
+      expect(alertSpy).toHaveBeenCalledWith(
+        'Unsupported file',
+        'The file is named .pdf but its content is image/png'
+      );
+    });
+  });
+
+  it('explains which upload limit a rejected file went over', async () => {
+    const alertSpy = vi.spyOn(Alert, 'alert');
+    mockApiFetch.mockResolvedValue(
+      new Response(
+        JSON.stringify({
+          error: 'scan.pdf is larger than 104857600 bytes',
+          limit: 'file',
+          limit_bytes: 104857600,
+          file_name: 'scan.pdf',
+        }),
+        { status: 413 }
+      )
+    );
+    renderDocumentCreateForm();
+    fireEvent.changeText(screen.getByPlaceholderText('Document title'), 'Hello');
+    fireEvent.changeText(screen.getByPlaceholderText('Document content'), 'World');
+    fireEvent.press(screen.getByText('Create document'));
+    await waitFor(() => {
+      expect(alertSpy).toHaveBeenCalledWith('File too large', 'scan.pdf is larger than 104857600 bytes');
+    });
+  });
 });
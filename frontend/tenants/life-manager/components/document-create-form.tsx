@@ -160,6 +160,30 @@ export default function DocumentCreateForm() {
       });
 
       const bodyText = await response.text();
+      if (response.status === 409) {
+        // The file was uploaded before; the server points at the existing document or job.
+        const data = JSON.parse(bodyText) as { error?: string; document_id?: string; job_id?: string };
+        const existing = data.document_id ? `document ${data.document_id}` : `job ${data.job_id}`;
+        Alert.alert('Already uploaded', `${data.error ?? 'This file was already uploaded'} (${existing}).`);
+        return;
+      }
+      if (response.status === 415) {
+        // The server explains which types it accepts, or why the file's name and content disagree.
+        const data = JSON.parse(bodyText) as { error?: string };
+        Alert.alert('Unsupported file', data.error ?? 'This type of file is not supported.');
+        return;
+      }
+      if (response.status === 413) {
+        // The server names the limit that was reached; a proxy in front of it answers without a body.
+        let data: { error?: string } = {};
+        try {
+          data = JSON.parse(bodyText) as { error?: string };
+        } catch {
+          // Not the backend's structured error.
+        }
+        Alert.alert('File too large', data.error ?? 'The upload is larger than the server accepts.');
+        return;
+      }
       if (!response.ok) {
         throw new Error(
           bodyText ? `Request failed (${response.status}): ${bodyText}` : `Request failed with status ${response.status}`
@@ -168,8 +192,11 @@ export default function DocumentCreateForm() {
 
       let message = 'Document created successfully.';
       try {
-        const data = JSON.parse(bodyText) as { id?: string; title?: string };
-        if (data.id) {
+        const data = JSON.parse(bodyText) as { id?: string; title?: string; file_name?: string };
+        if (response.status === 202 && data.id) {
+          // Uploaded files are read and summarized in the background.
+          message = `Processing "${data.file_name ?? title.trim()}" (job ${data.id}).`;
+        } else if (data.id) {
           message = `Created document "${data.title ?? title.trim()}" (${data.id}).`;
         }
       } catch {
@@ -45,7 +45,7 @@ describe('DocumentList', () => {
   beforeEach(() => {
     vi.clearAllMocks();
     mockUseAuth.mockReturnValue(defaultAuth());
-    mockAuthenticatedFetch.mockResolvedValue(new Response(JSON.stringify([]), { status: 200 }));
+    mockAuthenticatedFetch.mockResolvedValue(new Response(JSON.stringify({ items: [], next_cursor: null }), { status: 200 }));
   });
 
   it('shows sign in message when there is no token', () => {
@@ -57,10 +57,13 @@ describe('DocumentList', () => {
   it('loads and lists document titles', async () => {
     mockAuthenticatedFetch.mockResolvedValue(
       new Response(
-        JSON.stringify([
-          { id: '1', title: 'Alpha', content: 'c1' },
-          { id: '2', title: 'Beta', content: 'c2' },
-        ]),
+        JSON.stringify({
+          items: [
+            { id: '1', title: 'Alpha', content: 'c1' },
+            { id: '2', title: 'Beta', content: 'c2' },
+          ],
+          next_cursor: null,
+        }),
         { status: 200 }
       )
     );
@@ -83,8 +86,8 @@ describe('DocumentList', () => {
     });
   });
 
-  it('shows empty state when array is empty', async () => {
-    mockAuthenticatedFetch.mockResolvedValue(new Response(JSON.stringify([]), { status: 200 }));
+  it('shows empty state when the page is empty', async () => {
+    mockAuthenticatedFetch.mockResolvedValue(new Response(JSON.stringify({ items: [], next_cursor: null }), { status: 200 }));
     renderDocumentList();
     await waitFor(() => {
       expect(screen.getByText('No documents yet.')).toBeTruthy();
@@ -93,7 +96,13 @@ describe('DocumentList', () => {
 
   it('opens modal with title and content when a row is pressed', async () => {
     mockAuthenticatedFetch.mockResolvedValue(
-      new Response(JSON.stringify([{ id: '1', title: 'Doc A', content: 'Body text' }]), { status: 200 })
+      new Response(
+        JSON.stringify({
+          items: [{ id: '1', title: 'Doc A', content: 'Body text' }],
+          next_cursor: null,
+        }),
+        { status: 200 }
+      )
     );
     renderDocumentList();
     await waitFor(() => {
@@ -139,11 +139,11 @@ export default function DocumentList() {
             : `Request failed with status ${response.status}`
         );
       }
-      const data = JSON.parse(bodyText) as unknown;
-      if (!Array.isArray(data)) {
-        throw new Error('Invalid response: expected a list of documents.');
+      const data = JSON.parse(bodyText) as { items?: unknown } | null;
+      if (!data || !Array.isArray(data.items)) {
+        throw new Error('Invalid response: expected a page of documents.');
       }
-      const rows: DocumentRow[] = data.map((item) => {
+      const rows: DocumentRow[] = data.items.map((item) => {
         const d = item as Record<string, unknown>;
         return {
           id: String(d.id ?? ''),